@@ -0,0 +1,110 @@
+use std::{collections::HashMap, hint::black_box};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    swap_mode_to_trade_limit_ty, InfAmm,
+};
+use inf1_std::{
+    inf1_ctl_core::keys::LST_STATE_LIST_ID,
+    inf1_pp_core::pair::Pair,
+    inf1_svc_ag_std::{
+        inf1_svc_lido_core::solido_legacy_core::STSOL_MINT_ADDR,
+        inf1_svc_marinade_core::sanctum_marinade_liquid_staking_core::MSOL_MINT_ADDR,
+    },
+};
+use jupiter_amm_interface::{Amm, AmmContext, ClockRef, KeyedAccount, QuoteParams, SwapMode};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+fn fixture_inf_amm() -> InfAmm {
+    let (key, account) = ALL_FIXTURES
+        .get_key_value(&LST_STATE_LIST_ID.into())
+        .unwrap();
+    let mut inf = InfAmm::new(
+        &KeyedAccount {
+            key: *key,
+            account: account.clone(),
+            params: None,
+        },
+        &AmmContext {
+            clock_ref: ClockRef::default(),
+        },
+        SPL_LSTS.into_iter().collect(),
+    )
+    .unwrap();
+    // 2 update cycles, 1st one might fail because DEFAULT_MAINNET_POOL might be stale
+    for _ in 0..2 {
+        let am: HashMap<_, _> = inf
+            .get_accounts_to_update()
+            .into_iter()
+            .filter_map(|pk| {
+                let (k, v) = ALL_FIXTURES.get_key_value(&pk)?;
+                Some((*k, v.clone()))
+            })
+            .collect();
+        let _: Result<_, _> = inf.update(&am);
+    }
+    inf
+}
+
+fn quote_params() -> [(&'static str, QuoteParams); 5] {
+    let qp = |input_mint: Pubkey, output_mint: Pubkey, amount: u64| QuoteParams {
+        amount,
+        input_mint,
+        output_mint,
+        swap_mode: SwapMode::ExactIn,
+    };
+    [
+        (
+            "jupsol-wsol",
+            qp(
+                *CONST_PUBKEYS.jupsol_mint(),
+                WSOL_MINT_ADDR.into(),
+                1_000_000_000,
+            ),
+        ),
+        (
+            "wsol-msol",
+            qp(WSOL_MINT_ADDR.into(), MSOL_MINT_ADDR.into(), 1_000_000_000),
+        ),
+        (
+            "jupsol-stsol",
+            qp(*CONST_PUBKEYS.jupsol_mint(), STSOL_MINT_ADDR.into(), 6969),
+        ),
+        (
+            "add-liq-msol",
+            qp(MSOL_MINT_ADDR.into(), INF_MINT_ADDR.into(), 1_000_000_000),
+        ),
+        (
+            "remove-liq-stsol",
+            qp(INF_MINT_ADDR.into(), STSOL_MINT_ADDR.into(), 6969),
+        ),
+    ]
+}
+
+fn bench_quote(c: &mut Criterion) {
+    let inf = fixture_inf_amm();
+
+    let mut group = c.benchmark_group("quote");
+    for (name, qp) in quote_params() {
+        group.bench_function(name, |b| b.iter(|| inf.quote(black_box(&qp))));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("quote_raw");
+    for (name, qp) in quote_params() {
+        let pair = Pair {
+            inp: qp.input_mint.as_array(),
+            out: qp.output_mint.as_array(),
+        };
+        let limit_ty = swap_mode_to_trade_limit_ty(qp.swap_mode);
+        group.bench_function(name, |b| {
+            b.iter(|| inf.quote_raw(black_box(&pair), black_box(qp.amount), limit_ty, 0))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_quote);
+criterion_main!(benches);
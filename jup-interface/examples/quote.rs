@@ -0,0 +1,240 @@
+//! Quotes a pair against a live RPC with the same code paths as a router,
+//! for debugging quote discrepancies.
+//!
+//! Builds an [`InfAmm`] from the LST state list, runs its update cycles against the RPC
+//! with sysvar Clock, then prints the quote, the per-leg breakdown and the
+//! accounts of the instruction that would execute it.
+//!
+//! ```sh
+//! cargo run -p inf1-jup-interface --example quote -- \
+//!     --input jupSOL --output mSOL --amount 1000000000 --rpc <RPC_URL> [--exact-out]
+//! ```
+//!
+//! `--input` and `--output` are either symbols on the Sanctum LST list or base58 mints.
+
+use std::str::FromStr;
+
+use inf1_jup_interface::{
+    clock::ClockMode,
+    route::{Route, RoutedQuote},
+    InfAmm, INF_LST_LIST_ID,
+};
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use jupiter_amm_interface::{
+    AccountMap, Amm, AmmContext, ClockRef, KeyedAccount, QuoteParams, SwapMode, SwapParams,
+};
+use sanctum_lst_list::SanctumLstList;
+use solana_pubkey::Pubkey;
+use test_utils::fetch_keyed_ui_accounts;
+
+const USAGE: &str = "Usage: quote --input <SYMBOL|MINT> --output <SYMBOL|MINT> --amount <AMOUNT> --rpc <RPC_URL> [--exact-out]";
+
+/// 1 more than the 2 required by [`InfAmm`], same as `refresh_fixtures`
+const UPDATE_CYCLES: usize = 3;
+
+/// Placeholder user accounts for printing instruction accounts
+const USER_PLACEHOLDERS: [(&str, Pubkey); 3] = [
+    ("<signer>", Pubkey::new_from_array([1; 32])),
+    ("<input token account>", Pubkey::new_from_array([2; 32])),
+    ("<output token account>", Pubkey::new_from_array([3; 32])),
+];
+
+struct Args {
+    input: String,
+    output: String,
+    amount: u64,
+    rpc_url: String,
+    swap_mode: SwapMode,
+}
+
+impl Args {
+    fn parse() -> Self {
+        let mut input = None;
+        let mut output = None;
+        let mut amount = None;
+        let mut rpc_url = None;
+        let mut swap_mode = SwapMode::ExactIn;
+        let mut args = std::env::args().skip(1);
+        while let Some(flag) = args.next() {
+            let mut value = || args.next().expect(USAGE);
+            match flag.as_str() {
+                "--input" => input = Some(value()),
+                "--output" => output = Some(value()),
+                "--amount" => amount = Some(value().parse().expect("Invalid --amount")),
+                "--rpc" => rpc_url = Some(value()),
+                "--exact-out" => swap_mode = SwapMode::ExactOut,
+                _ => panic!("Unknown arg {flag}. {USAGE}"),
+            }
+        }
+        Self {
+            input: input.expect(USAGE),
+            output: output.expect(USAGE),
+            amount: amount.expect(USAGE),
+            rpc_url: rpc_url.expect(USAGE),
+            swap_mode,
+        }
+    }
+}
+
+/// Resolves `s` as a base58 mint, else as a symbol on the bundled Sanctum LST list
+fn resolve_mint(lst_list: &SanctumLstList, s: &str) -> Pubkey {
+    if let Ok(pk) = Pubkey::from_str(s) {
+        return pk;
+    }
+    lst_list
+        .data
+        .iter()
+        .find(|lst| lst.symbol.eq_ignore_ascii_case(s))
+        .unwrap_or_else(|| panic!("Unknown LST {s}"))
+        .mint
+}
+
+fn print_leg(label: &str, inf: &InfAmm, pair: &Pair<&[u8; 32]>, amount: u64, ty: TradeLimitTy) {
+    match inf.quote_raw(pair, amount, ty, inf.epoch()) {
+        Ok((fee_mint, q)) => println!(
+            "  {label}: in {} out {} lp_fee {} protocol_fee {} (fee mint {})",
+            q.inp,
+            q.out,
+            q.lp_fee,
+            q.protocol_fee,
+            Pubkey::new_from_array(fee_mint),
+        ),
+        Err(e) => println!("  {label}: {e}"),
+    }
+}
+
+fn main() {
+    let Args {
+        input,
+        output,
+        amount,
+        rpc_url,
+        swap_mode,
+    } = Args::parse();
+    let lst_list = SanctumLstList::load();
+    let input_mint = resolve_mint(&lst_list, &input);
+    let output_mint = resolve_mint(&lst_list, &output);
+
+    let (key, account) = fetch_keyed_ui_accounts(&rpc_url, &[INF_LST_LIST_ID])
+        .pop()
+        .expect("LST state list not found")
+        .into_keyed_account();
+    let mut inf = InfAmm::from_keyed_account(
+        &KeyedAccount {
+            key,
+            account,
+            params: None,
+        },
+        &AmmContext {
+            clock_ref: ClockRef::default(),
+        },
+    )
+    .unwrap()
+    .with_clock_mode(ClockMode::Sysvar);
+
+    // earlier cycles may fail because of pricing defaults, only the last one must succeed
+    let mut res = Ok(());
+    for _ in 0..UPDATE_CYCLES {
+        let account_map: AccountMap =
+            fetch_keyed_ui_accounts(&rpc_url, &inf.get_accounts_to_update())
+                .into_iter()
+                .map(|acc| acc.into_keyed_account())
+                .collect();
+        res = inf.update(&account_map);
+    }
+    res.unwrap();
+    println!("Updated at epoch {}", inf.epoch());
+
+    let quote = match inf.quote(&QuoteParams {
+        amount,
+        input_mint,
+        output_mint,
+        swap_mode,
+    }) {
+        Ok(q) => q,
+        Err(e) => {
+            println!("Quote failed: {e}");
+            return;
+        }
+    };
+    println!(
+        "Quote: in {} out {} fee {} ({} of {})",
+        quote.in_amount, quote.out_amount, quote.fee_amount, quote.fee_pct, quote.fee_mint
+    );
+
+    println!("Legs:");
+    let pair = Pair {
+        inp: input_mint.as_array(),
+        out: output_mint.as_array(),
+    };
+    let ty = match swap_mode {
+        SwapMode::ExactIn => TradeLimitTy::ExactIn,
+        SwapMode::ExactOut => TradeLimitTy::ExactOut,
+    };
+    let lp_mint = inf.inner.pool.lp_token_mint;
+    match (swap_mode, inf.quote_best_route(&pair, amount)) {
+        (
+            SwapMode::ExactIn,
+            Ok(RoutedQuote {
+                route: Route::ViaLpToken { lp_token_amount },
+                ..
+            }),
+        ) => {
+            print_leg(
+                "AddLiquidity",
+                &inf,
+                &Pair {
+                    inp: pair.inp,
+                    out: &lp_mint,
+                },
+                amount,
+                ty,
+            );
+            print_leg(
+                "RemoveLiquidity",
+                &inf,
+                &Pair {
+                    inp: &lp_mint,
+                    out: pair.out,
+                },
+                lp_token_amount,
+                ty,
+            );
+        }
+        _ => print_leg("Direct", &inf, &pair, amount, ty),
+    }
+
+    println!("Instruction accounts (direct route):");
+    let [(_, signer), (_, source_token_account), (_, destination_token_account)] =
+        USER_PLACEHOLDERS;
+    let ix = match inf.swap_instruction(&SwapParams {
+        swap_mode,
+        in_amount: quote.in_amount,
+        out_amount: quote.out_amount,
+        source_mint: input_mint,
+        destination_mint: output_mint,
+        source_token_account,
+        destination_token_account,
+        token_transfer_authority: signer,
+        quote_mint_to_referrer: None,
+        jupiter_program_id: &Pubkey::default(),
+        missing_dynamic_accounts_as_default: false,
+    }) {
+        Ok(ix) => ix,
+        Err(e) => {
+            println!("  {e}");
+            return;
+        }
+    };
+    for meta in ix.accounts {
+        let name = USER_PLACEHOLDERS
+            .iter()
+            .find(|(_, pk)| *pk == meta.pubkey)
+            .map_or_else(|| meta.pubkey.to_string(), |(name, _)| (*name).to_owned());
+        println!(
+            "  {name}{}{}",
+            if meta.is_writable { " (writable)" } else { "" },
+            if meta.is_signer { " (signer)" } else { "" },
+        );
+    }
+}
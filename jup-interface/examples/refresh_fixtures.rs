@@ -0,0 +1,61 @@
+//! Refreshes the fixtures in `test-fixtures/` of all accounts that [`InfAmm`] reads,
+//! by walking [`Amm::get_accounts_to_update`] on a live [`InfAmm`].
+//!
+//! Fixtures of accounts that are not read by [`InfAmm`], e.g. user token accounts
+//! and programs, are not touched.
+//!
+//! ```sh
+//! cargo run -p inf1-jup-interface --example refresh_fixtures -- <RPC_URL>
+//! ```
+
+use std::collections::HashMap;
+
+use inf1_jup_interface::{InfAmm, INF_LST_LIST_ID};
+use jupiter_amm_interface::{AccountMap, Amm, AmmContext, ClockRef, KeyedAccount};
+use test_utils::{fetch_keyed_ui_accounts, write_fixtures, KeyedUiAccount};
+
+/// 1 more than the 2 required by [`InfAmm`], in case the LST state list changes mid-walk
+const UPDATE_CYCLES: usize = 3;
+
+fn main() {
+    let rpc_url = std::env::args()
+        .nth(1)
+        .expect("Usage: refresh_fixtures <RPC_URL>");
+
+    let mut fetched: HashMap<String, KeyedUiAccount> =
+        fetch_keyed_ui_accounts(&rpc_url, &[INF_LST_LIST_ID])
+            .into_iter()
+            .map(|acc| (acc.pubkey.clone(), acc))
+            .collect();
+    let (key, account) = fetched[&INF_LST_LIST_ID.to_string()]
+        .clone()
+        .into_keyed_account();
+    let mut inf = InfAmm::from_keyed_account(
+        &KeyedAccount {
+            key,
+            account,
+            params: None,
+        },
+        &AmmContext {
+            clock_ref: ClockRef::default(),
+        },
+    )
+    .unwrap();
+
+    // earlier cycles may fail because of pricing defaults, only the last one must succeed
+    let mut res = Ok(());
+    for _ in 0..UPDATE_CYCLES {
+        let accounts = fetch_keyed_ui_accounts(&rpc_url, &inf.get_accounts_to_update());
+        let account_map: AccountMap = accounts
+            .iter()
+            .map(|acc| acc.clone().into_keyed_account())
+            .collect();
+        res = inf.update(&account_map);
+        fetched.extend(accounts.into_iter().map(|acc| (acc.pubkey.clone(), acc)));
+    }
+    res.unwrap();
+
+    for name in write_fixtures(fetched.values()) {
+        println!("{name}");
+    }
+}
@@ -0,0 +1,44 @@
+// shared between fuzz targets, each of which only uses some of these
+#![allow(dead_code)]
+
+use inf1_jup_interface::{InfAmm, INF_LST_LIST_ID};
+use jupiter_amm_interface::{AccountMap, Amm, AmmContext, ClockRef, KeyedAccount};
+use lazy_static::lazy_static;
+use solana_account::Account;
+use test_utils::{ALL_FIXTURES, SPL_LSTS};
+
+lazy_static! {
+    /// Updated against the test fixtures, same as the integration tests' `updated_inf_amm`
+    pub static ref UPDATED_INF_AMM: InfAmm = {
+        let mut inf = new_inf_amm(ALL_FIXTURES.get(&INF_LST_LIST_ID).unwrap().data.clone())
+            .unwrap();
+        let _: Result<_, _> = inf.update(&fixtures_account_map(&inf));
+        inf.update(&fixtures_account_map(&inf)).unwrap();
+        inf
+    };
+}
+
+pub fn new_inf_amm(lst_state_list_data: Vec<u8>) -> anyhow::Result<InfAmm> {
+    InfAmm::new(
+        &KeyedAccount {
+            key: INF_LST_LIST_ID,
+            account: Account {
+                data: lst_state_list_data,
+                ..ALL_FIXTURES.get(&INF_LST_LIST_ID).unwrap().clone()
+            },
+            params: None,
+        },
+        &AmmContext {
+            clock_ref: ClockRef::default(),
+        },
+        SPL_LSTS.into_iter().collect(),
+    )
+}
+
+/// [`Amm::get_accounts_to_update`] of `inf` that have fixtures
+pub fn fixtures_account_map(inf: &InfAmm) -> AccountMap {
+    inf.get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, ALL_FIXTURES.get(&pk)?.clone())))
+        .collect()
+}
@@ -0,0 +1,19 @@
+//! Constructs and updates [`inf1_jup_interface::InfAmm`] from arbitrary LST state list data.
+//! Both may fail, but must not panic.
+
+#![no_main]
+
+mod common;
+
+use jupiter_amm_interface::Amm;
+use libfuzzer_sys::fuzz_target;
+
+use common::{fixtures_account_map, new_inf_amm};
+
+fuzz_target!(|data: Vec<u8>| {
+    let Ok(mut inf) = new_inf_amm(data) else {
+        return;
+    };
+    let account_map = fixtures_account_map(&inf);
+    let _: Result<_, _> = inf.update(&account_map);
+});
@@ -0,0 +1,67 @@
+//! Replaces the data of arbitrary accounts to update with arbitrary bytes.
+//! Update may fail, but must not panic, and neither may quoting afterwards.
+
+#![no_main]
+
+mod common;
+
+use arbitrary::Arbitrary;
+use inf1_jup_interface::InfAmm;
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use libfuzzer_sys::fuzz_target;
+
+use common::{fixtures_account_map, UPDATED_INF_AMM};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    /// `(index into accounts to update, data)`
+    corruptions: Vec<(u16, Vec<u8>)>,
+
+    /// Indices into accounts to update to remove
+    removals: Vec<u16>,
+
+    amount: u64,
+}
+
+fuzz_target!(|input: Input| {
+    let Input {
+        corruptions,
+        removals,
+        amount,
+    } = input;
+    let mut inf = InfAmm::clone(&UPDATED_INF_AMM);
+    let keys = inf.get_accounts_to_update();
+    let mut account_map = fixtures_account_map(&inf);
+    corruptions.into_iter().for_each(|(i, data)| {
+        if let Some(acc) = keys
+            .get(usize::from(i) % keys.len())
+            .and_then(|pk| account_map.get_mut(pk))
+        {
+            acc.data = data;
+        }
+    });
+    removals.into_iter().for_each(|i| {
+        account_map.remove(&keys[usize::from(i) % keys.len()]);
+    });
+
+    // 2 cycles so that a corrupted LST state list changes the accounts to update
+    for _ in 0..2 {
+        let _: Result<_, _> = inf.update(&account_map);
+    }
+
+    let mints = inf.get_reserve_mints();
+    mints.iter().for_each(|input_mint| {
+        mints.iter().for_each(|output_mint| {
+            [SwapMode::ExactIn, SwapMode::ExactOut]
+                .into_iter()
+                .for_each(|swap_mode| {
+                    let _: Result<_, _> = inf.quote(&QuoteParams {
+                        amount,
+                        input_mint: *input_mint,
+                        output_mint: *output_mint,
+                        swap_mode,
+                    });
+                });
+        });
+    });
+});
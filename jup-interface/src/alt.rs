@@ -0,0 +1,303 @@
+//! Address lookup tables (ALTs) for INF instructions, which reference too many accounts
+//! to fit more than a couple of legs into a transaction without them.
+//!
+//! [`InfAmm::suggest_alts`] partitions the accounts that are the same for all users
+//! into tables such that every pair only needs the first table and at most 2 others,
+//! as long as the accounts shared between LSTs fit in a single table.
+//! [`create_alt_ix`] and [`extend_alt_ixs`] create and fill them, and [`missing_from_alts`]
+//! returns the accounts that existing tables need to be extended with after LSTs are added.
+//! [`resolve_with_alts`] splits instruction accounts into static keys and table lookups
+//! for packing v0 messages.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+#[cfg(feature = "tx")]
+use solana_sdk::message::AddressLookupTableAccount;
+
+use crate::{
+    ata::SYSTEM_PROGRAM_ID,
+    pda::{PdaProvider, SafePda},
+    writable::UserAccs,
+    InfAmm,
+};
+
+pub const ALT_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("AddressLookupTab1e1111111111111111111111111");
+
+/// Max number of addresses a lookup table can hold
+pub const MAX_ADDRESSES_PER_ALT: usize = 256;
+
+/// Max number of addresses per extend instruction such that it fits in a transaction
+/// on its own, with separate authority and payer signers
+pub const MAX_ADDRESSES_PER_EXTEND: usize = 27;
+
+/// `ProgramInstruction` discriminants of the address lookup table program, bincode u32s
+const CREATE_ALT_IX_DISCM: [u8; 4] = 0u32.to_le_bytes();
+const EXTEND_ALT_IX_DISCM: [u8; 4] = 2u32.to_le_bytes();
+
+/// An existing lookup table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alt {
+    pub key: Pubkey,
+    pub addresses: Vec<Pubkey>,
+}
+
+#[cfg(feature = "tx")]
+impl From<AddressLookupTableAccount> for Alt {
+    #[inline]
+    fn from(AddressLookupTableAccount { key, addresses }: AddressLookupTableAccount) -> Self {
+        Self { key, addresses }
+    }
+}
+
+/// Accounts loaded from a lookup table, by index into [`Alt::addresses`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AltLookup {
+    pub key: Pubkey,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+/// See [`resolve_with_alts`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ResolvedMetas {
+    /// Accounts not found in any table, and signers, deduped
+    pub static_metas: Vec<AccountMeta>,
+
+    /// Only tables that at least 1 account is loaded from
+    pub lookups: Vec<AltLookup>,
+}
+
+/// Returns the address of the lookup table created by `authority` at `recent_slot`
+#[inline]
+pub fn find_alt_address(authority: &Pubkey, recent_slot: u64) -> (Pubkey, u8) {
+    let (addr, bump) = SafePda::find(
+        &[authority.as_ref(), &recent_slot.to_le_bytes()],
+        ALT_PROGRAM_ID.as_array(),
+    )
+    // lookup table program uses find_program_address, which panics on the same condition
+    .expect("no off-curve lookup table address");
+    (Pubkey::new_from_array(addr), bump)
+}
+
+/// Returns the instruction that creates a lookup table owned by `authority`
+/// and paid for by `payer`, and the table's address.
+///
+/// `recent_slot` must be a slot in the slot hashes sysvar, e.g. the last finalized slot.
+pub fn create_alt_ix(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    recent_slot: u64,
+) -> (Instruction, Pubkey) {
+    let (alt, bump) = find_alt_address(authority, recent_slot);
+    let mut data = Vec::with_capacity(13);
+    data.extend(CREATE_ALT_IX_DISCM);
+    data.extend(recent_slot.to_le_bytes());
+    data.push(bump);
+    (
+        Instruction {
+            program_id: ALT_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(alt, false),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new(*payer, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID.into(), false),
+            ],
+            data,
+        },
+        alt,
+    )
+}
+
+/// Returns the instructions that append `addresses` to `alt`,
+/// [`MAX_ADDRESSES_PER_EXTEND`] at a time, each to be sent in its own transaction
+pub fn extend_alt_ixs(
+    alt: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    addresses
+        .chunks(MAX_ADDRESSES_PER_EXTEND)
+        .map(|chunk| {
+            let mut data = Vec::with_capacity(12 + 32 * chunk.len());
+            data.extend(EXTEND_ALT_IX_DISCM);
+            data.extend((chunk.len() as u64).to_le_bytes());
+            chunk.iter().for_each(|pk| data.extend(pk.to_bytes()));
+            Instruction {
+                program_id: ALT_PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(*alt, false),
+                    AccountMeta::new_readonly(*authority, true),
+                    AccountMeta::new(*payer, true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID.into(), false),
+                ],
+                data,
+            }
+        })
+        .collect()
+}
+
+/// Returns `addresses` that are not in any of `alts`, in order and deduped
+pub fn missing_from_alts(alts: &[Alt], addresses: &[Pubkey]) -> Vec<Pubkey> {
+    let mut seen: HashSet<&Pubkey> = alts.iter().flat_map(|a| &a.addresses).collect();
+    addresses
+        .iter()
+        .filter(|pk| seen.insert(pk))
+        .copied()
+        .collect()
+}
+
+/// Splits `metas` into static accounts and accounts loaded from `alts`,
+/// preferring earlier tables if an account is in more than one.
+///
+/// Duplicate accounts are merged, keeping the most privileged flags.
+/// Signers are always static, as lookup tables cannot load them.
+/// Program IDs of instructions must also be static, so should not be in `metas`.
+pub fn resolve_with_alts(metas: &[AccountMeta], alts: &[Alt]) -> ResolvedMetas {
+    let mut deduped: Vec<AccountMeta> = Vec::with_capacity(metas.len());
+    for meta in metas {
+        match deduped.iter_mut().find(|m| m.pubkey == meta.pubkey) {
+            Some(m) => {
+                m.is_signer |= meta.is_signer;
+                m.is_writable |= meta.is_writable;
+            }
+            None => deduped.push(meta.clone()),
+        }
+    }
+
+    let mut indexes: HashMap<&Pubkey, (usize, u8)> = HashMap::new();
+    for (alt_i, alt) in alts.iter().enumerate() {
+        for (i, pk) in alt.addresses.iter().enumerate().take(MAX_ADDRESSES_PER_ALT) {
+            indexes.entry(pk).or_insert((alt_i, i as u8));
+        }
+    }
+
+    let mut res = ResolvedMetas::default();
+    // by alt index so that lookups follow the order of `alts`
+    let mut lookups: BTreeMap<usize, AltLookup> = BTreeMap::new();
+    for meta in deduped {
+        let Some((alt_i, i)) = indexes.get(&meta.pubkey).filter(|_| !meta.is_signer) else {
+            res.static_metas.push(meta);
+            continue;
+        };
+        let lookup = lookups.entry(*alt_i).or_insert_with(|| AltLookup {
+            key: alts[*alt_i].key,
+            writable_indexes: Vec::new(),
+            readonly_indexes: Vec::new(),
+        });
+        if meta.is_writable {
+            lookup.writable_indexes.push(*i);
+        } else {
+            lookup.readonly_indexes.push(*i);
+        }
+    }
+    res.lookups = lookups.into_values().collect();
+    res
+}
+
+impl InfAmm {
+    /// Partitions the accounts of [`Self::accounts_for_pair`] over all pairs of the current
+    /// LST state list that are the same for all users into lookup tables.
+    ///
+    /// The first table holds accounts shared by LSTs, e.g. the INF program, pool state,
+    /// pricing program accounts and sol val calculator programs, most used first.
+    /// Accounts specific to an LST, e.g. its mint, reserves and stake pool, are kept together
+    /// in the same table, so any pair loads from at most 3 tables while the shared accounts
+    /// fit in the first table. Beyond [`MAX_ADDRESSES_PER_ALT`] shared accounts, they are
+    /// split over as many tables as needed, which a pair may load from in addition.
+    ///
+    /// Deterministic for the same LST state list and pool state.
+    pub fn suggest_alts(&self) -> Vec<Vec<Pubkey>> {
+        let mints: Vec<[u8; 32]> = self.live_mints().collect();
+        let placeholders = [
+            UserAccs::PLACEHOLDERS.signer,
+            UserAccs::PLACEHOLDERS.inp_token_acc,
+            UserAccs::PLACEHOLDERS.out_token_acc,
+        ];
+
+        // accounts of every tradeable pair involving each mint
+        let mut pairs_of_mint: HashMap<[u8; 32], Vec<HashSet<Pubkey>>> = HashMap::new();
+        // first-seen order and number of pairs, for determinism and ordering
+        let mut order: Vec<Pubkey> = Vec::new();
+        let mut pair_counts: HashMap<Pubkey, usize> = HashMap::new();
+        for inp in &mints {
+            for out in mints.iter().filter(|out| *out != inp) {
+                let Ok(metas) = self.accounts_for_pair(
+                    &Pair { inp, out },
+                    TradeLimitTy::ExactIn,
+                    &UserAccs::PLACEHOLDERS,
+                ) else {
+                    continue;
+                };
+                let accs: HashSet<Pubkey> = metas
+                    .into_iter()
+                    .map(|m| m.pubkey)
+                    .filter(|pk| !placeholders.contains(pk))
+                    .collect();
+                for pk in &accs {
+                    let count = pair_counts.entry(*pk).or_insert_with(|| {
+                        order.push(*pk);
+                        0
+                    });
+                    *count += 1;
+                }
+                for mint in [inp, out] {
+                    pairs_of_mint.entry(*mint).or_default().push(accs.clone());
+                }
+            }
+        }
+
+        // an account belongs to a mint if it is in every pair of that mint and no other mint's
+        let mut owner: HashMap<Pubkey, Option<[u8; 32]>> = HashMap::new();
+        for mint in &mints {
+            let Some(pairs) = pairs_of_mint.get(mint) else {
+                continue;
+            };
+            for pk in order
+                .iter()
+                .filter(|pk| pairs.iter().all(|p| p.contains(pk)))
+            {
+                owner
+                    .entry(*pk)
+                    .and_modify(|o| *o = None)
+                    .or_insert(Some(*mint));
+            }
+        }
+
+        let mut shared: Vec<Pubkey> = Vec::new();
+        let mut blocks: Vec<Vec<Pubkey>> = vec![Vec::new(); mints.len()];
+        for pk in &order {
+            match owner.get(pk).copied().flatten() {
+                Some(mint) => {
+                    let i = mints.iter().position(|m| *m == mint).unwrap();
+                    blocks[i].push(*pk);
+                }
+                None => shared.push(*pk),
+            }
+        }
+        // stable, so ties keep first-seen order
+        shared.sort_by_key(|pk| std::cmp::Reverse(pair_counts[pk]));
+
+        let mut tables: Vec<Vec<Pubkey>> = shared
+            .chunks(MAX_ADDRESSES_PER_ALT)
+            .map(<[Pubkey]>::to_vec)
+            .collect();
+        // first-fit decreasing, blocks are never split
+        let mut blocks: Vec<Vec<Pubkey>> = blocks.into_iter().filter(|b| !b.is_empty()).collect();
+        blocks.sort_by_key(|b| std::cmp::Reverse(b.len()));
+        for block in blocks {
+            match tables
+                .iter_mut()
+                .find(|t| t.len() + block.len() <= MAX_ADDRESSES_PER_ALT)
+            {
+                Some(table) => table.extend(block),
+                None => tables.push(block),
+            }
+        }
+        tables
+    }
+}
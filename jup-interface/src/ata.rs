@@ -0,0 +1,124 @@
+//! Associated token accounts that a trade may need created beforehand,
+//! e.g. the user's token account of a newly listed LST, which would otherwise
+//! fail the trade onchain with an account not found error.
+
+use anyhow::Result;
+use jupiter_amm_interface::SwapParams;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    lst_info::{ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    pda::find_ata,
+    InfAmm,
+};
+
+pub(crate) const SYSTEM_PROGRAM_ID: [u8; 32] = [0; 32];
+
+/// Associated token program `CreateIdempotent`
+const CREATE_ATA_IDEMPOTENT_IX_DISCM: u8 = 1;
+
+/// An associated token account to create with the associated token program
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CreateAtaIx {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub ata: [u8; 32],
+
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub owner: [u8; 32],
+
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub mint: [u8; 32],
+
+    /// Owner program of [`Self::mint`]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub token_program: [u8; 32],
+}
+
+impl CreateAtaIx {
+    /// Associated token program `CreateIdempotent` instruction,
+    /// with `payer` as signer.
+    ///
+    /// Succeeds if the account already exists, so can be included unconditionally.
+    pub fn to_instruction(&self, payer: &[u8; 32]) -> Instruction {
+        Instruction {
+            program_id: ASSOCIATED_TOKEN_PROGRAM_ID.into(),
+            accounts: vec![
+                AccountMeta::new((*payer).into(), true),
+                AccountMeta::new(self.ata.into(), false),
+                AccountMeta::new_readonly(self.owner.into(), false),
+                AccountMeta::new_readonly(self.mint.into(), false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID.into(), false),
+                AccountMeta::new_readonly(self.token_program.into(), false),
+            ],
+            data: vec![CREATE_ATA_IDEMPOTENT_IX_DISCM],
+        }
+    }
+}
+
+impl InfAmm {
+    /// Associated token accounts used by the INF instruction for `swap_params`
+    /// that may not exist yet, sorted by address:
+    /// - `source_token_account` and `destination_token_account`, if they are
+    ///   the ATAs of `token_transfer_authority`
+    /// - the protocol fee accumulators of the trade
+    ///
+    /// Whether they exist is not checked: callers should check the returned accounts
+    /// or include the returned instructions unconditionally, since they are idempotent.
+    ///
+    /// Errs under the same conditions as [`Self::swap_instruction`], except that
+    /// protocol fee accumulators missing from the last update are always included
+    /// instead of erring, see [`crate::dynamic`].
+    pub fn required_atas(&self, swap_params: &SwapParams) -> Result<Vec<(Pubkey, CreateAtaIx)>> {
+        let (_, metas) = self.trade_ix_args_and_metas_raw(swap_params)?;
+        let in_metas = |pk: &[u8; 32]| metas.iter().any(|m| m.pubkey.as_array() == pk);
+        let token_programs: Vec<_> = [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]
+            .into_iter()
+            .filter(|tp| in_metas(tp))
+            .collect();
+        let authority = swap_params.token_transfer_authority.to_bytes();
+        let protocol_fee = self.config.protocol_fee();
+
+        let user_atas = [
+            (swap_params.source_token_account, swap_params.source_mint),
+            (
+                swap_params.destination_token_account,
+                swap_params.destination_mint,
+            ),
+        ]
+        .into_iter()
+        .flat_map(|(token_acc, mint)| {
+            token_programs.iter().filter_map(move |tp| {
+                let ata = find_ata(&authority, tp, mint.as_array())?;
+                (ata == token_acc.to_bytes()).then_some(CreateAtaIx {
+                    ata,
+                    owner: authority,
+                    mint: mint.to_bytes(),
+                    token_program: *tp,
+                })
+            })
+        });
+        let protocol_fee_accumulators = [swap_params.source_mint, swap_params.destination_mint]
+            .into_iter()
+            .flat_map(|mint| {
+                token_programs.iter().filter_map(move |tp| {
+                    let ata = self.config.protocol_fee_accumulator(tp, mint.as_array())?;
+                    in_metas(&ata).then_some(CreateAtaIx {
+                        ata,
+                        owner: protocol_fee?,
+                        mint: mint.to_bytes(),
+                        token_program: *tp,
+                    })
+                })
+            });
+
+        let mut res: Vec<_> = user_atas
+            .chain(protocol_fee_accumulators)
+            .map(|c| (Pubkey::new_from_array(c.ata), c))
+            .collect();
+        res.sort_unstable_by_key(|(pk, _)| *pk);
+        res.dedup_by_key(|(pk, _)| *pk);
+        Ok(res)
+    }
+}
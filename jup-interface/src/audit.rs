@@ -0,0 +1,157 @@
+//! Self-check of the account metas generated for INF instructions, to catch mismatches
+//! between the keys and writability of upstream inf1 instruction account lists,
+//! e.g. after upstream account lists change.
+//!
+//! [`keys_writable_to_jup_metas`](crate::keys_writable_to_jup_metas) zips keys with their
+//! writability by position, so a key list and writability list that disagree produce metas
+//! with accounts of the wrong writability, which only fail at execution time.
+//! [`InfAmm::audit_metas`] checks the accounts whose writability does not depend on
+//! instruction specifics.
+
+use std::fmt::{self, Display, Formatter};
+
+use anyhow::Result;
+use inf1_std::{
+    inf1_ctl_core::keys::{LST_STATE_LIST_ID, POOL_STATE_ID},
+    inf1_pp_core::pair::Pair,
+    trade::TradeLimitTy,
+};
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{
+    lst_info::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    writable::{INP_TOKEN_ACC_PLACEHOLDER, OUT_TOKEN_ACC_PLACEHOLDER, SIGNER_PLACEHOLDER},
+    InfAmm,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum MetaAuditErr {
+    /// An account that the instruction must have is missing
+    Missing {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pubkey"))]
+        pk: Pubkey,
+    },
+
+    /// An account is in the instruction more times than expected
+    Duplicate {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pubkey"))]
+        pk: Pubkey,
+    },
+
+    /// An account has the wrong writability
+    Writability {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pubkey"))]
+        pk: Pubkey,
+        expected: bool,
+    },
+}
+
+impl Display for MetaAuditErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { pk } => f.write_fmt(format_args!("Missing: {pk}")),
+            Self::Duplicate { pk } => f.write_fmt(format_args!("Duplicate: {pk}")),
+            Self::Writability { pk, expected } => f.write_fmt(format_args!(
+                "Writability: {pk}. Expected is_writable: {expected}"
+            )),
+        }
+    }
+}
+
+impl std::error::Error for MetaAuditErr {}
+
+impl InfAmm {
+    /// Checks the account metas of the INF instruction for `pair`:
+    /// - user token accounts are present once and writable
+    /// - the signer is present once
+    /// - pool state, LST state list and LST reserves are writable
+    /// - mints are readonly, except the LP token mint, which is minted or burnt
+    /// - the pricing program and sol val calculator programs are readonly
+    ///
+    /// Returns all failed checks, empty if none. Errs under the same conditions as
+    /// [`Self::swap_instruction`].
+    pub fn audit_metas(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        trade_ty: TradeLimitTy,
+    ) -> Result<Vec<MetaAuditErr>> {
+        let metas = self.placeholder_metas(pair, trade_ty)?;
+        let mut errs = Vec::new();
+
+        for pk in [
+            SIGNER_PLACEHOLDER,
+            INP_TOKEN_ACC_PLACEHOLDER,
+            OUT_TOKEN_ACC_PLACEHOLDER,
+        ] {
+            let n = metas.iter().filter(|m| m.pubkey == pk).count();
+            if n > 1 {
+                errs.push(MetaAuditErr::Duplicate { pk });
+            }
+        }
+        check_all(&metas, &SIGNER_PLACEHOLDER, None, &mut errs);
+
+        let lp_mint = &self.inner.pool.lp_token_mint;
+        let writable = [
+            INP_TOKEN_ACC_PLACEHOLDER.to_bytes(),
+            OUT_TOKEN_ACC_PLACEHOLDER.to_bytes(),
+            *self.config.to_deployment(&POOL_STATE_ID),
+            *self.config.to_deployment(&LST_STATE_LIST_ID),
+        ];
+        writable
+            .iter()
+            .for_each(|pk| check_all(&metas, pk, Some(true), &mut errs));
+
+        for mint in [pair.inp, pair.out] {
+            let is_lp_mint = mint == lp_mint;
+            check_all(&metas, mint, Some(is_lp_mint), &mut errs);
+            if is_lp_mint {
+                continue;
+            }
+            // token program of the mint is unknown, so reserves of either must be present
+            let infos: Vec<_> = [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]
+                .iter()
+                .filter_map(|token_program| self.lst_info(mint, token_program))
+                .collect();
+            let Some(info) = infos
+                .iter()
+                .find(|info| metas.iter().any(|m| *m.pubkey.as_array() == info.reserves))
+                .or(infos.first())
+            else {
+                continue;
+            };
+            check_all(&metas, &info.reserves, Some(true), &mut errs);
+            check_all(&metas, &info.sol_value_calculator, Some(false), &mut errs);
+        }
+        check_all(
+            &metas,
+            &self.inner.pool.pricing_program,
+            Some(false),
+            &mut errs,
+        );
+
+        Ok(errs)
+    }
+}
+
+/// Checks that `pk` is in `metas` and, if `expected_writable` is `Some`,
+/// that all its occurences have that writability
+fn check_all(
+    metas: &[AccountMeta],
+    pk: &[u8; 32],
+    expected_writable: Option<bool>,
+    errs: &mut Vec<MetaAuditErr>,
+) {
+    let pk = Pubkey::new_from_array(*pk);
+    let mut occurences = metas.iter().filter(|m| m.pubkey == pk).peekable();
+    if occurences.peek().is_none() {
+        errs.push(MetaAuditErr::Missing { pk });
+        return;
+    }
+    if let Some(expected) = expected_writable {
+        if occurences.any(|m| m.is_writable != expected) {
+            errs.push(MetaAuditErr::Writability { pk, expected });
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Opt-in memoization of quotes between updates.
+//!
+//! Route search tends to quote the same `(input, output, amount)` repeatedly between updates,
+//! so results of the sol val calc and pricing math are cached here and cleared at the start of
+//! every [`jupiter_amm_interface::Amm::update`].
+
+use std::{collections::HashMap, sync::Mutex};
+
+use inf1_std::{quote::Quote, trade::TradeLimitTy};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QuoteCacheKey {
+    pub inp: [u8; 32],
+    pub out: [u8; 32],
+    pub amount: u64,
+    pub is_exact_out: bool,
+}
+
+impl QuoteCacheKey {
+    #[inline]
+    pub const fn new(inp: &[u8; 32], out: &[u8; 32], amount: u64, limit_ty: TradeLimitTy) -> Self {
+        Self {
+            inp: *inp,
+            out: *out,
+            amount,
+            is_exact_out: matches!(limit_ty, TradeLimitTy::ExactOut),
+        }
+    }
+}
+
+/// Only successful quotes are cached.
+///
+/// Once `max_entries` is reached, new quotes are no longer cached until the next clear.
+#[derive(Debug)]
+pub struct QuoteCache {
+    max_entries: usize,
+    entries: Mutex<HashMap<QuoteCacheKey, ([u8; 32], Quote)>>,
+}
+
+impl QuoteCache {
+    #[inline]
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[inline]
+    pub const fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Returns `(fee_mint, quote)`
+    pub fn get(&self, key: &QuoteCacheKey) -> Option<([u8; 32], Quote)> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(key)
+            .map(|(fee_mint, quote)| (*fee_mint, copy_quote(quote)))
+    }
+
+    pub fn insert(&self, key: QuoteCacheKey, fee_mint: &[u8; 32], quote: &Quote) {
+        // poisoned mutex: just dont cache
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if entries.len() >= self.max_entries {
+            return;
+        }
+        entries.insert(key, (*fee_mint, copy_quote(quote)));
+    }
+
+    /// See [`crate::memory`]
+    pub(crate) fn heap_bytes(&self) -> usize {
+        self.entries
+            .lock()
+            .map_or(0, |entries| crate::memory::hash_map_bytes(&entries))
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        // also resets poisoning, if any
+        self.entries = Mutex::new(HashMap::new());
+    }
+}
+
+/// Clones start out empty so that cloning an AMM for
+/// route-search workers stays cheap
+impl Clone for QuoteCache {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.max_entries)
+    }
+}
+
+#[inline]
+pub(crate) const fn copy_quote(
+    Quote {
+        inp,
+        out,
+        lp_fee,
+        protocol_fee,
+        inp_mint,
+        out_mint,
+    }: &Quote,
+) -> Quote {
+    Quote {
+        inp: *inp,
+        out: *out,
+        lp_fee: *lp_fee,
+        protocol_fee: *protocol_fee,
+        inp_mint: *inp_mint,
+        out_mint: *out_mint,
+    }
+}
@@ -0,0 +1,179 @@
+//! Detection of stake pool program upgrades that sol val calculators have not caught up with.
+//!
+//! Sol val calculators of upgradeable stake pool programs record the last upgrade slot of the
+//! stake pool program in their calculator state, and fail with an unexpected program upgrade
+//! error if it differs from the slot in the stake pool program's programdata account.
+//! After a stake pool program upgrade, every trade involving its LSTs reverts until
+//! the calculator's manager updates the recorded slot, even though quotes still succeed.
+//!
+//! [`InfAmm::with_calc_upgrade_checks`] additionally fetches the calculator states and
+//! programdata accounts of the calculators in use, records calculators whose slots differ in
+//! [`InfAmm::outdated_calcs`], and quoting their LSTs returns [`InfAmmErr::CalcProgramOutdated`].
+
+use inf1_std::{
+    inf1_ctl_core::typedefs::lst_state::LstState,
+    inf1_pp_core::pair::Pair,
+    inf1_svc_ag_std::{inf1_svc_lido_core, inf1_svc_marinade_core, inf1_svc_spl_core},
+    update::Account as _,
+};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    err::InfAmmErr, pair_update::in_scope, pda::find_pda, update::UpdateMapWithOwner, InfAmm,
+};
+
+const BPF_LOADER_UPGRADEABLE_ID: [u8; 32] =
+    Pubkey::from_str_const("BPFLoaderUpgradeab1e11111111111111111111111").to_bytes();
+
+const CALCULATOR_STATE_SEED: &[u8] = b"state";
+
+/// Offsets in calculator state data: `manager: Pubkey, last_upgrade_slot: u64`
+const CALCULATOR_STATE_LAST_UPGRADE_SLOT_OFFSET: usize = 32;
+
+/// Offsets in programdata account data, a bincode `UpgradeableLoaderState::ProgramData`:
+/// `discriminant: u32, slot: u64, upgrade_authority_address: Option<Pubkey>`
+const PROGRAMDATA_DISCM: u32 = 3;
+const PROGRAMDATA_SLOT_OFFSET: usize = 4;
+
+/// `(sol val calculator program, stake pool program)` of calculators that check
+/// the stake pool program's last upgrade slot
+const CALC_POOL_PROGRAMS: [([u8; 32], [u8; 32]); 5] = [
+    (
+        inf1_svc_spl_core::keys::spl::ID,
+        inf1_svc_spl_core::keys::spl::POOL_PROG_ID,
+    ),
+    (
+        inf1_svc_spl_core::keys::sanctum_spl::ID,
+        inf1_svc_spl_core::keys::sanctum_spl::POOL_PROG_ID,
+    ),
+    (
+        inf1_svc_spl_core::keys::sanctum_spl_multi::ID,
+        inf1_svc_spl_core::keys::sanctum_spl_multi::POOL_PROG_ID,
+    ),
+    (
+        inf1_svc_marinade_core::ID,
+        inf1_svc_marinade_core::keys::POOL_PROG_ID,
+    ),
+    (
+        inf1_svc_lido_core::ID,
+        inf1_svc_lido_core::keys::POOL_PROG_ID,
+    ),
+];
+
+/// The calculator state and stake pool programdata accounts of `calc_program`,
+/// `None` if it does not check the stake pool program's last upgrade slot
+fn upgrade_accs(calc_program: &[u8; 32]) -> Option<[[u8; 32]; 2]> {
+    let (_, pool_program) = CALC_POOL_PROGRAMS.iter().find(|(c, _)| c == calc_program)?;
+    let (calc_state, _) = find_pda(&[CALCULATOR_STATE_SEED], calc_program)?;
+    let (programdata, _) = find_pda(&[pool_program.as_slice()], &BPF_LOADER_UPGRADEABLE_ID)?;
+    Some([calc_state, programdata])
+}
+
+fn calc_state_last_upgrade_slot(data: &[u8]) -> Option<u64> {
+    data.get(CALCULATOR_STATE_LAST_UPGRADE_SLOT_OFFSET..)?
+        .first_chunk()
+        .map(|s| u64::from_le_bytes(*s))
+}
+
+fn programdata_slot(data: &[u8]) -> Option<u64> {
+    let discm = u32::from_le_bytes(*data.first_chunk()?);
+    if discm != PROGRAMDATA_DISCM {
+        return None;
+    }
+    data.get(PROGRAMDATA_SLOT_OFFSET..)?
+        .first_chunk()
+        .map(|s| u64::from_le_bytes(*s))
+}
+
+impl InfAmm {
+    /// Makes [`jupiter_amm_interface::Amm::update`] check whether sol val calculators
+    /// are up to date with upgrades of their stake pool programs, see [`crate::calc_upgrade`]
+    #[inline]
+    pub fn with_calc_upgrade_checks(mut self) -> Self {
+        self.calc_upgrade_checks = true;
+        self
+    }
+
+    /// Sol val calculator programs of LSTs on the LST state list that are in `scope`, deduped
+    fn calc_programs_in(&self, scope: Option<&[[u8; 32]]>) -> Vec<[u8; 32]> {
+        let mut res: Vec<[u8; 32]> = Vec::new();
+        let calcs = self
+            .inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state())
+            .filter(|lst_state| in_scope(scope, &lst_state.mint))
+            .map(|lst_state| lst_state.sol_value_calculator);
+        for calc in calcs {
+            if !res.contains(&calc) {
+                res.push(calc);
+            }
+        }
+        res
+    }
+
+    pub(crate) fn calc_upgrade_accs_to_update(
+        &self,
+        scope: Option<&[[u8; 32]]>,
+    ) -> impl Iterator<Item = [u8; 32]> {
+        let calcs = if self.calc_upgrade_checks {
+            self.calc_programs_in(scope)
+        } else {
+            Vec::new()
+        };
+        calcs.into_iter().filter_map(|c| upgrade_accs(&c)).flatten()
+    }
+
+    /// Records which calculators in `fetched` are outdated.
+    /// Calculators whose accounts are missing or malformed are assumed to be up to date.
+    /// Calculators only used by LSTs out of `scope` retain their previous record.
+    ///
+    /// Should only be called after a successful update.
+    pub(crate) fn record_outdated_calcs(
+        &mut self,
+        fetched: &impl UpdateMapWithOwner,
+        scope: Option<&[[u8; 32]]>,
+    ) {
+        if !self.calc_upgrade_checks {
+            self.outdated_calcs.clear();
+            return;
+        }
+        for calc in self.calc_programs_in(scope) {
+            let is_outdated = upgrade_accs(&calc).is_some_and(|[calc_state, programdata]| {
+                let recorded = fetched
+                    .get_account(&calc_state)
+                    .and_then(|acc| calc_state_last_upgrade_slot(acc.data()));
+                let actual = fetched
+                    .get_account(&programdata)
+                    .and_then(|acc| programdata_slot(acc.data()));
+                matches!((recorded, actual), (Some(r), Some(a)) if r != a)
+            });
+            if is_outdated {
+                self.outdated_calcs.insert(calc);
+            } else {
+                self.outdated_calcs.remove(&calc);
+            }
+        }
+    }
+
+    pub(crate) fn check_calcs_up_to_date(&self, pair: &Pair<&[u8; 32]>) -> Result<(), InfAmmErr> {
+        if self.outdated_calcs.is_empty() {
+            return Ok(());
+        }
+        let Ok(list) = self.inner.try_lst_state_list() else {
+            return Ok(());
+        };
+        match list
+            .iter()
+            .map(|l| l.into_lst_state())
+            .filter(|LstState { mint, .. }| mint == pair.inp || mint == pair.out)
+            .find(|l| self.outdated_calcs.contains(&l.sol_value_calculator))
+        {
+            Some(l) => Err(InfAmmErr::CalcProgramOutdated {
+                program: l.sol_value_calculator,
+            }),
+            None => Ok(()),
+        }
+    }
+}
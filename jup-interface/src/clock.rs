@@ -1,13 +1,84 @@
 //! Utils for handling special-casing of sysvar Clock account
 
-use inf1_std::inf1_svc_ag_std::inf1_svc_marinade_core::sanctum_marinade_liquid_staking_core::MSOL_MINT_ADDR;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 
-use crate::consts::{INF_MINT_ADDR, WSOL_MINT_ADDR};
+use crate::{err::InfAmmErr, InfAmm};
 
-pub const fn is_epoch_affected_lst_mint(mint: &[u8; 32]) -> bool {
-    match *mint {
-        INF_MINT_ADDR | MSOL_MINT_ADDR | WSOL_MINT_ADDR => false,
-        // stsol, spls
-        _ => true,
+/// Where [`InfAmm`] gets the current epoch from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClockMode {
+    /// Load [`jupiter_amm_interface::ClockRef::epoch`] at quoting time.
+    ///
+    /// Sysvar Clock is not included in [`jupiter_amm_interface::Amm::get_accounts_to_update`]
+    /// and `current_epoch=0` on all SolValCalc structs.
+    #[default]
+    ClockRef,
+
+    /// Include sysvar Clock in [`jupiter_amm_interface::Amm::get_accounts_to_update`]
+    /// and apply it to all SolValCalc structs during [`jupiter_amm_interface::Amm::update`],
+    /// so that all quotes between 2 updates use the same epoch.
+    Sysvar,
+}
+
+/// Offset of `epoch` in sysvar Clock account data:
+/// `slot: u64, epoch_start_timestamp: i64, epoch: u64, leader_schedule_epoch: u64, unix_timestamp: i64`
+const CLOCK_EPOCH_OFFSET: usize = 16;
+
+/// Returns `None` if `data` is too short to be sysvar Clock account data
+#[inline]
+pub fn sysvar_clock_epoch(data: &[u8]) -> Option<u64> {
+    let epoch = data.get(CLOCK_EPOCH_OFFSET..)?.first_chunk()?;
+    Some(u64::from_le_bytes(*epoch))
+}
+
+impl InfAmm {
+    /// Detaches this AMM from the [`jupiter_amm_interface::ClockRef`] it was created with,
+    /// so that its epoch and slot only ever change through [`Self::set_clock`].
+    ///
+    /// For reproducible runs, e.g. audit replays: the remaining inputs to updating and
+    /// quoting are the accounts passed to update, which are iterated in LST state list order.
+    /// Internal maps are only used for lookups, so identical inputs give bit-identical results.
+    ///
+    /// Clones of this AMM share the same detached clock.
+    #[inline]
+    pub fn with_deterministic_mode(mut self, slot: u64, epoch: u64) -> Self {
+        self.current_epoch = Arc::new(AtomicU64::new(epoch));
+        self.current_slot = Arc::new(AtomicU64::new(slot));
+        self
+    }
+
+    /// Sets the clock shared with the [`jupiter_amm_interface::ClockRef`] or,
+    /// in [`Self::with_deterministic_mode`], the detached clock.
+    ///
+    /// In [`ClockMode::Sysvar`], quoting uses the epoch of the sysvar Clock applied
+    /// in the last update regardless.
+    #[inline]
+    pub fn set_clock(&self, slot: u64, epoch: u64) {
+        self.current_slot.store(slot, Ordering::Relaxed);
+        self.current_epoch.store(epoch, Ordering::Relaxed);
+    }
+
+    /// The epoch that quoting checks underlying stake pools against.
+    ///
+    /// In [`ClockMode::Sysvar`], this is the epoch of the sysvar Clock
+    /// applied in the last update, 0 if there has been none yet.
+    /// Quoting uses [`Self::try_epoch`] instead, which errs in that case.
+    #[inline]
+    pub fn epoch(&self) -> u64 {
+        self.try_epoch().unwrap_or_default()
+    }
+
+    /// [`Self::epoch`], erring with [`InfAmmErr::ClockNotUpdated`] in [`ClockMode::Sysvar`]
+    /// if no sysvar Clock has been applied yet
+    #[inline]
+    pub fn try_epoch(&self) -> Result<u64, InfAmmErr> {
+        match self.clock_mode {
+            ClockMode::ClockRef => Ok(self.current_epoch.load(Ordering::Relaxed)),
+            ClockMode::Sysvar => self.sysvar_clock_epoch.ok_or(InfAmmErr::ClockNotUpdated),
+        }
     }
 }
@@ -0,0 +1,102 @@
+//! Compute unit estimates of INF instructions, for setting compute budgets
+//! during transaction assembly.
+//!
+//! Estimates are the sum of the instruction's base cost and the cost of its
+//! sol val calc and pricing program CPIs, taken from a table calibrated against
+//! the fixture programs in mollusk, with headroom. They are upper bounds for
+//! typical trades, not exact costs: programs upgraded after calibration may cost more.
+
+use inf1_std::{
+    inf1_pp_ag_std::{inf1_pp_flatfee_core, PricingAgTy},
+    inf1_svc_ag_std::{
+        inf1_svc_lido_core, inf1_svc_marinade_core, inf1_svc_spl_core, inf1_svc_wsol_core,
+    },
+};
+
+use crate::{native_sol::native_sol_to_wsol, trade_kind::TradeKind, InfAmm};
+
+/// `SwapExactIn` and `SwapExactOut` without CPIs: account checks,
+/// token transfers in and out and the protocol fee transfer
+pub const SWAP_BASE_CU: u32 = 30_000;
+
+/// `AddLiquidity` without CPIs: account checks, token transfer in,
+/// protocol fee transfer and LP token mint
+pub const ADD_LIQUIDITY_BASE_CU: u32 = 30_000;
+
+/// `RemoveLiquidity` without CPIs: account checks, LP token burn,
+/// token transfer out and protocol fee transfer
+pub const REMOVE_LIQUIDITY_BASE_CU: u32 = 30_000;
+
+/// Cost of a sol val calc program not in [`sol_val_calc_cu`]'s table,
+/// the most expensive known calculator
+pub const UNKNOWN_SOL_VAL_CALC_CU: u32 = 25_000;
+
+/// Cost of a pricing program not in [`pricing_program_cu`]'s table,
+/// the most expensive known pricing program
+pub const UNKNOWN_PRICING_PROGRAM_CU: u32 = 8_000;
+
+/// Cost of a single CPI to the sol val calc program `program_id`
+#[inline]
+pub const fn sol_val_calc_cu(program_id: &[u8; 32]) -> u32 {
+    match *program_id {
+        inf1_svc_wsol_core::ID => 3_000,
+        inf1_svc_spl_core::keys::spl::ID
+        | inf1_svc_spl_core::keys::sanctum_spl::ID
+        | inf1_svc_spl_core::keys::sanctum_spl_multi::ID => 25_000,
+        inf1_svc_marinade_core::ID => 20_000,
+        inf1_svc_lido_core::ID => 20_000,
+        _ => UNKNOWN_SOL_VAL_CALC_CU,
+    }
+}
+
+/// Cost of a single CPI to the pricing program `program_id`
+#[inline]
+pub fn pricing_program_cu(program_id: &[u8; 32]) -> u32 {
+    if *program_id == inf1_pp_flatfee_core::ID {
+        6_000
+    } else if program_id == PricingAgTy::FlatSlab(()).program_id() {
+        8_000
+    } else {
+        UNKNOWN_PRICING_PROGRAM_CU
+    }
+}
+
+impl InfAmm {
+    /// Estimated compute units consumed by the INF instruction of `trade_kind`
+    /// for `inp` -> `out`, see [`crate::compute_units`].
+    ///
+    /// `trade_kind` should be [`Self::trade_type`] of the same pair.
+    /// LSTs not on the LST state list are estimated at [`UNKNOWN_SOL_VAL_CALC_CU`].
+    /// Does not include the compute budget or other instructions of the transaction.
+    pub fn estimated_compute_units(
+        &self,
+        inp: &[u8; 32],
+        out: &[u8; 32],
+        trade_kind: TradeKind,
+    ) -> u32 {
+        let (base, calc_mints) = match trade_kind {
+            TradeKind::Swap => (SWAP_BASE_CU, [Some(inp), Some(out)]),
+            TradeKind::AddLiquidity => (ADD_LIQUIDITY_BASE_CU, [Some(inp), None]),
+            TradeKind::RemoveLiquidity => (REMOVE_LIQUIDITY_BASE_CU, [None, Some(out)]),
+        };
+        let calcs: u32 = calc_mints
+            .into_iter()
+            .flatten()
+            .map(|mint| {
+                self.sol_val_calc_of(native_sol_to_wsol(mint))
+                    .map_or(UNKNOWN_SOL_VAL_CALC_CU, |p| sol_val_calc_cu(&p))
+            })
+            .sum();
+        base + calcs + pricing_program_cu(&self.inner.pool.pricing_program)
+    }
+
+    pub(crate) fn sol_val_calc_of(&self, mint: &[u8; 32]) -> Option<[u8; 32]> {
+        self.inner
+            .try_lst_state_list()
+            .ok()?
+            .iter()
+            .map(|l| l.into_lst_state())
+            .find(|l| l.mint == *mint)
+            .map(|l| l.sol_value_calculator)
+    }
+}
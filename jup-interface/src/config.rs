@@ -0,0 +1,572 @@
+//! Deployment-specific addresses, for using [`crate::InfAmm`] against
+//! non-mainnet or forked deployments of the INF controller program.
+//!
+//! [`inf1_std`] always works with the mainnet addresses of the controller program,
+//! its pool state and its LST state list. [`crate::InfAmm`] translates between these and
+//! the configured addresses at its boundaries: accounts to update, fetched accounts
+//! and instruction accounts.
+//!
+//! The same goes for the PDAs [`inf1_std`] derives from the mainnet addresses, such as
+//! LST reserves and protocol fee accumulators, see [`DeploymentPdas`].
+//!
+//! Pricing programs and sol value calculators are identified by their program IDs in the
+//! pool state and LST state list, so deployments must use the mainnet ones, which is why
+//! [`crate::PROGRAM_DEPENDENCIES`] is the same on every [`Cluster`].
+//!
+//! [`InfAmmBuilder`] constructs an [`InfAmm`] with a deployment and any other
+//! construction-time knobs:
+//!
+//! ```ignore
+//! let inf = InfAmm::builder()
+//!     .amm_context(&amm_context)
+//!     .spl_lsts(spl_lsts)
+//!     .lenient_updates(true)
+//!     .build(&lst_state_list_keyed_account)?;
+//! ```
+//!
+//! [`Cluster`] selects the [`InfAmmConfig`] of well-known deployments, or of any other:
+//!
+//! ```ignore
+//! let inf = InfAmm::builder()
+//!     .cluster(Cluster::Custom {
+//!         api_url: None,
+//!         program_ids: local_validator_program_ids,
+//!     })
+//!     .spl_lsts(local_spl_lsts)
+//!     .build(&lst_state_list_keyed_account)?;
+//! ```
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{atomic::AtomicU64, Arc},
+};
+
+use anyhow::Result;
+use inf1_std::inf1_ctl_core::{
+    self,
+    accounts::pool_state::PoolState,
+    keys::{LST_STATE_LIST_ID, POOL_STATE_ID},
+};
+use jupiter_amm_interface::{AmmContext, ClockRef, KeyedAccount};
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{
+    consts::{DEFAULT_MAINNET_POOL, INF_MINT_ADDR},
+    lst_info::{ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAMS},
+    lst_list_cache::LstListCache,
+    lst_state_list_format::{LstStateListFormat, DEFAULT_LST_STATE_LIST_FORMATS},
+    metrics::InfAmmMetrics,
+    mint_meta::MintMeta,
+    pda::{PdaFns, PROTOCOL_FEE_SEED, REBALANCE_RECORD_SEED},
+    spl_lsts::{load_default_spl_lsts, FetchPolicy},
+    InfAmm,
+};
+
+#[derive(Debug, Clone)]
+pub struct InfAmmConfig {
+    pub program_id: [u8; 32],
+    pub pool_state_id: [u8; 32],
+    pub lst_state_list_id: [u8; 32],
+
+    /// Pool state used before the first update,
+    /// see [`DEFAULT_MAINNET_POOL`]
+    pub pricing_defaults: PoolState,
+
+    /// How PDAs are derived, see [`crate::pda`]
+    pub pda: PdaFns,
+
+    /// Where SPL LSTs and mint metadata are loaded from if not provided,
+    /// see [`crate::spl_lsts`]
+    pub lst_list: LstListSource,
+
+    /// Layouts the LST state list account is parsed from, see [`crate::lst_state_list_format`]
+    pub lst_state_list_formats: &'static [&'static dyn LstStateListFormat],
+}
+
+/// Where the LST list of a deployment is loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LstListSource {
+    /// The mainnet Sanctum LST list, falling back to the copy bundled with this crate,
+    /// see [`crate::spl_lsts::load_sanctum_spl_lsts`]
+    Sanctum,
+
+    /// An API serving the same schema as the Sanctum LST list.
+    /// No LSTs are loaded if fetching fails or the `remote` feature is disabled
+    Url(Cow<'static, str>),
+
+    /// No LST list, SPL LSTs must be provided with
+    /// [`InfAmmBuilder::spl_lsts`] or [`crate::spl_lsts::SPL_LSTS_PATH_ENV`]
+    None,
+}
+
+impl InfAmmConfig {
+    pub const MAINNET: Self = Self {
+        program_id: inf1_ctl_core::ID,
+        pool_state_id: POOL_STATE_ID,
+        lst_state_list_id: LST_STATE_LIST_ID,
+        pricing_defaults: DEFAULT_MAINNET_POOL,
+        pda: PdaFns::FAST,
+        lst_list: LstListSource::Sanctum,
+        lst_state_list_formats: DEFAULT_LST_STATE_LIST_FORMATS,
+    };
+
+    /// Maps a mainnet address used by [`inf1_std`] to this deployment's address
+    #[inline]
+    pub const fn to_deployment<'a>(&'a self, pk: &'a [u8; 32]) -> &'a [u8; 32] {
+        match *pk {
+            inf1_ctl_core::ID => &self.program_id,
+            POOL_STATE_ID => &self.pool_state_id,
+            LST_STATE_LIST_ID => &self.lst_state_list_id,
+            _ => pk,
+        }
+    }
+
+    /// Inverse of [`Self::to_deployment`]
+    #[inline]
+    pub fn to_mainnet<'a>(&self, pk: &'a [u8; 32]) -> &'a [u8; 32] {
+        if *pk == self.program_id {
+            &inf1_ctl_core::ID
+        } else if *pk == self.pool_state_id {
+            &POOL_STATE_ID
+        } else if *pk == self.lst_state_list_id {
+            &LST_STATE_LIST_ID
+        } else {
+            pk
+        }
+    }
+
+    /// Applies [`Self::to_deployment`] to all `metas`
+    #[inline]
+    pub fn metas_to_deployment(&self, metas: &mut [AccountMeta]) {
+        metas.iter_mut().for_each(|m| {
+            let pk = *self.to_deployment(m.pubkey.as_array());
+            m.pubkey = Pubkey::new_from_array(pk);
+        });
+    }
+
+    /// Whether PDAs derived from [`Self::program_id`] and [`Self::pool_state_id`]
+    /// are the same as on mainnet, i.e. [`DeploymentPdas`] are not needed
+    #[inline]
+    pub fn has_mainnet_pdas(&self) -> bool {
+        self.program_id == inf1_ctl_core::ID && self.pool_state_id == POOL_STATE_ID
+    }
+
+    /// The pool's reserves token account for `mint`, the ATA of [`Self::pool_state_id`].
+    ///
+    /// `token_program` is the owner program of `mint`.
+    #[inline]
+    pub fn lst_reserves(&self, token_program: &[u8; 32], mint: &[u8; 32]) -> Option<[u8; 32]> {
+        self.find_ata(&self.pool_state_id, token_program, mint)
+    }
+
+    /// The protocol fee PDA of [`Self::program_id`], which owns the protocol fee accumulators
+    #[inline]
+    pub fn protocol_fee(&self) -> Option<[u8; 32]> {
+        (self.pda.find)(&[PROTOCOL_FEE_SEED], &self.program_id).map(|(pda, _bump)| pda)
+    }
+
+    /// The token account that accumulates protocol fees in `mint`,
+    /// the ATA of [`Self::protocol_fee`]
+    #[inline]
+    pub fn protocol_fee_accumulator(
+        &self,
+        token_program: &[u8; 32],
+        mint: &[u8; 32],
+    ) -> Option<[u8; 32]> {
+        self.find_ata(&self.protocol_fee()?, token_program, mint)
+    }
+
+    /// The rebalance record PDA of [`Self::program_id`],
+    /// see [`crate::pda::REBALANCE_RECORD_SEED`]
+    #[inline]
+    pub fn rebalance_record(&self) -> Option<[u8; 32]> {
+        (self.pda.find)(&[REBALANCE_RECORD_SEED], &self.program_id).map(|(pda, _bump)| pda)
+    }
+
+    fn find_ata(
+        &self,
+        owner: &[u8; 32],
+        token_program: &[u8; 32],
+        mint: &[u8; 32],
+    ) -> Option<[u8; 32]> {
+        (self.pda.find)(
+            &[owner.as_slice(), token_program.as_slice(), mint.as_slice()],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .map(|(pda, _bump)| pda)
+    }
+}
+
+/// Mainnet <-> deployment addresses of the PDAs that [`inf1_std`] derives from the mainnet
+/// controller program and pool state: the protocol fee and rebalance record PDAs,
+/// and each LST's reserves and protocol fee accumulators under either token program.
+///
+/// Empty if [`InfAmmConfig::has_mainnet_pdas`].
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentPdas {
+    mints: Vec<[u8; 32]>,
+    to_deployment: HashMap<[u8; 32], [u8; 32]>,
+    to_mainnet: HashMap<[u8; 32], [u8; 32]>,
+}
+
+impl DeploymentPdas {
+    /// For the LSTs `mints` of `config`'s deployment
+    pub fn new(config: &InfAmmConfig, mints: Vec<[u8; 32]>) -> Self {
+        if config.has_mainnet_pdas() {
+            return Self::default();
+        }
+        let mainnet = &InfAmmConfig::MAINNET;
+        let lst_pdas = mints.iter().flat_map(|mint| {
+            TOKEN_PROGRAMS.iter().flat_map(move |tp| {
+                [
+                    (
+                        mainnet.lst_reserves(tp, mint),
+                        config.lst_reserves(tp, mint),
+                    ),
+                    (
+                        mainnet.protocol_fee_accumulator(tp, mint),
+                        config.protocol_fee_accumulator(tp, mint),
+                    ),
+                ]
+            })
+        });
+        let (to_deployment, to_mainnet) = [
+            (mainnet.protocol_fee(), config.protocol_fee()),
+            (mainnet.rebalance_record(), config.rebalance_record()),
+        ]
+        .into_iter()
+        .chain(lst_pdas)
+        .filter_map(|(m, d)| Some(((m?, d?), (d?, m?))))
+        .unzip();
+        Self {
+            mints,
+            to_deployment,
+            to_mainnet,
+        }
+    }
+
+    /// `self` if it is of the same `mints`, otherwise [`Self::new`]
+    pub fn refreshed(self: &Arc<Self>, config: &InfAmmConfig, mints: Vec<[u8; 32]>) -> Arc<Self> {
+        if config.has_mainnet_pdas() || self.mints == mints {
+            Arc::clone(self)
+        } else {
+            Arc::new(Self::new(config, mints))
+        }
+    }
+
+    /// [`InfAmmConfig::to_deployment`], including the PDAs of `self`
+    #[inline]
+    pub fn to_deployment<'a>(&'a self, config: &'a InfAmmConfig, pk: &'a [u8; 32]) -> &'a [u8; 32] {
+        self.to_deployment
+            .get(pk)
+            .unwrap_or_else(|| config.to_deployment(pk))
+    }
+
+    /// [`InfAmmConfig::to_mainnet`], including the PDAs of `self`
+    #[inline]
+    pub fn to_mainnet<'a>(&'a self, config: &InfAmmConfig, pk: &'a [u8; 32]) -> &'a [u8; 32] {
+        self.to_mainnet
+            .get(pk)
+            .unwrap_or_else(|| config.to_mainnet(pk))
+    }
+}
+
+impl InfAmm {
+    /// Maps a mainnet address used by [`inf1_std`] to this deployment's address,
+    /// see [`crate::config`]
+    #[inline]
+    pub fn to_deployment<'a>(&'a self, pk: &'a [u8; 32]) -> &'a [u8; 32] {
+        self.deployment_pdas.to_deployment(&self.config, pk)
+    }
+
+    /// Inverse of [`Self::to_deployment`]
+    #[inline]
+    pub fn to_mainnet<'a>(&'a self, pk: &'a [u8; 32]) -> &'a [u8; 32] {
+        self.deployment_pdas.to_mainnet(&self.config, pk)
+    }
+
+    /// Applies [`Self::to_deployment`] to all `metas`
+    #[inline]
+    pub(crate) fn metas_to_deployment(&self, metas: &mut [AccountMeta]) {
+        metas.iter_mut().for_each(|m| {
+            let pk = *self.to_deployment(m.pubkey.as_array());
+            m.pubkey = Pubkey::new_from_array(pk);
+        });
+    }
+
+    /// Updates [`Self::deployment_pdas`] to the LSTs currently on the LST state list
+    pub(crate) fn refresh_deployment_pdas(&mut self) {
+        let mints = self
+            .inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state().mint)
+            .collect();
+        self.deployment_pdas = self.deployment_pdas.refreshed(&self.config, mints);
+    }
+}
+
+impl Default for InfAmmConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
+
+/// Addresses of a deployment of the INF controller program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClusterProgramIds {
+    pub program_id: [u8; 32],
+    pub pool_state_id: [u8; 32],
+    pub lst_state_list_id: [u8; 32],
+    pub lp_mint: [u8; 32],
+}
+
+impl ClusterProgramIds {
+    pub const MAINNET: Self = Self {
+        program_id: inf1_ctl_core::ID,
+        pool_state_id: POOL_STATE_ID,
+        lst_state_list_id: LST_STATE_LIST_ID,
+        lp_mint: INF_MINT_ADDR,
+    };
+}
+
+/// Well-known deployments of INF, see [`crate::config`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Cluster {
+    #[default]
+    Mainnet,
+
+    /// Any other deployment, e.g. on testnet or a local validator
+    Custom {
+        /// See [`LstListSource::Url`]. No LST list if `None`
+        api_url: Option<String>,
+        program_ids: ClusterProgramIds,
+    },
+}
+
+impl Cluster {
+    #[inline]
+    pub fn program_ids(&self) -> ClusterProgramIds {
+        match self {
+            Self::Mainnet => ClusterProgramIds::MAINNET,
+            Self::Custom { program_ids, .. } => *program_ids,
+        }
+    }
+
+    /// The LP token mint, [`INF_MINT_ADDR`] on mainnet
+    #[inline]
+    pub fn inf_mint(&self) -> [u8; 32] {
+        self.program_ids().lp_mint
+    }
+
+    pub fn config(&self) -> InfAmmConfig {
+        match self {
+            Self::Mainnet => InfAmmConfig::MAINNET,
+            Self::Custom {
+                api_url,
+                program_ids:
+                    ClusterProgramIds {
+                        program_id,
+                        pool_state_id,
+                        lst_state_list_id,
+                        lp_mint,
+                    },
+            } => InfAmmConfig {
+                program_id: *program_id,
+                pool_state_id: *pool_state_id,
+                lst_state_list_id: *lst_state_list_id,
+                pricing_defaults: PoolState {
+                    lp_token_mint: *lp_mint,
+                    ..DEFAULT_MAINNET_POOL
+                },
+                lst_list: match api_url {
+                    Some(url) => LstListSource::Url(url.clone().into()),
+                    None => LstListSource::None,
+                },
+                ..InfAmmConfig::MAINNET
+            },
+        }
+    }
+}
+
+impl From<&Cluster> for InfAmmConfig {
+    #[inline]
+    fn from(cluster: &Cluster) -> Self {
+        cluster.config()
+    }
+}
+
+impl From<Cluster> for InfAmmConfig {
+    #[inline]
+    fn from(cluster: Cluster) -> Self {
+        cluster.config()
+    }
+}
+
+/// See [`crate::config`]
+#[derive(Debug, Clone, Default)]
+pub struct InfAmmBuilder {
+    spl_lsts: Option<HashMap<[u8; 32], [u8; 32]>>,
+    offline: bool,
+    lst_list_cache: Option<LstListCache>,
+    fetch_policy: Option<FetchPolicy>,
+    /// `(current_epoch, current_slot)`, see [`Self::amm_context`]
+    clock: Option<(Arc<AtomicU64>, Arc<AtomicU64>)>,
+    pricing_default: Option<PoolState>,
+    pda: Option<PdaFns>,
+    mint_metas: Option<HashMap<[u8; 32], MintMeta>>,
+    config: InfAmmConfig,
+    lenient_updates: bool,
+    metrics: Option<Arc<dyn InfAmmMetrics>>,
+}
+
+impl InfAmm {
+    /// See [`crate::config`]
+    #[inline]
+    pub fn builder() -> InfAmmBuilder {
+        InfAmmBuilder::default()
+    }
+}
+
+impl InfAmmBuilder {
+    /// SPL LST mint -> stake pool mapping, see [`InfAmm::new`].
+    ///
+    /// If not set, loaded as in [`jupiter_amm_interface::Amm::from_keyed_account`],
+    /// see [`crate::spl_lsts`]
+    #[inline]
+    pub fn spl_lsts(mut self, spl_lsts: HashMap<[u8; 32], [u8; 32]>) -> Self {
+        self.spl_lsts = Some(spl_lsts);
+        self
+    }
+
+    /// See [`InfAmm::with_mint_metas`]. If not set, taken from the Sanctum LST list
+    /// that SPL LSTs are loaded from, see [`crate::mint_meta`]
+    #[inline]
+    pub fn mint_metas(mut self, mint_metas: HashMap<[u8; 32], MintMeta>) -> Self {
+        self.mint_metas = Some(mint_metas);
+        self
+    }
+
+    /// If `true` and [`Self::spl_lsts`] is not set, only the copy of the Sanctum LST list
+    /// bundled with this crate is used instead of fetching it, so that building does not
+    /// make any network requests
+    #[inline]
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// On-disk cache of the Sanctum LST list used if [`Self::spl_lsts`] is not set.
+    /// If [`Self::offline`], read regardless of its age.
+    ///
+    /// Defaults to that at [`crate::lst_list_cache::LST_LIST_CACHE_PATH_ENV`], if set
+    #[inline]
+    pub fn lst_list_cache(mut self, cache: LstListCache) -> Self {
+        self.lst_list_cache = Some(cache);
+        self
+    }
+
+    /// Retries and timeouts of fetching the LST list used if [`Self::spl_lsts`] is not set,
+    /// including those of [`Self::lst_list_cache`].
+    ///
+    /// Defaults to [`FetchPolicy::DEFAULT`], or that of the cache
+    #[inline]
+    pub fn fetch_policy(mut self, fetch_policy: FetchPolicy) -> Self {
+        self.fetch_policy = Some(fetch_policy);
+        self
+    }
+
+    /// Shares the clock of `amm_context`. Defaults to a clock at slot 0 and epoch 0 that
+    /// only changes through [`InfAmm::set_clock`]
+    #[inline]
+    pub fn amm_context(mut self, amm_context: &AmmContext) -> Self {
+        self.clock = Some((
+            amm_context.clock_ref.epoch.clone(),
+            amm_context.clock_ref.slot.clone(),
+        ));
+        self
+    }
+
+    /// Pool state used before the first update, overrides that of [`Self::cluster`]
+    #[inline]
+    pub fn pricing_default(mut self, pool: PoolState) -> Self {
+        self.pricing_default = Some(pool);
+        self
+    }
+
+    /// How PDAs are derived, overrides that of [`Self::cluster`], see [`crate::pda`]
+    #[inline]
+    pub fn pda(mut self, pda: PdaFns) -> Self {
+        self.pda = Some(pda);
+        self
+    }
+
+    /// Deployment of the INF controller program to use, an [`InfAmmConfig`] or [`Cluster`].
+    /// Defaults to [`InfAmmConfig::MAINNET`]
+    #[inline]
+    pub fn cluster(mut self, config: impl Into<InfAmmConfig>) -> Self {
+        self.config = config.into();
+        self
+    }
+
+    /// See [`InfAmm::with_lenient_updates`]
+    #[inline]
+    pub fn lenient_updates(mut self, lenient_updates: bool) -> Self {
+        self.lenient_updates = lenient_updates;
+        self
+    }
+
+    /// See [`InfAmm::with_metrics`]
+    #[inline]
+    pub fn metrics(mut self, metrics: Arc<dyn InfAmmMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// The `keyed_account` should be the LST state list of [`Self::cluster`]
+    pub fn build(self, keyed_account: &KeyedAccount) -> Result<InfAmm> {
+        let Self {
+            spl_lsts,
+            offline,
+            lst_list_cache,
+            fetch_policy,
+            clock,
+            pricing_default,
+            pda,
+            mint_metas,
+            mut config,
+            lenient_updates,
+            metrics,
+        } = self;
+        let (spl_lsts, sanctum_mint_metas) = match spl_lsts {
+            Some(spl_lsts) => (spl_lsts, None),
+            None => load_default_spl_lsts(offline, lst_list_cache, &config.lst_list, fetch_policy)?,
+        };
+        if let Some(pool) = pricing_default {
+            config.pricing_defaults = pool;
+        }
+        if let Some(pda) = pda {
+            config.pda = pda;
+        }
+        let amm_context = AmmContext {
+            clock_ref: ClockRef::default(),
+        };
+        let mut inf = InfAmm::new_with_config(keyed_account, &amm_context, spl_lsts, config)?;
+        if let Some((current_epoch, current_slot)) = clock {
+            inf.current_epoch = current_epoch;
+            inf.current_slot = current_slot;
+        }
+        inf.lenient_updates = lenient_updates;
+        if let Some(mint_metas) = mint_metas.or(sanctum_mint_metas) {
+            inf = inf.with_mint_metas(mint_metas);
+        }
+        if let Some(metrics) = metrics {
+            inf = inf.with_metrics(metrics);
+        }
+        Ok(inf)
+    }
+}
@@ -9,6 +9,30 @@ pub const INF_MINT_ADDR: [u8; 32] =
 pub const WSOL_MINT_ADDR: [u8; 32] =
     Pubkey::from_str_const("So11111111111111111111111111111111111111112").to_bytes();
 
+/// Sentinel for native SOL, see [`crate::native_sol`]
+pub const NATIVE_SOL_MINT_ADDR: [u8; 32] =
+    Pubkey::from_str_const("So11111111111111111111111111111111111111111").to_bytes();
+
+/// Version of the order of account metas returned by
+/// [`jupiter_amm_interface::Amm::get_swap_and_account_metas`], for downstream signers
+/// that whitelist instruction shapes by account position.
+///
+/// Bumped whenever the position of any account changes. As of this version:
+/// - 0: INF program
+/// - 1: signer
+/// - 2, 3: input mint, output mint
+/// - 4, 5: user's input token account, user's output token account
+/// - 6: protocol fee accumulator
+/// - 7, 8: input mint's token program, output mint's token program
+/// - 9, 10: pool state, LST state list
+/// - 11, 12: input LST's reserves, output LST's reserves. Liquidity instructions
+///   only have the LST's at 11
+///
+/// followed by sol val calculator and pricing program accounts.
+/// `RemoveLiquidity` has the LST before the LP token in each of 2-3, 4-5 and 7-8,
+/// i.e. output before input.
+pub const META_LAYOUT_VERSION: u32 = 1;
+
 /// A dummy mainnet pool that tries to use the latest values of mainnet vars
 /// for vars that affect [`jupiter_amm_interface::Amm::get_accounts_to_update`]
 /// so that [`crate::Inf`] only needs 1 more update cycle before it's functioning
@@ -26,7 +50,9 @@ pub const DEFAULT_MAINNET_POOL: PoolState = PoolState {
     lp_protocol_fee_bps: 0,
 
     // dont-cares, since they dont affect
-    // jup functionality at all
+    // jup functionality at all.
+    // Replaced by the live values in the first update,
+    // see InfAmm::live_pool_state()
     padding: [0],
     admin: [0; 32],
     rebalance_authority: [0; 32],
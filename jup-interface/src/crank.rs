@@ -0,0 +1,291 @@
+//! Stake pool crank instructions to unblock pairs right after epoch rollover.
+//!
+//! Until an SPL stake pool is updated for the current epoch, quoting its LST fails with
+//! `NotUpdated`. Instead of waiting out the staleness window, integrators can prepend the
+//! instructions returned here to their swap to crank-and-swap atomically.
+
+use anyhow::Result;
+use inf1_std::{
+    err::InfErr,
+    inf1_pp_core::pair::Pair,
+    inf1_svc_ag_std::{
+        inf1_svc_lido_core::solido_legacy_core::SYSVAR_CLOCK, inf1_svc_spl_core, SvcAg,
+    },
+};
+use jupiter_amm_interface::AccountMap;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::{err::FmtErr, pda::PdaFns, InfAmm};
+
+/// Max number of validators to update per `UpdateValidatorListBalance` instruction,
+/// same as the spl stake pool CLI's
+pub const MAX_VALIDATORS_TO_UPDATE: usize = 5;
+
+const STAKE_PROGRAM_ID: [u8; 32] =
+    Pubkey::from_str_const("Stake11111111111111111111111111111111111111").to_bytes();
+const SYSVAR_STAKE_HISTORY_ID: [u8; 32] =
+    Pubkey::from_str_const("SysvarStakeHistory1111111111111111111111111").to_bytes();
+
+const WITHDRAW_AUTH_SEED: &[u8] = b"withdraw";
+const TRANSIENT_STAKE_SEED: &[u8] = b"transient";
+
+// StakePool account offsets
+const STAKE_POOL_VALIDATOR_LIST_OFFSET: usize = 98;
+const STAKE_POOL_RESERVE_STAKE_OFFSET: usize = 130;
+const STAKE_POOL_POOL_MINT_OFFSET: usize = 162;
+const STAKE_POOL_MANAGER_FEE_ACCOUNT_OFFSET: usize = 194;
+const STAKE_POOL_TOKEN_PROGRAM_OFFSET: usize = 226;
+
+// ValidatorList account offsets
+const VALIDATOR_LIST_LEN_OFFSET: usize = 5;
+const VALIDATOR_LIST_ENTRIES_OFFSET: usize = 9;
+const VALIDATOR_STAKE_INFO_LEN: usize = 73;
+const VALIDATOR_STAKE_INFO_TRANSIENT_SEED_SUFFIX_OFFSET: usize = 24;
+const VALIDATOR_STAKE_INFO_VALIDATOR_SEED_SUFFIX_OFFSET: usize = 36;
+const VALIDATOR_STAKE_INFO_VOTE_ACCOUNT_OFFSET: usize = 41;
+
+// StakePoolInstruction discriminants
+const UPDATE_VALIDATOR_LIST_BALANCE_IX_DISCM: u8 = 6;
+const UPDATE_STAKE_POOL_BALANCE_IX_DISCM: u8 = 7;
+const CLEANUP_REMOVED_VALIDATOR_ENTRIES_IX_DISCM: u8 = 8;
+
+/// A stake pool that needs to be cranked for the current epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StaleStakePool {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub mint: [u8; 32],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub pool_prog: [u8; 32],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub pool: [u8; 32],
+}
+
+impl InfAmm {
+    /// Returns the SPL-based stake pools of `pair` that have not been updated for the
+    /// current epoch, which blocks quoting for `pair`.
+    ///
+    /// Lido, which also is epoch affected, is not SPL-based and hence is not included.
+    pub fn stale_spl_stake_pools(&self, pair: &Pair<&[u8; 32]>) -> Vec<StaleStakePool> {
+        let current_epoch = self.epoch();
+        [pair.inp, pair.out]
+            .into_iter()
+            .filter_map(|mint| {
+                let pool_prog = match self.inner.try_get_lst_svc(mint).ok()?.as_sol_val_calc()? {
+                    SvcAg::Spl(_) => inf1_svc_spl_core::keys::spl::POOL_PROG_ID,
+                    SvcAg::SanctumSpl(_) => inf1_svc_spl_core::keys::sanctum_spl::POOL_PROG_ID,
+                    SvcAg::SanctumSplMulti(_) => {
+                        inf1_svc_spl_core::keys::sanctum_spl_multi::POOL_PROG_ID
+                    }
+                    SvcAg::Lido(_) | SvcAg::Marinade(_) | SvcAg::Wsol(_) => return None,
+                };
+                if self.lst_calc_epoch(mint)? >= current_epoch {
+                    return None;
+                }
+                Some(StaleStakePool {
+                    mint: *mint,
+                    pool_prog,
+                    pool: *self.inner.spl_lsts.get(mint)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the instructions to crank all of [`Self::stale_spl_stake_pools`] of `pair`,
+    /// empty if `pair` is not blocked by any stake pool.
+    ///
+    /// `account_map` must contain the stake pool account and its validator list account
+    /// (see [`stake_pool_validator_list`]) for every stale stake pool.
+    pub fn crank_ixs_for_pair(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        account_map: &AccountMap,
+    ) -> Result<Vec<Instruction>> {
+        let mut ixs = Vec::new();
+        for StaleStakePool {
+            pool_prog, pool, ..
+        } in self.stale_spl_stake_pools(pair)
+        {
+            let pool_data = account_data(account_map, &pool)?;
+            let validator_list = stake_pool_validator_list(pool_data)
+                .ok_or(FmtErr(InfErr::AccDeser { pk: pool }))?;
+            let validator_list_data = account_data(account_map, &validator_list)?;
+            ixs.extend(
+                spl_stake_pool_crank_ixs(
+                    &self.config.pda,
+                    &pool_prog,
+                    &pool,
+                    pool_data,
+                    validator_list_data,
+                )
+                .ok_or(FmtErr(InfErr::AccDeser { pk: validator_list }))?,
+            );
+        }
+        Ok(ixs)
+    }
+}
+
+fn account_data<'a>(account_map: &'a AccountMap, pk: &[u8; 32]) -> Result<&'a [u8]> {
+    account_map
+        .get(&Pubkey::new_from_array(*pk))
+        .map(|a| a.data.as_slice())
+        .ok_or_else(|| FmtErr(InfErr::MissingAcc { pk: *pk }).into())
+}
+
+/// Returns the validator list address of a stake pool given its account data
+#[inline]
+pub fn stake_pool_validator_list(pool_data: &[u8]) -> Option<[u8; 32]> {
+    chunk_at(pool_data, STAKE_POOL_VALIDATOR_LIST_OFFSET).copied()
+}
+
+/// Returns, in order:
+/// - `UpdateValidatorListBalance` for every validator, in batches of [`MAX_VALIDATORS_TO_UPDATE`]
+/// - `UpdateStakePoolBalance`
+/// - `CleanupRemovedValidatorEntries`
+///
+/// PDAs are found with `pda`, e.g. [`crate::config::InfAmmConfig::pda`].
+///
+/// Returns `None` if `pool_data` or `validator_list_data` is malformed
+pub fn spl_stake_pool_crank_ixs(
+    pda: &PdaFns,
+    pool_prog: &[u8; 32],
+    pool: &[u8; 32],
+    pool_data: &[u8],
+    validator_list_data: &[u8],
+) -> Option<Vec<Instruction>> {
+    let [validator_list, reserve_stake, pool_mint, manager_fee_account, token_program] = [
+        STAKE_POOL_VALIDATOR_LIST_OFFSET,
+        STAKE_POOL_RESERVE_STAKE_OFFSET,
+        STAKE_POOL_POOL_MINT_OFFSET,
+        STAKE_POOL_MANAGER_FEE_ACCOUNT_OFFSET,
+        STAKE_POOL_TOKEN_PROGRAM_OFFSET,
+    ]
+    .map(|offset| chunk_at::<32>(pool_data, offset).copied());
+    let [validator_list, reserve_stake, pool_mint, manager_fee_account, token_program] = [
+        validator_list?,
+        reserve_stake?,
+        pool_mint?,
+        manager_fee_account?,
+        token_program?,
+    ]
+    .map(Pubkey::new_from_array);
+    let pool_prog_id = Pubkey::new_from_array(*pool_prog);
+    let pool_pk = Pubkey::new_from_array(*pool);
+    let (withdraw_auth, _bump) = (pda.find)(&[pool.as_slice(), WITHDRAW_AUTH_SEED], pool_prog)?;
+    let withdraw_auth = Pubkey::new_from_array(withdraw_auth);
+
+    let validator_stake_accs =
+        validator_list_stake_accs(pda, pool_prog, pool, validator_list_data)?;
+
+    let mut ixs: Vec<_> = validator_stake_accs
+        .chunks(MAX_VALIDATORS_TO_UPDATE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let start_index = u32::try_from(i * MAX_VALIDATORS_TO_UPDATE).unwrap_or(u32::MAX);
+            let mut data = vec![UPDATE_VALIDATOR_LIST_BALANCE_IX_DISCM];
+            data.extend(start_index.to_le_bytes());
+            // no_merge = false
+            data.push(0);
+            Instruction {
+                program_id: pool_prog_id,
+                accounts: [
+                    AccountMeta::new_readonly(pool_pk, false),
+                    AccountMeta::new_readonly(withdraw_auth, false),
+                    AccountMeta::new(validator_list, false),
+                    AccountMeta::new(reserve_stake, false),
+                    AccountMeta::new_readonly(SYSVAR_CLOCK.into(), false),
+                    AccountMeta::new_readonly(SYSVAR_STAKE_HISTORY_ID.into(), false),
+                    AccountMeta::new_readonly(STAKE_PROGRAM_ID.into(), false),
+                ]
+                .into_iter()
+                .chain(chunk.iter().flat_map(|(validator_stake, transient_stake)| {
+                    [
+                        AccountMeta::new(*validator_stake, false),
+                        AccountMeta::new(*transient_stake, false),
+                    ]
+                }))
+                .collect(),
+                data,
+            }
+        })
+        .collect();
+
+    ixs.push(Instruction {
+        program_id: pool_prog_id,
+        accounts: vec![
+            AccountMeta::new(pool_pk, false),
+            AccountMeta::new_readonly(withdraw_auth, false),
+            AccountMeta::new(validator_list, false),
+            AccountMeta::new_readonly(reserve_stake, false),
+            AccountMeta::new(manager_fee_account, false),
+            AccountMeta::new(pool_mint, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+        data: vec![UPDATE_STAKE_POOL_BALANCE_IX_DISCM],
+    });
+    ixs.push(Instruction {
+        program_id: pool_prog_id,
+        accounts: vec![
+            AccountMeta::new_readonly(pool_pk, false),
+            AccountMeta::new(validator_list, false),
+        ],
+        data: vec![CLEANUP_REMOVED_VALIDATOR_ENTRIES_IX_DISCM],
+    });
+
+    Some(ixs)
+}
+
+/// Returns `(validator_stake_acc, transient_stake_acc)` of every validator on the list
+fn validator_list_stake_accs(
+    pda: &PdaFns,
+    pool_prog: &[u8; 32],
+    pool: &[u8; 32],
+    validator_list_data: &[u8],
+) -> Option<Vec<(Pubkey, Pubkey)>> {
+    let len = u32::from_le_bytes(*chunk_at(validator_list_data, VALIDATOR_LIST_LEN_OFFSET)?);
+    let entries = validator_list_data.get(VALIDATOR_LIST_ENTRIES_OFFSET..)?;
+    let entries = entries.get(..(len as usize).checked_mul(VALIDATOR_STAKE_INFO_LEN)?)?;
+    entries
+        .chunks_exact(VALIDATOR_STAKE_INFO_LEN)
+        .map(|entry| {
+            let vote: &[u8; 32] = chunk_at(entry, VALIDATOR_STAKE_INFO_VOTE_ACCOUNT_OFFSET)?;
+            let validator_seed_suffix =
+                chunk_at::<4>(entry, VALIDATOR_STAKE_INFO_VALIDATOR_SEED_SUFFIX_OFFSET)?;
+            let transient_seed_suffix =
+                chunk_at::<8>(entry, VALIDATOR_STAKE_INFO_TRANSIENT_SEED_SUFFIX_OFFSET)?;
+
+            // seed suffix is omitted if 0
+            let (validator_stake, _bump) = if u32::from_le_bytes(*validator_seed_suffix) == 0 {
+                (pda.find)(&[vote.as_slice(), pool.as_slice()], pool_prog)?
+            } else {
+                (pda.find)(
+                    &[
+                        vote.as_slice(),
+                        pool.as_slice(),
+                        validator_seed_suffix.as_slice(),
+                    ],
+                    pool_prog,
+                )?
+            };
+            let (transient_stake, _bump) = (pda.find)(
+                &[
+                    TRANSIENT_STAKE_SEED,
+                    vote.as_slice(),
+                    pool.as_slice(),
+                    transient_seed_suffix.as_slice(),
+                ],
+                pool_prog,
+            )?;
+            Some((
+                Pubkey::new_from_array(validator_stake),
+                Pubkey::new_from_array(transient_stake),
+            ))
+        })
+        .collect()
+}
+
+#[inline]
+fn chunk_at<const N: usize>(data: &[u8], at: usize) -> Option<&[u8; N]> {
+    data.get(at..).and_then(|s| s.first_chunk())
+}
@@ -0,0 +1,57 @@
+//! Pruning of state kept for LSTs that were removed from the LST state list.
+//!
+//! Sol val calculators and reserves balances, and the per-LST records of this crate,
+//! are keyed by mint and would otherwise outlive the LST's removal from the list,
+//! since pair-scoped updates retain the records of LSTs out of scope.
+//! Every update prunes the entries of mints that are no longer on the list,
+//! so that a delisted LST's accounts are no longer fetched and it is no longer quotable.
+
+use std::collections::HashSet;
+
+use inf1_std::InfStd;
+
+use crate::InfAmm;
+
+/// Mints on the LST state list of `inner`, `None` if it cannot be deserialized
+fn listed_mints(inner: &InfStd) -> Option<HashSet<[u8; 32]>> {
+    Some(
+        inner
+            .try_lst_state_list()
+            .ok()?
+            .iter()
+            .map(|l| l.into_lst_state().mint)
+            .collect(),
+    )
+}
+
+/// Removes the sol val calculators and reserves balances of mints
+/// that are not on the LST state list of `inner`.
+///
+/// Should be called after the LST state list is updated.
+pub(crate) fn prune_delisted_lsts(inner: &mut InfStd) {
+    let Some(listed) = listed_mints(inner) else {
+        return;
+    };
+    inner.lst_calcs.retain(|mint, _| listed.contains(mint));
+    inner.lst_reserves.retain(|mint, _| listed.contains(mint));
+}
+
+impl InfAmm {
+    /// Removes the per-LST records of mints that are not on the LST state list.
+    /// The LP token mint's token program is retained.
+    ///
+    /// Should only be called after a successful update.
+    pub(crate) fn prune_delisted_records(&mut self) {
+        let Some(listed) = listed_mints(&self.inner) else {
+            return;
+        };
+        let lp_mint = self.inner.pool.lp_token_mint;
+        self.lst_health.retain(|mint, _| listed.contains(mint));
+        self.invalid_reserves.retain(|mint| listed.contains(mint));
+        self.token_programs
+            .retain(|mint, _| *mint == lp_mint || listed.contains(mint));
+        if let Some(d) = &mut self.dynamic_accs {
+            d.retain_mints(|mint| listed.contains(mint));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Opt-in tracking of the accounts applied in each update, so that operators with
+//! account subscriptions only need to feed in the accounts that changed.
+//!
+//! Enabled with [`InfAmm::with_delta_updates`], which makes [`InfAmm`] retain
+//! a copy of the data of all accounts to update.
+
+use std::{
+    collections::HashMap,
+    sync::{atomic::Ordering, Arc},
+};
+
+use anyhow::{anyhow, Result};
+use inf1_std::update::{Account as _, UpdateMap};
+use jupiter_amm_interface::{AccountMap, Amm};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    memory::hash_map_bytes,
+    update::{AccountMapRef, UiAccountDataRef, UpdateMapWithOwner},
+    InfAmm,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedAccount {
+    pub owner: [u8; 32],
+    pub data: Vec<u8>,
+
+    /// Slot of [`jupiter_amm_interface::ClockRef`] when this account was last fed in,
+    /// whether or not it changed
+    pub slot: u64,
+}
+
+/// Accounts last applied, keyed by deployment address
+#[derive(Debug, Clone, Default)]
+pub struct DeltaTracker(Arc<HashMap<[u8; 32], AppliedAccount>>);
+
+impl DeltaTracker {
+    #[inline]
+    pub fn get(&self, pk: &[u8; 32]) -> Option<&AppliedAccount> {
+        self.0.get(pk)
+    }
+
+    #[inline]
+    pub(crate) fn heap_bytes(&self) -> usize {
+        hash_map_bytes(&*self.0) + self.0.values().map(|a| a.data.capacity()).sum::<usize>()
+    }
+
+    /// Records the `accounts_to_update` present in `fetched` as seen at `slot`
+    pub(crate) fn record(
+        &mut self,
+        accounts_to_update: &[Pubkey],
+        fetched: &impl UpdateMapWithOwner,
+        slot: u64,
+    ) {
+        let applied = Arc::make_mut(&mut self.0);
+        accounts_to_update.iter().for_each(|pk| {
+            let pk = pk.as_array();
+            let (Some(acc), Some(owner)) = (fetched.get_account(pk), fetched.get_owner(pk)) else {
+                return;
+            };
+            applied.insert(
+                *pk,
+                AppliedAccount {
+                    owner,
+                    data: acc.data().to_vec(),
+                    slot,
+                },
+            );
+        });
+    }
+}
+
+impl UpdateMap for DeltaTracker {
+    type Account<'acc>
+        = UiAccountDataRef<'acc>
+    where
+        Self: 'acc;
+
+    #[inline]
+    fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+        self.0.get(pk).map(|acc| UiAccountDataRef(&acc.data))
+    }
+}
+
+impl UpdateMapWithOwner for DeltaTracker {
+    #[inline]
+    fn get_owner(&self, pk: &[u8; 32]) -> Option<[u8; 32]> {
+        self.0.get(pk).map(|acc| acc.owner)
+    }
+}
+
+impl InfAmm {
+    /// Retain the accounts applied in each update, enabling [`Self::update_delta`]
+    /// and filtering with [`Self::changed_accounts`]
+    #[inline]
+    pub fn with_delta_updates(mut self) -> Self {
+        self.delta = Some(DeltaTracker::default());
+        self
+    }
+
+    /// The entries of `account_map` whose data or owner differ from those last applied.
+    ///
+    /// Returns all of `account_map` if delta updates are not enabled.
+    pub fn changed_accounts(&self, account_map: &AccountMap) -> AccountMap {
+        account_map
+            .iter()
+            .filter(|(pk, acc)| {
+                self.delta
+                    .as_ref()
+                    .and_then(|d| d.get(pk.as_array()))
+                    .is_none_or(|applied| {
+                        applied.data != acc.data || applied.owner != acc.owner.to_bytes()
+                    })
+            })
+            .map(|(pk, acc)| (*pk, acc.clone()))
+            .collect()
+    }
+
+    /// The accounts of [`Amm::get_accounts_to_update`] that have not been
+    /// fed in at or after `slot`, including those never fed in.
+    ///
+    /// Returns all accounts to update if delta updates are not enabled.
+    pub fn get_accounts_to_update_since(&self, slot: u64) -> Vec<Pubkey> {
+        let mut res = self.get_accounts_to_update();
+        if let Some(d) = &self.delta {
+            res.retain(|pk| d.get(pk.as_array()).is_none_or(|a| a.slot < slot));
+        }
+        res
+    }
+
+    /// [`Amm::update`] with only the accounts that changed since the last update,
+    /// the rest are taken from those retained. `delta` may contain all accounts.
+    ///
+    /// Errs if delta updates are not enabled, see [`Self::with_delta_updates`]
+    pub fn update_delta(&mut self, delta: &AccountMap) -> Result<()> {
+        let mut tracker = self
+            .delta
+            .take()
+            .ok_or_else(|| anyhow!("Delta updates not enabled"))?;
+        let accounts_to_update = self.get_accounts_to_update();
+        tracker.record(
+            &accounts_to_update,
+            &AccountMapRef(delta),
+            self.current_slot.load(Ordering::Relaxed),
+        );
+        let res = self.update_with(&tracker);
+        self.delta = Some(tracker);
+        res
+    }
+}
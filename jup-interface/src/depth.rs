@@ -0,0 +1,55 @@
+//! Liquidity depth / price impact curves, e.g. for analytics dashboards
+
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use rust_decimal::Decimal;
+
+use crate::{err::InfAmmErr, InfAmm};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DepthPoint {
+    pub in_amount: u64,
+    pub out_amount: u64,
+
+    /// `out_amount / in_amount`, in atomic units of the respective mints
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::display"))]
+    pub effective_price: Decimal,
+}
+
+impl InfAmm {
+    /// ExactIn quotes `inp -> out` for `steps` evenly spaced input amounts
+    /// up to and including `max_amount`.
+    ///
+    /// Stops at the first amount that the pool does not have enough liquidity for,
+    /// so the returned points may be fewer than `steps`.
+    pub fn depth(
+        &self,
+        inp: &[u8; 32],
+        out: &[u8; 32],
+        max_amount: u64,
+        steps: u64,
+    ) -> Result<Vec<DepthPoint>, InfAmmErr> {
+        let pair = Pair { inp, out };
+        let limit_ty = TradeLimitTy::ExactIn;
+        self.check_pair_quotable(&pair, limit_ty, self.try_epoch()?)?;
+
+        let mut res = Vec::new();
+        for i in 1..=steps {
+            // cannot overflow, result <= max_amount
+            let amount = (u128::from(max_amount) * u128::from(i) / u128::from(steps)) as u64;
+            if amount == 0 {
+                continue;
+            }
+            match self.quote_raw_unchecked(&pair, amount, limit_ty) {
+                Ok((_, q)) => res.push(DepthPoint {
+                    in_amount: q.inp,
+                    out_amount: q.out,
+                    effective_price: Decimal::from(q.out) / Decimal::from(q.inp),
+                }),
+                Err(e) if e.is_not_enough_liquidity() => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,203 @@
+//! Tracking of accounts that trades use but that the pool does not need to exist,
+//! i.e. protocol fee accumulators, which may not have been created yet for newly added LSTs.
+//!
+//! Opt-in with [`InfAmm::with_dynamic_account_checks`]. Once enabled,
+//! [`Amm::get_accounts_to_update`](jupiter_amm_interface::Amm::get_accounts_to_update)
+//! additionally returns all LST mints and, from the next update cycle onwards once the mints'
+//! token programs are known, their protocol fee accumulators. Accumulators absent from an
+//! update are recorded as missing, and
+//! [`SwapParams::missing_dynamic_accounts_as_default`](jupiter_amm_interface::SwapParams::missing_dynamic_accounts_as_default)
+//! is honored for them: they are substituted with the default pubkey if set,
+//! else [`InfAmmErr::MissingDynamicAcc`](crate::err::InfAmmErr::MissingDynamicAcc) is returned.
+//!
+//! Use [`InfAmm::missing_dynamic_accs`] and [`MissingDynamicAcc::create_ix`]
+//! to create the missing accounts.
+
+use std::collections::HashMap;
+
+use inf1_std::inf1_ctl_core::typedefs::lst_state::LstState;
+use solana_instruction::Instruction;
+
+use crate::{
+    ata::CreateAtaIx, config::InfAmmConfig, lst_info::ASSOCIATED_TOKEN_PROGRAM_ID,
+    pair_update::in_scope, update::UpdateMapWithOwner, InfAmm,
+};
+
+/// A protocol fee accumulator that was missing from the last update,
+/// at the deployment's address, see [`crate::config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MissingDynamicAcc {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub pubkey: [u8; 32],
+
+    /// The deployment's protocol fee PDA, which owns this account
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub owner: [u8; 32],
+
+    /// The LST that this account accumulates protocol fees in
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub mint: [u8; 32],
+
+    /// Owner program of [`Self::mint`]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub token_program: [u8; 32],
+}
+
+impl MissingDynamicAcc {
+    /// Associated token program `CreateIdempotent` instruction that creates this account,
+    /// with `payer` as signer
+    pub fn create_ix(&self, payer: &[u8; 32]) -> Option<Instruction> {
+        Some(
+            CreateAtaIx {
+                ata: self.pubkey,
+                owner: self.owner,
+                mint: self.mint,
+                token_program: self.token_program,
+            }
+            .to_instruction(payer),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DynamicAccs {
+    /// Owner program of each LST mint as of the last update
+    token_programs: HashMap<[u8; 32], [u8; 32]>,
+
+    /// Keyed by [`MissingDynamicAcc::pubkey`]
+    missing: HashMap<[u8; 32], MissingDynamicAcc>,
+}
+
+impl DynamicAccs {
+    #[inline]
+    pub fn get_missing(&self, pk: &[u8; 32]) -> Option<&MissingDynamicAcc> {
+        self.missing.get(pk)
+    }
+
+    /// Retains only the records of LST mints for which `f` returns `true`
+    pub(crate) fn retain_mints(&mut self, f: impl Fn(&[u8; 32]) -> bool) {
+        self.token_programs.retain(|mint, _| f(mint));
+        self.missing.retain(|_, m| f(&m.mint));
+    }
+
+    /// `config`'s protocol fee accumulator of `lst_state`, derived with the bump stored on
+    /// the LST state list. `None` if the mint's token program is not known yet.
+    fn protocol_fee_accumulator(
+        &self,
+        config: &InfAmmConfig,
+        lst_state: &LstState,
+    ) -> Option<MissingDynamicAcc> {
+        let token_program = *self.token_programs.get(&lst_state.mint)?;
+        let owner = config.protocol_fee()?;
+        let pubkey = (config.pda.create_raw)(
+            &[
+                owner.as_slice(),
+                token_program.as_slice(),
+                lst_state.mint.as_slice(),
+                &[lst_state.protocol_fee_accumulator_bump],
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )?;
+        Some(MissingDynamicAcc {
+            pubkey,
+            owner,
+            mint: lst_state.mint,
+            token_program,
+        })
+    }
+}
+
+impl InfAmm {
+    /// See [`crate::dynamic`]
+    #[inline]
+    pub fn with_dynamic_account_checks(mut self) -> Self {
+        self.dynamic_accs = Some(DynamicAccs::default());
+        self
+    }
+
+    /// Protocol fee accumulators that were missing from the last update.
+    ///
+    /// Empty if dynamic account checks are not enabled, see [`crate::dynamic`].
+    pub fn missing_dynamic_accs(&self) -> Vec<MissingDynamicAcc> {
+        let mut res: Vec<_> = self
+            .dynamic_accs
+            .iter()
+            .flat_map(|d| d.missing.values().copied())
+            .collect();
+        res.sort_unstable_by_key(|m| m.pubkey);
+        res
+    }
+
+    /// LST mints and their known protocol fee accumulators
+    /// to append to [`jupiter_amm_interface::Amm::get_accounts_to_update`]
+    pub(crate) fn dynamic_accs_to_update(&self, scope: Option<&[[u8; 32]]>) -> Vec<[u8; 32]> {
+        let Some(d) = &self.dynamic_accs else {
+            return Vec::new();
+        };
+        self.inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state())
+            .filter(|lst_state| in_scope(scope, &lst_state.mint))
+            .flat_map(|lst_state| {
+                [
+                    Some(lst_state.mint),
+                    d.protocol_fee_accumulator(&self.config, &lst_state)
+                        .map(|m| m.pubkey),
+                ]
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Records the token programs of LST mints in `fetched`
+    /// and which known protocol fee accumulators it is missing.
+    /// LSTs out of `scope` retain their previous records.
+    ///
+    /// Should only be called after a successful update.
+    pub(crate) fn record_dynamic_accs(
+        &mut self,
+        fetched: &impl UpdateMapWithOwner,
+        scope: Option<&[[u8; 32]]>,
+    ) {
+        let Some(d) = &self.dynamic_accs else {
+            return;
+        };
+        let lst_states: Vec<_> = self
+            .inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state())
+            .filter(|lst_state| in_scope(scope, &lst_state.mint))
+            .collect();
+        let missing = d
+            .missing
+            .iter()
+            .filter(|(_, m)| !in_scope(scope, &m.mint))
+            .map(|(pk, m)| (*pk, *m))
+            .chain(
+                lst_states
+                    .iter()
+                    .filter_map(|lst_state| d.protocol_fee_accumulator(&self.config, lst_state))
+                    .filter(|m| fetched.get_owner(&m.pubkey).is_none())
+                    .map(|m| (m.pubkey, m)),
+            )
+            .collect();
+        let token_programs = d
+            .token_programs
+            .iter()
+            .filter(|(mint, _)| !in_scope(scope, mint))
+            .map(|(mint, token_program)| (*mint, *token_program))
+            .chain(lst_states.iter().filter_map(|lst_state| {
+                Some((lst_state.mint, fetched.get_owner(&lst_state.mint)?))
+            }))
+            .collect();
+        self.dynamic_accs = Some(DynamicAccs {
+            token_programs,
+            missing,
+        });
+    }
+}
@@ -0,0 +1,62 @@
+//! Epoch rollover detection.
+//!
+//! SPL and Lido sol val calcs keep the epoch their stake pool was last updated for
+//! from the last account refresh, while the current epoch is only read at quoting time.
+//! Right after an epoch rollover, these LSTs cannot be quoted until their stake pools
+//! are cranked and the crank is picked up by an update.
+//!
+//! To minimize that window:
+//! - [`InfAmm::epoch_stale_mints`] marks such LSTs stale as soon as the current epoch
+//!   increments, without waiting for an update
+//! - their accounts are placed at the front of [`jupiter_amm_interface::Amm::get_accounts_to_update`]
+//! - [`crate::metrics::InfAmmMetrics::on_epoch_rollover`] is called on the first update
+//!   in a new epoch
+
+use crate::InfAmm;
+
+impl InfAmm {
+    /// Mints of LSTs on the LST state list, in list order, whose underlying stake pool
+    /// has not been updated for [`Self::epoch`], see [`Self::lst_calc_epoch`].
+    ///
+    /// Quoting these LSTs fails with `NotUpdated`.
+    pub fn epoch_stale_mints(&self) -> Vec<[u8; 32]> {
+        let current_epoch = self.epoch();
+        self.inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state().mint)
+            .filter(|mint| self.is_epoch_stale(mint, current_epoch))
+            .collect()
+    }
+
+    #[inline]
+    pub(crate) fn is_epoch_stale(&self, mint: &[u8; 32], current_epoch: u64) -> bool {
+        self.lst_calc_epoch(mint)
+            .is_some_and(|calc_epoch| calc_epoch < current_epoch)
+    }
+
+    /// Should only be called after a successful update.
+    ///
+    /// Reports the LSTs that are still stale after the first update in a new epoch,
+    /// i.e. whose stake pools have not been cranked yet.
+    pub(crate) fn record_epoch(&mut self) {
+        let epoch = self.epoch();
+        let prev_epoch = self.last_update_epoch.replace(epoch);
+        match prev_epoch {
+            Some(prev_epoch) if epoch > prev_epoch => {
+                let stale_mints = self.epoch_stale_mints();
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    prev_epoch,
+                    epoch,
+                    stale = stale_mints.len(),
+                    "epoch rollover"
+                );
+                self.metrics
+                    .on_epoch_rollover(prev_epoch, epoch, &stale_mints);
+            }
+            _ => (),
+        }
+    }
+}
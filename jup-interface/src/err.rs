@@ -14,8 +14,11 @@ use inf1_std::{
         pricing::PricingAgErr,
         PricingAg, PricingProgAgErr,
     },
+    inf1_pp_core::pair::Pair,
     inf1_svc_ag_std::{
         calc::SvcCalcAgErr,
+        inf1_svc_lido_core::calc::LidoCalcErr,
+        inf1_svc_spl_core::calc::SplCalcErr,
         update::{LidoUpdateErr, MarinadeUpdateErr, SplUpdateErr, UpdateSvcErr},
         SvcAg,
     },
@@ -27,6 +30,373 @@ use solana_pubkey::Pubkey;
 #[allow(deprecated)]
 use inf1_std::quote::liquidity::remove::RemoveLiqQuoteErr;
 
+/// Errors of this crate, [`InfErr`] + additional checks done by [`crate::InfAmm`]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum InfAmmErr {
+    Inf(#[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::inf_err"))] InfErr),
+
+    /// The INF program does not support ExactOut for AddLiquidity and RemoveLiquidity,
+    /// i.e. pairs involving the LP token.
+    ///
+    /// See [`crate::InfAmm::supports_exact_out_for_pair`]
+    ExactOutUnsupportedForLiquidity {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pair"))]
+        pair: Pair<[u8; 32]>,
+    },
+
+    /// The LP token mint's mint authority, as of the last update, is not the pool state.
+    ///
+    /// All pairs involving the LP token are refused until this is resolved,
+    /// see [`crate::health::LpMintHealth`]
+    LpMintAuthorityMismatch {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+        expected: [u8; 32],
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58_opt"))]
+        actual: Option<[u8; 32]>,
+    },
+
+    /// The pool is disabled by its admin, all trades revert.
+    ///
+    /// See [`crate::InfAmm::is_active`]
+    PoolDisabled,
+
+    /// The pool is in the middle of a rebalance, all trades revert.
+    ///
+    /// See [`crate::InfAmm::is_active`]
+    PoolRebalancing,
+
+    /// Input and output mints are the same, which no INF instruction supports.
+    ///
+    /// Checked before everything else by [`Amm::quote`](jupiter_amm_interface::Amm::quote)
+    /// and [`Amm::get_swap_and_account_metas`](jupiter_amm_interface::Amm::get_swap_and_account_metas)
+    SamePairMint {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+        mint: [u8; 32],
+    },
+
+    /// A referrer token account was provided for `mint` in
+    /// [`jupiter_amm_interface::SwapParams::quote_mint_to_referrer`],
+    /// but the INF program does not take referral or platform fees.
+    ///
+    /// See [`crate::InfAmm::supports_referral_fees`]
+    ReferralFeesUnsupported {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+        mint: [u8; 32],
+    },
+
+    /// A protocol fee accumulator used by the trade was missing from the last update
+    /// and [`jupiter_amm_interface::SwapParams::missing_dynamic_accounts_as_default`] is not set.
+    ///
+    /// See [`crate::dynamic`]
+    MissingDynamicAcc {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+        pk: [u8; 32],
+    },
+
+    /// The pool's reserves account for `mint` was missing or invalid in the last update,
+    /// so all trades involving `mint` fail onchain.
+    ///
+    /// See [`crate::reserves`]
+    ReserveAccountInvalid {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+        mint: [u8; 32],
+    },
+
+    /// The stake pool program of sol val calculator `program` was upgraded
+    /// since the calculator last recorded its upgrade slot as of the last update,
+    /// so all trades involving LSTs using `program` fail onchain.
+    ///
+    /// See [`crate::calc_upgrade`]
+    CalcProgramOutdated {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+        program: [u8; 32],
+    },
+
+    /// In [`crate::clock::ClockMode::Sysvar`], no sysvar Clock has been applied yet,
+    /// so the current epoch is unknown.
+    ///
+    /// See [`crate::InfAmm::try_epoch`]
+    ClockNotUpdated,
+}
+
+impl InfAmmErr {
+    /// Whether the pool does not have enough of the output LST for the trade
+    #[allow(deprecated)]
+    #[inline]
+    pub const fn is_not_enough_liquidity(&self) -> bool {
+        matches!(
+            self,
+            Self::Inf(
+                InfErr::SwapQuote(SwapQuoteErr::NotEnoughLiquidity(_))
+                    | InfErr::RemoveLiqQuote(RemoveLiqQuoteErr::NotEnoughLiquidity(_))
+            )
+        )
+    }
+
+    /// Whether an underlying stake pool has not been updated for the current epoch
+    #[inline]
+    pub const fn is_not_updated(&self) -> bool {
+        match self {
+            Self::Inf(InfErr::SwapQuote(SwapQuoteErr::InpCalc(e) | SwapQuoteErr::OutCalc(e))) => {
+                is_svc_calc_not_updated(e)
+            }
+            _ => false,
+        }
+    }
+
+    /// Stable machine-readable code of this error, e.g. for JSON-RPC error responses.
+    ///
+    /// The thousands digit is the [`ErrCategory`], see [`ErrCategory::base_code`].
+    /// Codes are never reassigned across releases, new ones are only appended.
+    pub const fn code(&self) -> u32 {
+        match self {
+            // Stale
+            _ if self.is_not_updated() => 1001,
+            Self::CalcProgramOutdated { .. } => 1002,
+            Self::ClockNotUpdated => 1003,
+
+            // MissingData
+            Self::Inf(e) if acc_deser_pk(e).is_some() => 2001,
+            Self::Inf(InfErr::MissingAcc { .. }) => 2002,
+            Self::Inf(InfErr::MissingSplData { .. }) => 2003,
+            Self::Inf(InfErr::MissingSvcData { .. }) => 2004,
+            Self::MissingDynamicAcc { .. } => 2005,
+            Self::ReserveAccountInvalid { .. } => 2006,
+            Self::Inf(InfErr::UpdatePp(_)) => 2007,
+            Self::Inf(InfErr::UpdateSvc(_)) => 2008,
+
+            // Liquidity
+            _ if self.is_not_enough_liquidity() => 3001,
+            Self::Inf(InfErr::RebalanceQuote(RebalanceQuoteErr::NotEnoughLiquidity(_))) => 3002,
+
+            // Unsupported
+            Self::SamePairMint { .. } => 4001,
+            Self::ExactOutUnsupportedForLiquidity { .. } => 4002,
+            Self::ReferralFeesUnsupported { .. } => 4003,
+            Self::Inf(InfErr::UnsupportedMint { .. }) => 4004,
+            Self::Inf(InfErr::UnknownPp { .. }) => 4005,
+            Self::Inf(InfErr::UnknownSvc { .. }) => 4006,
+
+            // Unavailable
+            Self::PoolDisabled => 5001,
+            Self::PoolRebalancing => 5002,
+            Self::LpMintAuthorityMismatch { .. } => 5003,
+
+            // Internal
+            Self::Inf(InfErr::NoValidPda) => 9001,
+            Self::Inf(
+                InfErr::AddLiqQuote(_)
+                | InfErr::PricingProg(_)
+                | InfErr::RebalanceQuote(_)
+                | InfErr::RemoveLiqQuote(_)
+                | InfErr::SwapQuote(_),
+            ) => 9002,
+            // all other variants are matched above,
+            // but guards make the compiler unable to tell
+            Self::Inf(_) => 9000,
+        }
+    }
+
+    #[inline]
+    pub const fn category(&self) -> ErrCategory {
+        match ErrCategory::from_code(self.code()) {
+            Some(c) => c,
+            None => ErrCategory::Internal,
+        }
+    }
+}
+
+/// Coarse classification of [`InfAmmErr`]s by what a caller can do about them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ErrCategory {
+    /// Onchain state needs to be cranked, e.g. a stake pool not updated for the current epoch.
+    /// Retry after the next update.
+    Stale,
+
+    /// Accounts required for the trade were missing or invalid in the last update
+    MissingData,
+
+    /// The pool does not have enough of the output token
+    Liquidity,
+
+    /// The trade is never supported by the INF program or this crate
+    Unsupported,
+
+    /// The pool is temporarily not accepting trades
+    Unavailable,
+
+    /// Arithmetic or other unexpected errors
+    Internal,
+}
+
+impl ErrCategory {
+    /// Every [`InfAmmErr::code`] of this category is in `base_code..base_code + 1000`
+    #[inline]
+    pub const fn base_code(&self) -> u32 {
+        match self {
+            Self::Stale => 1000,
+            Self::MissingData => 2000,
+            Self::Liquidity => 3000,
+            Self::Unsupported => 4000,
+            Self::Unavailable => 5000,
+            Self::Internal => 9000,
+        }
+    }
+
+    /// Inverse of [`Self::base_code`] for any code in its range
+    #[inline]
+    pub const fn from_code(code: u32) -> Option<Self> {
+        Some(match code / 1000 {
+            1 => Self::Stale,
+            2 => Self::MissingData,
+            3 => Self::Liquidity,
+            4 => Self::Unsupported,
+            5 => Self::Unavailable,
+            9 => Self::Internal,
+            _ => return None,
+        })
+    }
+}
+
+impl From<InfAmmErr> for u32 {
+    #[inline]
+    fn from(e: InfAmmErr) -> Self {
+        e.code()
+    }
+}
+
+#[inline]
+const fn is_svc_calc_not_updated(e: &SvcCalcAgErr) -> bool {
+    matches!(
+        e,
+        SvcAg::Lido(LidoCalcErr::NotUpdated)
+            | SvcAg::SanctumSpl(SplCalcErr::NotUpdated)
+            | SvcAg::SanctumSplMulti(SplCalcErr::NotUpdated)
+            | SvcAg::Spl(SplCalcErr::NotUpdated)
+    )
+}
+
+impl From<InfErr> for InfAmmErr {
+    #[inline]
+    fn from(e: InfErr) -> Self {
+        Self::Inf(e)
+    }
+}
+
+impl Display for InfAmmErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inf(e) => Display::fmt(&FmtErr(*e), f),
+            Self::ExactOutUnsupportedForLiquidity { pair } => f.write_fmt(format_args!(
+                "ExactOutUnsupportedForLiquidity: {} -> {}",
+                Pubkey::new_from_array(pair.inp),
+                Pubkey::new_from_array(pair.out)
+            )),
+            Self::LpMintAuthorityMismatch { expected, actual } => f.write_fmt(format_args!(
+                "LpMintAuthorityMismatch. Expected: {}. Actual: {}",
+                Pubkey::new_from_array(*expected),
+                actual.map_or_else(
+                    || "None".to_owned(),
+                    |a| Pubkey::new_from_array(a).to_string()
+                )
+            )),
+            Self::PoolDisabled => f.write_str("PoolDisabled"),
+            Self::PoolRebalancing => f.write_str("PoolRebalancing"),
+            Self::SamePairMint { mint } => f.write_fmt(format_args!(
+                "SamePairMint: {}",
+                Pubkey::new_from_array(*mint)
+            )),
+            Self::ReferralFeesUnsupported { mint } => f.write_fmt(format_args!(
+                "ReferralFeesUnsupported: {}",
+                Pubkey::new_from_array(*mint)
+            )),
+            Self::MissingDynamicAcc { pk } => f.write_fmt(format_args!(
+                "MissingDynamicAcc: {}",
+                Pubkey::new_from_array(*pk)
+            )),
+            Self::ReserveAccountInvalid { mint } => f.write_fmt(format_args!(
+                "ReserveAccountInvalid: {}",
+                Pubkey::new_from_array(*mint)
+            )),
+            Self::CalcProgramOutdated { program } => f.write_fmt(format_args!(
+                "CalcProgramOutdated: {}",
+                Pubkey::new_from_array(*program)
+            )),
+            Self::ClockNotUpdated => f.write_str("ClockNotUpdated"),
+        }
+    }
+}
+
+impl Error for InfAmmErr {}
+
+/// An account that failed to deserialize during [`crate::InfAmm::update_with`],
+/// with enough context to tell a missing account from a wrong size or a layout change.
+///
+/// Returned in place of the bare `AccDeser: <pubkey>` of [`InfErr::AccDeser`]
+/// and the `AccDeser` variants of the pricing program and sol val calc update errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AccDeserErr {
+    /// Mainnet address of the account, see [`crate::config`]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub pk: [u8; 32],
+
+    /// Minimum data length of accounts of this kind,
+    /// `None` if not known to this crate
+    pub expected_len: Option<usize>,
+
+    /// `None` if the account was not found
+    pub actual_len: Option<usize>,
+
+    /// `None` if the account was not found
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58_opt"))]
+    pub owner: Option<[u8; 32]>,
+}
+
+impl Display for AccDeserErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "AccDeser: {}. ",
+            Pubkey::new_from_array(self.pk)
+        ))?;
+        let Some(actual_len) = self.actual_len else {
+            return f.write_str("Account not found");
+        };
+        match self.expected_len {
+            Some(expected_len) => f.write_fmt(format_args!("Expected len: >= {expected_len}. "))?,
+            None => f.write_str("Expected len: unknown. ")?,
+        }
+        f.write_fmt(format_args!(
+            "Actual len: {actual_len}. Owner: {}",
+            self.owner.map_or_else(
+                || "None".to_owned(),
+                |o| Pubkey::new_from_array(o).to_string()
+            )
+        ))
+    }
+}
+
+impl Error for AccDeserErr {}
+
+/// The account of an `AccDeser` error nested anywhere in `e`
+pub(crate) const fn acc_deser_pk(e: &InfErr) -> Option<[u8; 32]> {
+    match e {
+        InfErr::AccDeser { pk }
+        | InfErr::UpdatePp(PricingAg::FlatFee(FlatFeePricingUpdateErr::AccDeser { pk }))
+        | InfErr::UpdatePp(PricingAg::FlatSlab(FlatSlabPricingUpdateErr::AccDeser { pk }))
+        | InfErr::UpdateSvc(SvcAg::Lido(LidoUpdateErr::AccDeser { pk }))
+        | InfErr::UpdateSvc(SvcAg::Marinade(MarinadeUpdateErr::AccDeser { pk }))
+        | InfErr::UpdateSvc(
+            SvcAg::SanctumSpl(SplUpdateErr::AccDeser { pk })
+            | SvcAg::SanctumSplMulti(SplUpdateErr::AccDeser { pk })
+            | SvcAg::Spl(SplUpdateErr::AccDeser { pk }),
+        ) => Some(*pk),
+        _ => None,
+    }
+}
+
 /// Newtype wrapper to enable pretty-printing of pubkeys
 #[repr(transparent)]
 pub struct FmtErr<E>(pub E);
@@ -0,0 +1,110 @@
+//! Emulation of ExactOut quotes for pairs whose direct ExactOut quote fails,
+//! e.g. because a sol value calculator of the pair cannot be inverted.
+//!
+//! The input amount is searched for by iterating ExactIn quotes: the smallest input amount
+//! whose ExactIn output is at least the target. The emulated quote's output can therefore
+//! exceed the target by at most the output of 1 more unit of input.
+//!
+//! The INF program still executes ExactOut the same way as the direct quote, so an emulated
+//! quote must be executed as an ExactIn swap of its input amount, with the target as the
+//! minimum output. Emulation is therefore only available through
+//! [`InfAmm::quote_exact_out_emulated`], whose [`ExactOutQuote::approximate`] flags it;
+//! [`jupiter_amm_interface::Amm::quote`] never emulates, and e.g. still fails ExactOut
+//! LP token pairs with [`InfAmmErr::ExactOutUnsupportedForLiquidity`].
+
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+
+use crate::{err::InfAmmErr, InfAmm};
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExactOutQuote {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub fee_mint: [u8; 32],
+
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::inf_quote"))]
+    pub quote: inf1_std::quote::Quote,
+
+    /// `true` if emulated with ExactIn quotes, see [`crate::exact_out`].
+    /// `quote.out` is then at least, instead of exactly, the target output amount.
+    pub approximate: bool,
+}
+
+impl InfAmm {
+    /// Quotes ExactOut `out_amount` of `pair`, emulating it with ExactIn quotes
+    /// if the direct quote fails, see [`crate::exact_out`].
+    ///
+    /// Returns the direct quote's error if emulation fails too.
+    pub fn quote_exact_out_emulated(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        out_amount: u64,
+    ) -> Result<ExactOutQuote, InfAmmErr> {
+        let current_epoch = self.try_epoch()?;
+        let e = match self.quote_raw(pair, out_amount, TradeLimitTy::ExactOut, current_epoch) {
+            Ok((fee_mint, quote)) => {
+                return Ok(ExactOutQuote {
+                    fee_mint,
+                    quote,
+                    approximate: false,
+                })
+            }
+            Err(e) => e,
+        };
+        self.emulate_exact_out(pair, out_amount, current_epoch)
+            .ok_or(e)
+    }
+
+    fn emulate_exact_out(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        out_amount: u64,
+        current_epoch: u64,
+    ) -> Option<ExactOutQuote> {
+        if out_amount == 0 {
+            return None;
+        }
+        self.check_pair_quotable(pair, TradeLimitTy::ExactIn, current_epoch)
+            .ok()?;
+        let quote_in = |amount| {
+            self.quote_raw_unchecked(pair, amount, TradeLimitTy::ExactIn)
+                .ok()
+        };
+
+        // invariant: the ExactIn output of `lo` is less than `out_amount`.
+        // Failed quotes are assumed to be due to too large an input,
+        // e.g. the output exceeding the output LST's reserves
+        let mut lo = 0;
+        let mut hi = out_amount;
+        let mut found = None;
+        loop {
+            match quote_in(hi) {
+                Some((fee_mint, quote)) if quote.out >= out_amount => {
+                    found = Some((fee_mint, quote));
+                    break;
+                }
+                Some(_) => {
+                    lo = hi;
+                    hi = hi.checked_mul(2)?;
+                }
+                None => break,
+            }
+        }
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            match quote_in(mid) {
+                Some((fee_mint, quote)) if quote.out >= out_amount => {
+                    found = Some((fee_mint, quote));
+                    hi = mid;
+                }
+                Some(_) => lo = mid,
+                None => hi = mid,
+            }
+        }
+        found.map(|(fee_mint, quote)| ExactOutQuote {
+            fee_mint,
+            quote,
+            approximate: true,
+        })
+    }
+}
@@ -0,0 +1,85 @@
+//! Computation of the `fee_pct` of [`jupiter_amm_interface::Quote`]s.
+//!
+//! `fee_pct` is computed exactly as the [`Decimal`] ratio of
+//! [`crate::quote_core::fee_pct_ratio`], without going through `f64`,
+//! so that identical quotes always report identical decimals.
+//! It is then optionally rounded, see [`InfAmm::with_fee_pct_rounding`].
+
+use anyhow::Result;
+use jupiter_amm_interface::Quote;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::{to_jup_quote_with_fee_pct_rounding, InfAmm};
+
+/// Rounding of `fee_pct` to a fixed number of decimal places
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeePctRounding {
+    /// Decimal places of the fraction, e.g. `6` rounds 0.0001234567 (~1.23 bps) to 0.000123
+    pub dp: u32,
+
+    pub mode: FeePctRoundingMode,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeePctRoundingMode {
+    /// Round half to even, i.e. banker's rounding
+    #[default]
+    HalfEven,
+
+    /// Towards zero, never overstates fees
+    Down,
+
+    /// Away from zero, never understates fees
+    Up,
+}
+
+impl FeePctRoundingMode {
+    #[inline]
+    pub const fn to_strategy(self) -> RoundingStrategy {
+        match self {
+            Self::HalfEven => RoundingStrategy::MidpointNearestEven,
+            Self::Down => RoundingStrategy::ToZero,
+            Self::Up => RoundingStrategy::AwayFromZero,
+        }
+    }
+}
+
+/// `num / denom`, rounded with `rounding` if set.
+///
+/// `None` if `denom` is 0.
+#[inline]
+pub fn fee_pct_decimal(num: u64, denom: u64, rounding: Option<FeePctRounding>) -> Option<Decimal> {
+    let res = Decimal::from(num).checked_div(Decimal::from(denom))?;
+    Some(match rounding {
+        None => res,
+        Some(FeePctRounding { dp, mode }) => res.round_dp_with_strategy(dp, mode.to_strategy()),
+    })
+}
+
+impl InfAmm {
+    /// Rounds the `fee_pct` of [`jupiter_amm_interface::Amm::quote`] to a fixed number of
+    /// decimal places. Defaults to `None`, which reports the exact ratio up to [`Decimal`]'s
+    /// 28 decimal places of precision.
+    #[inline]
+    pub fn with_fee_pct_rounding(mut self, fee_pct_rounding: Option<FeePctRounding>) -> Self {
+        self.fee_pct_rounding = fee_pct_rounding;
+        self
+    }
+
+    /// Converts `quote` with [`Self::jup_quote_fees`] and [`Self::fee_pct_rounding`]
+    #[inline]
+    pub(crate) fn jup_quote(
+        &self,
+        fee_mint: &[u8; 32],
+        quote: inf1_std::quote::Quote,
+    ) -> Result<Quote> {
+        to_jup_quote_with_fee_pct_rounding(
+            fee_mint,
+            quote,
+            self.jup_quote_fees,
+            self.fee_pct_rounding,
+        )
+    }
+}
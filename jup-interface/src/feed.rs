@@ -0,0 +1,114 @@
+//! Keeping an [`InfAmm`] updated from account subscriptions, e.g. websocket pubsub or geyser.
+//!
+//! [`InfAmmFeed`] owns the [`InfAmm`], feeds the accounts that changed into
+//! [`InfAmm::update_delta`] and publishes a snapshot after every successful update,
+//! which quoting threads can cheaply load from an [`InfAmmReader`].
+
+use std::{future::Future, sync::Arc};
+
+use anyhow::Result;
+use jupiter_amm_interface::{AccountMap, Amm};
+use solana_pubkey::Pubkey;
+use tokio::sync::watch;
+
+use crate::InfAmm;
+
+/// A source of account updates
+pub trait AccountStream: Send {
+    /// Replaces the set of accounts subscribed to with `pks`.
+    ///
+    /// Implementations should yield the current state of newly subscribed accounts
+    /// in a subsequent [`Self::next_batch`].
+    fn subscribe(&mut self, pks: &[Pubkey]) -> impl Future<Output = Result<()>> + Send;
+
+    /// Returns the next batch of updated accounts, or `None` if the stream has ended.
+    ///
+    /// Batches may contain accounts that did not change.
+    fn next_batch(&mut self) -> impl Future<Output = Option<AccountMap>> + Send;
+}
+
+/// Cheaply cloneable handle for loading the latest [`InfAmm`] published by an [`InfAmmFeed`]
+#[derive(Debug, Clone)]
+pub struct InfAmmReader(watch::Receiver<Arc<InfAmm>>);
+
+impl InfAmmReader {
+    /// The AMM as of the last successful update
+    #[inline]
+    pub fn load(&self) -> Arc<InfAmm> {
+        self.0.borrow().clone()
+    }
+
+    /// Waits for the next successful update. Errs if the feed has stopped.
+    #[inline]
+    pub async fn changed(&mut self) -> Result<()> {
+        Ok(self.0.changed().await?)
+    }
+}
+
+#[derive(Debug)]
+pub struct InfAmmFeed<S> {
+    amm: InfAmm,
+    stream: S,
+    subscribed: Vec<Pubkey>,
+    tx: watch::Sender<Arc<InfAmm>>,
+}
+
+impl<S: AccountStream> InfAmmFeed<S> {
+    /// Enables [`InfAmm::with_delta_updates`] on `amm`
+    pub fn new(amm: InfAmm, stream: S) -> Self {
+        let amm = amm.with_delta_updates();
+        let (tx, _) = watch::channel(Arc::new(published(&amm)));
+        Self {
+            amm,
+            stream,
+            subscribed: Vec::new(),
+            tx,
+        }
+    }
+
+    #[inline]
+    pub fn reader(&self) -> InfAmmReader {
+        InfAmmReader(self.tx.subscribe())
+    }
+
+    /// Applies account updates from the stream until it ends.
+    ///
+    /// Updates that fail, e.g. because not all accounts have been received yet,
+    /// are not published and retried with the next batch.
+    /// Resubscribes whenever the accounts to update change, e.g. when a LST is added.
+    ///
+    /// Only errs if subscribing fails.
+    pub async fn run(mut self) -> Result<()> {
+        self.resubscribe_if_changed().await?;
+        while let Some(batch) = self.stream.next_batch().await {
+            let changed = self.amm.changed_accounts(&batch);
+            if changed.is_empty() {
+                continue;
+            }
+            if self.amm.update_delta(&changed).is_ok() {
+                self.tx.send_replace(Arc::new(published(&self.amm)));
+            }
+            self.resubscribe_if_changed().await?;
+        }
+        Ok(())
+    }
+
+    async fn resubscribe_if_changed(&mut self) -> Result<()> {
+        let mut accounts_to_update = self.amm.get_accounts_to_update();
+        accounts_to_update.sort_unstable();
+        accounts_to_update.dedup();
+        if accounts_to_update != self.subscribed {
+            self.stream.subscribe(&accounts_to_update).await?;
+            self.subscribed = accounts_to_update;
+        }
+        Ok(())
+    }
+}
+
+/// Clone of `amm` without its retained accounts,
+/// which readers have no use for
+fn published(amm: &InfAmm) -> InfAmm {
+    let mut amm = amm.clone();
+    amm.delta = None;
+    amm
+}
@@ -0,0 +1,166 @@
+//! Per-LST tracking of which sol val calc accounts were last applied and when,
+//! so that operators can pinpoint which account is blocking a pair,
+//! and cross-validation of the LP token mint
+
+use std::sync::atomic::Ordering;
+
+use inf1_std::inf1_svc_ag_std::{inf1_svc_lido_core::solido_legacy_core::SYSVAR_CLOCK, SvcAg};
+use solana_pubkey::Pubkey;
+
+use crate::{pair_update::in_scope, update::UpdateMapWithOwner, InfAmm};
+
+/// A sol val calc account that was applied to a LST's calc in an update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AppliedAcc {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pubkey"))]
+    pub pubkey: Pubkey,
+
+    /// Owner program of the account at the time it was applied.
+    ///
+    /// Use this to tell stake pool state, validator list and
+    /// programdata (owned by BPF loader upgradeable) accounts apart.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pubkey"))]
+    pub owner: Pubkey,
+
+    /// [`InfAmm::generation`] of the update that applied this account
+    pub generation: u64,
+
+    /// Slot of [`jupiter_amm_interface::ClockRef`] at the time this account was applied
+    pub slot: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LstHealth {
+    /// All accounts, excluding sysvar clock, that the LST's sol val calc
+    /// was last updated with
+    pub applied_accs: Vec<AppliedAcc>,
+
+    /// The epoch that the LST's underlying stake pool was last updated for,
+    /// as of the last update.
+    ///
+    /// `None` for LSTs whose sol val calc is not epoch affected.
+    /// Quoting fails with `NotUpdated` if this is less than the current epoch.
+    pub calc_epoch: Option<u64>,
+}
+
+/// LP token mint state as of the last update, cross-validated against the pool state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LpMintHealth {
+    /// `None` if the mint has no mint authority
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58_opt"))]
+    pub mint_authority: Option<[u8; 32]>,
+    pub decimals: u8,
+}
+
+impl LpMintHealth {
+    /// Mint authority of the LP token mint should always be the pool state PDA.
+    ///
+    /// `pool_state_id` should be the deployment's, see [`crate::config`]
+    #[inline]
+    pub fn is_mint_authority_ok(&self, pool_state_id: &[u8; 32]) -> bool {
+        self.mint_authority.as_ref() == Some(pool_state_id)
+    }
+}
+
+/// Offsets in SPL token / token-2022 mint account data:
+/// `mint_authority: COption<Pubkey>, supply: u64, decimals: u8, ..`
+const MINT_AUTHORITY_TAG_OFFSET: usize = 0;
+const MINT_AUTHORITY_OFFSET: usize = 4;
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Returns `None` if `data` is too short to be mint account data
+pub(crate) fn parse_lp_mint_health(data: &[u8]) -> Option<LpMintHealth> {
+    let tag: &[u8; 4] = data.get(MINT_AUTHORITY_TAG_OFFSET..)?.first_chunk()?;
+    let authority: &[u8; 32] = data.get(MINT_AUTHORITY_OFFSET..)?.first_chunk()?;
+    let decimals = *data.get(MINT_DECIMALS_OFFSET)?;
+    Some(LpMintHealth {
+        mint_authority: (u32::from_le_bytes(*tag) != 0).then_some(*authority),
+        decimals,
+    })
+}
+
+impl InfAmm {
+    /// Returns `None` if there has not been a successful update yet
+    #[inline]
+    pub const fn lp_mint_health(&self) -> Option<&LpMintHealth> {
+        self.lp_mint_health.as_ref()
+    }
+
+    /// Whether the LP token mint's mint authority was the pool state as of the last update.
+    ///
+    /// `true` if there has not been a successful update yet.
+    #[inline]
+    pub fn is_lp_mint_authority_ok(&self) -> bool {
+        self.lp_mint_health
+            .is_none_or(|h| h.is_mint_authority_ok(&self.config.pool_state_id))
+    }
+
+    /// Returns `None` if `mint` has not been successfully updated yet
+    #[inline]
+    pub fn lst_health(&self, mint: &[u8; 32]) -> Option<&LstHealth> {
+        self.lst_health.get(mint)
+    }
+
+    /// The epoch that the LST's underlying stake pool was last updated for.
+    ///
+    /// Returns `None` if the LST's sol val calc is not epoch affected or
+    /// the LST has no sol val calc data
+    pub fn lst_calc_epoch(&self, mint: &[u8; 32]) -> Option<u64> {
+        match self.inner.try_get_lst_svc(mint).ok()?.as_sol_val_calc()? {
+            SvcAg::Lido(c) => Some(c.exchange_rate.computed_in_epoch),
+            SvcAg::SanctumSpl(c) | SvcAg::SanctumSplMulti(c) | SvcAg::Spl(c) => {
+                Some(c.last_update_epoch)
+            }
+            SvcAg::Marinade(_) | SvcAg::Wsol(_) => None,
+        }
+    }
+
+    /// Records the accounts in `fetched` that were applied to each LST's sol val calc.
+    ///
+    /// Should only be called after all LSTs' sol val calcs have been successfully updated.
+    /// LSTs without sol val calc data or out of `scope` retain their previous record, if any.
+    pub(crate) fn record_lst_health(
+        &mut self,
+        fetched: &impl UpdateMapWithOwner,
+        scope: Option<&[[u8; 32]]>,
+    ) {
+        let generation = self.generation;
+        let slot = self.current_slot.load(Ordering::Relaxed);
+        let healths: Vec<_> = self
+            .inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state())
+            .filter(|lst_state| in_scope(scope, &lst_state.mint))
+            .filter_map(|lst_state| {
+                let applied_accs = self
+                    .inner
+                    .accounts_to_update_lst(&lst_state)
+                    .ok()?
+                    .filter(|pk| *pk != SYSVAR_CLOCK)
+                    .filter_map(|pk| {
+                        let owner = fetched.get_owner(&pk)?;
+                        Some(AppliedAcc {
+                            pubkey: Pubkey::new_from_array(pk),
+                            owner: Pubkey::new_from_array(owner),
+                            generation,
+                            slot,
+                        })
+                    })
+                    .collect();
+                Some((
+                    lst_state.mint,
+                    LstHealth {
+                        applied_accs,
+                        calc_epoch: self.lst_calc_epoch(&lst_state.mint),
+                    },
+                ))
+            })
+            .collect();
+        self.lst_health.extend(healths);
+    }
+}
@@ -0,0 +1,89 @@
+//! Projected reserve balances after a quoted trade, e.g. for risk systems that enforce
+//! position limits against the pool's composition after the trade.
+//!
+//! The input LST's reserves receive the input amount and the output LST's reserves
+//! pay out the output amount. The protocol fee is transferred out of the reserves of
+//! the quote's fee mint to the protocol fee accumulator, while the LP fee remains in them.
+//! The LP token has no reserves: it is minted on AddLiquidity and burnt on RemoveLiquidity.
+
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+
+use crate::{err::InfAmmErr, InfAmm};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct QuoteImpact {
+    /// Balance of the input LST's reserves after the trade.
+    ///
+    /// `None` if the input mint is the LP token or its reserves have not been fetched.
+    pub inp_reserve_after: Option<u64>,
+
+    /// Balance of the output LST's reserves after the trade.
+    ///
+    /// `None` if the output mint is the LP token or its reserves have not been fetched.
+    pub out_reserve_after: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ImpactQuote {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub fee_mint: [u8; 32],
+
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::inf_quote"))]
+    pub quote: inf1_std::quote::Quote,
+
+    pub impact: QuoteImpact,
+}
+
+impl InfAmm {
+    /// [`Self::quote_raw`] at the current epoch, with the projected reserve balances
+    /// after the trade, see [`crate::impact`]
+    pub fn quote_with_impact(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        amount: u64,
+        limit_ty: TradeLimitTy,
+    ) -> Result<ImpactQuote, InfAmmErr> {
+        let (fee_mint, quote) = self.quote_raw(pair, amount, limit_ty, self.try_epoch()?)?;
+        Ok(ImpactQuote {
+            fee_mint,
+            quote,
+            impact: self.quote_impact(pair, &fee_mint, &quote),
+        })
+    }
+
+    /// Projected reserve balances after executing `quote` of `pair`,
+    /// whose fees are in `fee_mint`, see [`crate::impact`]
+    pub fn quote_impact(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        fee_mint: &[u8; 32],
+        quote: &inf1_std::quote::Quote,
+    ) -> QuoteImpact {
+        let protocol_fee_of = |mint: &[u8; 32]| {
+            if mint == fee_mint {
+                quote.protocol_fee
+            } else {
+                0
+            }
+        };
+        let reserves_of = |mint: &[u8; 32]| {
+            if *mint == self.inner.pool.lp_token_mint {
+                None
+            } else {
+                self.reserves_balance(mint)
+            }
+        };
+        QuoteImpact {
+            inp_reserve_after: reserves_of(pair.inp).and_then(|r| {
+                r.checked_add(quote.inp)?
+                    .checked_sub(protocol_fee_of(pair.inp))
+            }),
+            out_reserve_after: reserves_of(pair.out).and_then(|r| {
+                r.checked_sub(quote.out)?
+                    .checked_sub(protocol_fee_of(pair.out))
+            }),
+        }
+    }
+}
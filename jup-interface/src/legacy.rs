@@ -0,0 +1,252 @@
+//! [`LegacyInfAmm`], an adapter implementing the `Amm` trait of jupiter-amm-interface 0.4,
+//! before `single_program_amm!` and `SingleProgramAmm`, for integrators still pinned to it.
+//!
+//! Everything is delegated to the wrapped [`InfAmm`], converting between the solana 1.x types
+//! of the older version and the solana 2.x types of this crate by their bytes.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use jupiter_amm_interface::{Amm, KeyedAccount, QuoteParams, Swap, SwapMode, SwapParams};
+use jupiter_amm_interface_legacy as legacy;
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use solana_sdk_legacy::{
+    account::Account as LegacyAccount, instruction::AccountMeta as LegacyAccountMeta,
+    pubkey::Pubkey as LegacyPubkey,
+};
+
+use crate::InfAmm;
+
+/// See [`crate::legacy`]
+#[derive(Debug, Clone)]
+pub struct LegacyInfAmm(pub InfAmm);
+
+impl From<InfAmm> for LegacyInfAmm {
+    #[inline]
+    fn from(amm: InfAmm) -> Self {
+        Self(amm)
+    }
+}
+
+#[inline]
+fn to_pubkey(pk: &LegacyPubkey) -> Pubkey {
+    Pubkey::new_from_array(pk.to_bytes())
+}
+
+#[inline]
+fn to_legacy_pubkey(pk: &Pubkey) -> LegacyPubkey {
+    LegacyPubkey::new_from_array(pk.to_bytes())
+}
+
+#[inline]
+fn to_account(
+    LegacyAccount {
+        lamports,
+        data,
+        owner,
+        executable,
+        rent_epoch,
+    }: &LegacyAccount,
+) -> Account {
+    Account {
+        lamports: *lamports,
+        data: data.clone(),
+        owner: to_pubkey(owner),
+        executable: *executable,
+        rent_epoch: *rent_epoch,
+    }
+}
+
+#[inline]
+const fn to_swap_mode(swap_mode: legacy::SwapMode) -> SwapMode {
+    match swap_mode {
+        legacy::SwapMode::ExactIn => SwapMode::ExactIn,
+        legacy::SwapMode::ExactOut => SwapMode::ExactOut,
+    }
+}
+
+/// Only the variants that [`InfAmm`] produces
+fn to_legacy_swap(swap: Swap) -> Result<legacy::Swap> {
+    Ok(match swap {
+        Swap::SanctumS {
+            src_lst_value_calc_accs,
+            dst_lst_value_calc_accs,
+            src_lst_index,
+            dst_lst_index,
+        } => legacy::Swap::SanctumS {
+            src_lst_value_calc_accs,
+            dst_lst_value_calc_accs,
+            src_lst_index,
+            dst_lst_index,
+        },
+        Swap::SanctumSAddLiquidity {
+            lst_value_calc_accs,
+            lst_index,
+        } => legacy::Swap::SanctumSAddLiquidity {
+            lst_value_calc_accs,
+            lst_index,
+        },
+        Swap::SanctumSRemoveLiquidity {
+            lst_value_calc_accs,
+            lst_index,
+        } => legacy::Swap::SanctumSRemoveLiquidity {
+            lst_value_calc_accs,
+            lst_index,
+        },
+        other => return Err(anyhow::anyhow!("Unexpected swap {other:?}")),
+    })
+}
+
+impl legacy::Amm for LegacyInfAmm {
+    /// Same as [`Amm::from_keyed_account`], sharing the clock of `amm_context`
+    fn from_keyed_account(
+        legacy::KeyedAccount { key, account, .. }: &legacy::KeyedAccount,
+        amm_context: &legacy::AmmContext,
+    ) -> Result<Self> {
+        let mut inf = InfAmm::builder().build(&KeyedAccount {
+            key: to_pubkey(key),
+            account: to_account(account),
+            params: None,
+        })?;
+        inf.current_epoch = amm_context.clock_ref.epoch.clone();
+        inf.current_slot = amm_context.clock_ref.slot.clone();
+        Ok(Self(inf))
+    }
+
+    fn label(&self) -> String {
+        self.0.label()
+    }
+
+    fn program_id(&self) -> LegacyPubkey {
+        to_legacy_pubkey(&self.0.program_id())
+    }
+
+    fn key(&self) -> LegacyPubkey {
+        to_legacy_pubkey(&self.0.key())
+    }
+
+    fn get_reserve_mints(&self) -> Vec<LegacyPubkey> {
+        self.0
+            .get_reserve_mints()
+            .iter()
+            .map(to_legacy_pubkey)
+            .collect()
+    }
+
+    fn get_accounts_to_update(&self) -> Vec<LegacyPubkey> {
+        self.0
+            .get_accounts_to_update()
+            .iter()
+            .map(to_legacy_pubkey)
+            .collect()
+    }
+
+    fn update(&mut self, account_map: &legacy::AccountMap) -> Result<()> {
+        let account_map = account_map
+            .iter()
+            .map(|(pk, acc)| (to_pubkey(pk), to_account(acc)))
+            .collect();
+        self.0.update(&account_map)
+    }
+
+    fn quote(
+        &self,
+        legacy::QuoteParams {
+            amount,
+            input_mint,
+            output_mint,
+            swap_mode,
+            ..
+        }: &legacy::QuoteParams,
+    ) -> Result<legacy::Quote> {
+        let quote = self.0.quote(&QuoteParams {
+            amount: *amount,
+            input_mint: to_pubkey(input_mint),
+            output_mint: to_pubkey(output_mint),
+            swap_mode: to_swap_mode(*swap_mode),
+        })?;
+        Ok(legacy::Quote {
+            in_amount: quote.in_amount,
+            out_amount: quote.out_amount,
+            fee_amount: quote.fee_amount,
+            fee_mint: to_legacy_pubkey(&quote.fee_mint),
+            fee_pct: quote.fee_pct,
+            ..Default::default()
+        })
+    }
+
+    fn get_swap_and_account_metas(
+        &self,
+        legacy::SwapParams {
+            swap_mode,
+            in_amount,
+            out_amount,
+            source_mint,
+            destination_mint,
+            source_token_account,
+            destination_token_account,
+            token_transfer_authority,
+            quote_mint_to_referrer,
+            jupiter_program_id,
+            missing_dynamic_accounts_as_default,
+            ..
+        }: &legacy::SwapParams,
+    ) -> Result<legacy::SwapAndAccountMetas> {
+        let quote_mint_to_referrer: Option<HashMap<Pubkey, Pubkey>> =
+            quote_mint_to_referrer.map(|m| {
+                m.iter()
+                    .map(|(mint, referrer)| (to_pubkey(mint), to_pubkey(referrer)))
+                    .collect()
+            });
+        let swap_and_account_metas = self.0.get_swap_and_account_metas(&SwapParams {
+            swap_mode: to_swap_mode(*swap_mode),
+            in_amount: *in_amount,
+            out_amount: *out_amount,
+            source_mint: to_pubkey(source_mint),
+            destination_mint: to_pubkey(destination_mint),
+            source_token_account: to_pubkey(source_token_account),
+            destination_token_account: to_pubkey(destination_token_account),
+            token_transfer_authority: to_pubkey(token_transfer_authority),
+            quote_mint_to_referrer: quote_mint_to_referrer.as_ref(),
+            jupiter_program_id: &to_pubkey(jupiter_program_id),
+            missing_dynamic_accounts_as_default: *missing_dynamic_accounts_as_default,
+        })?;
+        Ok(legacy::SwapAndAccountMetas {
+            swap: to_legacy_swap(swap_and_account_metas.swap)?,
+            account_metas: swap_and_account_metas
+                .account_metas
+                .into_iter()
+                .map(|m| LegacyAccountMeta {
+                    pubkey: to_legacy_pubkey(&m.pubkey),
+                    is_signer: m.is_signer,
+                    is_writable: m.is_writable,
+                })
+                .collect(),
+        })
+    }
+
+    fn clone_amm(&self) -> Box<dyn legacy::Amm + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn has_dynamic_accounts(&self) -> bool {
+        self.0.has_dynamic_accounts()
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        self.0.supports_exact_out()
+    }
+
+    fn program_dependencies(&self) -> Vec<(LegacyPubkey, String)> {
+        self.0
+            .program_dependencies()
+            .into_iter()
+            .map(|(pk, label)| (to_legacy_pubkey(&pk), label))
+            .collect()
+    }
+
+    fn get_accounts_len(&self) -> usize {
+        self.0.get_accounts_len()
+    }
+}
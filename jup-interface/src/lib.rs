@@ -1,5 +1,6 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    hash::BuildHasher,
     iter::once,
     sync::{
         atomic::{AtomicU64, Ordering},
@@ -7,18 +8,26 @@ use std::{
     },
 };
 
-use ::sanctum_lst_list::{PoolInfo, SanctumLst};
 use anyhow::{anyhow, Context, Result};
 use inf1_std::{
     err::InfErr,
     inf1_ctl_core::{
-        accounts::lst_state_list::LstStatePackedList,
+        accounts::{lst_state_list::LstStatePackedList, pool_state::PoolState},
+        instructions::{
+            liquidity::{
+                add::AddLiquidityIxData, remove::RemoveLiquidityIxData, IxArgs as LiqIxArgs,
+            },
+            swap::{
+                exact_in::SwapExactInIxData, exact_out::SwapExactOutIxData, IxArgs as SwapIxArgs,
+            },
+        },
         keys::{LST_STATE_LIST_ID, POOL_STATE_ID},
         typedefs::lst_state::LstState,
     },
     inf1_pp_ag_std::{
         inf1_pp_flatfee_core,
         update::{all::AccountsToUpdateAll, UpdatePricingProg},
+        PricingAgTy,
     },
     inf1_pp_core::pair::Pair,
     inf1_svc_ag_std::{
@@ -35,7 +44,7 @@ use inf1_std::{
     },
     quote::swap::err::SwapQuoteErr,
     trade::{instruction::TradeIxArgs, Trade, TradeLimitTy},
-    update::UpdateErr,
+    update::{Account as _, UpdateErr, UpdateMap},
     InfStd,
 };
 use jupiter_amm_interface::{
@@ -43,16 +52,32 @@ use jupiter_amm_interface::{
     SingleProgramAmm, Swap, SwapAndAccountMetas, SwapMode, SwapParams,
 };
 use rust_decimal::Decimal;
-use solana_instruction::AccountMeta;
+use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 
 use crate::{
-    clock::is_epoch_affected_lst_mint,
-    consts::{DEFAULT_MAINNET_POOL, LABEL},
-    err::FmtErr,
-    pda::{create_raw_pda, find_pda},
-    sanctum_lst_list::load_sanctum_lst_list,
-    update::AccountMapRef,
+    cache::{QuoteCache, QuoteCacheKey},
+    clock::{sysvar_clock_epoch, ClockMode},
+    config::{DeploymentPdas, InfAmmBuilder, InfAmmConfig},
+    consts::LABEL,
+    delist::prune_delisted_lsts,
+    delta::DeltaTracker,
+    dynamic::DynamicAccs,
+    err::{FmtErr, InfAmmErr},
+    fee_pct::{fee_pct_decimal, FeePctRounding},
+    health::{parse_lp_mint_health, LpMintHealth, LstHealth},
+    lst_state_list_format::{version_of, LstStateListOverride, PACKED_VERSION},
+    metrics::{InfAmmMetrics, NoopMetrics, QuoteErrKind, UpdateTimer},
+    mint_meta::{bundled_mint_metas, MintMeta},
+    native_sol::native_sol_to_wsol,
+    pair_fees::FeeSchedule,
+    pair_update::in_scope,
+    rebalance::RebalanceInProgress,
+    reserves::is_reserves_valid,
+    update::{
+        acc_deser_err, update_err_with_ctx, AccountMapRef, DeploymentAccountMapRef,
+        UpdateMapWithOwner,
+    },
 };
 
 #[allow(deprecated)]
@@ -61,20 +86,81 @@ use inf1_std::instructions::liquidity::{
     remove::{remove_liquidity_ix_is_writer, remove_liquidity_ix_keys_owned},
 };
 
+pub mod alt;
+pub mod ata;
+pub mod audit;
+pub mod cache;
+pub mod calc_upgrade;
 pub mod clock;
+pub mod compute_units;
+pub mod config;
 pub mod consts;
+pub mod crank;
+pub mod delist;
+pub mod delta;
+pub mod depth;
+pub mod dynamic;
+pub mod epoch;
 pub mod err;
+pub mod exact_out;
+pub mod fee_pct;
+#[cfg(feature = "feed")]
+pub mod feed;
+pub mod health;
+pub mod impact;
+#[cfg(feature = "legacy")]
+pub mod legacy;
+pub mod liquidity;
+pub mod lst_info;
+pub mod lst_list_cache;
+pub mod lst_state_list_format;
+pub mod memory;
+pub mod metrics;
+pub mod mint_meta;
+pub mod native_sol;
+pub mod pair_fees;
+pub mod pair_update;
+pub mod pairs;
+pub mod pda;
+pub mod pool_defaults;
+pub mod prices;
+pub mod pricing_migration;
+pub mod rebalance;
+#[cfg(feature = "replay")]
+pub mod replay;
+pub mod reserves;
+pub mod route;
+pub mod shared;
+#[cfg(feature = "simulate")]
+pub mod simulate;
+pub mod slippage;
+#[cfg(feature = "serde")]
+pub mod snapshot;
+pub mod spl_lsts;
+pub mod stateless;
+pub mod token_program;
+pub mod trade_kind;
+#[cfg(feature = "tx")]
+pub mod tx;
+pub mod typed;
 pub mod update;
+pub mod validate;
+pub mod writable;
 
-mod pda;
 mod sanctum_lst_list;
+#[cfg(feature = "serde")]
+mod ser;
+
+pub use inf1_jup_quote_core as quote_core;
+pub use quote_core::JupQuoteFees;
 
 pub const INF_PROGRAM_ID: Pubkey = Pubkey::new_from_array(inf1_std::inf1_ctl_core::ID);
 pub const INF_LST_LIST_ID: Pubkey = Pubkey::new_from_array(LST_STATE_LIST_ID);
 
 // Note on Clock hax:
 // Because `Clock` is a special-case account, and because it's only used
-// by Lido and Spl SolValCalcs to check current epoch to filter out unexecutable quoting rn:
+// by Lido and Spl SolValCalcs to check current epoch to filter out unexecutable quoting rn,
+// in the default `ClockMode::ClockRef`:
 // - we exclude it from all update accounts
 // - update procedures use the `_no_clock()` variants that dont
 //   update clock data and hence dont rely on clock acc being in AccountMap
@@ -82,72 +168,197 @@ pub const INF_LST_LIST_ID: Pubkey = Pubkey::new_from_array(LST_STATE_LIST_ID);
 //   fail due to the underlying stake pool not being updated for the epoch
 // - we only check for underlying stake pool not being updated for the epoch
 //   during the quoting procedure to determine whether to return err
+//
+// In `ClockMode::Sysvar`, Clock is included in update accounts once and applied to
+// all SolValCalc structs with the regular update procedures instead.
 
-fn build_spl_lsts() -> HashMap<[u8; 32], [u8; 32]> {
-    load_sanctum_lst_list()
-        .into_iter()
-        .filter_map(|SanctumLst { mint, pool, .. }| {
-            let stake_pool_address = match pool {
-                PoolInfo::Lido => return None,
-                PoolInfo::Marinade => return None,
-                PoolInfo::ReservePool => return None,
-                PoolInfo::SanctumSpl(spl_pool_accounts) => spl_pool_accounts.pool.to_bytes(),
-                PoolInfo::Spl(spl_pool_accounts) => spl_pool_accounts.pool.to_bytes(),
-                PoolInfo::SPool(_) => return None,
-                PoolInfo::SanctumSplMulti(spl_pool_accounts) => spl_pool_accounts.pool.to_bytes(),
-            };
-            Some((mint.to_bytes(), stake_pool_address))
-        })
-        .collect()
-}
-
+/// Cloning is cheap: LST calc state and retained account data are behind [`Arc`]s
+/// that are shared between clones, and copied-on-write by the clone that is updated.
+/// This keeps [`Amm::clone_amm`] O(1) for route-search workers that only quote.
 #[derive(Debug, Clone)]
 pub struct InfAmm {
-    pub inner: InfStd,
+    pub inner: Arc<InfStd>,
     pub current_epoch: Arc<AtomicU64>,
+    pub current_slot: Arc<AtomicU64>,
+
+    /// Number of times [`Amm::update`] has been called
+    pub generation: u64,
+
+    /// See [`health`]
+    pub lst_health: HashMap<[u8; 32], LstHealth>,
+
+    /// See [`Self::lp_mint_health`]
+    pub lp_mint_health: Option<LpMintHealth>,
+
+    /// Mints of LSTs whose reserves account was invalid in the last update, see [`reserves`]
+    pub invalid_reserves: HashSet<[u8; 32]>,
+
+    /// See [`Self::with_calc_upgrade_checks`]
+    pub calc_upgrade_checks: bool,
+
+    /// Sol val calculator programs that were outdated as of the last update,
+    /// see [`calc_upgrade`]
+    pub outdated_calcs: HashSet<[u8; 32]>,
+
+    /// Opt-in, see [`Self::with_quote_cache`]
+    pub quote_cache: Option<QuoteCache>,
+
+    /// Opt-in, see [`Self::with_delta_updates`]
+    pub delta: Option<DeltaTracker>,
+
+    /// Opt-in, see [`Self::with_dynamic_account_checks`]
+    pub dynamic_accs: Option<DynamicAccs>,
+
+    /// See [`Self::with_jup_quote_fees`]
+    pub jup_quote_fees: JupQuoteFees,
+
+    /// See [`Self::with_fee_pct_rounding`]
+    pub fee_pct_rounding: Option<FeePctRounding>,
+
+    /// See [`Self::with_clock_mode`]
+    pub clock_mode: ClockMode,
+
+    /// See [`Self::with_epoch_stale_exclusion`]
+    pub exclude_epoch_stale: bool,
+
+    /// See [`Self::with_pricing_migration`]
+    pub pricing_migration: bool,
+
+    /// See [`pricing_migration`]
+    pub(crate) pricing_standby: Option<Arc<InfStd>>,
+
+    /// See [`Self::with_lenient_updates`]
+    pub lenient_updates: bool,
+
+    /// See [`Self::with_rebalance_tracking`]
+    pub rebalance_tracking: bool,
+
+    /// See [`Self::rebalance_in_progress`]
+    pub rebalance_in_progress: Option<RebalanceInProgress>,
+
+    /// Epoch of the sysvar Clock applied in the last update.
+    /// Always `None` in [`ClockMode::ClockRef`].
+    pub sysvar_clock_epoch: Option<u64>,
+
+    /// [`Self::epoch`] as of the last update, see [`epoch`]
+    pub last_update_epoch: Option<u64>,
+
+    /// Token program of each mint, see [`token_program`]
+    pub token_programs: HashMap<[u8; 32], [u8; 32]>,
+
+    /// See [`Self::mint_metadata`]
+    pub mint_metas: Arc<HashMap<[u8; 32], MintMeta>>,
+
+    /// See [`Self::pair_fees`]
+    pub fee_schedule: FeeSchedule,
+
+    /// See [`Self::new_with_config`]
+    pub config: InfAmmConfig,
+
+    /// See [`DeploymentPdas`]
+    pub deployment_pdas: Arc<DeploymentPdas>,
+
+    /// See [`Self::with_metrics`]
+    pub metrics: Arc<dyn InfAmmMetrics>,
+
+    /// Data of the accounts applied in the last update, see [`snapshot`].
+    ///
+    /// Opt-in, see [`Self::with_snapshots`]
+    #[cfg(feature = "serde")]
+    pub(crate) snapshot_accounts: Option<Arc<HashMap<[u8; 32], snapshot::SnapshotAccount>>>,
 }
 single_program_amm!(InfAmm, INF_PROGRAM_ID, LABEL);
 
 impl InfAmm {
+    #[inline]
     pub fn new(
         keyed_account: &KeyedAccount,
         amm_context: &AmmContext,
         spl_lsts: HashMap<[u8; 32], [u8; 32]>,
     ) -> Result<Self> {
-        if *keyed_account.key.as_array() != LST_STATE_LIST_ID {
+        Self::new_with_config(keyed_account, amm_context, spl_lsts, InfAmmConfig::MAINNET)
+    }
+
+    /// For use against a non-mainnet deployment of the INF controller program,
+    /// see [`config`] for caveats.
+    ///
+    /// The `keyed_account` should be `config.lst_state_list_id`.
+    pub fn new_with_config(
+        keyed_account: &KeyedAccount,
+        amm_context: &AmmContext,
+        spl_lsts: HashMap<[u8; 32], [u8; 32]>,
+        config: InfAmmConfig,
+    ) -> Result<Self> {
+        if *keyed_account.key.as_array() != config.lst_state_list_id {
             return Err(anyhow!("Incorrect LST state list keyed_account"));
         }
 
+        let lst_state_list_data = lst_state_list_format::to_packed(
+            config.lst_state_list_formats,
+            &keyed_account.account.data,
+        )?;
+
         let mut res = Self {
-            inner: InfStd::new(
-                DEFAULT_MAINNET_POOL,
-                keyed_account.account.data.clone().into_boxed_slice(),
-                None,
-                None,
-                Default::default(),
-                Default::default(),
-                spl_lsts,
-                find_pda,
-                create_raw_pda,
-            )
-            .map_err(FmtErr)?,
+            inner: Arc::new(
+                InfStd::new(
+                    config.pricing_defaults.clone(),
+                    lst_state_list_data.to_vec().into_boxed_slice(),
+                    None,
+                    None,
+                    Default::default(),
+                    Default::default(),
+                    spl_lsts,
+                    config.pda.find,
+                    config.pda.create_raw,
+                )
+                .map_err(FmtErr)?,
+            ),
             current_epoch: amm_context.clock_ref.epoch.clone(),
+            current_slot: amm_context.clock_ref.slot.clone(),
+            generation: 0,
+            lst_health: HashMap::new(),
+            lp_mint_health: None,
+            invalid_reserves: HashSet::new(),
+            calc_upgrade_checks: false,
+            outdated_calcs: HashSet::new(),
+            quote_cache: None,
+            delta: None,
+            dynamic_accs: None,
+            jup_quote_fees: JupQuoteFees::default(),
+            fee_pct_rounding: None,
+            clock_mode: ClockMode::default(),
+            exclude_epoch_stale: false,
+            pricing_migration: false,
+            pricing_standby: None,
+            lenient_updates: false,
+            rebalance_tracking: false,
+            rebalance_in_progress: None,
+            sysvar_clock_epoch: None,
+            last_update_epoch: None,
+            token_programs: HashMap::new(),
+            mint_metas: bundled_mint_metas(),
+            fee_schedule: FeeSchedule::default(),
+            config,
+            deployment_pdas: Default::default(),
+            metrics: Arc::new(NoopMetrics),
+            #[cfg(feature = "serde")]
+            snapshot_accounts: None,
         };
 
         // need to initialize sol val calc data for all LSTs on the list
         // so that first update doesnt fail with InfErr::MissingSvcData
 
-        let lst_state_list = LstStatePackedList::of_acc_data(&keyed_account.account.data)
+        let lst_state_list = LstStatePackedList::of_acc_data(&lst_state_list_data)
             .context("LstStatePackedList::of_acc_data failed")?;
+        let inner = Arc::make_mut(&mut res.inner);
         lst_state_list
             .0
             .iter()
             .try_for_each(
-                |s| match res.inner.try_get_or_init_lst_svc(&s.into_lst_state()) {
+                |s| match inner.try_get_or_init_lst_svc(&s.into_lst_state()) {
                     Ok(_) => Ok(()),
                     Err(error) => {
-                        // Do not cause an error when we don't have the necessary spl data for a LST
-                        if matches!(error, InfErr::MissingSplData { .. }) {
+                        if is_skippable_svc_err(&error) {
                             Ok(())
                         } else {
                             Err(error)
@@ -156,85 +367,474 @@ impl InfAmm {
                 },
             )
             .map_err(FmtErr)?;
+        res.refresh_deployment_pdas();
 
         Ok(res)
     }
-}
 
-impl Amm for InfAmm {
-    /// The `keyed_account` should be the `LST_STATE_LIST`, **NOT** `POOL_STATE`.
-    fn from_keyed_account(keyed_account: &KeyedAccount, amm_context: &AmmContext) -> Result<Self>
-    where
-        Self: Sized,
-    {
-        Self::new(keyed_account, amm_context, build_spl_lsts())
+    /// Enables memoization of quotes, keyed by `(input_mint, output_mint, amount, swap_mode)`,
+    /// holding at most `max_entries` quotes. The cache is cleared at the start of every
+    /// [`Amm::update`].
+    #[inline]
+    pub fn with_quote_cache(mut self, max_entries: usize) -> Self {
+        self.quote_cache = Some(QuoteCache::new(max_entries));
+        self
     }
 
-    fn label(&self) -> String {
-        LABEL.to_owned()
+    /// Sets which fee components are reported in the [`Quote`]s returned by [`Amm::quote`].
+    /// Regardless of this setting, [`Self::quote_raw`] always returns the full breakdown.
+    #[inline]
+    pub fn with_jup_quote_fees(mut self, jup_quote_fees: JupQuoteFees) -> Self {
+        self.jup_quote_fees = jup_quote_fees;
+        self
     }
 
-    fn program_id(&self) -> Pubkey {
-        inf1_std::inf1_ctl_core::ID.into()
+    /// Sets where the current epoch is obtained from, see [`ClockMode`].
+    ///
+    /// If set to [`ClockMode::Sysvar`], quoting fails with [`InfAmmErr::ClockNotUpdated`]
+    /// until the next [`Amm::update`] that applies sysvar Clock.
+    #[inline]
+    pub fn with_clock_mode(mut self, clock_mode: ClockMode) -> Self {
+        self.clock_mode = clock_mode;
+        self
     }
 
-    /// S Pools are 1 per program, so just use program ID as key
-    fn key(&self) -> Pubkey {
-        INF_LST_LIST_ID
+    /// Makes [`Amm::get_reserve_mints`] return [`Self::quotable_mints`] instead of all mints
+    /// that can be quoted once their stake pools are updated, so that routers skip pairs
+    /// with LSTs whose stake pools have not been updated for the current epoch instead of
+    /// getting `NotUpdated` errors, and pick them back up once the stake pools are updated.
+    ///
+    /// Only has an effect on routers that call [`Amm::get_reserve_mints`] after every update.
+    #[inline]
+    pub fn with_epoch_stale_exclusion(mut self) -> Self {
+        self.exclude_epoch_stale = true;
+        self
     }
 
-    fn get_reserve_mints(&self) -> Vec<Pubkey> {
-        let lst_state_list = self.inner.try_lst_state_list().unwrap_or_default();
-        lst_state_list
+    /// Makes [`Amm::update`] skip LSTs whose sol val calc fails to update, e.g. because
+    /// of malformed or missing stake pool accounts, instead of failing the whole update.
+    ///
+    /// Skipped LSTs keep their sol val calc state from the previous update and are counted
+    /// in `lsts_skipped` of [`InfAmmMetrics::on_update_complete`].
+    /// Failures to update the pool state, LST state list, LP token mint or pricing program
+    /// still fail the update.
+    #[inline]
+    pub fn with_lenient_updates(mut self) -> Self {
+        self.lenient_updates = true;
+        self
+    }
+
+    /// Sets the hooks that update and quote outcomes are reported to, see [`metrics`]
+    #[inline]
+    pub fn with_metrics(mut self, metrics: Arc<dyn InfAmmMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Same as [`Amm::quote`], but returns `(fee_mint, quote)` as-is
+    /// without going through [`anyhow`], so the happy path does not allocate.
+    ///
+    /// `current_epoch` is taken as an arg so that callers quoting in a loop
+    /// only need to load [`Self::epoch`] once.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            skip_all,
+            fields(
+                inp = %Pubkey::new_from_array(*pair.inp),
+                out = %Pubkey::new_from_array(*pair.out),
+                amount = amount,
+                limit_ty = ?limit_ty,
+                current_epoch = current_epoch,
+            ),
+            err(level = "debug", Display)
+        )
+    )]
+    pub fn quote_raw(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        amount: u64,
+        limit_ty: TradeLimitTy,
+        current_epoch: u64,
+    ) -> Result<([u8; 32], inf1_std::quote::Quote), InfAmmErr> {
+        self.check_pair_quotable(pair, limit_ty, current_epoch)
+            .and_then(|_| self.quote_raw_unchecked(pair, amount, limit_ty))
+            .inspect_err(|e| self.metrics.on_quote_err(QuoteErrKind::of(e)))
+    }
+
+    /// [`Amm::quote`] at `current_epoch` instead of [`Self::epoch`], e.g. for backtesting
+    /// against historical account snapshots whose stake pools were updated for
+    /// an epoch other than the live one.
+    ///
+    /// Overrides the epoch even in [`ClockMode::Sysvar`].
+    /// See [`Self::with_deterministic_mode`] to fix the epoch of every quote instead.
+    pub fn quote_at_epoch(
+        &self,
+        QuoteParams {
+            amount,
+            input_mint,
+            output_mint,
+            swap_mode,
+            ..
+        }: &QuoteParams,
+        current_epoch: u64,
+    ) -> Result<Quote> {
+        let pair = Pair {
+            inp: native_sol_to_wsol(input_mint.as_array()),
+            out: native_sol_to_wsol(output_mint.as_array()),
+        };
+        let limit_ty = swap_mode_to_trade_limit_ty(*swap_mode);
+        let (fee_mint, quote) = self.quote_raw(&pair, *amount, limit_ty, current_epoch)?;
+        self.jup_quote(&fee_mint, quote)
+    }
+
+    /// Quotes `pair` for each of `amounts`, e.g. for building a liquidity depth curve.
+    ///
+    /// `quote_many(pair, amounts, swap_mode)[i]` is the same as [`Amm::quote`] for `amounts[i]`,
+    /// including [`consts::NATIVE_SOL_MINT_ADDR`] being quoted as wSOL,
+    /// but the epoch is only loaded once and pair-level checks,
+    /// such as whether the underlying stake pools are updated for the current epoch
+    /// and whether their sol value calculators are up to date, are only done once for all amounts.
+    pub fn quote_many(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        amounts: &[u64],
+        swap_mode: SwapMode,
+    ) -> Vec<Result<Quote>> {
+        let pair = Pair {
+            inp: native_sol_to_wsol(pair.inp),
+            out: native_sol_to_wsol(pair.out),
+        };
+        let limit_ty = swap_mode_to_trade_limit_ty(swap_mode);
+        if let Err(e) = self
+            .try_epoch()
+            .and_then(|epoch| self.check_pair_quotable(&pair, limit_ty, epoch))
+        {
+            let kind = QuoteErrKind::of(&e);
+            return amounts
+                .iter()
+                .map(|_| {
+                    self.metrics.on_quote_err(kind);
+                    Err(e.into())
+                })
+                .collect();
+        }
+        amounts
             .iter()
-            .map(|s| s.into_lst_state().mint.into())
-            .chain(once(self.inner.pool.lp_token_mint.into()))
+            .map(|amount| {
+                let (fee_mint, quote) = self
+                    .quote_raw_unchecked(&pair, *amount, limit_ty)
+                    .inspect_err(|e| self.metrics.on_quote_err(QuoteErrKind::of(e)))?;
+                self.jup_quote(&fee_mint, quote)
+            })
             .collect()
     }
 
-    /// Note: does not dedup
-    fn get_accounts_to_update(&self) -> Vec<Pubkey> {
-        let lst_state_iter = self
-            .inner
+    /// Checks done before quoting, for trades that the INF program would revert.
+    ///
+    /// There is no per-LST capacity check: neither `LstState` nor `PoolState` have cap
+    /// or max SOL value fields, so the INF program does not limit how much of an LST
+    /// the pool holds. The only limit on trade size is the output LST's reserves,
+    /// see [`InfAmmErr::is_not_enough_liquidity`].
+    pub(crate) fn check_pair_quotable(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        limit_ty: TradeLimitTy,
+        current_epoch: u64,
+    ) -> Result<(), InfAmmErr> {
+        check_distinct_mints(pair)?;
+        self.check_pool_active()?;
+        self.check_limit_ty_supported(pair, limit_ty)?;
+        self.check_lp_mint_authority(pair)?;
+        self.check_reserves_valid(pair)?;
+        self.check_calcs_up_to_date(pair)?;
+
+        // clock special-case handling:
+        // early return err if any of the mints are
+        // epoch affected and epoch conditions dont hold
+        for mint in [pair.inp, pair.out] {
+            self.check_epoch_updated(mint, current_epoch)?;
+        }
+        Ok(())
+    }
+
+    /// [`Self::quote_raw`] without [`Self::check_pair_quotable`]
+    pub(crate) fn quote_raw_unchecked(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        amount: u64,
+        limit_ty: TradeLimitTy,
+    ) -> Result<([u8; 32], inf1_std::quote::Quote), InfAmmErr> {
+        let cache_key = QuoteCacheKey::new(pair.inp, pair.out, amount, limit_ty);
+        if let Some(hit) = self.quote_cache.as_ref().and_then(|c| c.get(&cache_key)) {
+            return Ok(hit);
+        }
+
+        let trade = self.inner.quote_trade(pair, amount, limit_ty)?;
+        let (fee_mint, quote) = match trade {
+            #[allow(deprecated)]
+            Trade::AddLiquidity(q) => (*q.fee_mint(), q.0),
+            #[allow(deprecated)]
+            Trade::RemoveLiquidity(q) => (*q.fee_mint(), q.0),
+            Trade::SwapExactIn(q) => (*q.fee_mint(), q.0),
+            Trade::SwapExactOut(q) => (*q.fee_mint(), q.0),
+        };
+
+        if let Some(c) = &self.quote_cache {
+            c.insert(cache_key, &fee_mint, &quote);
+        }
+        Ok((fee_mint, quote))
+    }
+
+    /// ExactOut is supported for all pairs except those involving the LP token,
+    /// since the INF program does not support ExactOut for AddLiquidity and RemoveLiquidity.
+    ///
+    /// Routers should check this before attempting ExactOut for `pair`, else
+    /// [`Amm::quote`] and [`Amm::get_swap_and_account_metas`] return
+    /// [`InfAmmErr::ExactOutUnsupportedForLiquidity`].
+    #[inline]
+    pub fn supports_exact_out_for_pair(&self, pair: &Pair<&[u8; 32]>) -> bool {
+        let lp_mint = &self.inner.pool.lp_token_mint;
+        pair.inp != lp_mint && pair.out != lp_mint
+    }
+
+    /// Always `false`: the INF program has no referral or platform fee accounts,
+    /// protocol fees are accumulated in the pool's own protocol fee accounts.
+    ///
+    /// [`Amm::get_swap_and_account_metas`] returns [`InfAmmErr::ReferralFeesUnsupported`]
+    /// instead of silently dropping a referrer token account provided for the input or output mint
+    /// in [`SwapParams::quote_mint_to_referrer`].
+    #[inline]
+    pub const fn supports_referral_fees(&self) -> bool {
+        false
+    }
+
+    /// Whether the pool, as of the last update, is neither disabled nor in the middle
+    /// of a rebalance. All trades revert onchain if not.
+    ///
+    /// Routers should exclude this AMM while this returns `false`, else [`Amm::quote`]
+    /// returns [`InfAmmErr::PoolDisabled`] or [`InfAmmErr::PoolRebalancing`].
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.inner.pool.is_disabled == 0 && self.inner.pool.is_rebalancing == 0
+    }
+
+    /// The pool state as of the last successful update, `None` if there has been none yet.
+    ///
+    /// Until then, [`Self::inner`] holds [`consts::DEFAULT_MAINNET_POOL`], whose admin,
+    /// rebalance authority and protocol fee beneficiary are placeholders
+    /// that must not be used in instructions.
+    #[inline]
+    pub fn live_pool_state(&self) -> Option<&PoolState> {
+        // set together with inner on every successful update
+        self.lp_mint_health.map(|_| &self.inner.pool)
+    }
+
+    /// The pool state that quotes are computed against, without a copy.
+    ///
+    /// Same as [`Self::live_pool_state`] after a successful update,
+    /// [`InfAmmConfig::pricing_defaults`] before.
+    #[inline]
+    pub fn pool_state(&self) -> &PoolState {
+        &self.inner.pool
+    }
+
+    /// The LST state list as of the last update that applied it,
+    /// else as of the `keyed_account` passed at construction.
+    ///
+    /// Empty if the LST state list data is malformed.
+    #[inline]
+    pub fn lst_states(&self) -> impl Iterator<Item = LstState> + '_ {
+        self.inner
             .try_lst_state_list()
-            .unwrap_or_default() // TODO: should this panic instead if LstStateList format unexpectedly changed?
+            .unwrap_or_default()
             .iter()
-            .map(|l| l.into_lst_state());
-        [
-            POOL_STATE_ID,
-            LST_STATE_LIST_ID,
-            self.inner.pool.lp_token_mint,
-        ]
-        .into_iter()
-        .chain(
-            self.inner
-                .pricing
-                .accounts_to_update_all(lst_state_iter.clone().map(|LstState { mint, .. }| mint)),
-        )
-        .chain(
-            lst_state_iter
-                .filter_map(|lst_state| {
-                    // ignore err here, some LSTs may not have their.
-                    // sol val calc accounts fetched yet.
-                    //
-                    // update() should call `try_get_or_init_lst_svc_mut`
-                    // which will make it no longer err for the next update cycle
-                    self.inner
-                        .accounts_to_update_lst(&lst_state)
-                        .ok()
-                        .map(|iter| iter.filter(|pk| *pk != SYSVAR_CLOCK))
-                })
-                .flatten(),
-        )
-        .map(Pubkey::new_from_array)
-        .collect()
+            .map(|l| l.into_lst_state())
     }
 
-    fn update(&mut self, account_map: &AccountMap) -> Result<()> {
-        let fetched = AccountMapRef(account_map);
-        self.inner.update_pool(fetched).map_err(FmtErr)?;
-        self.inner.update_lst_state_list(fetched).map_err(FmtErr)?;
-        self.inner.update_lp_token_supply(fetched).map_err(FmtErr)?;
+    /// See [`Self::live_pool_state`]
+    #[inline]
+    pub fn admin(&self) -> Option<Pubkey> {
+        self.live_pool_state()
+            .map(|p| Pubkey::new_from_array(p.admin))
+    }
+
+    /// See [`Self::live_pool_state`]
+    #[inline]
+    pub fn rebalance_authority(&self) -> Option<Pubkey> {
+        self.live_pool_state()
+            .map(|p| Pubkey::new_from_array(p.rebalance_authority))
+    }
+
+    /// Owner of the destination token accounts of protocol fee withdrawals.
+    ///
+    /// See [`Self::live_pool_state`]
+    #[inline]
+    pub fn protocol_fee_beneficiary(&self) -> Option<Pubkey> {
+        self.live_pool_state()
+            .map(|p| Pubkey::new_from_array(p.protocol_fee_beneficiary))
+    }
+
+    /// Mints on the LST state list, and the LP token mint, whose sol val calcs can be quoted
+    /// against at the current epoch as of the last update.
+    ///
+    /// Excludes epoch affected LSTs whose stake pools have not been updated for the
+    /// current epoch, LSTs without sol val calc data, e.g. those skipped in updates,
+    /// and LSTs whose reserves account was invalid, see [`reserves`].
+    /// Also excludes the LST that an in-progress rebalance is waiting on, if tracked,
+    /// see [`Self::rebalance_in_progress`].
+    /// Does not consider whether the pool is active or an LST's input is disabled.
+    pub fn quotable_mints(&self) -> Vec<Pubkey> {
+        let current_epoch = self.epoch();
+        let rebalancing_mint = self.rebalance_in_progress.map(|r| r.inp_mint);
+        self.all_mints()
+            .filter(|mint| Some(*mint) != rebalancing_mint)
+            .filter(|mint| !self.invalid_reserves.contains(mint))
+            .filter(|mint| self.check_epoch_updated(mint, current_epoch).is_ok())
+            .map(Pubkey::new_from_array)
+            .collect()
+    }
+
+    /// Mints on the LST state list, then the LP token mint
+    fn all_mints(&self) -> impl Iterator<Item = [u8; 32]> + '_ {
+        self.inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|s| s.into_lst_state().mint)
+            .chain(once(self.inner.pool.lp_token_mint))
+    }
+
+    /// [`Self::all_mints`] that have sol val calc data and a valid reserves account
+    /// as of the last update, i.e. that can be quoted once their stake pools are updated.
+    fn live_mints(&self) -> impl Iterator<Item = [u8; 32]> + '_ {
+        self.all_mints()
+            .filter(|mint| !self.invalid_reserves.contains(mint))
+            // no stake pool is behind epoch 0,
+            // so this only checks that the mint has sol val calc data
+            .filter(|mint| self.check_epoch_updated(mint, 0).is_ok())
+    }
+
+    /// Total supply of the LP token (INF) as of the last update, 0 if there has been none yet
+    #[inline]
+    pub fn lp_token_supply(&self) -> u64 {
+        self.inner.lp_token_supply.unwrap_or_default()
+    }
+
+    /// SOL value of 1 LP token (INF), i.e. the pool's total SOL value divided by
+    /// [`Self::lp_token_supply`]. Both INF and SOL have 9 decimals.
+    ///
+    /// Returns `None` if LP token supply is 0, e.g. if there has not been an update yet.
+    #[inline]
+    pub fn inf_sol_value_per_token(&self) -> Option<Decimal> {
+        Decimal::from(self.inner.pool.total_sol_value)
+            .checked_div(Decimal::from(self.lp_token_supply()))
+    }
+
+    /// [`Amm::update`] with any [`UpdateMapWithOwner`], e.g. [`update::UiAccountMap`]
+    ///
+    /// Atomic: if any account fails to apply, pool, LST and sol val calc state
+    /// are all left as of the last successful update.
+    #[inline]
+    pub fn update_with<M: UpdateMapWithOwner>(&mut self, fetched_raw: &M) -> Result<()> {
+        self.update_in(fetched_raw, None)
+    }
+
+    /// See [`Self::with_snapshots`]
+    #[inline]
+    const fn snapshots_enabled(&self) -> bool {
+        #[cfg(feature = "serde")]
+        {
+            self.snapshot_accounts.is_some()
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            false
+        }
+    }
+
+    /// [`Self::update_with`] that only updates the LSTs in `scope` if set,
+    /// see [`pair_update`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "update_with",
+            level = "debug",
+            skip_all,
+            fields(generation = self.generation + 1, epoch = self.epoch()),
+            err(Display)
+        )
+    )]
+    pub(crate) fn update_in<M: UpdateMapWithOwner>(
+        &mut self,
+        fetched_raw: &M,
+        scope: Option<&[[u8; 32]]>,
+    ) -> Result<()> {
+        let start = UpdateTimer::start();
+        let accounts_to_update = if self.snapshots_enabled() || self.delta.is_some() {
+            self.accounts_to_update_in(scope)
+        } else {
+            Vec::new()
+        };
+
+        self.generation += 1;
+        if let Some(c) = &mut self.quote_cache {
+            c.clear();
+        }
+
+        let deployment = DeploymentAccountMapRef {
+            map: fetched_raw,
+            config: &self.config,
+            pdas: &self.deployment_pdas,
+        };
+        // only newer layouts need converting, the packed layout is applied as is
+        let lst_state_list = match deployment.get_account(&LST_STATE_LIST_ID) {
+            Some(acc) if version_of(acc.data()) != PACKED_VERSION => Some(
+                lst_state_list_format::to_packed(self.config.lst_state_list_formats, acc.data())?
+                    .into_owned(),
+            ),
+            _ => None,
+        };
+        let fetched = LstStateListOverride {
+            map: &deployment,
+            lst_state_list: lst_state_list.as_deref(),
+        };
+        let prev_lp_mint = self.inner.pool.lp_token_mint;
+        let with_ctx = |e: UpdateErr<InfErr>| update_err_with_ctx(&fetched, &prev_lp_mint, e);
+        let sysvar_clock_epoch = match self.clock_mode {
+            ClockMode::ClockRef => None,
+            ClockMode::Sysvar => {
+                let clock = fetched
+                    .get_account(&SYSVAR_CLOCK)
+                    .ok_or(FmtErr(UpdateErr::<InfErr>::AccMissing { pk: SYSVAR_CLOCK }))?;
+                Some(
+                    sysvar_clock_epoch(clock.data())
+                        .ok_or_else(|| acc_deser_err(&fetched, &prev_lp_mint, SYSVAR_CLOCK))?,
+                )
+            }
+        };
+        let with_clock = sysvar_clock_epoch.is_some();
+
+        // All stages are applied to a scratch copy that only replaces self.inner
+        // once every stage has succeeded, so a failed update leaves self as it was
+        // instead of mixing accounts from the old and new snapshots.
+        // Clones of self keep quoting against the old state either way.
+        let mut staged = InfStd::clone(&self.inner);
+        let inner = &mut staged;
+        inner.update_pool(fetched).map_err(with_ctx)?;
+        self.swap_in_standby_pricing(inner);
+        inner.update_lst_state_list(fetched).map_err(with_ctx)?;
+        prune_delisted_lsts(inner);
+        inner.update_lp_token_supply(fetched).map_err(with_ctx)?;
+        let lp_token_mint = inner.pool.lp_token_mint;
+        let lp_mint_health = fetched
+            .get_account(&lp_token_mint)
+            .and_then(|acc| parse_lp_mint_health(acc.data()))
+            .ok_or_else(|| acc_deser_err(&fetched, &lp_token_mint, lp_token_mint))?;
+
+        #[cfg(feature = "tracing")]
+        let pricing_program = Pubkey::new_from_array(inner.pool.pricing_program);
 
         let InfStd {
             lst_state_list_data,
@@ -244,32 +844,84 @@ impl Amm for InfAmm {
             lst_reserves,
             create_pda,
             ..
-        } = &mut self.inner;
+        } = inner;
 
-        let mut all_lst_states = LstStatePackedList::of_acc_data(lst_state_list_data)
-            .ok_or(FmtErr(InfErr::AccDeser {
-                pk: LST_STATE_LIST_ID,
-            }))?
-            .0
+        let lst_state_list = LstStatePackedList::of_acc_data(lst_state_list_data)
+            .ok_or_else(|| acc_deser_err(&fetched, &lp_token_mint, LST_STATE_LIST_ID))?
+            .0;
+        // includes LSTs listed in this update, whose reserves are looked up below
+        let deployment_pdas = self.deployment_pdas.refreshed(
+            &self.config,
+            lst_state_list
+                .iter()
+                .map(|s| s.into_lst_state().mint)
+                .collect(),
+        );
+        let reserves_fetched = DeploymentAccountMapRef {
+            map: fetched_raw,
+            config: &self.config,
+            pdas: &deployment_pdas,
+        };
+        let mut all_lst_states = lst_state_list
             .iter()
-            .map(|s| s.into_lst_state());
+            .map(|s| s.into_lst_state())
+            .filter(|LstState { mint, .. }| in_scope(scope, mint));
+        let lsts_total = all_lst_states.clone().count();
+        let mut lsts_skipped = 0;
+        let pool_state_id = self.config.pool_state_id;
+        let create_raw_pda = self.config.pda.create_raw;
+        // LSTs out of scope retain their reserves validity as of their last update
+        let mut invalid_reserves: HashSet<_> = self
+            .invalid_reserves
+            .iter()
+            .filter(|mint| !in_scope(scope, mint))
+            .copied()
+            .collect();
+        let lenient_updates = self.lenient_updates;
+        let prev_lst_calcs = &self.inner.lst_calcs;
 
-        pricing.update_all(
-            all_lst_states.clone().map(|LstState { mint, .. }| mint),
-            fetched,
-        )?;
+        {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("update_pricing", %pricing_program).entered();
+            pricing.update_all(
+                all_lst_states.clone().map(|LstState { mint, .. }| mint),
+                fetched,
+            )?;
+        }
 
         all_lst_states
             .try_for_each(|lst_state| {
-                InfStd::update_lst_reserves(lst_reserves, create_pda as &_, &lst_state, fetched)?;
+                #[cfg(feature = "tracing")]
+                let _span = tracing::trace_span!(
+                    "update_lst",
+                    mint = %Pubkey::new_from_array(lst_state.mint)
+                )
+                .entered();
+
+                // reserves are validated against the deployment's pool state,
+                // so look them up in fetched_raw, which is keyed by deployment address
+                if is_reserves_valid(&pool_state_id, create_raw_pda, &lst_state, fetched_raw) {
+                    InfStd::update_lst_reserves(
+                        lst_reserves,
+                        create_pda as &_,
+                        &lst_state,
+                        reserves_fetched,
+                    )?;
+                } else {
+                    invalid_reserves.insert(lst_state.mint);
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!("reserves account invalid");
+                }
 
                 let calc =
                     match InfStd::try_get_or_init_lst_svc_static(lst_calcs, spl_lsts, &lst_state) {
                         Ok(calc) => calc,
                         Err(error) => {
-                            // Do not cause an error when we don't have the necessary spl data for a LST
-                            if matches!(error, InfErr::MissingSplData { .. }) {
+                            if is_skippable_svc_err(&error) {
                                 lst_calcs.remove(&lst_state.mint);
+                                lsts_skipped += 1;
+                                #[cfg(feature = "tracing")]
+                                tracing::debug!(err = %FmtErr(error), "skipped");
                                 return Ok(());
                             } else {
                                 return Err(UpdateErr::Inner(error));
@@ -277,113 +929,228 @@ impl Amm for InfAmm {
                         }
                     };
 
-                match &mut calc.0 {
-                    // omit clock for these variants
+                let res = match &mut calc.0 {
+                    // in ClockMode::Sysvar, all these variants are updated
+                    // with the same clock, so current_epoch is consistent across them
+                    #[cfg(feature = "lido")]
+                    SvcAg::Lido(c) if with_clock => c
+                        .update_svc(fetched)
+                        .map_err(|e| e.map_inner(SvcAg::Lido).map_inner(InfErr::UpdateSvc)),
+                    #[cfg(feature = "spl")]
+                    SvcAg::SanctumSpl(c) if with_clock => c
+                        .update_svc(fetched)
+                        .map_err(|e| e.map_inner(SvcAg::SanctumSpl).map_inner(InfErr::UpdateSvc)),
+                    #[cfg(feature = "spl")]
+                    SvcAg::SanctumSplMulti(c) if with_clock => c.update_svc(fetched).map_err(|e| {
+                        e.map_inner(SvcAg::SanctumSplMulti)
+                            .map_inner(InfErr::UpdateSvc)
+                    }),
+                    #[cfg(feature = "spl")]
+                    SvcAg::Spl(c) if with_clock => c
+                        .update_svc(fetched)
+                        .map_err(|e| e.map_inner(SvcAg::Spl).map_inner(InfErr::UpdateSvc)),
+
+                    // else omit clock for these variants
+                    #[cfg(feature = "lido")]
                     SvcAg::Lido(c) => c
                         .update_svc_no_clock(fetched)
                         .map_err(|e| e.map_inner(SvcAg::Lido).map_inner(InfErr::UpdateSvc)),
+                    #[cfg(feature = "spl")]
                     SvcAg::SanctumSpl(c) => c
                         .update_svc_no_clock(fetched)
                         .map_err(|e| e.map_inner(SvcAg::SanctumSpl).map_inner(InfErr::UpdateSvc)),
+                    #[cfg(feature = "spl")]
                     SvcAg::SanctumSplMulti(c) => c.update_svc_no_clock(fetched).map_err(|e| {
                         e.map_inner(SvcAg::SanctumSplMulti)
                             .map_inner(InfErr::UpdateSvc)
                     }),
+                    #[cfg(feature = "spl")]
                     SvcAg::Spl(c) => c
                         .update_svc_no_clock(fetched)
                         .map_err(|e| e.map_inner(SvcAg::Spl).map_inner(InfErr::UpdateSvc)),
 
                     // following variants unaffected by clock
+                    #[cfg(feature = "marinade")]
                     SvcAg::Marinade(c) => c
                         .update_svc(fetched)
                         .map_err(|e| e.map_inner(SvcAg::Marinade).map_inner(InfErr::UpdateSvc)),
+                    #[cfg(feature = "wsol")]
                     SvcAg::Wsol(c) => c
                         .update_svc(fetched)
                         .map_err(|e| e.map_inner(SvcAg::Wsol).map_inner(InfErr::UpdateSvc)),
+
+                    // handling of this variant is disabled by cargo features.
+                    // Dont fail the whole update, quoting will return
+                    // InfErr::UnsupportedMint for this LST instead
+                    #[allow(unreachable_patterns)]
+                    _ => {
+                        lsts_skipped += 1;
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!("skipped, sol val calc disabled by cargo features");
+                        Ok(())
+                    }
+                };
+                #[cfg(feature = "tracing")]
+                if let Err(e) = &res {
+                    tracing::debug!(err = ?e, "sol val calc update failed");
                 }
+                if res.is_err() && lenient_updates {
+                    // update_svc may have partially applied before failing
+                    match prev_lst_calcs.get(&lst_state.mint) {
+                        Some(prev) => lst_calcs.insert(lst_state.mint, prev.clone()),
+                        None => lst_calcs.remove(&lst_state.mint),
+                    };
+                    lsts_skipped += 1;
+                    return Ok(());
+                }
+                res
             })
-            .map_err(FmtErr)?;
+            .map_err(with_ctx)?;
+
+        // commit
+        let is_first_update = self.lp_mint_health.is_none();
+        self.inner = Arc::new(staged);
+        self.deployment_pdas = deployment_pdas;
+        self.lp_mint_health = Some(lp_mint_health);
+        self.invalid_reserves = invalid_reserves;
+        self.sysvar_clock_epoch = sysvar_clock_epoch;
+        self.prune_delisted_records();
+
+        self.record_lst_health(fetched_raw, scope);
+        self.record_dynamic_accs(fetched_raw, scope);
+        self.record_token_programs(fetched_raw, scope);
+        self.record_fee_schedule(fetched_raw, scope);
+        self.record_pricing_standby(fetched_raw, scope);
+        self.record_rebalance_in_progress(fetched_raw);
+        self.record_outdated_calcs(fetched_raw, scope);
+        self.record_epoch();
+        if is_first_update {
+            self.report_pool_defaults();
+        }
+        #[cfg(feature = "serde")]
+        self.record_snapshot_accounts(&accounts_to_update, fetched_raw, scope.is_some());
+        if let Some(d) = &mut self.delta {
+            d.record(
+                &accounts_to_update,
+                fetched_raw,
+                self.current_slot.load(Ordering::Relaxed),
+            );
+        }
+
+        self.metrics
+            .on_update_complete(start.elapsed(), lsts_total - lsts_skipped, lsts_skipped);
 
         Ok(())
     }
 
-    fn quote(
+    /// [`Amm::get_accounts_to_update`] that only includes the accounts of the LSTs
+    /// in `scope` if set, see [`pair_update`]
+    pub(crate) fn accounts_to_update_in(&self, scope: Option<&[[u8; 32]]>) -> Vec<Pubkey> {
+        let lst_state_iter = self
+            .inner
+            .try_lst_state_list()
+            .unwrap_or_default() // TODO: should this panic instead if LstStateList format unexpectedly changed?
+            .iter()
+            .map(|l| l.into_lst_state())
+            .filter(|LstState { mint, .. }| in_scope(scope, mint));
+        let sysvar_clock = match self.clock_mode {
+            ClockMode::ClockRef => None,
+            ClockMode::Sysvar => Some(SYSVAR_CLOCK),
+        };
+        let current_epoch = self.epoch();
+        let (stale_lsts, fresh_lsts): (Vec<_>, Vec<_>) = lst_state_iter
+            .clone()
+            .partition(|lst_state| self.is_epoch_stale(&lst_state.mint, current_epoch));
+        let lst_accs = |lst_states: Vec<LstState>| {
+            lst_states
+                .into_iter()
+                .filter_map(|lst_state| {
+                    // ignore err here, some LSTs may not have their.
+                    // sol val calc accounts fetched yet.
+                    //
+                    // update() should call `try_get_or_init_lst_svc_mut`
+                    // which will make it no longer err for the next update cycle
+                    //
+                    // clock is always filtered out here so that it's included at most once
+                    self.inner
+                        .accounts_to_update_lst(&lst_state)
+                        .ok()
+                        .map(|iter| iter.filter(|pk| *pk != SYSVAR_CLOCK))
+                })
+                .flatten()
+        };
+        lst_accs(stale_lsts)
+            .chain([
+                POOL_STATE_ID,
+                LST_STATE_LIST_ID,
+                self.inner.pool.lp_token_mint,
+            ])
+            .chain(sysvar_clock)
+            .chain(
+                self.inner
+                    .pricing
+                    .accounts_to_update_all(lst_state_iter.map(|LstState { mint, .. }| mint)),
+            )
+            .chain(self.standby_pricing_accs_to_update(scope))
+            .chain(lst_accs(fresh_lsts))
+            .chain(self.mints_to_update(scope))
+            .chain(self.dynamic_accs_to_update(scope))
+            .chain(self.rebalance_record_to_update())
+            .chain(self.calc_upgrade_accs_to_update(scope))
+            .map(|pk| Pubkey::new_from_array(*self.to_deployment(&pk)))
+            .collect()
+    }
+
+    /// Returns the complete, ready-to-send INF instruction for `swap_params`,
+    /// for use outside of jupiter.
+    ///
+    /// `in_amount` and `out_amount` are used as the amount and slippage limit:
+    /// - ExactIn: swaps exactly `in_amount`, receiving at least `out_amount`
+    /// - ExactOut: receives exactly `out_amount`, paying at most `in_amount`
+    ///
+    /// `token_transfer_authority` is set as signer.
+    pub fn swap_instruction(&self, swap_params: &SwapParams) -> Result<Instruction> {
+        let (ix_args, mut accounts) = self.trade_ix_args_and_metas(swap_params)?;
+        // keys_writable_to_jup_metas() sets all is_signer to false
+        accounts
+            .iter_mut()
+            .filter(|m| m.pubkey == swap_params.token_transfer_authority)
+            .for_each(|m| m.is_signer = true);
+        Ok(Instruction {
+            program_id: self.config.program_id.into(),
+            accounts,
+            data: trade_ix_args_to_ix_data(&ix_args),
+        })
+    }
+
+    /// Returns the full ix args and account metas of the INF instruction for `swap_params`.
+    ///
+    /// Account metas are as returned by [`keys_writable_to_jup_metas`], so all `is_signer` are false.
+    fn trade_ix_args_and_metas(
         &self,
-        QuoteParams {
-            amount,
-            input_mint,
-            output_mint,
-            swap_mode,
-            ..
-        }: &QuoteParams,
-    ) -> Result<Quote> {
-        // clock special-case handling:
-        // early return err if any of the mints are
-        // epoch affected and epoch conditions dont hold
-        for mint in [input_mint, output_mint] {
-            let mint = mint.as_array();
-            if !is_epoch_affected_lst_mint(mint) {
-                continue;
-            }
-            // since INF is not clock affected, we dont need to
-            // worry about try_get_lst_svc() failing for it.
-            // In future vers, INF will also have its own sol val calc anyway.
-            match self
-                .inner
-                .try_get_lst_svc(mint)
-                .map_err(FmtErr)?
-                .as_sol_val_calc()
-            {
-                // kinda sloppy, but if NotUpdated err encountered, just return it under
-                // SwapQuoteErr::InpCalc instead of determining what kind of swap and
-                // what position the affected mint was in
-                Some(c) => match c {
-                    SvcAg::Marinade(_) | SvcAg::Wsol(_) => continue,
-                    SvcAg::Lido(c) => {
-                        if c.exchange_rate.computed_in_epoch
-                            < self.current_epoch.load(Ordering::Relaxed)
-                        {
-                            return Err(FmtErr(InfErr::SwapQuote(SwapQuoteErr::InpCalc(
-                                SvcAg::Lido(LidoCalcErr::NotUpdated),
-                            )))
-                            .into());
-                        }
-                    }
-                    SvcAg::SanctumSpl(c) | SvcAg::SanctumSplMulti(c) | SvcAg::Spl(c) => {
-                        if c.last_update_epoch < self.current_epoch.load(Ordering::Relaxed) {
-                            return Err(FmtErr(InfErr::SwapQuote(SwapQuoteErr::InpCalc(
-                                SvcAg::Spl(SplCalcErr::NotUpdated),
-                            )))
-                            .into());
-                        }
+        swap_params: &SwapParams,
+    ) -> Result<(TradeIxArgsFull, Vec<AccountMeta>)> {
+        let (ix_args, mut account_metas) = self.trade_ix_args_and_metas_raw(swap_params)?;
+        if let Some(d) = &self.dynamic_accs {
+            for meta in account_metas.iter_mut() {
+                if d.get_missing(meta.pubkey.as_array()).is_none() {
+                    continue;
+                }
+                if !swap_params.missing_dynamic_accounts_as_default {
+                    return Err(InfAmmErr::MissingDynamicAcc {
+                        pk: meta.pubkey.to_bytes(),
                     }
-                },
-                None => return Err(FmtErr(InfErr::MissingSvcData { mint: *mint }).into()),
+                    .into());
+                }
+                meta.pubkey = Pubkey::default();
             }
         }
-
-        match self
-            .inner
-            .quote_trade(
-                &Pair {
-                    inp: input_mint.as_array(),
-                    out: output_mint.as_array(),
-                },
-                *amount,
-                swap_mode_to_trade_limit_ty(*swap_mode),
-            )
-            .map_err(FmtErr)?
-        {
-            #[allow(deprecated)]
-            Trade::AddLiquidity(q) => to_jup_quote(q.fee_mint(), q.0),
-            #[allow(deprecated)]
-            Trade::RemoveLiquidity(q) => to_jup_quote(q.fee_mint(), q.0),
-            Trade::SwapExactIn(q) => to_jup_quote(q.fee_mint(), q.0),
-            Trade::SwapExactOut(q) => to_jup_quote(q.fee_mint(), q.0),
-        }
+        Ok((ix_args, account_metas))
     }
 
-    fn get_swap_and_account_metas(
+    /// [`Self::trade_ix_args_and_metas`] without substituting missing dynamic accounts,
+    /// see [`dynamic`]
+    fn trade_ix_args_and_metas_raw(
         &self,
         SwapParams {
             swap_mode,
@@ -394,10 +1161,20 @@ impl Amm for InfAmm {
             source_token_account,
             destination_token_account,
             token_transfer_authority,
+            quote_mint_to_referrer,
             ..
         }: &SwapParams,
-    ) -> Result<SwapAndAccountMetas> {
+    ) -> Result<(TradeIxArgsFull, Vec<AccountMeta>)> {
         let limit_ty = swap_mode_to_trade_limit_ty(*swap_mode);
+        let mints = Pair {
+            inp: source_mint.as_array(),
+            out: destination_mint.as_array(),
+        };
+        check_distinct_mints(&mints)?;
+        self.check_pool_active()?;
+        self.check_limit_ty_supported(&mints, limit_ty)?;
+        self.check_lp_mint_authority(&mints)?;
+        check_no_referrer(*quote_mint_to_referrer, &mints)?;
         let (amt, limit) = match limit_ty {
             TradeLimitTy::ExactIn => (in_amount, out_amount),
             TradeLimitTy::ExactOut => (out_amount, in_amount),
@@ -405,10 +1182,7 @@ impl Amm for InfAmm {
         let args = TradeIxArgs {
             amt: *amt,
             limit: *limit,
-            mints: &Pair {
-                inp: source_mint.as_array(),
-                out: destination_mint.as_array(),
-            },
+            mints: &mints,
             signer: token_transfer_authority.as_array(),
             token_accs: &Pair {
                 inp: source_token_account.as_array(),
@@ -416,72 +1190,212 @@ impl Amm for InfAmm {
             },
         };
         let ix = self.inner.trade_ix(&args, limit_ty).map_err(FmtErr)?;
-        let mut account_metas = vec![AccountMeta::new_readonly(Self::PROGRAM_ID, false)];
-        Ok(match ix {
+        let mut account_metas = Vec::new();
+        let ix_args = match ix {
             Trade::AddLiquidity(ix) => {
-                let a = ix.to_full();
                 #[allow(deprecated)]
                 account_metas.extend(keys_writable_to_jup_metas(
                     add_liquidity_ix_keys_owned(&ix.accs).seq(),
                     add_liquidity_ix_is_writer(&ix.accs).seq(),
                 ));
-                SwapAndAccountMetas {
-                    swap: Swap::SanctumSAddLiquidity {
-                        lst_value_calc_accs: a.lst_value_calc_accs,
-                        lst_index: a.lst_index,
-                    },
-                    account_metas,
-                }
+                Trade::AddLiquidity(ix.to_full())
             }
             Trade::RemoveLiquidity(ix) => {
-                let a = ix.to_full();
                 #[allow(deprecated)]
                 account_metas.extend(keys_writable_to_jup_metas(
                     remove_liquidity_ix_keys_owned(&ix.accs).seq(),
                     remove_liquidity_ix_is_writer(&ix.accs).seq(),
                 ));
-                SwapAndAccountMetas {
-                    swap: Swap::SanctumSRemoveLiquidity {
-                        lst_value_calc_accs: a.lst_value_calc_accs,
-                        lst_index: a.lst_index,
-                    },
-                    account_metas,
-                }
+                Trade::RemoveLiquidity(ix.to_full())
             }
             Trade::SwapExactIn(ix) => {
-                let a = ix.to_full();
                 #[allow(deprecated)]
                 account_metas.extend(keys_writable_to_jup_metas(
                     swap_exact_in_ix_keys_owned(&ix.accs).seq(),
                     swap_exact_in_ix_is_writer(&ix.accs).seq(),
                 ));
-                SwapAndAccountMetas {
-                    swap: Swap::SanctumS {
-                        src_lst_value_calc_accs: a.inp_lst_value_calc_accs,
-                        dst_lst_value_calc_accs: a.out_lst_value_calc_accs,
-                        src_lst_index: a.inp_lst_index,
-                        dst_lst_index: a.out_lst_index,
-                    },
-                    account_metas,
-                }
+                Trade::SwapExactIn(ix.to_full())
             }
             Trade::SwapExactOut(ix) => {
-                let a = ix.to_full();
                 #[allow(deprecated)]
                 account_metas.extend(keys_writable_to_jup_metas(
                     swap_exact_out_ix_keys_owned(&ix.accs).seq(),
                     swap_exact_out_ix_is_writer(&ix.accs).seq(),
                 ));
-                SwapAndAccountMetas {
-                    swap: Swap::SanctumS {
-                        src_lst_value_calc_accs: a.inp_lst_value_calc_accs,
-                        dst_lst_value_calc_accs: a.out_lst_value_calc_accs,
-                        src_lst_index: a.inp_lst_index,
-                        dst_lst_index: a.out_lst_index,
+                Trade::SwapExactOut(ix.to_full())
+            }
+        };
+        self.fix_token_program_metas(&mints, &mut account_metas);
+        self.metas_to_deployment(&mut account_metas);
+        Ok((ix_args, account_metas))
+    }
+
+    fn check_limit_ty_supported(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        limit_ty: TradeLimitTy,
+    ) -> Result<(), InfAmmErr> {
+        match limit_ty {
+            TradeLimitTy::ExactOut if !self.supports_exact_out_for_pair(pair) => {
+                Err(InfAmmErr::ExactOutUnsupportedForLiquidity {
+                    pair: Pair {
+                        inp: *pair.inp,
+                        out: *pair.out,
                     },
-                    account_metas,
-                }
+                })
             }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_pool_active(&self) -> Result<(), InfAmmErr> {
+        if self.inner.pool.is_disabled != 0 {
+            Err(InfAmmErr::PoolDisabled)
+        } else if self.inner.pool.is_rebalancing != 0 {
+            Err(InfAmmErr::PoolRebalancing)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_lp_mint_authority(&self, pair: &Pair<&[u8; 32]>) -> Result<(), InfAmmErr> {
+        let lp_mint = &self.inner.pool.lp_token_mint;
+        if (pair.inp != lp_mint && pair.out != lp_mint) || self.is_lp_mint_authority_ok() {
+            return Ok(());
+        }
+        Err(InfAmmErr::LpMintAuthorityMismatch {
+            expected: self.config.pool_state_id,
+            actual: self.lp_mint_health.and_then(|h| h.mint_authority),
+        })
+    }
+
+    /// Returns err if `mint` is epoch affected and its underlying
+    /// stake pool has not been updated for `current_epoch`
+    fn check_epoch_updated(&self, mint: &[u8; 32], current_epoch: u64) -> Result<(), InfErr> {
+        // INF is not clock affected and currently does not have a sol val calc.
+        // Once it has its own sol val calc on the LST state list,
+        // it is handled like every other LST below.
+        if *mint == self.inner.pool.lp_token_mint && !self.inner.lst_calcs.contains_key(mint) {
+            return Ok(());
+        }
+        match self.inner.try_get_lst_svc(mint)?.as_sol_val_calc() {
+            // kinda sloppy, but if NotUpdated err encountered, just return it under
+            // SwapQuoteErr::InpCalc instead of determining what kind of swap and
+            // what position the affected mint was in
+            Some(c) => match c {
+                #[cfg(feature = "marinade")]
+                SvcAg::Marinade(_) => Ok(()),
+                #[cfg(feature = "wsol")]
+                SvcAg::Wsol(_) => Ok(()),
+                #[cfg(feature = "lido")]
+                SvcAg::Lido(c) => {
+                    if c.exchange_rate.computed_in_epoch < current_epoch {
+                        Err(InfErr::SwapQuote(SwapQuoteErr::InpCalc(SvcAg::Lido(
+                            LidoCalcErr::NotUpdated,
+                        ))))
+                    } else {
+                        Ok(())
+                    }
+                }
+                #[cfg(feature = "spl")]
+                SvcAg::SanctumSpl(c) | SvcAg::SanctumSplMulti(c) | SvcAg::Spl(c) => {
+                    if c.last_update_epoch < current_epoch {
+                        Err(InfErr::SwapQuote(SwapQuoteErr::InpCalc(SvcAg::Spl(
+                            SplCalcErr::NotUpdated,
+                        ))))
+                    } else {
+                        Ok(())
+                    }
+                }
+                // handling of this variant is disabled by cargo features
+                #[allow(unreachable_patterns)]
+                _ => Err(InfErr::UnsupportedMint { mint: *mint }),
+            },
+            None => Err(InfErr::MissingSvcData { mint: *mint }),
+        }
+    }
+}
+
+impl Amm for InfAmm {
+    /// The `keyed_account` should be the `LST_STATE_LIST`, **NOT** `POOL_STATE`.
+    ///
+    /// SPL LSTs are loaded from the file at [`spl_lsts::SPL_LSTS_PATH_ENV`] if set,
+    /// else from the Sanctum LST list, cached at [`lst_list_cache::LST_LIST_CACHE_PATH_ENV`] if set.
+    ///
+    /// Use [`InfAmm::builder`] for anything other than these defaults.
+    fn from_keyed_account(keyed_account: &KeyedAccount, amm_context: &AmmContext) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        InfAmmBuilder::default()
+            .amm_context(amm_context)
+            .build(keyed_account)
+    }
+
+    fn label(&self) -> String {
+        LABEL.to_owned()
+    }
+
+    fn program_id(&self) -> Pubkey {
+        self.config.program_id.into()
+    }
+
+    /// S Pools are 1 per program, so just use LST state list as key
+    fn key(&self) -> Pubkey {
+        self.config.lst_state_list_id.into()
+    }
+
+    /// Mints on the LST state list and the LP token mint, excluding those that
+    /// cannot be quoted until the next update: LSTs without sol val calc data,
+    /// e.g. those skipped in updates, and LSTs whose reserves account was invalid.
+    ///
+    /// All mints before the first successful update, since no sol val calc data
+    /// has been loaded yet.
+    ///
+    /// See [`InfAmm::with_epoch_stale_exclusion`] to also exclude epoch stale LSTs.
+    fn get_reserve_mints(&self) -> Vec<Pubkey> {
+        if self.exclude_epoch_stale {
+            return self.quotable_mints();
+        }
+        if self.lp_mint_health.is_none() {
+            return self.all_mints().map(Pubkey::new_from_array).collect();
+        }
+        self.live_mints().map(Pubkey::new_from_array).collect()
+    }
+
+    /// Note: does not dedup
+    ///
+    /// Accounts of [`InfAmm::epoch_stale_mints`] come first, see [`epoch`]
+    #[inline]
+    fn get_accounts_to_update(&self) -> Vec<Pubkey> {
+        self.accounts_to_update_in(None)
+    }
+
+    fn update(&mut self, account_map: &AccountMap) -> Result<()> {
+        self.update_with(&AccountMapRef(account_map))
+    }
+
+    /// [`consts::NATIVE_SOL_MINT_ADDR`] is quoted as wSOL, see [`native_sol`].
+    ///
+    /// Always quotes the direct swap: never via the LP token, see [`route`],
+    /// and ExactOut is never emulated, see [`exact_out`]
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        let epoch = self
+            .try_epoch()
+            .inspect_err(|e| self.metrics.on_quote_err(QuoteErrKind::of(e)))?;
+        self.quote_at_epoch(quote_params, epoch)
+    }
+
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas> {
+        let (ix_args, metas) = self.trade_ix_args_and_metas(swap_params)?;
+        Ok(SwapAndAccountMetas {
+            swap: trade_ix_args_to_jup_swap(&ix_args),
+            account_metas: once(AccountMeta::new_readonly(
+                self.config.program_id.into(),
+                false,
+            ))
+            .chain(metas)
+            .collect(),
         })
     }
 
@@ -493,24 +1407,47 @@ impl Amm for InfAmm {
         true
     }
 
+    /// ExactOut is not supported for AddLiquidity and RemoveLiquidity,
+    /// see [`InfAmm::supports_exact_out_for_pair`]
     fn supports_exact_out(&self) -> bool {
-        // Because AddLiquidity and RemoveLiquidity does not support
-        false
+        true
     }
 
+    /// Only the dependencies of the sol val calculators of LSTs that were updated
+    /// and of the current pricing program, as of the last update.
+    ///
+    /// LSTs skipped in updates, e.g. those with unknown sol val calculators,
+    /// do not contribute dependencies.
+    ///
+    /// [`all_program_dependencies`] before the first successful update.
     fn program_dependencies(&self) -> Vec<(Pubkey, String)> {
+        if self.lp_mint_health.is_none() {
+            return all_program_dependencies();
+        }
+        let lst_state_list = self.inner.try_lst_state_list().unwrap_or_default();
+        let active: Vec<[u8; 32]> = lst_state_list
+            .iter()
+            .map(|l| l.into_lst_state())
+            .filter(|lst_state| self.inner.lst_calcs.contains_key(&lst_state.mint))
+            .map(|lst_state| lst_state.sol_value_calculator)
+            .chain(once(self.inner.pool.pricing_program))
+            .collect();
         PROGRAM_DEPENDENCIES
             .into_iter()
+            .filter(|(program_id, _)| active.contains(program_dependency_of(program_id)))
             .map(|(program_id, label)| (program_id.into(), label.into()))
             .collect()
     }
 
+    /// Estimate for all pairs, some pairs need fewer or more accounts.
+    /// See [`InfAmm::accounts_len_for_pair`] for the exact count.
     fn get_accounts_len(&self) -> usize {
         32
     }
 }
 
-pub const PROGRAM_DEPENDENCIES: [([u8; 32], &str); 12] = [
+/// All programs that any LST or pricing program supported by this crate may depend on
+pub const PROGRAM_DEPENDENCIES: [([u8; 32], &str); 13] = [
     // SPL
     (inf1_svc_spl_core::keys::spl::POOL_PROG_ID, "spl_stake_pool"),
     (inf1_svc_spl_core::keys::spl::ID, "spl_calculator"),
@@ -542,8 +1479,42 @@ pub const PROGRAM_DEPENDENCIES: [([u8; 32], &str); 12] = [
     (inf1_svc_wsol_core::ID, "wsol_calculator"),
     // pricing program
     (inf1_pp_flatfee_core::ID, "flat_fee_pricing_program"),
+    (
+        *PricingAgTy::FlatSlab(()).program_id(),
+        "flat_slab_pricing_program",
+    ),
 ];
 
+/// [`PROGRAM_DEPENDENCIES`], regardless of the current LST list and pricing program
+#[inline]
+pub fn all_program_dependencies() -> Vec<(Pubkey, String)> {
+    PROGRAM_DEPENDENCIES
+        .into_iter()
+        .map(|(program_id, label)| (program_id.into(), label.into()))
+        .collect()
+}
+
+/// Returns the sol val calculator or pricing program that requires
+/// `program_id`, an entry of [`PROGRAM_DEPENDENCIES`].
+///
+/// Stake pool programs map to their sol val calculator programs,
+/// everything else maps to itself.
+#[inline]
+fn program_dependency_of(program_id: &[u8; 32]) -> &[u8; 32] {
+    match *program_id {
+        inf1_svc_spl_core::keys::spl::POOL_PROG_ID => &inf1_svc_spl_core::keys::spl::ID,
+        inf1_svc_spl_core::keys::sanctum_spl::POOL_PROG_ID => {
+            &inf1_svc_spl_core::keys::sanctum_spl::ID
+        }
+        inf1_svc_spl_core::keys::sanctum_spl_multi::POOL_PROG_ID => {
+            &inf1_svc_spl_core::keys::sanctum_spl_multi::ID
+        }
+        inf1_svc_marinade_core::keys::POOL_PROG_ID => &inf1_svc_marinade_core::ID,
+        inf1_svc_lido_core::keys::POOL_PROG_ID => &inf1_svc_lido_core::ID,
+        _ => program_id,
+    }
+}
+
 #[inline]
 pub const fn swap_mode_to_trade_limit_ty(sm: SwapMode) -> TradeLimitTy {
     match sm {
@@ -552,8 +1523,109 @@ pub const fn swap_mode_to_trade_limit_ty(sm: SwapMode) -> TradeLimitTy {
     }
 }
 
+/// Inverse of [`swap_mode_to_trade_limit_ty`]
+#[inline]
+pub const fn trade_limit_ty_to_swap_mode(limit_ty: TradeLimitTy) -> SwapMode {
+    match limit_ty {
+        TradeLimitTy::ExactIn => SwapMode::ExactIn,
+        TradeLimitTy::ExactOut => SwapMode::ExactOut,
+    }
+}
+
+/// LSTs with these errors are skipped on update instead of failing the whole update,
+/// and fail to quote. Do not cause an error when:
+/// - we don't have the necessary spl data for a LST
+/// - the LST's sol val calc program is newer than this crate, e.g. INF's own sol val calc,
+///   so that other LSTs keep working until this crate is upgraded
+#[inline]
+const fn is_skippable_svc_err(e: &InfErr) -> bool {
+    matches!(e, InfErr::MissingSplData { .. } | InfErr::UnknownSvc { .. })
+}
+
+/// Done before anything else so that degenerate pairs generated by routers
+/// do not bubble up errors from deeper in the quote path
+#[inline]
+fn check_distinct_mints(pair: &Pair<&[u8; 32]>) -> Result<(), InfAmmErr> {
+    if pair.inp == pair.out {
+        Err(InfAmmErr::SamePairMint { mint: *pair.inp })
+    } else {
+        Ok(())
+    }
+}
+
+fn check_no_referrer(
+    quote_mint_to_referrer: Option<&HashMap<Pubkey, Pubkey, impl BuildHasher>>,
+    pair: &Pair<&[u8; 32]>,
+) -> Result<(), InfAmmErr> {
+    let Some(referrers) = quote_mint_to_referrer else {
+        return Ok(());
+    };
+    [pair.inp, pair.out].into_iter().try_for_each(|mint| {
+        if referrers.contains_key(&Pubkey::new_from_array(*mint)) {
+            Err(InfAmmErr::ReferralFeesUnsupported { mint: *mint })
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// The full ix args of each [`Trade`] instruction
+pub type TradeIxArgsFull = Trade<LiqIxArgs, LiqIxArgs, SwapIxArgs, SwapIxArgs>;
+
+/// Single source of truth for which jup [`Swap`] variant a [`Trade`] maps to:
+/// - `AddLiquidity` -> [`Swap::SanctumSAddLiquidity`]
+/// - `RemoveLiquidity` -> [`Swap::SanctumSRemoveLiquidity`]
+/// - `SwapExactIn`, `SwapExactOut` -> [`Swap::SanctumS`]
+#[inline]
+pub const fn trade_ix_args_to_jup_swap(ix_args: &TradeIxArgsFull) -> Swap {
+    match ix_args {
+        Trade::AddLiquidity(a) => Swap::SanctumSAddLiquidity {
+            lst_value_calc_accs: a.lst_value_calc_accs,
+            lst_index: a.lst_index,
+        },
+        Trade::RemoveLiquidity(a) => Swap::SanctumSRemoveLiquidity {
+            lst_value_calc_accs: a.lst_value_calc_accs,
+            lst_index: a.lst_index,
+        },
+        Trade::SwapExactIn(a) | Trade::SwapExactOut(a) => Swap::SanctumS {
+            src_lst_value_calc_accs: a.inp_lst_value_calc_accs,
+            dst_lst_value_calc_accs: a.out_lst_value_calc_accs,
+            src_lst_index: a.inp_lst_index,
+            dst_lst_index: a.out_lst_index,
+        },
+    }
+}
+
+/// Serializes the instruction data of a [`Trade`] instruction
+#[inline]
+pub fn trade_ix_args_to_ix_data(ix_args: &TradeIxArgsFull) -> Vec<u8> {
+    match *ix_args {
+        Trade::AddLiquidity(a) => AddLiquidityIxData::new(a).as_buf().to_vec(),
+        Trade::RemoveLiquidity(a) => RemoveLiquidityIxData::new(a).as_buf().to_vec(),
+        Trade::SwapExactIn(a) => SwapExactInIxData::new(a).as_buf().to_vec(),
+        Trade::SwapExactOut(a) => SwapExactOutIxData::new(a).as_buf().to_vec(),
+    }
+}
+
 #[inline]
 pub fn to_jup_quote(
+    fee_mint: &[u8; 32],
+    quote: inf1_std::quote::Quote,
+) -> Result<Quote, anyhow::Error> {
+    to_jup_quote_with_fees(fee_mint, quote, JupQuoteFees::All)
+}
+
+#[inline]
+pub fn to_jup_quote_with_fees(
+    fee_mint: &[u8; 32],
+    quote: inf1_std::quote::Quote,
+    fees: JupQuoteFees,
+) -> Result<Quote, anyhow::Error> {
+    to_jup_quote_with_fee_pct_rounding(fee_mint, quote, fees, None)
+}
+
+/// `fee_pct` is rounded with `fee_pct_rounding` if set, see [`fee_pct`]
+pub fn to_jup_quote_with_fee_pct_rounding(
     fee_mint: &[u8; 32],
     inf1_std::quote::Quote {
         inp: in_amount,
@@ -563,35 +1635,46 @@ pub fn to_jup_quote(
         inp_mint,
         out_mint: _,
     }: inf1_std::quote::Quote,
+    fees: JupQuoteFees,
+    fee_pct_rounding: Option<FeePctRounding>,
 ) -> Result<Quote, anyhow::Error> {
-    let fee_amount = lp_fee.saturating_add(protocol_fee);
-    let fee_pct_f64 = {
-        let denom = if *fee_mint == inp_mint {
-            in_amount
-        } else {
-            out_amount.saturating_add(fee_amount)
-        };
-        (fee_amount as f64) / (denom as f64)
-    };
-    let fee_pct = Decimal::from_f64_retain(fee_pct_f64).ok_or_else(|| anyhow!("Decimal err"))?;
+    let (fee_pct_num, fee_pct_denom) = quote_core::fee_pct_ratio(
+        *fee_mint == inp_mint,
+        in_amount,
+        out_amount,
+        lp_fee,
+        protocol_fee,
+        fees,
+    );
+    let fee_pct = fee_pct_decimal(fee_pct_num, fee_pct_denom, fee_pct_rounding)
+        .ok_or_else(|| anyhow!("Decimal err"))?;
     Ok(Quote {
         in_amount,
         out_amount,
-        fee_amount,
+        fee_amount: quote_core::fee_amount(lp_fee, protocol_fee, fees),
         fee_mint: Pubkey::new_from_array(*fee_mint),
         fee_pct,
     })
 }
 
+/// `keys` and `writable` must be of the same length, which is debug asserted.
+/// See [`audit`] for a runtime check of the resulting metas.
 pub fn keys_writable_to_jup_metas<'a>(
-    keys: impl Iterator<Item = &'a [u8; 32]>,
-    writable: impl Iterator<Item = &'a bool>,
+    mut keys: impl Iterator<Item = &'a [u8; 32]>,
+    mut writable: impl Iterator<Item = &'a bool>,
 ) -> Vec<AccountMeta> {
-    keys.zip(writable)
+    let res = keys
+        .by_ref()
+        .zip(writable.by_ref())
         .map(|(key, writable)| AccountMeta {
             pubkey: Pubkey::new_from_array(*key),
             is_signer: false, // The signer is elevated by the jupiter instruction, otherwise uses shared accounts and elevated internally before CPI
             is_writable: *writable,
         })
-        .collect()
+        .collect();
+    debug_assert!(
+        keys.next().is_none() && writable.next().is_none(),
+        "keys and writable lengths differ"
+    );
+    res
 }
@@ -0,0 +1,122 @@
+//! Previews of adding and removing liquidity for wallet integrators,
+//! e.g. "deposit X jupSOL, receive Y INF", without going through jupiter types.
+//!
+//! These use the same quoting as [`jupiter_amm_interface::Amm::quote`] for pairs involving
+//! the LP token, i.e. the `AddLiquidity` and `RemoveLiquidity` instructions.
+
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+
+use crate::{err::InfAmmErr, InfAmm};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LiquidityPreview {
+    /// The LST deposited or withdrawn
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub mint: [u8; 32],
+
+    /// Amount of LST deposited for add, amount of LP token burnt for remove
+    pub in_amount: u64,
+
+    /// Amount of LP token minted for add, amount of LST withdrawn for remove
+    pub out_amount: u64,
+
+    /// In [`Self::mint`], remains in the pool
+    pub lp_fee: u64,
+
+    /// In [`Self::mint`], transferred to the protocol fee accumulator
+    pub protocol_fee: u64,
+
+    /// The pool's total SOL value after the trade, derived from the quote
+    /// at the pool's current SOL value per LP token, so it may be off by rounding.
+    ///
+    /// `None` if LP token supply is 0.
+    pub pool_sol_value_after: Option<u64>,
+}
+
+impl InfAmm {
+    /// Previews depositing `amount` of `mint` for the LP token
+    pub fn preview_add_liquidity(
+        &self,
+        mint: &[u8; 32],
+        amount: u64,
+    ) -> Result<LiquidityPreview, InfAmmErr> {
+        let (_, q) = self.quote_raw(
+            &Pair {
+                inp: mint,
+                out: &self.inner.pool.lp_token_mint,
+            },
+            amount,
+            TradeLimitTy::ExactIn,
+            self.try_epoch()?,
+        )?;
+        // out_amount LP tokens are worth (in_amount - fees) of LST,
+        // of which only protocol_fee leaves the pool
+        let pool_sol_value_after = self.lp_token_sol_value(q.out).and_then(|minted| {
+            let added = mul_div(
+                minted,
+                q.inp.checked_sub(q.protocol_fee)?,
+                q.inp.checked_sub(q.lp_fee)?.checked_sub(q.protocol_fee)?,
+            )?;
+            self.inner.pool.total_sol_value.checked_add(added)
+        });
+        Ok(LiquidityPreview {
+            mint: *mint,
+            in_amount: q.inp,
+            out_amount: q.out,
+            lp_fee: q.lp_fee,
+            protocol_fee: q.protocol_fee,
+            pool_sol_value_after,
+        })
+    }
+
+    /// Previews burning `amount` of the LP token for `mint`
+    pub fn preview_remove_liquidity(
+        &self,
+        mint: &[u8; 32],
+        amount: u64,
+    ) -> Result<LiquidityPreview, InfAmmErr> {
+        let (_, q) = self.quote_raw(
+            &Pair {
+                inp: &self.inner.pool.lp_token_mint,
+                out: mint,
+            },
+            amount,
+            TradeLimitTy::ExactIn,
+            self.try_epoch()?,
+        )?;
+        // in_amount LP tokens are worth (out_amount + fees) of LST,
+        // of which only lp_fee remains in the pool
+        let pool_sol_value_after = self.lp_token_sol_value(q.inp).and_then(|burnt| {
+            let removed = mul_div(
+                burnt,
+                q.out.checked_add(q.protocol_fee)?,
+                q.out.checked_add(q.lp_fee)?.checked_add(q.protocol_fee)?,
+            )?;
+            self.inner.pool.total_sol_value.checked_sub(removed)
+        });
+        Ok(LiquidityPreview {
+            mint: *mint,
+            in_amount: q.inp,
+            out_amount: q.out,
+            lp_fee: q.lp_fee,
+            protocol_fee: q.protocol_fee,
+            pool_sol_value_after,
+        })
+    }
+
+    /// SOL value of `amount` LP tokens at [`Self::inf_sol_value_per_token`]
+    fn lp_token_sol_value(&self, amount: u64) -> Option<u64> {
+        mul_div(
+            amount,
+            self.inner.pool.total_sol_value,
+            self.lp_token_supply(),
+        )
+    }
+}
+
+/// `a * b / c` rounded down, `None` if `c` is 0 or the result overflows
+fn mul_div(a: u64, b: u64, c: u64) -> Option<u64> {
+    let res = (a as u128 * b as u128).checked_div(c as u128)?;
+    u64::try_from(res).ok()
+}
@@ -0,0 +1,198 @@
+//! Per-LST info and annotated trade accounts for support and monitoring,
+//! so that operators dont need to re-derive pool accounts with external scripts
+
+use anyhow::Result;
+use inf1_std::{
+    inf1_ctl_core::typedefs::lst_state::LstState, inf1_pp_core::pair::Pair, trade::TradeLimitTy,
+};
+use solana_pubkey::Pubkey;
+
+use crate::{native_sol::native_sol_to_wsol, trade_kind::TradeKind, writable::UserAccs, InfAmm};
+
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: [u8; 32] =
+    Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL").to_bytes();
+
+pub const TOKEN_PROGRAM_ID: [u8; 32] =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").to_bytes();
+
+pub const TOKEN_2022_PROGRAM_ID: [u8; 32] =
+    Pubkey::from_str_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb").to_bytes();
+
+/// Owner programs of mint and token accounts
+pub const TOKEN_PROGRAMS: [[u8; 32]; 2] = [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LstInfo {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub mint: [u8; 32],
+
+    /// Index of this LST on the LST state list
+    pub index: usize,
+
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub sol_value_calculator: [u8; 32],
+
+    /// The pool's reserves token account for this LST,
+    /// the ATA of the pool state for this LST
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub reserves: [u8; 32],
+
+    /// The token account that accumulates protocol fees in this LST,
+    /// see [`crate::config::InfAmmConfig::protocol_fee_accumulator`]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub protocol_fee_accumulator: [u8; 32],
+}
+
+/// What an account of [`InfAmm::annotated_accounts_for_pair`] is used for,
+/// see [`crate::consts::META_LAYOUT_VERSION`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum MetaRole {
+    InfProgram,
+    Signer,
+    Mint,
+    UserTokenAcc,
+    ProtocolFeeAccumulator,
+    TokenProgram,
+    PoolState,
+    LstStateList,
+    Reserves,
+
+    /// An account of the LST's sol value calculator, starting with the calculator program
+    SolValCalc,
+
+    /// An account of the pool's pricing program, starting with the pricing program
+    Pricing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AnnotatedMeta {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub pubkey: [u8; 32],
+
+    pub is_writable: bool,
+
+    pub role: MetaRole,
+
+    /// The mint that this account is for, `None` for accounts shared by all mints
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58_opt"))]
+    pub mint: Option<[u8; 32]>,
+}
+
+impl InfAmm {
+    /// Returns `None` if `mint` is not on the LST state list.
+    ///
+    /// `token_program` is the owner program of `mint`. Reserves are derived with the
+    /// bump stored on the LST state list, so no PDA search is done.
+    /// The protocol fee accumulator is found with [`crate::config::InfAmmConfig::pda`],
+    /// whose bumps are cached, see [`crate::pda`].
+    pub fn lst_info(&self, mint: &[u8; 32], token_program: &[u8; 32]) -> Option<LstInfo> {
+        let (index, lst_state) = self
+            .inner
+            .try_lst_state_list()
+            .ok()?
+            .iter()
+            .map(|l| l.into_lst_state())
+            .enumerate()
+            .find(|(_, l)| l.mint == *mint)?;
+        let LstState {
+            pool_reserves_bump,
+            sol_value_calculator,
+            ..
+        } = lst_state;
+        let reserves = (self.config.pda.create_raw)(
+            &[
+                self.config.pool_state_id.as_slice(),
+                token_program.as_slice(),
+                mint.as_slice(),
+                &[pool_reserves_bump],
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )?;
+        let protocol_fee_accumulator = self.config.protocol_fee_accumulator(token_program, mint)?;
+        Some(LstInfo {
+            mint: *mint,
+            index,
+            sol_value_calculator,
+            reserves,
+            protocol_fee_accumulator,
+        })
+    }
+
+    /// [`Self::accounts_for_pair`], with what each account is used for and which mint it is for.
+    ///
+    /// Errs under the same conditions as [`Self::accounts_for_pair`].
+    pub fn annotated_accounts_for_pair(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        trade_ty: TradeLimitTy,
+        user: &UserAccs,
+    ) -> Result<Vec<AnnotatedMeta>> {
+        let metas = self.accounts_for_pair(pair, trade_ty, user)?;
+        let [inp, out] = [pair.inp, pair.out].map(native_sol_to_wsol);
+        let kind = self.trade_type(inp, out);
+        // mints of the mint, user token account and token program accounts
+        let [first, second] = match kind {
+            TradeKind::RemoveLiquidity => [*out, *inp],
+            TradeKind::Swap | TradeKind::AddLiquidity => [*inp, *out],
+        };
+        let (fee_mint, lsts) = match kind {
+            TradeKind::Swap => (*out, [Some(*inp), Some(*out)]),
+            TradeKind::AddLiquidity => (*inp, [Some(*inp), None]),
+            TradeKind::RemoveLiquidity => (*out, [Some(*out), None]),
+        };
+        let lsts: Vec<_> = lsts.into_iter().flatten().collect();
+
+        let mut roles = vec![
+            (MetaRole::InfProgram, None),
+            (MetaRole::Signer, None),
+            (MetaRole::Mint, Some(first)),
+            (MetaRole::Mint, Some(second)),
+            (MetaRole::UserTokenAcc, Some(first)),
+            (MetaRole::UserTokenAcc, Some(second)),
+            (MetaRole::ProtocolFeeAccumulator, Some(fee_mint)),
+            (MetaRole::TokenProgram, Some(first)),
+            (MetaRole::TokenProgram, Some(second)),
+            (MetaRole::PoolState, None),
+            (MetaRole::LstStateList, None),
+        ];
+        roles.extend(lsts.iter().map(|mint| (MetaRole::Reserves, Some(*mint))));
+
+        // The accounts of each LST's sol value calculator, then of the pricing program,
+        // each starting with the program
+        let pricing_start = metas
+            .iter()
+            .rposition(|m| m.pubkey.as_array() == &self.inner.pool.pricing_program)
+            .filter(|i| *i >= roles.len())
+            .unwrap_or(metas.len());
+        let mut start = roles.len();
+        for (i, mint) in lsts.iter().enumerate() {
+            let end = lsts
+                .get(i + 1)
+                .and_then(|next| self.sol_val_calc_of(next))
+                .and_then(|next_calc| {
+                    metas
+                        .get(start + 1..pricing_start)?
+                        .iter()
+                        .position(|m| m.pubkey.as_array() == &next_calc)
+                })
+                .map_or(pricing_start, |p| start + 1 + p);
+            roles.extend((start..end).map(|_| (MetaRole::SolValCalc, Some(*mint))));
+            start = end;
+        }
+        roles.resize(metas.len(), (MetaRole::Pricing, None));
+
+        Ok(metas
+            .into_iter()
+            .zip(roles)
+            .map(|(m, (role, mint))| AnnotatedMeta {
+                pubkey: m.pubkey.to_bytes(),
+                is_writable: m.is_writable,
+                role,
+                mint,
+            })
+            .collect())
+    }
+}
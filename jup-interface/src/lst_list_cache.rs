@@ -0,0 +1,245 @@
+//! On-disk cache of the Sanctum LST list, so that restarting processes do not refetch it
+//! every time and still start with a recent copy if the Sanctum API is briefly down.
+//!
+//! The cache is a JSON file containing the last response of the Sanctum API,
+//! its ETag and when it was fetched. [`LstListCache::load`]:
+//!
+//! 1. returns the cached list if it is younger than the TTL
+//! 2. else revalidates it with a conditional request, only downloading the list if
+//!    its ETag changed, and rewrites the cache
+//! 3. else, if the request fails, returns the stale cached list
+//! 4. else returns the copy bundled with this crate
+//!
+//! Without the `remote` feature, the cached list is returned regardless of its age.
+//!
+//! If [`LST_LIST_CACHE_PATH_ENV`] is set, [`jupiter_amm_interface::Amm::from_keyed_account`]
+//! and [`crate::spl_lsts::load_sanctum_spl_lsts`] use the cache at that path.
+//! Use [`crate::config::InfAmmBuilder::lst_list_cache`] to configure it in code instead.
+//!
+//! With the `wasm` feature, the cache is a no-op since there is no clock or filesystem
+//! on wasm32-unknown-unknown: [`LstListCache::load`] returns the copy bundled with this crate,
+//! [`LstListCache::store`] errs and cached lists are never fresh.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+use sanctum_lst_list::{SanctumLst, SanctumLstList};
+use serde_json::{json, Value};
+
+use crate::sanctum_lst_list::FetchPolicy;
+
+/// Env var of the path to the Sanctum LST list cache, see [`crate::lst_list_cache`]
+pub const LST_LIST_CACHE_PATH_ENV: &str = "INF_LST_LIST_CACHE_PATH";
+
+/// Env var of the TTL in seconds of the cache at [`LST_LIST_CACHE_PATH_ENV`].
+/// Defaults to [`LstListCache::DEFAULT_TTL`]
+pub const LST_LIST_CACHE_TTL_SECS_ENV: &str = "INF_LST_LIST_CACHE_TTL_SECS";
+
+/// Max time requests are retried for if there is a stale cached list to fall back to,
+/// overrides [`FetchPolicy::max_elapsed`] if shorter
+#[cfg(feature = "remote")]
+const STALE_FALLBACK_MAX_RETRY: Duration = Duration::from_secs(10);
+
+/// See [`crate::lst_list_cache`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LstListCache {
+    pub path: PathBuf,
+    pub ttl: Duration,
+    pub fetch_policy: FetchPolicy,
+}
+
+/// Contents of a [`LstListCache`]
+#[derive(Debug, Clone)]
+pub struct CachedLstList {
+    pub fetched_at: SystemTime,
+    pub etag: Option<String>,
+    pub lsts: Vec<SanctumLst>,
+}
+
+impl CachedLstList {
+    /// `false` if fetched more than `ttl` ago, or in the future.
+    /// Always `false` with the `wasm` feature.
+    #[inline]
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        now()
+            .and_then(|now| now.duration_since(self.fetched_at).ok())
+            .is_some_and(|age| age < ttl)
+    }
+}
+
+impl LstListCache {
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ttl: Self::DEFAULT_TTL,
+            fetch_policy: FetchPolicy::DEFAULT,
+        }
+    }
+
+    #[inline]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    #[inline]
+    pub fn with_fetch_policy(mut self, fetch_policy: FetchPolicy) -> Self {
+        self.fetch_policy = fetch_policy;
+        self
+    }
+
+    /// The cache at [`LST_LIST_CACHE_PATH_ENV`], with TTL [`LST_LIST_CACHE_TTL_SECS_ENV`].
+    ///
+    /// Returns `None` if the path env var is not set.
+    /// Unparseable TTLs fall back to [`Self::DEFAULT_TTL`].
+    pub fn from_env() -> Option<Self> {
+        let cache = Self::new(std::env::var_os(LST_LIST_CACHE_PATH_ENV)?);
+        Some(
+            match std::env::var(LST_LIST_CACHE_TTL_SECS_ENV)
+                .ok()
+                .and_then(|s| s.parse().ok())
+            {
+                Some(secs) => cache.with_ttl(Duration::from_secs(secs)),
+                None => cache,
+            },
+        )
+    }
+
+    /// Reads the cache file, regardless of its age
+    pub fn read(&self) -> Result<CachedLstList> {
+        let bytes = fs::read(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        parse_cache(&bytes).with_context(|| format!("Invalid cache {}", self.path.display()))
+    }
+
+    /// See [`crate::lst_list_cache`]. Never fails, only falls back.
+    pub fn load(&self) -> Vec<SanctumLst> {
+        if cfg!(feature = "wasm") {
+            return SanctumLstList::load().data;
+        }
+        let cached = self.read().ok();
+        match cached {
+            Some(cached) if cached.is_fresh(self.ttl) => cached.lsts,
+            #[cfg(feature = "remote")]
+            cached => self.revalidate(cached),
+            #[cfg(not(feature = "remote"))]
+            cached => cached.map_or_else(|| SanctumLstList::load().data, |c| c.lsts),
+        }
+    }
+
+    #[cfg(feature = "remote")]
+    fn revalidate(&self, cached: Option<CachedLstList>) -> Vec<SanctumLst> {
+        use crate::sanctum_lst_list::{fetch_sanctum_lst_list_if_none_match, ConditionalFetch};
+
+        let Some(cached) = cached else {
+            return match fetch_sanctum_lst_list_if_none_match(None, &self.fetch_policy) {
+                Ok(ConditionalFetch::Modified { etag, body }) => self
+                    .store(etag.as_deref(), body)
+                    .unwrap_or_else(|_| SanctumLstList::load().data),
+                // did not send an ETag, so cannot be not modified
+                Ok(ConditionalFetch::NotModified) | Err(_) => SanctumLstList::load().data,
+            };
+        };
+        let policy = FetchPolicy {
+            max_elapsed: self.fetch_policy.max_elapsed.min(STALE_FALLBACK_MAX_RETRY),
+            ..self.fetch_policy
+        };
+        match fetch_sanctum_lst_list_if_none_match(cached.etag.as_deref(), &policy) {
+            Ok(ConditionalFetch::NotModified) => {
+                // best-effort, a failed write just means revalidating again next time
+                let _ = self.touch();
+                cached.lsts
+            }
+            Ok(ConditionalFetch::Modified { etag, body }) => {
+                self.store(etag.as_deref(), body).unwrap_or(cached.lsts)
+            }
+            Err(_) => cached.lsts,
+        }
+    }
+
+    /// Writes a fetched response to the cache, returning its LSTs.
+    ///
+    /// The response is parsed before writing so that invalid responses never replace
+    /// a valid cache.
+    pub fn store(&self, etag: Option<&str>, body: Value) -> Result<Vec<SanctumLst>> {
+        let now = now().ok_or_else(|| anyhow!("No clock to timestamp the cache with"))?;
+        let SanctumLstList { data } = serde_json::from_value(body.clone())?;
+        self.write(&json!({
+            "fetched_at": unix_secs(now),
+            "etag": etag,
+            "body": body,
+        }))?;
+        Ok(data)
+    }
+
+    /// Marks the cached list as fetched now, after the server confirmed it is unchanged
+    fn touch(&self) -> Result<()> {
+        let now = now().ok_or_else(|| anyhow!("No clock to timestamp the cache with"))?;
+        let bytes = fs::read(&self.path)?;
+        let mut cache: Value = serde_json::from_slice(&bytes)?;
+        let obj = cache
+            .as_object_mut()
+            .ok_or_else(|| anyhow!("Expected JSON object"))?;
+        obj.insert("fetched_at".to_owned(), unix_secs(now).into());
+        self.write(&cache)
+    }
+
+    /// Writes to a temporary file then renames it over the cache, so that concurrently
+    /// starting processes never read a partially written cache
+    fn write(&self, cache: &Value) -> Result<()> {
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = tmp_path(&self.path);
+        fs::write(&tmp, serde_json::to_vec(cache)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+fn parse_cache(bytes: &[u8]) -> Result<CachedLstList> {
+    let Value::Object(mut cache) = serde_json::from_slice(bytes)? else {
+        return Err(anyhow!("Expected JSON object"));
+    };
+    let fetched_at = cache
+        .get("fetched_at")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| anyhow!("Missing fetched_at"))?;
+    let etag = cache.get("etag").and_then(Value::as_str).map(str::to_owned);
+    let body = cache
+        .remove("body")
+        .ok_or_else(|| anyhow!("Missing body"))?;
+    let SanctumLstList { data } = serde_json::from_value(body)?;
+    Ok(CachedLstList {
+        fetched_at: UNIX_EPOCH + Duration::from_secs(fetched_at),
+        etag,
+        lsts: data,
+    })
+}
+
+/// [`SystemTime::now`] panics on wasm32-unknown-unknown, so this is `None`
+/// with the `wasm` feature
+fn now() -> Option<SystemTime> {
+    if cfg!(feature = "wasm") {
+        None
+    } else {
+        Some(SystemTime::now())
+    }
+}
+
+fn unix_secs(t: SystemTime) -> u64 {
+    t.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", std::process::id()));
+    tmp.into()
+}
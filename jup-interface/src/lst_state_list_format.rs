@@ -0,0 +1,162 @@
+//! Versioned layouts of the LST state list account.
+//!
+//! The LST state list is currently a packed array of `LstState`s without a header,
+//! which is what [`inf1_std`]'s `try_lst_state_list()` reads. Should the INF program
+//! migrate it to another layout, e.g. paged or compressed, a [`LstStateListFormat`]
+//! for the new layout converts the account data back to a packed array before it is applied,
+//! so that `try_lst_state_list()` and everything built on it keep working unchanged.
+//!
+//! The layout of account data is identified by its version byte, see [`version_of`].
+//! Formats are looked up in [`InfAmmConfig::lst_state_list_formats`](crate::config::InfAmmConfig::lst_state_list_formats).
+
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+use inf1_std::{
+    inf1_ctl_core::{accounts::lst_state_list::LstStatePackedList, keys::LST_STATE_LIST_ID},
+    update::{Account, UpdateMap},
+};
+
+use crate::update::UpdateMapWithOwner;
+
+/// Version of the current headerless packed layout, see [`version_of`]
+pub const PACKED_VERSION: u8 = 0;
+
+/// Formats supported out of the box, [`Packed`] only
+pub const DEFAULT_LST_STATE_LIST_FORMATS: &[&dyn LstStateListFormat] = &[&Packed];
+
+/// A layout of the LST state list account, see [`crate::lst_state_list_format`]
+pub trait LstStateListFormat: Debug + Send + Sync {
+    /// The version byte of account data in this layout, see [`version_of`]
+    fn version(&self) -> u8;
+
+    /// Converts account data in this layout to a packed array of `LstState`s.
+    ///
+    /// Returns `None` if `data` is malformed.
+    fn to_packed<'a>(&self, data: &'a [u8]) -> Option<Cow<'a, [u8]>>;
+}
+
+/// The current headerless layout, a packed array of `LstState`s
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Packed;
+
+impl LstStateListFormat for Packed {
+    #[inline]
+    fn version(&self) -> u8 {
+        PACKED_VERSION
+    }
+
+    #[inline]
+    fn to_packed<'a>(&self, data: &'a [u8]) -> Option<Cow<'a, [u8]>> {
+        LstStatePackedList::of_acc_data(data).map(|_| Cow::Borrowed(data))
+    }
+}
+
+/// The version byte of LST state list account data.
+///
+/// The packed layout has no header and starts with `is_input_disabled` of the first LST,
+/// which is always 0 or 1, so any other first byte is the version of a newer layout.
+/// Empty data is an empty packed list.
+#[inline]
+pub const fn version_of(data: &[u8]) -> u8 {
+    match data.first() {
+        Some(v) if *v > 1 => *v,
+        _ => PACKED_VERSION,
+    }
+}
+
+/// Converts LST state list account data in any of `formats` to a packed array of `LstState`s
+pub fn to_packed<'a>(
+    formats: &[&dyn LstStateListFormat],
+    data: &'a [u8],
+) -> Result<Cow<'a, [u8]>, LstStateListFormatErr> {
+    let version = version_of(data);
+    let format = formats
+        .iter()
+        .find(|f| f.version() == version)
+        .ok_or(LstStateListFormatErr::UnknownVersion(version))?;
+    format
+        .to_packed(data)
+        .ok_or(LstStateListFormatErr::Malformed { version })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LstStateListFormatErr {
+    /// No [`LstStateListFormat`] of this version is configured
+    UnknownVersion(u8),
+
+    /// Data does not match the layout of its version
+    Malformed { version: u8 },
+}
+
+impl Display for LstStateListFormatErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownVersion(v) => {
+                f.write_fmt(format_args!("Unknown LST state list version {v}"))
+            }
+            Self::Malformed { version } => f.write_fmt(format_args!(
+                "Malformed LST state list of version {version}"
+            )),
+        }
+    }
+}
+
+impl Error for LstStateListFormatErr {}
+
+/// [`UpdateMap`] that returns `lst_state_list`, if set, in place of the fetched LST state list,
+/// so that [`inf1_std`] only ever sees the packed layout
+#[derive(Debug)]
+pub(crate) struct LstStateListOverride<'a, M> {
+    pub map: &'a M,
+    pub lst_state_list: Option<&'a [u8]>,
+}
+
+impl<M> Clone for LstStateListOverride<'_, M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for LstStateListOverride<'_, M> {}
+
+pub(crate) enum OverrideAcc<'a, A> {
+    Fetched(A),
+    LstStateList(&'a [u8]),
+}
+
+impl<A: Account> Account for OverrideAcc<'_, A> {
+    #[inline]
+    fn data(&self) -> &[u8] {
+        match self {
+            Self::Fetched(a) => a.data(),
+            Self::LstStateList(d) => d,
+        }
+    }
+}
+
+impl<M: UpdateMap> UpdateMap for LstStateListOverride<'_, M> {
+    type Account<'acc>
+        = OverrideAcc<'acc, M::Account<'acc>>
+    where
+        Self: 'acc;
+
+    #[inline]
+    fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+        match self.lst_state_list {
+            Some(d) if *pk == LST_STATE_LIST_ID => Some(OverrideAcc::LstStateList(d)),
+            _ => self.map.get_account(pk).map(OverrideAcc::Fetched),
+        }
+    }
+}
+
+impl<M: UpdateMapWithOwner> UpdateMapWithOwner for LstStateListOverride<'_, M> {
+    #[inline]
+    fn get_owner(&self, pk: &[u8; 32]) -> Option<[u8; 32]> {
+        self.map.get_owner(pk)
+    }
+}
@@ -0,0 +1,95 @@
+//! Approximate accounting of memory held by [`InfAmm`], for capacity planning
+//! and leak detection when loading many AMMs.
+//!
+//! Counts allocated capacity rather than length, but only one level deep:
+//! heap allocations owned by individual calc and pricing states are not included.
+//!
+//! Allocations shared between clones of an [`InfAmm`] are counted in full for each clone.
+
+use std::{collections::HashMap, mem::size_of_val};
+
+use crate::InfAmm;
+
+/// All values are in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MemoryBreakdown {
+    /// [`InfAmm`] itself and its [`inf1_std::InfStd`], excluding [`Self::pricing`]
+    /// and the heap allocations counted in the rest below
+    pub amm: usize,
+    pub lst_state_list_data: usize,
+    pub lst_calcs: usize,
+
+    /// Pricing program state is stored inline in [`inf1_std::InfStd`]
+    pub pricing: usize,
+    pub lst_reserves: usize,
+    pub spl_lsts: usize,
+    pub lst_health: usize,
+    pub quote_cache: usize,
+    pub delta: usize,
+
+    /// 0 unless snapshots are enabled, see [`crate::InfAmm::with_snapshots`]
+    pub snapshot_accounts: usize,
+}
+
+impl MemoryBreakdown {
+    #[inline]
+    pub const fn total(&self) -> usize {
+        let Self {
+            amm,
+            lst_state_list_data,
+            lst_calcs,
+            pricing,
+            lst_reserves,
+            spl_lsts,
+            lst_health,
+            quote_cache,
+            delta,
+            snapshot_accounts,
+        } = self;
+        *amm + *lst_state_list_data
+            + *lst_calcs
+            + *pricing
+            + *lst_reserves
+            + *spl_lsts
+            + *lst_health
+            + *quote_cache
+            + *delta
+            + *snapshot_accounts
+    }
+}
+
+/// Bytes held by the table of `m`, excluding heap allocations owned by its keys and values
+#[inline]
+pub(crate) fn hash_map_bytes<K, V, S>(m: &HashMap<K, V, S>) -> usize {
+    m.capacity() * (size_of::<K>() + size_of::<V>())
+}
+
+impl InfAmm {
+    pub fn memory_usage(&self) -> MemoryBreakdown {
+        let pricing = size_of_val(&self.inner.pricing);
+        MemoryBreakdown {
+            amm: size_of_val(self) + size_of_val(&*self.inner) - pricing,
+            lst_state_list_data: self.inner.lst_state_list_data.len(),
+            lst_calcs: hash_map_bytes(&self.inner.lst_calcs),
+            pricing,
+            lst_reserves: hash_map_bytes(&self.inner.lst_reserves),
+            spl_lsts: hash_map_bytes(&self.inner.spl_lsts),
+            lst_health: hash_map_bytes(&self.lst_health)
+                + self
+                    .lst_health
+                    .values()
+                    .map(|h| size_of_val(h.applied_accs.as_slice()))
+                    .sum::<usize>(),
+            quote_cache: self.quote_cache.as_ref().map_or(0, |c| c.heap_bytes()),
+            delta: self.delta.as_ref().map_or(0, |d| d.heap_bytes()),
+            #[cfg(feature = "serde")]
+            snapshot_accounts: self.snapshot_accounts.as_deref().map_or(0, |accounts| {
+                hash_map_bytes(accounts)
+                    + accounts.values().map(|a| a.data.capacity()).sum::<usize>()
+            }),
+            #[cfg(not(feature = "serde"))]
+            snapshot_accounts: 0,
+        }
+    }
+}
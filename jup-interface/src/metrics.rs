@@ -0,0 +1,141 @@
+//! Hooks for observing update and quote outcomes, e.g. to export prometheus counters.
+//!
+//! Set with [`crate::InfAmm::with_metrics`]. Callbacks are called synchronously
+//! on the update and quote paths, so implementations should be cheap.
+
+#[cfg(not(feature = "wasm"))]
+use std::time::Instant;
+use std::{fmt::Debug, time::Duration};
+
+use inf1_std::err::InfErr;
+
+use crate::{err::InfAmmErr, pool_defaults::PoolDefaultsCheck};
+
+/// All methods default to no-ops
+pub trait InfAmmMetrics: Debug + Send + Sync {
+    /// Called at the end of every successful [`crate::InfAmm::update_with`].
+    ///
+    /// `lsts_skipped` counts LSTs on the LST state list that were not updated,
+    /// either because of `MissingSplData`, because their sol val calc program
+    /// is unknown to this crate (`UnknownSvc`), or because their sol val calc backend
+    /// is disabled by cargo features.
+    ///
+    /// `duration` is always 0 with the `wasm` feature,
+    /// since there is no clock to measure it with on wasm32-unknown-unknown.
+    #[inline]
+    fn on_update_complete(&self, _duration: Duration, _lsts_updated: usize, _lsts_skipped: usize) {}
+
+    /// Called at the end of the first successful [`crate::InfAmm::update_with`]
+    /// at an epoch later than the previous update's.
+    ///
+    /// `stale_mints` are [`crate::InfAmm::epoch_stale_mints`] after the update,
+    /// i.e. LSTs that cannot be quoted until their stake pools are cranked.
+    #[inline]
+    fn on_epoch_rollover(&self, _prev_epoch: u64, _epoch: u64, _stale_mints: &[[u8; 32]]) {}
+
+    /// Called at the end of the first successful [`crate::InfAmm::update_with`]
+    /// if the default pool state differs from the live one, see [`crate::pool_defaults`]
+    #[inline]
+    fn on_pool_defaults_diverged(&self, _check: &PoolDefaultsCheck) {}
+
+    /// Called for every failed [`crate::InfAmm::quote_raw`]
+    /// and every failed amount of [`crate::InfAmm::quote_many`]
+    #[inline]
+    fn on_quote_err(&self, _kind: QuoteErrKind) {}
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NoopMetrics;
+
+/// Measures the duration passed to [`InfAmmMetrics::on_update_complete`].
+///
+/// [`Instant::now`] panics on wasm32-unknown-unknown, so this is a no-op
+/// with the `wasm` feature.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct UpdateTimer(#[cfg(not(feature = "wasm"))] Instant);
+
+impl UpdateTimer {
+    #[inline]
+    pub(crate) fn start() -> Self {
+        Self(
+            #[cfg(not(feature = "wasm"))]
+            Instant::now(),
+        )
+    }
+
+    #[inline]
+    pub(crate) fn elapsed(&self) -> Duration {
+        #[cfg(not(feature = "wasm"))]
+        {
+            self.0.elapsed()
+        }
+        #[cfg(feature = "wasm")]
+        {
+            Duration::ZERO
+        }
+    }
+}
+
+impl InfAmmMetrics for NoopMetrics {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum QuoteErrKind {
+    /// See [`InfAmmErr::is_not_updated`]
+    NotUpdated,
+
+    /// See [`InfAmmErr::is_not_enough_liquidity`]
+    NotEnoughLiquidity,
+
+    /// See [`InfAmmErr::ExactOutUnsupportedForLiquidity`]
+    ExactOutUnsupportedForLiquidity,
+
+    /// See [`InfAmmErr::LpMintAuthorityMismatch`]
+    LpMintAuthorityMismatch,
+
+    PoolDisabled,
+    PoolRebalancing,
+
+    /// See [`InfAmmErr::SamePairMint`]
+    SamePairMint,
+
+    /// See [`InfAmmErr::ReserveAccountInvalid`]
+    ReserveAccountInvalid,
+
+    /// See [`InfAmmErr::CalcProgramOutdated`]
+    CalcProgramOutdated,
+
+    /// See [`InfAmmErr::ClockNotUpdated`]
+    ClockNotUpdated,
+
+    MissingSvcData,
+    UnsupportedMint,
+    Other,
+}
+
+impl QuoteErrKind {
+    #[inline]
+    pub const fn of(e: &InfAmmErr) -> Self {
+        if e.is_not_updated() {
+            return Self::NotUpdated;
+        }
+        if e.is_not_enough_liquidity() {
+            return Self::NotEnoughLiquidity;
+        }
+        match e {
+            InfAmmErr::ExactOutUnsupportedForLiquidity { .. } => {
+                Self::ExactOutUnsupportedForLiquidity
+            }
+            InfAmmErr::LpMintAuthorityMismatch { .. } => Self::LpMintAuthorityMismatch,
+            InfAmmErr::PoolDisabled => Self::PoolDisabled,
+            InfAmmErr::PoolRebalancing => Self::PoolRebalancing,
+            InfAmmErr::SamePairMint { .. } => Self::SamePairMint,
+            InfAmmErr::ReserveAccountInvalid { .. } => Self::ReserveAccountInvalid,
+            InfAmmErr::CalcProgramOutdated { .. } => Self::CalcProgramOutdated,
+            InfAmmErr::ClockNotUpdated => Self::ClockNotUpdated,
+            InfAmmErr::Inf(InfErr::MissingSvcData { .. }) => Self::MissingSvcData,
+            InfAmmErr::Inf(InfErr::UnsupportedMint { .. }) => Self::UnsupportedMint,
+            _ => Self::Other,
+        }
+    }
+}
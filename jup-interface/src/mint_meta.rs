@@ -0,0 +1,75 @@
+//! Display metadata of reserve mints, e.g. for routers rendering quotes,
+//! taken from the Sanctum LST list at construction so that the mints
+//! need not be fetched.
+//!
+//! [`jupiter_amm_interface::Amm::from_keyed_account`] and [`crate::config::InfAmmBuilder`]
+//! use the Sanctum LST list that their SPL LSTs are loaded from, if any.
+//! Otherwise, the copy bundled with this crate is used. LSTs that are not on the list
+//! used have no metadata until it is replaced with [`InfAmm::with_mint_metas`].
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+};
+
+use sanctum_lst_list::{SanctumLst, SanctumLstList};
+
+use crate::{native_sol::native_sol_to_wsol, InfAmm};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MintMeta {
+    pub symbol: String,
+    pub decimals: u8,
+    pub logo_uri: String,
+}
+
+/// Metadata of every mint on `lsts`
+pub fn sanctum_mint_metas(lsts: &[SanctumLst]) -> HashMap<[u8; 32], MintMeta> {
+    lsts.iter()
+        .map(
+            |SanctumLst {
+                 mint,
+                 symbol,
+                 decimals,
+                 logo_uri,
+                 ..
+             }| {
+                (
+                    mint.to_bytes(),
+                    MintMeta {
+                        symbol: symbol.clone(),
+                        decimals: *decimals,
+                        logo_uri: logo_uri.clone(),
+                    },
+                )
+            },
+        )
+        .collect()
+}
+
+/// [`sanctum_mint_metas`] of the copy of the Sanctum LST list bundled with this crate,
+/// only parsed once per process
+pub(crate) fn bundled_mint_metas() -> Arc<HashMap<[u8; 32], MintMeta>> {
+    static BUNDLED: OnceLock<Arc<HashMap<[u8; 32], MintMeta>>> = OnceLock::new();
+    BUNDLED
+        .get_or_init(|| Arc::new(sanctum_mint_metas(&SanctumLstList::load().data)))
+        .clone()
+}
+
+impl InfAmm {
+    /// Native SOL has the metadata of wSOL.
+    ///
+    /// Returns `None` if `mint` is not on the Sanctum LST list used, see [`crate::mint_meta`]
+    #[inline]
+    pub fn mint_metadata(&self, mint: &[u8; 32]) -> Option<&MintMeta> {
+        self.mint_metas.get(native_sol_to_wsol(mint))
+    }
+
+    /// Replaces the metadata returned by [`Self::mint_metadata`]
+    #[inline]
+    pub fn with_mint_metas(mut self, mint_metas: HashMap<[u8; 32], MintMeta>) -> Self {
+        self.mint_metas = Arc::new(mint_metas);
+        self
+    }
+}
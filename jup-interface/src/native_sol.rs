@@ -0,0 +1,67 @@
+//! Native SOL as a pseudo-mint, for direct integrators that do not handle
+//! wrapping and unwrapping themselves like jupiter does.
+//!
+//! [`Amm::quote`] treats [`NATIVE_SOL_MINT_ADDR`] as [`WSOL_MINT_ADDR`].
+//! [`Amm::get_swap_and_account_metas`] does not accept it, use
+//! [`InfAmm::get_swap_and_account_metas_native`] instead, which flags the
+//! wrap and unwrap instructions that the integrator must add around the swap.
+
+use anyhow::Result;
+use jupiter_amm_interface::{Amm, SwapAndAccountMetas, SwapParams};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    consts::{NATIVE_SOL_MINT_ADDR, WSOL_MINT_ADDR},
+    InfAmm,
+};
+
+/// Returns [`WSOL_MINT_ADDR`] if `mint` is [`NATIVE_SOL_MINT_ADDR`], else `mint`
+#[inline]
+pub const fn native_sol_to_wsol(mint: &[u8; 32]) -> &[u8; 32] {
+    match *mint {
+        NATIVE_SOL_MINT_ADDR => &WSOL_MINT_ADDR,
+        _ => mint,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NativeSolSwapAndAccountMetas {
+    /// With [`NATIVE_SOL_MINT_ADDR`] replaced by [`WSOL_MINT_ADDR`]
+    pub swap_and_account_metas: SwapAndAccountMetas,
+
+    /// Whether the input is native SOL that must be wrapped into
+    /// [`SwapParams::source_token_account`] before the swap
+    pub wrap_inp: bool,
+
+    /// Whether the output is native SOL that must be unwrapped from
+    /// [`SwapParams::destination_token_account`] after the swap
+    pub unwrap_out: bool,
+}
+
+impl InfAmm {
+    /// [`Amm::get_swap_and_account_metas`], but `swap_params` may have
+    /// [`NATIVE_SOL_MINT_ADDR`] as source or destination mint.
+    ///
+    /// The corresponding token accounts must be wSOL token accounts.
+    pub fn get_swap_and_account_metas_native(
+        &self,
+        swap_params: &SwapParams,
+    ) -> Result<NativeSolSwapAndAccountMetas> {
+        let wrap_inp = *swap_params.source_mint.as_array() == NATIVE_SOL_MINT_ADDR;
+        let unwrap_out = *swap_params.destination_mint.as_array() == NATIVE_SOL_MINT_ADDR;
+        let swap_and_account_metas = self.get_swap_and_account_metas(&SwapParams {
+            source_mint: Pubkey::new_from_array(*native_sol_to_wsol(
+                swap_params.source_mint.as_array(),
+            )),
+            destination_mint: Pubkey::new_from_array(*native_sol_to_wsol(
+                swap_params.destination_mint.as_array(),
+            )),
+            ..*swap_params
+        })?;
+        Ok(NativeSolSwapAndAccountMetas {
+            swap_and_account_metas,
+            wrap_inp,
+            unwrap_out,
+        })
+    }
+}
@@ -0,0 +1,208 @@
+//! Fee configuration of the pricing program per pair, e.g. for analytics,
+//! parsed from the pricing program accounts applied in updates so that no quote is needed.
+//!
+//! - Flat fee pricing program: each LST's fee account has an input and an output fee in bps.
+//!   Adding liquidity is free, removing liquidity charges the program state's
+//!   LP withdrawal fee.
+//! - Flat slab pricing program: the slab has an input and an output fee in nanos
+//!   (1 bps = 100,000 nanos) for each mint, including the LP token mint.
+//!
+//! Fees may be negative, i.e. rebates.
+
+use std::collections::HashMap;
+
+use inf1_std::{
+    inf1_pp_ag_std::{inf1_pp_flatfee_core, update::all::AccountsToUpdateAll, PricingAgTy},
+    update::Account as _,
+};
+use rust_decimal::Decimal;
+
+use crate::{
+    native_sol::native_sol_to_wsol,
+    pair_update::in_scope,
+    pda::{flat_fee_fee_account, flat_fee_program_state},
+    trade_kind::TradeKind,
+    update::UpdateMapWithOwner,
+    InfAmm,
+};
+
+/// Flat fee program state: `manager: Pubkey, lp_withdrawal_fee_bps: u16, ..`.
+/// Fee accounts are `input_fee_bps: i16, output_fee_bps: i16, ..`
+const FLAT_FEE_LP_WITHDRAWAL_FEE_OFFSET: usize = 32;
+
+/// `admin: Pubkey`, followed by the entries
+const FLAT_SLAB_HEADER_LEN: usize = 32;
+
+/// `mint: Pubkey, inp_fee_nanos: i32, out_fee_nanos: i32`
+const FLAT_SLAB_ENTRY_LEN: usize = 40;
+
+/// Decimal places of flat slab fees in bps
+const NANOS_BPS_SCALE: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LpProtocolSplit {
+    /// Share of fees that go to the LPs, in bps of the fees
+    pub lp_bps: u16,
+
+    /// Share of fees that go to the protocol, in bps of the fees
+    pub protocol_bps: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PairFees {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::display"))]
+    pub in_fee_bps: Decimal,
+
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::display"))]
+    pub out_fee_bps: Decimal,
+
+    /// The pool state's trading protocol fee for swaps, LP protocol fee for liquidity
+    pub lp_protocol_split: LpProtocolSplit,
+}
+
+/// Fees of each mint as of the last update, see [`crate::pair_fees`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeSchedule {
+    /// Pricing program that the fees are of
+    pub pricing_program: [u8; 32],
+
+    /// mint -> `(input fee bps, output fee bps)`
+    pub mints: HashMap<[u8; 32], (Decimal, Decimal)>,
+
+    /// Flat fee pricing program only
+    pub lp_withdrawal_fee_bps: Option<Decimal>,
+}
+
+impl InfAmm {
+    /// Fees of `inp -> out` as of the last update, see [`crate::pair_fees`].
+    ///
+    /// Returns `None` if the fees of either mint were not applied in an update.
+    pub fn pair_fees(&self, inp: &[u8; 32], out: &[u8; 32]) -> Option<PairFees> {
+        let [inp, out] = [inp, out].map(native_sol_to_wsol);
+        let FeeSchedule {
+            pricing_program,
+            mints,
+            lp_withdrawal_fee_bps,
+        } = &self.fee_schedule;
+        let trade_kind = self.trade_type(inp, out);
+        let (in_fee_bps, out_fee_bps) = match trade_kind {
+            TradeKind::AddLiquidity if *pricing_program == inf1_pp_flatfee_core::ID => {
+                (Decimal::ZERO, Decimal::ZERO)
+            }
+            TradeKind::RemoveLiquidity if *pricing_program == inf1_pp_flatfee_core::ID => {
+                ((*lp_withdrawal_fee_bps)?, Decimal::ZERO)
+            }
+            _ => (mints.get(inp)?.0, mints.get(out)?.1),
+        };
+        let protocol_bps = match trade_kind {
+            TradeKind::Swap => self.inner.pool.trading_protocol_fee_bps,
+            TradeKind::AddLiquidity | TradeKind::RemoveLiquidity => {
+                self.inner.pool.lp_protocol_fee_bps
+            }
+        };
+        Some(PairFees {
+            in_fee_bps,
+            out_fee_bps,
+            lp_protocol_split: LpProtocolSplit {
+                lp_bps: 10_000u16.saturating_sub(protocol_bps),
+                protocol_bps,
+            },
+        })
+    }
+
+    /// Parses the fees of the LSTs in `scope` and the LP token mint
+    /// from the pricing program accounts in `fetched`.
+    ///
+    /// Should only be called after a successful update.
+    pub(crate) fn record_fee_schedule(
+        &mut self,
+        fetched: &impl UpdateMapWithOwner,
+        scope: Option<&[[u8; 32]]>,
+    ) {
+        let pricing_program = self.inner.pool.pricing_program;
+        if self.fee_schedule.pricing_program != pricing_program {
+            self.fee_schedule = FeeSchedule {
+                pricing_program,
+                ..Default::default()
+            };
+        }
+        let mints: Vec<_> = self
+            .inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state().mint)
+            .filter(|mint| in_scope(scope, mint))
+            .chain([self.inner.pool.lp_token_mint])
+            .collect();
+        let data_of = |pk: &[u8; 32]| {
+            fetched
+                .get_owner(pk)
+                .filter(|owner| *owner == pricing_program)
+                .and(fetched.get_account(pk))
+                .map(|acc| acc.data().to_vec())
+        };
+        let FeeSchedule {
+            mints: fees,
+            lp_withdrawal_fee_bps,
+            ..
+        } = &mut self.fee_schedule;
+
+        if pricing_program == inf1_pp_flatfee_core::ID {
+            if let Some(bps) = flat_fee_program_state()
+                .and_then(|pk| data_of(&pk))
+                .and_then(|d| chunk_at::<2>(&d, FLAT_FEE_LP_WITHDRAWAL_FEE_OFFSET).copied())
+            {
+                *lp_withdrawal_fee_bps = Some(u16::from_le_bytes(bps).into());
+            }
+            for mint in mints {
+                let Some(d) = flat_fee_fee_account(&mint).and_then(|pk| data_of(&pk)) else {
+                    continue;
+                };
+                let (Some(inp), Some(out)) = (chunk_at::<2>(&d, 0), chunk_at::<2>(&d, 2)) else {
+                    continue;
+                };
+                fees.insert(
+                    mint,
+                    (
+                        i16::from_le_bytes(*inp).into(),
+                        i16::from_le_bytes(*out).into(),
+                    ),
+                );
+            }
+        } else if pricing_program == *PricingAgTy::FlatSlab(()).program_id() {
+            let slabs = self
+                .inner
+                .pricing
+                .accounts_to_update_all(mints.iter().copied())
+                .filter_map(|pk| data_of(&pk));
+            for slab in slabs {
+                let Some(entries) = slab.get(FLAT_SLAB_HEADER_LEN..) else {
+                    continue;
+                };
+                for entry in entries.chunks_exact(FLAT_SLAB_ENTRY_LEN) {
+                    let (Some(mint), Some(inp), Some(out)) = (
+                        chunk_at::<32>(entry, 0),
+                        chunk_at::<4>(entry, 32),
+                        chunk_at::<4>(entry, 36),
+                    ) else {
+                        continue;
+                    };
+                    if !mints.contains(mint) {
+                        continue;
+                    }
+                    let to_bps = |nanos: &[u8; 4]| {
+                        Decimal::new(i32::from_le_bytes(*nanos).into(), NANOS_BPS_SCALE)
+                    };
+                    fees.insert(*mint, (to_bps(inp), to_bps(out)));
+                }
+            }
+        }
+    }
+}
+
+fn chunk_at<const N: usize>(data: &[u8], at: usize) -> Option<&[u8; N]> {
+    data.get(at..).and_then(|s| s.first_chunk())
+}
@@ -0,0 +1,47 @@
+//! Updates limited to a single pair, for consumers that only quote one pair
+//! and should not have to fetch the accounts of every other LST on the list.
+//!
+//! The working set is the pool state, LST state list, LP token mint, the pricing
+//! program accounts of the pair and the sol val calc and reserves accounts of the pair's LSTs,
+//! as well as sysvar Clock, dynamic accounts and the rebalance record if enabled.
+//!
+//! LSTs outside of the pair keep their state as of their last update, and quoting them
+//! may return stale results. Use [`jupiter_amm_interface::Amm::update`]
+//! to update every LST again.
+
+use anyhow::Result;
+use inf1_std::inf1_pp_core::pair::Pair;
+use jupiter_amm_interface::AccountMap;
+use solana_pubkey::Pubkey;
+
+use crate::{native_sol::native_sol_to_wsol, update::AccountMapRef, InfAmm};
+
+/// Whether `mint` is in the `scope` of a limited update, `None` being all mints
+#[inline]
+pub(crate) fn in_scope(scope: Option<&[[u8; 32]]>, mint: &[u8; 32]) -> bool {
+    scope.is_none_or(|s| s.contains(mint))
+}
+
+impl InfAmm {
+    /// [`jupiter_amm_interface::Amm::get_accounts_to_update`] for only `pair`,
+    /// see [`crate::pair_update`]
+    pub fn get_accounts_to_update_for_pair(&self, pair: &Pair<&[u8; 32]>) -> Vec<Pubkey> {
+        self.accounts_to_update_in(Some(&pair_scope(pair)))
+    }
+
+    /// [`jupiter_amm_interface::Amm::update`] for only `pair`, see [`crate::pair_update`].
+    ///
+    /// `account_map` should contain [`Self::get_accounts_to_update_for_pair`] of the same `pair`.
+    pub fn update_for_pair(
+        &mut self,
+        pair: &Pair<&[u8; 32]>,
+        account_map: &AccountMap,
+    ) -> Result<()> {
+        self.update_in(&AccountMapRef(account_map), Some(&pair_scope(pair)))
+    }
+}
+
+/// [`crate::native_sol`] is updated as wSOL
+fn pair_scope(pair: &Pair<&[u8; 32]>) -> [[u8; 32]; 2] {
+    [pair.inp, pair.out].map(|mint| *native_sol_to_wsol(mint))
+}
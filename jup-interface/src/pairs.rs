@@ -0,0 +1,89 @@
+//! Enumeration of every supported pair with flags of whether it is currently viable,
+//! e.g. for bootstrapping routers without a trial quote per pair.
+
+use std::sync::Arc;
+
+use crate::{trade_kind::TradeKind, InfAmm};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PairInfo {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub inp: [u8; 32],
+
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub out: [u8; 32],
+
+    pub kind: TradeKind,
+
+    /// Either mint is epoch affected and its stake pool has not been updated
+    /// for the current epoch, see [`crate::epoch`]
+    pub epoch_stale: bool,
+
+    /// The input LST has input disabled on the LST state list
+    pub input_disabled: bool,
+
+    /// The output LST's reserves are empty or have not been fetched yet
+    pub zero_reserve: bool,
+
+    /// The pair trades through the LP token, i.e. `AddLiquidity` or `RemoveLiquidity`,
+    /// which do not support ExactOut
+    pub lp_route: bool,
+}
+
+impl PairInfo {
+    /// Whether ExactIn quotes of the pair are expected to succeed, barring pool-wide
+    /// conditions such as the pool being disabled or rebalancing
+    #[inline]
+    pub const fn is_viable(&self) -> bool {
+        !self.epoch_stale && !self.input_disabled && !self.zero_reserve
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MintFlags {
+    mint: [u8; 32],
+    epoch_stale: bool,
+    input_disabled: bool,
+    zero_reserve: bool,
+}
+
+impl InfAmm {
+    /// Every ordered pair of distinct mints that have sol val calc data and a valid
+    /// reserves account as of the last update, including the LP token mint,
+    /// with flags of whether each is currently viable.
+    ///
+    /// Flags are computed once per mint instead of once per pair.
+    pub fn pairs(&self) -> impl Iterator<Item = PairInfo> + '_ {
+        let current_epoch = self.epoch();
+        let lp_mint = self.inner.pool.lp_token_mint;
+        let mints: Arc<[MintFlags]> = self
+            .live_mints()
+            .map(|mint| MintFlags {
+                mint,
+                epoch_stale: self.is_epoch_stale(&mint, current_epoch),
+                input_disabled: self
+                    .lst_states()
+                    .find(|l| l.mint == mint)
+                    .is_some_and(|l| l.is_input_disabled != 0),
+                zero_reserve: mint != lp_mint && self.reserves_balance(&mint).unwrap_or(0) == 0,
+            })
+            .collect();
+        (0..mints.len()).flat_map(move |i| {
+            let mints = Arc::clone(&mints);
+            (0..mints.len()).filter(move |j| *j != i).map(move |j| {
+                let (inp, out) = (&mints[i], &mints[j]);
+                let kind = self.trade_type(&inp.mint, &out.mint);
+                PairInfo {
+                    inp: inp.mint,
+                    out: out.mint,
+                    kind,
+                    epoch_stale: inp.epoch_stale || out.epoch_stale,
+                    input_disabled: inp.input_disabled,
+                    zero_reserve: out.zero_reserve,
+                    lp_route: kind != TradeKind::Swap,
+                }
+            })
+        })
+    }
+}
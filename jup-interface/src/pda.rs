@@ -1,8 +1,130 @@
+//! PDAs of the INF controller program and the programs it uses, e.g. for downstream
+//! tools that need to pre-create accounts.
+//!
+//! The free fns of this module derive the addresses of the mainnet deployment,
+//! see [`InfAmmConfig`] for those of any deployment.
+//!
+//! Bumps found by [`find_pda`] are cached for the lifetime of the process,
+//! so repeated derivations of the same PDA only cost a single hash.
+//!
+//! [`crate::InfAmm`] derives PDAs with the [`PdaFns`] of its [`crate::config::InfAmmConfig`],
+//! [`PdaFns::FAST`] by default. The free fns of this module always use [`FastPda`].
+
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+use inf1_std::inf1_pp_ag_std::inf1_pp_flatfee_core;
 use solana_pubkey::Pubkey;
 use solana_sha256_hasher::Hasher;
 
+use crate::{config::InfAmmConfig, lst_info::ASSOCIATED_TOKEN_PROGRAM_ID};
+
 const PDA_MARKER: &[u8; 21] = b"ProgramDerivedAddress";
 
+/// Seed of the INF controller program's protocol fee PDA
+pub const PROTOCOL_FEE_SEED: &[u8] = b"protocol-fee";
+
+/// Seed of the INF controller program's rebalance record PDA,
+/// which only exists between `StartRebalance` and `EndRebalance`
+pub const REBALANCE_RECORD_SEED: &[u8] = b"rebalance-record";
+
+/// Seed of the flat fee pricing program's program state PDA
+pub const FLAT_FEE_PROGRAM_STATE_SEED: &[u8] = b"state";
+
+/// Seed prefix of the flat fee pricing program's per-LST fee account PDAs
+pub const FLAT_FEE_FEE_ACCOUNT_SEED_PREFIX: &[u8] = b"fee";
+
+pub type FindPdaFn = fn(&[&[u8]], &[u8; 32]) -> Option<([u8; 32], u8)>;
+
+pub type CreateRawPdaFn = fn(&[&[u8]], &[u8; 32]) -> Option<[u8; 32]>;
+
+/// Strategy for deriving PDAs.
+///
+/// Associated fns instead of methods because [`inf1_std`] takes them as fn pointers,
+/// see [`PdaFns::of`]
+pub trait PdaProvider {
+    /// Finds the PDA of `seeds` and `program_id` and its bump,
+    /// `None` if there is none
+    fn find(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)>;
+
+    /// Derives the PDA of `seeds`, which includes the bump, and `program_id`
+    fn create_raw(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<[u8; 32]>;
+}
+
+/// [`find_pda`], with bumps found cached, and a [`PdaProvider::create_raw`]
+/// that does not check seed lengths or if the PDA is off-curve
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FastPda;
+
+impl PdaProvider for FastPda {
+    #[inline]
+    fn find(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+        find_pda(seeds, program_id)
+    }
+
+    #[inline]
+    fn create_raw(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<[u8; 32]> {
+        create_raw_pda(seeds, program_id)
+    }
+}
+
+/// [`Pubkey::try_find_program_address`] and [`Pubkey::create_program_address`]:
+/// full seed length and off-curve checks, no caching
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SafePda;
+
+impl PdaProvider for SafePda {
+    #[inline]
+    fn find(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+        Pubkey::try_find_program_address(seeds, &Pubkey::new_from_array(*program_id))
+            .map(|(pda, bump)| (pda.to_bytes(), bump))
+    }
+
+    #[inline]
+    fn create_raw(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<[u8; 32]> {
+        Pubkey::create_program_address(seeds, &Pubkey::new_from_array(*program_id))
+            .ok()
+            .map(|pda| pda.to_bytes())
+    }
+}
+
+/// The fns of a [`PdaProvider`], to select one at runtime
+#[derive(Debug, Clone, Copy)]
+pub struct PdaFns {
+    pub find: FindPdaFn,
+    pub create_raw: CreateRawPdaFn,
+}
+
+impl PdaFns {
+    pub const FAST: Self = Self::of::<FastPda>();
+
+    pub const SAFE: Self = Self::of::<SafePda>();
+
+    #[inline]
+    pub const fn of<P: PdaProvider>() -> Self {
+        Self {
+            find: P::find,
+            create_raw: P::create_raw,
+        }
+    }
+}
+
+impl Default for PdaFns {
+    #[inline]
+    fn default() -> Self {
+        Self::FAST
+    }
+}
+
+type BumpCacheKey = ([u8; 32], Vec<Vec<u8>>);
+
+fn bump_cache() -> &'static RwLock<HashMap<BumpCacheKey, u8>> {
+    static BUMP_CACHE: OnceLock<RwLock<HashMap<BumpCacheKey, u8>>> = OnceLock::new();
+    BUMP_CACHE.get_or_init(Default::default)
+}
+
 /// This fn omits the following checks for performance, at the cost of safety:
 /// - does not check if seed lenghts are within bounds
 /// - does not check if resulting PDA is indeed not on curve
@@ -19,7 +141,84 @@ pub(crate) fn create_raw_pda(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<[
     Some(hash.to_bytes())
 }
 
-pub(crate) fn find_pda(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
-    Pubkey::try_find_program_address(seeds, &Pubkey::new_from_array(*program_id))
-        .map(|(pk, bump)| (pk.to_bytes(), bump))
+/// [`Pubkey::try_find_program_address`], but the off-curve search is only done
+/// the first time `seeds` and `program_id` are encountered
+pub fn find_pda(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+    let key = (*program_id, seeds.iter().map(|s| s.to_vec()).collect());
+    // a poisoned cache is bypassed
+    if let Some(bump) = bump_cache().read().ok().and_then(|c| c.get(&key).copied()) {
+        let bump_seed = [bump];
+        let seeds_with_bump: Vec<_> = seeds
+            .iter()
+            .copied()
+            .chain([bump_seed.as_slice()])
+            .collect();
+        return create_raw_pda(&seeds_with_bump, program_id).map(|pda| (pda, bump));
+    }
+    let (pda, bump) =
+        Pubkey::try_find_program_address(seeds, &Pubkey::new_from_array(*program_id))?;
+    if let Ok(mut c) = bump_cache().write() {
+        c.insert(key, bump);
+    }
+    Some((pda.to_bytes(), bump))
+}
+
+/// `owner`'s associated token account for `mint`.
+///
+/// `token_program` is the owner program of `mint`.
+#[inline]
+pub fn find_ata(owner: &[u8; 32], token_program: &[u8; 32], mint: &[u8; 32]) -> Option<[u8; 32]> {
+    find_pda(
+        &[owner.as_slice(), token_program.as_slice(), mint.as_slice()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .map(|(pda, _bump)| pda)
+}
+
+/// The pool's reserves token account for `mint`, the ATA of the pool state.
+///
+/// See [`crate::InfAmm::lst_info`] to derive this with the bump stored on the LST state list instead.
+/// Of mainnet, see [`InfAmmConfig::lst_reserves`]
+#[inline]
+pub fn lst_reserves(token_program: &[u8; 32], mint: &[u8; 32]) -> Option<[u8; 32]> {
+    InfAmmConfig::MAINNET.lst_reserves(token_program, mint)
+}
+
+/// The INF controller program's protocol fee PDA, which owns the protocol fee accumulators.
+///
+/// Of mainnet, see [`InfAmmConfig::protocol_fee`]
+#[inline]
+pub fn protocol_fee() -> Option<[u8; 32]> {
+    InfAmmConfig::MAINNET.protocol_fee()
+}
+
+/// The token account that accumulates protocol fees in `mint`, the ATA of [`protocol_fee`].
+///
+/// Of mainnet, see [`InfAmmConfig::protocol_fee_accumulator`]
+#[inline]
+pub fn protocol_fee_accumulator(token_program: &[u8; 32], mint: &[u8; 32]) -> Option<[u8; 32]> {
+    InfAmmConfig::MAINNET.protocol_fee_accumulator(token_program, mint)
+}
+
+/// The INF controller program's rebalance record PDA, see [`REBALANCE_RECORD_SEED`].
+///
+/// Of mainnet, see [`InfAmmConfig::rebalance_record`]
+#[inline]
+pub fn rebalance_record() -> Option<[u8; 32]> {
+    InfAmmConfig::MAINNET.rebalance_record()
+}
+
+#[inline]
+pub fn flat_fee_program_state() -> Option<[u8; 32]> {
+    find_pda(&[FLAT_FEE_PROGRAM_STATE_SEED], &inf1_pp_flatfee_core::ID).map(|(pda, _bump)| pda)
+}
+
+/// The flat fee pricing program's account holding the input and output fees of `mint`
+#[inline]
+pub fn flat_fee_fee_account(mint: &[u8; 32]) -> Option<[u8; 32]> {
+    find_pda(
+        &[FLAT_FEE_FEE_ACCOUNT_SEED_PREFIX, mint.as_slice()],
+        &inf1_pp_flatfee_core::ID,
+    )
+    .map(|(pda, _bump)| pda)
 }
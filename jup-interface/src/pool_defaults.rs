@@ -0,0 +1,83 @@
+//! Detection of [`InfAmmConfig::pricing_defaults`](crate::config::InfAmmConfig::pricing_defaults),
+//! e.g. [`crate::consts::DEFAULT_MAINNET_POOL`], going out of date.
+//!
+//! The pricing program and LP token mint of the default pool state determine the accounts
+//! of the first [`jupiter_amm_interface::Amm::get_accounts_to_update`]. If they differ from
+//! the live pool state, the first update cycle fails and the defaults should be refreshed.
+//!
+//! On the first successful update, the defaults are compared against the live values.
+//! If they differ, they are reported to
+//! [`crate::metrics::InfAmmMetrics::on_pool_defaults_diverged`] and,
+//! with the `tracing` feature, logged at warn level.
+
+use inf1_std::inf1_ctl_core::accounts::pool_state::PoolState;
+#[cfg(feature = "tracing")]
+use solana_pubkey::Pubkey;
+
+use crate::InfAmm;
+
+/// The fields of a pool state that affect the accounts to update before the first update
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PoolDefaults {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub pricing_program: [u8; 32],
+
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub lp_token_mint: [u8; 32],
+}
+
+impl PoolDefaults {
+    #[inline]
+    pub const fn of(pool: &PoolState) -> Self {
+        Self {
+            pricing_program: pool.pricing_program,
+            lp_token_mint: pool.lp_token_mint,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PoolDefaultsCheck {
+    /// Of [`InfAmmConfig::pricing_defaults`](crate::config::InfAmmConfig::pricing_defaults)
+    pub defaults: PoolDefaults,
+
+    /// Of the live pool state, see [`InfAmm::live_pool_state`]
+    pub live: PoolDefaults,
+}
+
+impl PoolDefaultsCheck {
+    #[inline]
+    pub fn is_diverged(&self) -> bool {
+        self.defaults != self.live
+    }
+}
+
+impl InfAmm {
+    /// The default pool state's values against the live ones,
+    /// `None` if there has not been a successful update yet, see [`crate::pool_defaults`]
+    #[inline]
+    pub fn pool_defaults_check(&self) -> Option<PoolDefaultsCheck> {
+        self.live_pool_state().map(|live| PoolDefaultsCheck {
+            defaults: PoolDefaults::of(&self.config.pricing_defaults),
+            live: PoolDefaults::of(live),
+        })
+    }
+
+    /// Should only be called after the first successful update
+    pub(crate) fn report_pool_defaults(&self) {
+        let Some(check) = self.pool_defaults_check().filter(|c| c.is_diverged()) else {
+            return;
+        };
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            default_pricing_program = %Pubkey::new_from_array(check.defaults.pricing_program),
+            live_pricing_program = %Pubkey::new_from_array(check.live.pricing_program),
+            default_lp_token_mint = %Pubkey::new_from_array(check.defaults.lp_token_mint),
+            live_lp_token_mint = %Pubkey::new_from_array(check.live.lp_token_mint),
+            "default pool state diverged from live pool state"
+        );
+        self.metrics.on_pool_defaults_diverged(&check);
+    }
+}
@@ -0,0 +1,84 @@
+//! SOL valuation of every LST in the pool in a single pass, e.g. for dashboards,
+//! instead of quoting each LST against wSOL.
+//!
+//! Valuations are those recorded by the pool on the LST state list, i.e. as of the
+//! last time each LST's SOL value was synced onchain, which happens on every trade
+//! involving the LST.
+
+use inf1_std::inf1_ctl_core::typedefs::lst_state::LstState;
+use rust_decimal::Decimal;
+
+use crate::InfAmm;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LstValuation {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub mint: [u8; 32],
+
+    /// Balance of the pool's reserves of this LST, in atomic units
+    pub reserves: u64,
+
+    /// SOL value of [`Self::reserves`] in lamports, as recorded on the LST state list
+    pub sol_value: u64,
+
+    /// Lamports per atomic unit of this LST, `sol_value / reserves`.
+    ///
+    /// `None` if reserves are empty.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::display_opt"))]
+    pub lst_to_sol: Option<Decimal>,
+
+    /// Fraction of the pool's total SOL value held in this LST, `sol_value / total_sol_value`.
+    ///
+    /// `None` if the pool's total SOL value is 0.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::display_opt"))]
+    pub share: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PoolValuation {
+    /// The pool's total SOL value in lamports
+    pub total_sol_value: u64,
+
+    /// See [`InfAmm::inf_sol_value_per_token`]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::display_opt"))]
+    pub inf_sol_value_per_token: Option<Decimal>,
+
+    /// In LST state list order
+    pub lsts: Vec<LstValuation>,
+}
+
+impl InfAmm {
+    /// SOL valuation of every LST on the LST state list, see [`crate::prices`].
+    ///
+    /// LSTs whose reserves have not been fetched, e.g. out of scope of every
+    /// [`crate::pair_update`] so far, have 0 reserves.
+    pub fn pool_valuation(&self) -> PoolValuation {
+        let total_sol_value = self.inner.pool.total_sol_value;
+        let lsts = self
+            .inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| {
+                let LstState {
+                    mint, sol_value, ..
+                } = l.into_lst_state();
+                let reserves = self.reserves_balance(&mint).unwrap_or(0);
+                LstValuation {
+                    mint,
+                    reserves,
+                    sol_value,
+                    lst_to_sol: Decimal::from(sol_value).checked_div(Decimal::from(reserves)),
+                    share: Decimal::from(sol_value).checked_div(Decimal::from(total_sol_value)),
+                }
+            })
+            .collect();
+        PoolValuation {
+            total_sol_value,
+            inf_sol_value_per_token: self.inf_sol_value_per_token(),
+            lsts,
+        }
+    }
+}
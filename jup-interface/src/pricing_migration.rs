@@ -0,0 +1,204 @@
+//! Opt-in handling of pricing program migrations, i.e. the pool state's pricing program
+//! changing between the flat fee and flat slab pricing programs.
+//!
+//! Normally, the pricing program's accounts are only requested for the pool state's
+//! current pricing program, so the update that first sees the new pricing program does not
+//! have its accounts and fails, and quoting fails until the next update cycle.
+//!
+//! With [`InfAmm::with_pricing_migration`], a standby pricing state of the other pricing
+//! program is kept warm alongside the live one: its accounts are included in
+//! [`jupiter_amm_interface::Amm::get_accounts_to_update`] and applied in every update,
+//! tolerating accounts that do not exist or fail to deserialize under it.
+//! Once the pool state switches pricing programs, the standby state is swapped in,
+//! so quoting continues without a dead window. The live pool state's pricing program
+//! is always the one quoted with.
+
+use std::sync::Arc;
+
+use inf1_std::{
+    inf1_ctl_core::{keys::POOL_STATE_ID, typedefs::lst_state::LstState},
+    inf1_pp_ag_std::{
+        inf1_pp_flatfee_core,
+        update::{all::AccountsToUpdateAll, UpdatePricingProg},
+        PricingAgTy,
+    },
+    update::{Account, UpdateMap},
+    InfStd,
+};
+
+use crate::{
+    pair_update::in_scope,
+    update::{DeploymentAccountMapRef, UpdateMapWithOwner},
+    InfAmm,
+};
+
+/// Offset of `pricing_program` in pool state account data,
+/// `total_sol_value: u64, .., pricing_program: Pubkey, lp_token_mint: Pubkey`
+pub(crate) const POOL_STATE_PRICING_PROGRAM_OFFSET: usize = 112;
+
+/// The pricing programs that can be migrated between
+fn pricing_programs() -> [[u8; 32]; 2] {
+    [
+        inf1_pp_flatfee_core::ID,
+        *PricingAgTy::FlatSlab(()).program_id(),
+    ]
+}
+
+impl InfAmm {
+    /// Keeps a standby pricing state of the pricing program that is not live,
+    /// see [`crate::pricing_migration`]
+    #[inline]
+    pub fn with_pricing_migration(mut self) -> Self {
+        self.pricing_migration = true;
+        self
+    }
+
+    /// Pricing program of the standby pricing state, if any
+    #[inline]
+    pub fn standby_pricing_program(&self) -> Option<&[u8; 32]> {
+        self.pricing_standby
+            .as_ref()
+            .map(|s| &s.pool.pricing_program)
+    }
+
+    /// Accounts of the standby pricing state for the LSTs in `scope`,
+    /// to append to [`jupiter_amm_interface::Amm::get_accounts_to_update`]
+    pub(crate) fn standby_pricing_accs_to_update(
+        &self,
+        scope: Option<&[[u8; 32]]>,
+    ) -> Vec<[u8; 32]> {
+        let Some(standby) = &self.pricing_standby else {
+            return Vec::new();
+        };
+        standby
+            .pricing
+            .accounts_to_update_all(self.mints_in(scope))
+            .collect()
+    }
+
+    /// Swaps the standby pricing state into `staged` if the pool state's pricing program
+    /// was changed to the standby's by the update that `staged` is of.
+    pub(crate) fn swap_in_standby_pricing(&self, staged: &mut InfStd) {
+        let Some(standby) = &self.pricing_standby else {
+            return;
+        };
+        let new = &staged.pool.pricing_program;
+        if *new != self.inner.pool.pricing_program && *new == standby.pool.pricing_program {
+            staged.pricing = standby.pricing.clone();
+        }
+    }
+
+    /// Applies the pricing program accounts in `fetched_raw` to the standby pricing state,
+    /// initializing it from the live state if it is not of the pricing program that is
+    /// not live.
+    ///
+    /// Should only be called after a successful update.
+    pub(crate) fn record_pricing_standby(
+        &mut self,
+        fetched_raw: &impl UpdateMapWithOwner,
+        scope: Option<&[[u8; 32]]>,
+    ) {
+        if !self.pricing_migration {
+            self.pricing_standby = None;
+            return;
+        }
+        let live = self.inner.pool.pricing_program;
+        let Some(program) = pricing_programs().into_iter().find(|p| *p != live) else {
+            return;
+        };
+        let fetched = DeploymentAccountMapRef {
+            map: fetched_raw,
+            config: &self.config,
+            pdas: &self.deployment_pdas,
+        };
+        let mut standby = match &self.pricing_standby {
+            Some(s) if s.pool.pricing_program == program => InfStd::clone(s),
+            _ => {
+                let Some(mut pool_state) = fetched
+                    .get_account(&POOL_STATE_ID)
+                    .map(|acc| acc.data().to_vec())
+                else {
+                    return;
+                };
+                let Some(pricing_program) = pool_state
+                    .get_mut(POOL_STATE_PRICING_PROGRAM_OFFSET..)
+                    .and_then(|s| s.first_chunk_mut::<32>())
+                else {
+                    return;
+                };
+                *pricing_program = program;
+                let mut standby = InfStd::clone(&self.inner);
+                let overridden = PoolStateOverride {
+                    map: &fetched,
+                    pool_state: &pool_state,
+                };
+                if standby.update_pool(overridden).is_err() {
+                    return;
+                }
+                standby
+            }
+        };
+        // accounts that do not exist or fail to deserialize under the standby program
+        // leave it as it was, so that they are requested again in the next update
+        let prev = standby.pricing.clone();
+        if standby
+            .pricing
+            .update_all(self.mints_in(scope), fetched)
+            .is_err()
+        {
+            standby.pricing = prev;
+        }
+        self.pricing_standby = Some(Arc::new(standby));
+    }
+
+    fn mints_in<'a>(
+        &'a self,
+        scope: Option<&'a [[u8; 32]]>,
+    ) -> impl Iterator<Item = [u8; 32]> + Clone + 'a {
+        self.inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state())
+            .map(|LstState { mint, .. }| mint)
+            .filter(move |mint| in_scope(scope, mint))
+    }
+}
+
+/// [`UpdateMap`] with the pool state replaced by `pool_state`
+#[derive(Clone, Copy)]
+struct PoolStateOverride<'a, M> {
+    map: &'a M,
+    pool_state: &'a [u8],
+}
+
+enum OverrideAcc<'a, A> {
+    Fetched(A),
+    PoolState(&'a [u8]),
+}
+
+impl<A: Account> Account for OverrideAcc<'_, A> {
+    #[inline]
+    fn data(&self) -> &[u8] {
+        match self {
+            Self::Fetched(a) => a.data(),
+            Self::PoolState(d) => d,
+        }
+    }
+}
+
+impl<M: UpdateMap> UpdateMap for PoolStateOverride<'_, M> {
+    type Account<'acc>
+        = OverrideAcc<'acc, M::Account<'acc>>
+    where
+        Self: 'acc;
+
+    #[inline]
+    fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+        if *pk == POOL_STATE_ID {
+            Some(OverrideAcc::PoolState(self.pool_state))
+        } else {
+            self.map.get_account(pk).map(OverrideAcc::Fetched)
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Rebalance quoting and instruction building, for use by the pool's rebalance authority.
+//!
+//! A rebalance withdraws `out` LST from the pool's reserves with `StartRebalance`
+//! and must be followed by an `EndRebalance` in the same transaction after at least
+//! the quoted amount of `inp` LST has been deposited into the pool's reserves.
+//!
+//! While a rebalance is in progress, the pool state's `is_rebalancing` is set and all
+//! other trades revert. [`InfAmm::with_rebalance_tracking`] additionally fetches the
+//! rebalance record to tell which LST the rebalance is waiting on.
+
+use anyhow::{anyhow, Result};
+use inf1_std::{
+    inf1_ctl_core::instructions::rebalance::{
+        end::EndRebalanceIxData, start::StartRebalanceIxData,
+    },
+    inf1_pp_core::pair::Pair,
+    instructions::rebalance::{
+        end::{end_rebalance_ix_is_writer, end_rebalance_ix_keys_owned},
+        start::{start_rebalance_ix_is_writer, start_rebalance_ix_keys_owned},
+    },
+    quote::rebalance::RebalanceQuote,
+    rebalance::instruction::RebalanceIxArgs,
+    update::Account as _,
+};
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+use crate::{err::FmtErr, keys_writable_to_jup_metas, update::UpdateMapWithOwner, InfAmm};
+
+/// Offsets in rebalance record data: `old_total_sol_value: u64, inp_lst_index: u32, ..`
+const REBALANCE_RECORD_OLD_TOTAL_SOL_VALUE_OFFSET: usize = 0;
+const REBALANCE_RECORD_INP_LST_INDEX_OFFSET: usize = 8;
+
+/// A rebalance that was in progress as of the last update
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RebalanceInProgress {
+    /// Mint of the LST that must be deposited to end the rebalance
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub inp_mint: [u8; 32],
+
+    /// The pool's total SOL value before the rebalance started
+    pub old_total_sol_value: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RebalanceParams {
+    /// Mint of the LST to deposit into the pool's reserves
+    pub inp_mint: Pubkey,
+
+    /// Mint of the LST to withdraw from the pool's reserves
+    pub out_mint: Pubkey,
+
+    /// Amount of `out_mint` to withdraw
+    pub amount: u64,
+
+    /// Slippage limit on the `inp_mint` amount to be deposited
+    pub max_inp_amount: u64,
+
+    /// Token account that receives the withdrawn `out_mint`
+    pub withdraw_to: Pubkey,
+}
+
+impl InfAmm {
+    /// Quotes the minimum amount of `inp_mint` that must be deposited
+    /// into the pool after withdrawing `amount` of `out_mint`
+    pub fn quote_rebalance(
+        &self,
+        inp_mint: &Pubkey,
+        out_mint: &Pubkey,
+        amount: u64,
+    ) -> Result<RebalanceQuote> {
+        Ok(self
+            .inner
+            .quote_rebalance(
+                &Pair {
+                    inp: inp_mint.as_array(),
+                    out: out_mint.as_array(),
+                },
+                amount,
+            )
+            .map_err(FmtErr)?)
+    }
+
+    /// Returns `[StartRebalance, EndRebalance]`.
+    ///
+    /// The pool's rebalance authority as of the last update is set as signer.
+    /// Errs if there has not been a successful [`jupiter_amm_interface::Amm::update`] yet,
+    /// see [`InfAmm::live_pool_state`].
+    pub fn rebalance_ixs(
+        &self,
+        RebalanceParams {
+            inp_mint,
+            out_mint,
+            amount,
+            max_inp_amount,
+            withdraw_to,
+        }: &RebalanceParams,
+    ) -> Result<[Instruction; 2]> {
+        let rebalance_auth = self
+            .rebalance_authority()
+            .ok_or_else(|| anyhow!("Pool state not updated yet"))?;
+        let ixs = self
+            .inner
+            .rebalance_ixs(&RebalanceIxArgs {
+                amt: *amount,
+                limit: *max_inp_amount,
+                mints: &Pair {
+                    inp: inp_mint.as_array(),
+                    out: out_mint.as_array(),
+                },
+                signer: rebalance_auth.as_array(),
+                withdraw_to: withdraw_to.as_array(),
+            })
+            .map_err(FmtErr)?;
+
+        let mut start = Instruction {
+            program_id: self.config.program_id.into(),
+            accounts: keys_writable_to_jup_metas(
+                start_rebalance_ix_keys_owned(&ixs.start.accs).seq(),
+                start_rebalance_ix_is_writer(&ixs.start.accs).seq(),
+            ),
+            data: StartRebalanceIxData::new(ixs.start.to_full())
+                .as_buf()
+                .to_vec(),
+        };
+        let mut end = Instruction {
+            program_id: self.config.program_id.into(),
+            accounts: keys_writable_to_jup_metas(
+                end_rebalance_ix_keys_owned(&ixs.end.accs).seq(),
+                end_rebalance_ix_is_writer(&ixs.end.accs).seq(),
+            ),
+            data: EndRebalanceIxData::new().as_buf().to_vec(),
+        };
+        // keys_writable_to_jup_metas() sets all is_signer to false
+        for ix in [&mut start, &mut end] {
+            self.metas_to_deployment(&mut ix.accounts);
+            ix.accounts
+                .iter_mut()
+                .filter(|m| m.pubkey == rebalance_auth)
+                .for_each(|m| m.is_signer = true);
+        }
+
+        Ok([start, end])
+    }
+}
+
+impl InfAmm {
+    /// Fetches the rebalance record whenever the pool state is rebalancing,
+    /// see [`Self::rebalance_in_progress`]
+    #[inline]
+    pub fn with_rebalance_tracking(mut self) -> Self {
+        self.rebalance_tracking = true;
+        self
+    }
+
+    /// The rebalance that was in progress as of the last update.
+    ///
+    /// Always `None` if rebalance tracking is not enabled, see [`Self::with_rebalance_tracking`].
+    /// Also `None` in the first update after the pool state started rebalancing,
+    /// since the rebalance record is only fetched from the next update onwards.
+    #[inline]
+    pub fn rebalance_in_progress(&self) -> Option<RebalanceInProgress> {
+        self.rebalance_in_progress
+    }
+
+    /// This deployment's rebalance record, if it is to be fetched in the next update
+    pub(crate) fn rebalance_record_to_update(&self) -> Option<[u8; 32]> {
+        if !self.rebalance_tracking || self.inner.pool.is_rebalancing == 0 {
+            return None;
+        }
+        self.config.rebalance_record()
+    }
+
+    /// Parses the rebalance record in `fetched`, if any.
+    ///
+    /// Should only be called after a successful update.
+    pub(crate) fn record_rebalance_in_progress(&mut self, fetched: &impl UpdateMapWithOwner) {
+        self.rebalance_in_progress = self
+            .rebalance_record_to_update()
+            .and_then(|pk| fetched.get_account(&pk))
+            .and_then(|acc| {
+                let data = acc.data();
+                let old_total_sol_value = u64::from_le_bytes(
+                    *data
+                        .get(REBALANCE_RECORD_OLD_TOTAL_SOL_VALUE_OFFSET..)?
+                        .first_chunk()?,
+                );
+                let inp_lst_index = u32::from_le_bytes(
+                    *data
+                        .get(REBALANCE_RECORD_INP_LST_INDEX_OFFSET..)?
+                        .first_chunk()?,
+                );
+                let inp_mint = self
+                    .inner
+                    .try_lst_state_list()
+                    .ok()?
+                    .iter()
+                    .nth(usize::try_from(inp_lst_index).ok()?)?
+                    .into_lst_state()
+                    .mint;
+                Some(RebalanceInProgress {
+                    inp_mint,
+                    old_total_sol_value,
+                })
+            });
+    }
+}
@@ -0,0 +1,146 @@
+//! Driving [`InfAmm`] from recorded account snapshots, e.g. to validate fee changes
+//! and sol val calculator upgrades against history before deploying.
+//!
+//! A recording is a directory of JSON files, one [`ReplayStep`] per file:
+//!
+//! ```json
+//! { "slot": 1, "epoch": 0, "accounts": [{ "pubkey": "..", "account": { .. } }] }
+//! ```
+//!
+//! where each account is in the format of `solana account --output json`.
+//! [`load_dir`] sorts steps by slot, regardless of file names. Each step's accounts are
+//! layered over those of the steps before it, so steps after the first only need to
+//! contain the accounts that changed.
+//!
+//! Every step sets the AMM's clock to the step's slot and epoch, see [`InfAmm::set_clock`],
+//! so replay on an AMM created with [`InfAmm::with_deterministic_mode`] to not
+//! affect other AMMs sharing a live [`jupiter_amm_interface::ClockRef`].
+//! Quotes are made with [`InfAmm::quote_at_epoch`] at the step's epoch.
+
+use std::{collections::HashMap, fs::File, io::BufReader, path::Path};
+
+use anyhow::{Context, Result};
+use jupiter_amm_interface::{Quote, QuoteParams};
+use serde::{Deserialize, Serialize};
+use solana_account_decoder_client_types::UiAccount;
+use solana_pubkey::Pubkey;
+
+use crate::{update::UiAccountMap, InfAmm};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyedUiAccount {
+    pub pubkey: String,
+    pub account: UiAccount,
+}
+
+/// The accounts recorded at `slot`, see [`crate::replay`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayStep {
+    pub slot: u64,
+    pub epoch: u64,
+    pub accounts: Vec<KeyedUiAccount>,
+}
+
+#[derive(Debug)]
+pub struct ReplayOutcome {
+    pub slot: u64,
+    pub epoch: u64,
+
+    /// Result of applying this step's accounts, see [`InfAmm::update_with`]
+    pub update: Result<()>,
+
+    /// Quotes of each of the replay's quote params, in the same order.
+    /// Empty if [`Self::update`] failed.
+    pub quotes: Vec<Result<Quote>>,
+}
+
+/// Reads every `.json` file in `dir` as a [`ReplayStep`], sorted by slot
+pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<ReplayStep>> {
+    let dir = dir.as_ref();
+    let mut steps = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| dir.display().to_string())? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let file = File::open(&path).with_context(|| path.display().to_string())?;
+        let step: ReplayStep = serde_json::from_reader(BufReader::new(file))
+            .with_context(|| path.display().to_string())?;
+        steps.push(step);
+    }
+    steps.sort_by_key(|s| s.slot);
+    Ok(steps)
+}
+
+/// Iterator over the [`ReplayOutcome`] of each step, see [`InfAmm::replay`].
+///
+/// Yields `Err` for a step with an invalid pubkey, whose accounts are then not applied.
+#[derive(Debug)]
+pub struct Replay<'a, I> {
+    amm: &'a mut InfAmm,
+    steps: I,
+    quote_params: &'a [QuoteParams],
+    accounts: HashMap<Pubkey, UiAccount>,
+}
+
+impl<I: Iterator<Item = ReplayStep>> Iterator for Replay<'_, I> {
+    type Item = Result<ReplayOutcome>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ReplayStep {
+            slot,
+            epoch,
+            accounts,
+        } = self.steps.next()?;
+        let accounts = accounts
+            .into_iter()
+            .map(|KeyedUiAccount { pubkey, account }| {
+                let pk: Pubkey = pubkey
+                    .parse()
+                    .with_context(|| format!("slot {slot}: {pubkey}"))?;
+                Ok((pk, account))
+            })
+            .collect::<Result<Vec<_>>>();
+        let accounts = match accounts {
+            Ok(a) => a,
+            Err(e) => return Some(Err(e)),
+        };
+        self.accounts.extend(accounts);
+
+        self.amm.set_clock(slot, epoch);
+        let map: UiAccountMap = self.accounts.clone().into();
+        let update = self.amm.update_with(&map);
+        let quotes = if update.is_ok() {
+            self.quote_params
+                .iter()
+                .map(|qp| self.amm.quote_at_epoch(qp, epoch))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Some(Ok(ReplayOutcome {
+            slot,
+            epoch,
+            update,
+            quotes,
+        }))
+    }
+}
+
+impl InfAmm {
+    /// Applies `steps` in order, quoting each of `quote_params` after each step,
+    /// see [`crate::replay`]
+    #[inline]
+    pub fn replay<'a, I: IntoIterator<Item = ReplayStep>>(
+        &'a mut self,
+        steps: I,
+        quote_params: &'a [QuoteParams],
+    ) -> Replay<'a, I::IntoIter> {
+        Replay {
+            amm: self,
+            steps: steps.into_iter(),
+            quote_params,
+            accounts: HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Validation of the pool's LST reserves token accounts during update.
+//!
+//! If an LST's reserves account was closed or never created, or is not a token account
+//! of that LST owned by the pool state, every trade involving the LST fails onchain.
+//! Such LSTs are recorded in [`InfAmm::invalid_reserves`] and their reserves balances are
+//! not updated, and quoting them returns [`InfAmmErr::ReserveAccountInvalid`]
+//! until an update where their reserves account is valid again.
+
+use inf1_std::{
+    inf1_ctl_core::typedefs::lst_state::LstState, inf1_pp_core::pair::Pair, update::Account as _,
+};
+
+use crate::{
+    err::InfAmmErr,
+    lst_info::{ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    pda::CreateRawPdaFn,
+    update::UpdateMapWithOwner,
+    InfAmm,
+};
+
+/// Size of a token account without token-2022 extensions
+const TOKEN_ACC_LEN: usize = 165;
+
+/// Offsets in token account data: `mint: Pubkey, owner: Pubkey, ..`
+const TOKEN_ACC_MINT_OFFSET: usize = 0;
+const TOKEN_ACC_OWNER_OFFSET: usize = 32;
+
+/// Whether `fetched` has a valid reserves account for `lst_state` under either token program.
+///
+/// `pool_state_id` should be the deployment's, see [`crate::config`]
+pub(crate) fn is_reserves_valid(
+    pool_state_id: &[u8; 32],
+    create_raw_pda: CreateRawPdaFn,
+    lst_state: &LstState,
+    fetched: &impl UpdateMapWithOwner,
+) -> bool {
+    [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]
+        .iter()
+        .any(|token_program| {
+            let Some(reserves) = create_raw_pda(
+                &[
+                    pool_state_id.as_slice(),
+                    token_program.as_slice(),
+                    lst_state.mint.as_slice(),
+                    &[lst_state.pool_reserves_bump],
+                ],
+                &ASSOCIATED_TOKEN_PROGRAM_ID,
+            ) else {
+                return false;
+            };
+            if fetched.get_owner(&reserves).as_ref() != Some(token_program) {
+                return false;
+            }
+            fetched.get_account(&reserves).is_some_and(|acc| {
+                let data = acc.data();
+                data.len() >= TOKEN_ACC_LEN
+                    && data[TOKEN_ACC_MINT_OFFSET..][..32] == lst_state.mint
+                    && data[TOKEN_ACC_OWNER_OFFSET..][..32] == *pool_state_id
+            })
+        })
+}
+
+impl InfAmm {
+    /// Balance of the pool's reserves of `mint` as of the last update that fetched it,
+    /// `None` if it has not been fetched yet
+    #[inline]
+    pub fn reserves_balance(&self, mint: &[u8; 32]) -> Option<u64> {
+        self.inner.lst_reserves.get(mint).copied()
+    }
+
+    /// Mints of LSTs whose reserves account was invalid in the last update, sorted.
+    ///
+    /// See [`crate::reserves`]
+    pub fn invalid_reserves_mints(&self) -> Vec<[u8; 32]> {
+        let mut res: Vec<_> = self.invalid_reserves.iter().copied().collect();
+        res.sort_unstable();
+        res
+    }
+
+    pub(crate) fn check_reserves_valid(&self, pair: &Pair<&[u8; 32]>) -> Result<(), InfAmmErr> {
+        match [pair.inp, pair.out]
+            .into_iter()
+            .find(|mint| self.invalid_reserves.contains(*mint))
+        {
+            Some(mint) => Err(InfAmmErr::ReserveAccountInvalid { mint: *mint }),
+            None => Ok(()),
+        }
+    }
+}
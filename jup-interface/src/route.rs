@@ -0,0 +1,169 @@
+//! Routing of LST-LST swaps through the LP token, i.e. `AddLiquidity` then
+//! `RemoveLiquidity`, for pairs where that is cheaper than the direct swap
+//! due to fee configuration.
+//!
+//! The routed trade cannot be expressed as a single [`jupiter_amm_interface::Swap`],
+//! so routing is only available through [`InfAmm::quote_best_route`], whose quotes must be
+//! executed with [`InfAmm::routed_swap_instructions`].
+//! [`jupiter_amm_interface::Amm::quote`] and
+//! [`Amm::get_swap_and_account_metas`](jupiter_amm_interface::Amm::get_swap_and_account_metas)
+//! always use the direct swap.
+
+use anyhow::Result;
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use jupiter_amm_interface::{SwapMode, SwapParams};
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+use crate::{err::InfAmmErr, InfAmm};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Route {
+    /// A single INF instruction
+    Direct,
+
+    /// `AddLiquidity` of the input LST for `lp_token_amount` of the LP token,
+    /// then `RemoveLiquidity` of `lp_token_amount` for the output LST
+    ViaLpToken { lp_token_amount: u64 },
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RoutedQuote {
+    pub route: Route,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::b58"))]
+    pub fee_mint: [u8; 32],
+
+    /// For [`Route::ViaLpToken`], fees of both legs in terms of the output LST
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::inf_quote"))]
+    pub quote: inf1_std::quote::Quote,
+}
+
+impl InfAmm {
+    /// Quotes ExactIn `amount` of `pair` by both the direct swap and via the LP token,
+    /// returning the one with the larger output amount.
+    ///
+    /// Pairs involving the LP token are always [`Route::Direct`].
+    /// Returns the direct quote's error if both fail.
+    pub fn quote_best_route(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        amount: u64,
+    ) -> Result<RoutedQuote, InfAmmErr> {
+        let current_epoch = self.try_epoch()?;
+        let direct = self
+            .quote_raw(pair, amount, TradeLimitTy::ExactIn, current_epoch)
+            .map(|(fee_mint, quote)| RoutedQuote {
+                route: Route::Direct,
+                fee_mint,
+                quote,
+            });
+        let lp_mint = &self.inner.pool.lp_token_mint;
+        if pair.inp == lp_mint || pair.out == lp_mint {
+            return direct;
+        }
+        let via = self.quote_via_lp_token(pair, amount, current_epoch);
+        match (direct, via) {
+            (Ok(d), Ok(v)) => Ok(if v.quote.out > d.quote.out { v } else { d }),
+            (Ok(d), Err(_)) => Ok(d),
+            (Err(_), Ok(v)) => Ok(v),
+            (Err(e), Err(_)) => Err(e),
+        }
+    }
+
+    fn quote_via_lp_token(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        amount: u64,
+        current_epoch: u64,
+    ) -> Result<RoutedQuote, InfAmmErr> {
+        let lp_mint = &self.inner.pool.lp_token_mint;
+        let (_, add) = self.quote_raw(
+            &Pair {
+                inp: pair.inp,
+                out: lp_mint,
+            },
+            amount,
+            TradeLimitTy::ExactIn,
+            current_epoch,
+        )?;
+        let (_, remove) = self.quote_raw(
+            &Pair {
+                inp: lp_mint,
+                out: pair.out,
+            },
+            add.out,
+            TradeLimitTy::ExactIn,
+            current_epoch,
+        )?;
+        // add's fees are in the input LST, convert at the rate of the whole route before fees
+        let to_out = |fee: u64| {
+            let num = fee as u128
+                * (remove.out as u128 + remove.lp_fee as u128 + remove.protocol_fee as u128);
+            let denom = add
+                .inp
+                .saturating_sub(add.lp_fee)
+                .saturating_sub(add.protocol_fee);
+            u64::try_from(num.checked_div(denom as u128).unwrap_or_default()).unwrap_or(u64::MAX)
+        };
+        Ok(RoutedQuote {
+            route: Route::ViaLpToken {
+                lp_token_amount: add.out,
+            },
+            fee_mint: *pair.out,
+            quote: inf1_std::quote::Quote {
+                inp: add.inp,
+                out: remove.out,
+                lp_fee: remove.lp_fee.saturating_add(to_out(add.lp_fee)),
+                protocol_fee: remove.protocol_fee.saturating_add(to_out(add.protocol_fee)),
+                inp_mint: *pair.inp,
+                out_mint: *pair.out,
+            },
+        })
+    }
+
+    /// The instructions that execute `swap_params` by the route returned by
+    /// [`Self::quote_best_route`], i.e. 1 for [`Route::Direct`] and 2 for [`Route::ViaLpToken`].
+    ///
+    /// `lp_token_account` is the user's LP token account that the first leg mints to
+    /// and the second leg burns from. Its balance is left unchanged: the first leg's
+    /// slippage limit is the full quoted LP token amount, so that the second leg never burns
+    /// LP tokens the user already held. `out_amount` is the slippage limit of the second leg.
+    ///
+    /// ExactOut is always [`Route::Direct`].
+    pub fn routed_swap_instructions(
+        &self,
+        swap_params: &SwapParams,
+        lp_token_account: &Pubkey,
+    ) -> Result<Vec<Instruction>> {
+        if matches!(swap_params.swap_mode, SwapMode::ExactOut) {
+            return Ok(vec![self.swap_instruction(swap_params)?]);
+        }
+        let routed = self.quote_best_route(
+            &Pair {
+                inp: swap_params.source_mint.as_array(),
+                out: swap_params.destination_mint.as_array(),
+            },
+            swap_params.in_amount,
+        )?;
+        let lp_token_amount = match routed.route {
+            Route::Direct => return Ok(vec![self.swap_instruction(swap_params)?]),
+            Route::ViaLpToken { lp_token_amount } => lp_token_amount,
+        };
+        let lp_mint = Pubkey::new_from_array(self.inner.pool.lp_token_mint);
+        let add = self.swap_instruction(&SwapParams {
+            out_amount: lp_token_amount,
+            destination_mint: lp_mint,
+            destination_token_account: *lp_token_account,
+            ..*swap_params
+        })?;
+        let remove = self.swap_instruction(&SwapParams {
+            in_amount: lp_token_amount,
+            source_mint: lp_mint,
+            source_token_account: *lp_token_account,
+            ..*swap_params
+        })?;
+        Ok(vec![add, remove])
+    }
+}
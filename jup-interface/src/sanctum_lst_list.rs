@@ -1,21 +1,136 @@
-use anyhow::Result;
-use backoff::{retry, ExponentialBackoff};
+use std::time::Duration;
+
 use sanctum_lst_list::{SanctumLst, SanctumLstList};
 
+#[cfg(feature = "remote")]
 pub const SANCTUM_API_URL: &str = "https://sanctum-api.ironforge.network/lsts-pub";
 
-fn load_remote_sanctum_lst_list_with_retries() -> Result<Vec<SanctumLst>> {
-    let mut response = retry(ExponentialBackoff::default(), || {
-        ureq::get(SANCTUM_API_URL).call().map_err(Into::into)
+/// Retries and timeouts of fetching LST lists, ignored without the `remote` feature.
+///
+/// Failed requests are retried with exponential backoff starting at
+/// [`Self::initial_interval`] until [`Self::max_elapsed`] has passed,
+/// so fetching takes at most about `max_elapsed + timeout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchPolicy {
+    /// Time after which no more retries are made
+    pub max_elapsed: Duration,
+
+    /// Wait before the first retry, randomized and increased for every subsequent retry
+    pub initial_interval: Duration,
+
+    /// Hard limit on each request, including connecting and reading the response
+    pub timeout: Duration,
+}
+
+impl FetchPolicy {
+    /// Same retries as the default exponential backoff of the `backoff` crate,
+    /// with 30s request timeouts
+    pub const DEFAULT: Self = Self {
+        max_elapsed: Duration::from_secs(15 * 60),
+        initial_interval: Duration::from_millis(500),
+        timeout: Duration::from_secs(30),
+    };
+}
+
+impl Default for FetchPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(feature = "remote")]
+fn backoff_of(policy: &FetchPolicy) -> backoff::ExponentialBackoff {
+    backoff::ExponentialBackoffBuilder::new()
+        .with_initial_interval(policy.initial_interval)
+        .with_max_elapsed_time(Some(policy.max_elapsed))
+        .build()
+}
+
+#[cfg(feature = "remote")]
+fn get(url: &str, policy: &FetchPolicy) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
+    ureq::get(url)
+        .config()
+        .timeout_global(Some(policy.timeout))
+        .build()
+}
+
+#[cfg(feature = "remote")]
+fn load_remote_lst_list_with_retries(
+    url: &str,
+    policy: &FetchPolicy,
+) -> anyhow::Result<Vec<SanctumLst>> {
+    use backoff::retry;
+
+    let mut response = retry(backoff_of(policy), || {
+        get(url, policy).call().map_err(Into::into)
     })?;
 
     let SanctumLstList { data } = response.body_mut().read_json()?;
     Ok(data)
 }
 
-pub fn load_sanctum_lst_list() -> Vec<SanctumLst> {
-    match load_remote_sanctum_lst_list_with_retries() {
-        Ok(sanctum_lst_list) => sanctum_lst_list,
-        Err(_error) => SanctumLstList::load().data,
+/// Response to a conditional request for the Sanctum LST list
+#[cfg(feature = "remote")]
+#[derive(Debug)]
+pub(crate) enum ConditionalFetch {
+    NotModified,
+    Modified {
+        etag: Option<String>,
+        body: serde_json::Value,
+    },
+}
+
+/// Fetches the Sanctum LST list unless its ETag is still `etag`
+#[cfg(feature = "remote")]
+pub(crate) fn fetch_sanctum_lst_list_if_none_match(
+    etag: Option<&str>,
+    policy: &FetchPolicy,
+) -> anyhow::Result<ConditionalFetch> {
+    use backoff::retry;
+
+    let mut response = retry(backoff_of(policy), || {
+        let request = get(SANCTUM_API_URL, policy);
+        let request = match etag {
+            Some(etag) => request.header("If-None-Match", etag),
+            None => request,
+        };
+        request.call().map_err(Into::into)
+    })?;
+    if response.status() == 304 {
+        return Ok(ConditionalFetch::NotModified);
+    }
+
+    let etag = response
+        .headers()
+        .get("etag")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned);
+    let body = response.body_mut().read_json()?;
+    Ok(ConditionalFetch::Modified { etag, body })
+}
+
+/// Without the `remote` feature, always the copy bundled with this crate
+pub fn load_sanctum_lst_list(policy: &FetchPolicy) -> Vec<SanctumLst> {
+    #[cfg(feature = "remote")]
+    if let Ok(sanctum_lst_list) = load_remote_lst_list_with_retries(SANCTUM_API_URL, policy) {
+        return sanctum_lst_list;
+    }
+    #[cfg(not(feature = "remote"))]
+    let _ = policy;
+    SanctumLstList::load().data
+}
+
+/// LST list served at `url` in the same schema as the Sanctum LST list.
+///
+/// Empty if fetching fails or without the `remote` feature, since the bundled copy
+/// is only of the mainnet list
+pub fn load_lst_list_from(url: &str, policy: &FetchPolicy) -> Vec<SanctumLst> {
+    #[cfg(feature = "remote")]
+    if let Ok(lst_list) = load_remote_lst_list_with_retries(url, policy) {
+        return lst_list;
     }
+    #[cfg(not(feature = "remote"))]
+    let _ = (url, policy);
+    Vec::new()
 }
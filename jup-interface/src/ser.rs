@@ -0,0 +1,113 @@
+//! `serialize_with` helpers for the report types of this crate,
+//! which render pubkeys in base58 and foreign types without serde impls as their
+//! pretty-printed or plain fields
+
+use std::fmt::Display;
+
+use inf1_std::{err::InfErr, inf1_pp_core::pair::Pair};
+use jupiter_amm_interface::{Quote, SwapMode};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use solana_pubkey::Pubkey;
+
+use crate::err::FmtErr;
+
+#[repr(transparent)]
+struct B58<'a>(&'a [u8; 32]);
+
+impl Serialize for B58<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(&Pubkey::new_from_array(*self.0))
+    }
+}
+
+#[inline]
+pub(crate) fn b58<S: Serializer>(pk: &[u8; 32], s: S) -> Result<S::Ok, S::Error> {
+    B58(pk).serialize(s)
+}
+
+#[inline]
+pub(crate) fn b58_opt<S: Serializer>(pk: &Option<[u8; 32]>, s: S) -> Result<S::Ok, S::Error> {
+    pk.as_ref().map(B58).serialize(s)
+}
+
+#[inline]
+pub(crate) fn pubkey<S: Serializer>(pk: &Pubkey, s: S) -> Result<S::Ok, S::Error> {
+    s.collect_str(pk)
+}
+
+/// For types without serde impls whose [`Display`] is their canonical repr, e.g. `Decimal`
+#[inline]
+pub(crate) fn display<T: Display, S: Serializer>(v: &T, s: S) -> Result<S::Ok, S::Error> {
+    s.collect_str(v)
+}
+
+#[inline]
+pub(crate) fn display_opt<T: Display, S: Serializer>(
+    v: &Option<T>,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    match v {
+        Some(v) => s.collect_str(v),
+        None => s.serialize_none(),
+    }
+}
+
+#[inline]
+pub(crate) fn inf_err<S: Serializer>(e: &InfErr, s: S) -> Result<S::Ok, S::Error> {
+    s.collect_str(&FmtErr(*e))
+}
+
+pub(crate) fn pair<S: Serializer>(pair: &Pair<[u8; 32]>, s: S) -> Result<S::Ok, S::Error> {
+    let mut st = s.serialize_struct("Pair", 2)?;
+    st.serialize_field("inp", &B58(&pair.inp))?;
+    st.serialize_field("out", &B58(&pair.out))?;
+    st.end()
+}
+
+pub(crate) fn inf_quote<S: Serializer>(
+    quote: &inf1_std::quote::Quote,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    let inf1_std::quote::Quote {
+        inp,
+        out,
+        lp_fee,
+        protocol_fee,
+        inp_mint,
+        out_mint,
+    } = quote;
+    let mut st = s.serialize_struct("Quote", 6)?;
+    st.serialize_field("inp", inp)?;
+    st.serialize_field("out", out)?;
+    st.serialize_field("lp_fee", lp_fee)?;
+    st.serialize_field("protocol_fee", protocol_fee)?;
+    st.serialize_field("inp_mint", &B58(inp_mint))?;
+    st.serialize_field("out_mint", &B58(out_mint))?;
+    st.end()
+}
+
+pub(crate) fn jup_quote<S: Serializer>(quote: &Quote, s: S) -> Result<S::Ok, S::Error> {
+    let Quote {
+        in_amount,
+        out_amount,
+        fee_amount,
+        fee_mint,
+        fee_pct,
+    } = quote;
+    let mut st = s.serialize_struct("Quote", 5)?;
+    st.serialize_field("in_amount", in_amount)?;
+    st.serialize_field("out_amount", out_amount)?;
+    st.serialize_field("fee_amount", fee_amount)?;
+    st.serialize_field("fee_mint", &B58(fee_mint.as_array()))?;
+    st.serialize_field("fee_pct", &fee_pct.to_string())?;
+    st.end()
+}
+
+#[inline]
+pub(crate) fn swap_mode<S: Serializer>(mode: &SwapMode, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(match mode {
+        SwapMode::ExactIn => "ExactIn",
+        SwapMode::ExactOut => "ExactOut",
+    })
+}
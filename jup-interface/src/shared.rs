@@ -0,0 +1,96 @@
+//! Sharing an [`InfAmm`] between threads without external locking.
+//!
+//! [`Amm::update`] takes `&mut self`, which forces callers that quote from many threads
+//! to wrap the [`InfAmm`] in a lock held across quotes.
+//! [`InfAmmShared`] instead applies each update to a private clone and atomically
+//! publishes it as a new snapshot, so quotes only ever hold a lock for the duration of
+//! an [`Arc`] clone and never observe a partially applied update.
+
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use anyhow::Result;
+use jupiter_amm_interface::{AccountMap, Amm, Quote, QuoteParams};
+use solana_pubkey::Pubkey;
+
+use crate::InfAmm;
+
+/// [`InfAmm`] with `update(&self)` and `quote(&self)`, safe to share between threads,
+/// e.g. in an [`Arc`].
+///
+/// Updates are serialized with each other but never block quotes.
+/// A failed update publishes nothing, so retrying with the same accounts is idempotent.
+#[derive(Debug)]
+pub struct InfAmmShared {
+    current: RwLock<Arc<InfAmm>>,
+    update_lock: Mutex<()>,
+}
+
+impl InfAmmShared {
+    #[inline]
+    pub fn new(amm: InfAmm) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(amm)),
+            update_lock: Mutex::new(()),
+        }
+    }
+
+    /// The AMM as of the last successful update.
+    ///
+    /// The returned snapshot is unaffected by later updates.
+    #[inline]
+    pub fn load(&self) -> Arc<InfAmm> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Quotes against the AMM as of the last successful update
+    #[inline]
+    pub fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        self.load().quote(quote_params)
+    }
+
+    #[inline]
+    pub fn get_accounts_to_update(&self) -> Vec<Pubkey> {
+        self.load().get_accounts_to_update()
+    }
+
+    /// Applies `account_map` to a clone of the current AMM and publishes it on success.
+    ///
+    /// Concurrent calls are applied one after the other, each on top of the previous
+    /// one's result. On error, the current AMM is left as is.
+    pub fn update(&self, account_map: &AccountMap) -> Result<()> {
+        let _guard = self
+            .update_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut next = InfAmm::clone(&self.load());
+        next.update(account_map)?;
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(next);
+        Ok(())
+    }
+
+    /// Replaces the current AMM, e.g. with one restored from a snapshot
+    pub fn replace(&self, amm: InfAmm) {
+        let _guard = self
+            .update_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(amm);
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> Arc<InfAmm> {
+        self.current
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl From<InfAmm> for InfAmmShared {
+    #[inline]
+    fn from(amm: InfAmm) -> Self {
+        Self::new(amm)
+    }
+}
@@ -0,0 +1,135 @@
+//! Local execution of the instructions returned by [`InfAmm::swap_instruction`]
+//! with [`mollusk_svm`], for pre-flight checks of quotes against actual program behaviour.
+//!
+//! Program ELFs of the INF controller, pricing and sol val calc programs are not bundled
+//! with this crate and must be provided to [`SwapSimulator::new`], e.g. dumped from
+//! mainnet with `solana program dump`. The SPL token and associated token programs
+//! are always loaded.
+//!
+//! Simulation only sees the accounts passed to [`InfAmm::simulate_swap`], which must
+//! include every account of the instruction, including program accounts.
+
+use std::sync::atomic::Ordering;
+
+use anyhow::{anyhow, Result};
+use jupiter_amm_interface::{AccountMap, SwapParams};
+use mollusk_svm::{
+    result::{InstructionResult, ProgramResult},
+    Mollusk,
+};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+
+use crate::InfAmm;
+
+/// Loader that mainnet INF programs are deployed with
+const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    Pubkey::from_str_const("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// Offset of `amount: u64` in token account data, after `mint: Pubkey, owner: Pubkey`
+const TOKEN_ACC_AMOUNT_OFFSET: usize = 64;
+
+pub struct SwapSimulator {
+    pub svm: Mollusk,
+}
+
+impl SwapSimulator {
+    /// `programs` are `(program_id, elf)`, loaded with the upgradeable BPF loader
+    pub fn new<'a>(programs: impl IntoIterator<Item = (Pubkey, &'a [u8])>) -> Self {
+        let mut svm = Mollusk::default();
+        mollusk_svm_programs_token::token::add_program(&mut svm);
+        mollusk_svm_programs_token::associated_token::add_program(&mut svm);
+        programs.into_iter().for_each(|(program_id, elf)| {
+            svm.add_program_with_elf_and_loader(&program_id, elf, &BPF_LOADER_UPGRADEABLE_ID);
+        });
+        Self { svm }
+    }
+}
+
+#[derive(Debug)]
+pub struct SimResult {
+    pub program_result: ProgramResult,
+    pub compute_units_consumed: u64,
+
+    /// Decrease in balance of [`SwapParams::source_token_account`], 0 if failed
+    pub in_amount: u64,
+
+    /// Increase in balance of [`SwapParams::destination_token_account`], 0 if failed
+    pub out_amount: u64,
+}
+
+impl SimResult {
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        matches!(self.program_result, ProgramResult::Success)
+    }
+}
+
+impl InfAmm {
+    /// Executes [`Self::swap_instruction`] of `swap_params` against `accounts`,
+    /// with the clock programs see set to this AMM's current slot and epoch.
+    ///
+    /// Errs if the instruction cannot be built or any of its accounts is not in `accounts`.
+    /// Failure of the instruction itself is reported in [`SimResult::program_result`].
+    pub fn simulate_swap(
+        &self,
+        sim: &mut SwapSimulator,
+        swap_params: &SwapParams,
+        accounts: &AccountMap,
+    ) -> Result<SimResult> {
+        let ix = self.swap_instruction(swap_params)?;
+
+        let mut keys: Vec<_> = ix.accounts.iter().map(|m| m.pubkey).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let accs_bef = keys
+            .iter()
+            .map(|pk| {
+                let acc = accounts
+                    .get(pk)
+                    .ok_or_else(|| anyhow!("Missing account {pk}"))?;
+                Ok((*pk, acc.clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let clock = &mut sim.svm.sysvars.clock;
+        clock.slot = self.current_slot.load(Ordering::Relaxed);
+        clock.epoch = self.epoch();
+        clock.leader_schedule_epoch = clock.epoch;
+
+        let InstructionResult {
+            program_result,
+            compute_units_consumed,
+            resulting_accounts,
+            ..
+        } = sim.svm.process_instruction(&ix, &accs_bef);
+
+        let success = matches!(program_result, ProgramResult::Success);
+        let balance_change = |pk: &Pubkey| -> Option<(u64, u64)> {
+            if !success {
+                return None;
+            }
+            let bef = token_acc_amount(&accs_bef.iter().find(|(k, _)| k == pk)?.1)?;
+            let aft = token_acc_amount(&resulting_accounts.iter().find(|(k, _)| k == pk)?.1)?;
+            Some((bef, aft))
+        };
+        let in_amount = balance_change(&swap_params.source_token_account)
+            .map_or(0, |(bef, aft)| bef.saturating_sub(aft));
+        let out_amount = balance_change(&swap_params.destination_token_account)
+            .map_or(0, |(bef, aft)| aft.saturating_sub(bef));
+
+        Ok(SimResult {
+            program_result,
+            compute_units_consumed,
+            in_amount,
+            out_amount,
+        })
+    }
+}
+
+fn token_acc_amount(acc: &Account) -> Option<u64> {
+    acc.data
+        .get(TOKEN_ACC_AMOUNT_OFFSET..)?
+        .first_chunk()
+        .map(|b| u64::from_le_bytes(*b))
+}
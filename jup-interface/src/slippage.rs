@@ -0,0 +1,95 @@
+//! Slippage limits derived from [`Quote`]s, consistent with how the INF program
+//! interprets the `limit` instruction arg:
+//! - ExactIn: `limit` is the min output amount
+//! - ExactOut: `limit` is the max input amount
+
+use anyhow::Result;
+use jupiter_amm_interface::{Amm, Quote, QuoteParams, SwapMode};
+use rust_decimal::Decimal;
+
+use crate::{
+    quote_core::{exact_in_slippage_limit, exact_out_slippage_limit},
+    InfAmm,
+};
+
+/// Returns the min output amount for [`SwapMode::ExactIn`]
+/// or the max input amount for [`SwapMode::ExactOut`],
+/// rounding against the user
+#[inline]
+pub const fn slippage_limit(quoted_amt: u64, slippage_bps: u16, swap_mode: SwapMode) -> u64 {
+    match swap_mode {
+        SwapMode::ExactIn => exact_in_slippage_limit(quoted_amt, slippage_bps),
+        SwapMode::ExactOut => exact_out_slippage_limit(quoted_amt, slippage_bps),
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SlippageQuote {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::jup_quote"))]
+    pub quote: Quote,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::swap_mode"))]
+    pub swap_mode: SwapMode,
+
+    /// Slippage limit for ExactIn, [`Quote::out_amount`] for ExactOut
+    pub min_out_amount: u64,
+
+    /// [`Quote::in_amount`] for ExactIn, slippage limit for ExactOut
+    pub max_in_amount: u64,
+}
+
+impl SlippageQuote {
+    #[inline]
+    pub fn new(quote: Quote, swap_mode: SwapMode, slippage_bps: u16) -> Self {
+        let (min_out_amount, max_in_amount) = match swap_mode {
+            SwapMode::ExactIn => (
+                slippage_limit(quote.out_amount, slippage_bps, swap_mode),
+                quote.in_amount,
+            ),
+            SwapMode::ExactOut => (
+                quote.out_amount,
+                slippage_limit(quote.in_amount, slippage_bps, swap_mode),
+            ),
+        };
+        Self {
+            quote,
+            swap_mode,
+            min_out_amount,
+            max_in_amount,
+        }
+    }
+
+    /// The `limit` instruction arg, [`Self::min_out_amount`] for ExactIn
+    /// and [`Self::max_in_amount`] for ExactOut
+    #[inline]
+    pub const fn limit(&self) -> u64 {
+        match self.swap_mode {
+            SwapMode::ExactIn => self.min_out_amount,
+            SwapMode::ExactOut => self.max_in_amount,
+        }
+    }
+
+    /// Output atoms per input atom of the quote, before slippage.
+    ///
+    /// Returns `None` if the quoted input amount is 0.
+    #[inline]
+    pub fn effective_price(&self) -> Option<Decimal> {
+        Decimal::from(self.quote.out_amount).checked_div(Decimal::from(self.quote.in_amount))
+    }
+}
+
+impl InfAmm {
+    /// [`Amm::quote`] with the slippage limits for `slippage_bps`
+    #[inline]
+    pub fn quote_with_slippage(
+        &self,
+        quote_params: &QuoteParams,
+        slippage_bps: u16,
+    ) -> Result<SlippageQuote> {
+        Ok(SlippageQuote::new(
+            self.quote(quote_params)?,
+            quote_params.swap_mode,
+            slippage_bps,
+        ))
+    }
+}
@@ -0,0 +1,247 @@
+//! Persisting [`InfAmm`] state so that it can be resumed without RPC round trips,
+//! e.g. across router process restarts.
+//!
+//! Rather than serializing [`inf1_std`]'s internal structs, a snapshot holds the raw data
+//! of the accounts that were applied in the last [`InfAmm::update_with`], which
+//! [`InfAmm::restore`] replays to rebuild the same state. [`InfAmm`] only retains a copy
+//! of these accounts' data once opted in with [`InfAmm::with_snapshots`].
+//!
+//! Not included in snapshots:
+//! - the quote cache, which is cleared on update anyway. Re-enable it with [`InfAmm::with_quote_cache`].
+//! - [`InfAmm::lst_health`], which is recomputed by the replayed update with the restored
+//!   [`InfAmm::generation`] and the slot of the `amm_context` passed to [`InfAmm::restore`].
+//! - [`InfAmmConfig::pricing_defaults`], which are overwritten by the replayed update
+
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use inf1_std::update::{Account as _, UpdateMap};
+use jupiter_amm_interface::{AmmContext, KeyedAccount};
+use serde::{Deserialize, Serialize};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+
+use crate::{
+    clock::ClockMode,
+    config::InfAmmConfig,
+    fee_pct::FeePctRounding,
+    update::{UiAccountDataRef, UpdateMapWithOwner},
+    InfAmm, JupQuoteFees,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotAccount {
+    pub owner: [u8; 32],
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InfAmmSnapshot {
+    pub program_id: [u8; 32],
+    pub pool_state_id: [u8; 32],
+    pub lst_state_list_id: [u8; 32],
+    pub generation: u64,
+    pub clock_mode: ClockMode,
+    pub jup_quote_fees: JupQuoteFees,
+    pub fee_pct_rounding: Option<FeePctRounding>,
+    pub exclude_epoch_stale: bool,
+    pub pricing_migration: bool,
+    pub rebalance_tracking: bool,
+    pub lenient_updates: bool,
+    pub calc_upgrade_checks: bool,
+
+    /// `(mint, stake_pool_address)`, see [`InfAmm::new`], sorted
+    pub spl_lsts: Vec<([u8; 32], [u8; 32])>,
+
+    /// `(mint, token_program)`, see [`InfAmm::token_programs`], sorted.
+    ///
+    /// Persisted because mints are no longer fetched once their token program is known.
+    pub token_programs: Vec<([u8; 32], [u8; 32])>,
+
+    /// Accounts applied in the last update, keyed by deployment address, sorted by pubkey.
+    ///
+    /// Not a map because JSON map keys must be strings.
+    pub accounts: Vec<([u8; 32], SnapshotAccount)>,
+}
+
+impl InfAmm {
+    /// Retain the data of the accounts applied in each update, enabling [`Self::to_snapshot`]
+    #[inline]
+    pub fn with_snapshots(mut self) -> Self {
+        self.snapshot_accounts = Some(Default::default());
+        self
+    }
+
+    /// Returns `None` if snapshots are not enabled, see [`Self::with_snapshots`],
+    /// or if there has not been a successful update since
+    pub fn to_snapshot(&self) -> Option<InfAmmSnapshot> {
+        let snapshot_accounts = self
+            .snapshot_accounts
+            .as_deref()
+            .filter(|accounts| !accounts.is_empty())?;
+        let mut spl_lsts: Vec<_> = self
+            .inner
+            .spl_lsts
+            .iter()
+            .map(|(mint, pool)| (*mint, *pool))
+            .collect();
+        let mut token_programs: Vec<_> = self
+            .token_programs
+            .iter()
+            .map(|(mint, program)| (*mint, *program))
+            .collect();
+        let mut accounts: Vec<_> = snapshot_accounts
+            .iter()
+            .map(|(pk, acc)| (*pk, acc.clone()))
+            .collect();
+        // sorted so that identical state gives identical bytes
+        spl_lsts.sort_unstable();
+        token_programs.sort_unstable();
+        accounts.sort_unstable_by_key(|(pk, _)| *pk);
+        Some(InfAmmSnapshot {
+            program_id: self.config.program_id,
+            pool_state_id: self.config.pool_state_id,
+            lst_state_list_id: self.config.lst_state_list_id,
+            generation: self.generation,
+            clock_mode: self.clock_mode,
+            jup_quote_fees: self.jup_quote_fees,
+            fee_pct_rounding: self.fee_pct_rounding,
+            exclude_epoch_stale: self.exclude_epoch_stale,
+            pricing_migration: self.pricing_migration,
+            rebalance_tracking: self.rebalance_tracking,
+            lenient_updates: self.lenient_updates,
+            calc_upgrade_checks: self.calc_upgrade_checks,
+            spl_lsts,
+            token_programs,
+            accounts,
+        })
+    }
+
+    /// [`Self::to_snapshot`] serialized as JSON
+    pub fn snapshot(&self) -> Result<Vec<u8>> {
+        let snapshot = self
+            .to_snapshot()
+            .ok_or_else(|| anyhow!("Snapshots not enabled or no successful update to snapshot"))?;
+        Ok(serde_json::to_vec(&snapshot)?)
+    }
+
+    /// Inverse of [`Self::snapshot`]
+    pub fn restore(bytes: &[u8], amm_context: &AmmContext) -> Result<Self> {
+        Self::from_snapshot(serde_json::from_slice(bytes)?, amm_context)
+    }
+
+    pub fn from_snapshot(snapshot: InfAmmSnapshot, amm_context: &AmmContext) -> Result<Self> {
+        let InfAmmSnapshot {
+            program_id,
+            pool_state_id,
+            lst_state_list_id,
+            generation,
+            clock_mode,
+            jup_quote_fees,
+            fee_pct_rounding,
+            exclude_epoch_stale,
+            pricing_migration,
+            rebalance_tracking,
+            lenient_updates,
+            calc_upgrade_checks,
+            spl_lsts,
+            token_programs,
+            accounts,
+        } = snapshot;
+        let accounts = SnapshotAccountMap(accounts.into_iter().collect());
+
+        let key = Pubkey::new_from_array(lst_state_list_id);
+        let lst_state_list = accounts
+            .0
+            .get(&lst_state_list_id)
+            .ok_or_else(|| anyhow!("LST state list missing from snapshot"))?;
+        let keyed_account = KeyedAccount {
+            key,
+            account: Account {
+                data: lst_state_list.data.clone(),
+                owner: Pubkey::new_from_array(lst_state_list.owner),
+                ..Default::default()
+            },
+            params: None,
+        };
+        let config = InfAmmConfig {
+            program_id,
+            pool_state_id,
+            lst_state_list_id,
+            ..InfAmmConfig::MAINNET
+        };
+
+        let mut res = Self::new_with_config(
+            &keyed_account,
+            amm_context,
+            spl_lsts.into_iter().collect(),
+            config,
+        )?
+        .with_clock_mode(clock_mode)
+        .with_jup_quote_fees(jup_quote_fees)
+        .with_fee_pct_rounding(fee_pct_rounding)
+        .with_snapshots();
+        res.exclude_epoch_stale = exclude_epoch_stale;
+        res.pricing_migration = pricing_migration;
+        res.rebalance_tracking = rebalance_tracking;
+        res.lenient_updates = lenient_updates;
+        res.calc_upgrade_checks = calc_upgrade_checks;
+        res.token_programs = token_programs.into_iter().collect();
+        // 2 update cycles, 1st one might fail because pricing_defaults might be stale
+        res.generation = generation.saturating_sub(1);
+        if res.update_with(&accounts).is_err() {
+            res.generation = generation.saturating_sub(1);
+            res.update_with(&accounts)?;
+        }
+        Ok(res)
+    }
+
+    /// Retains the data of `accounts_to_update` that were fetched,
+    /// replacing those of the previous update, or merged into them if `merge`.
+    ///
+    /// No-op if snapshots are not enabled
+    pub(crate) fn record_snapshot_accounts(
+        &mut self,
+        accounts_to_update: &[Pubkey],
+        fetched: &impl UpdateMapWithOwner,
+        merge: bool,
+    ) {
+        let Some(snapshot_accounts) = &mut self.snapshot_accounts else {
+            return;
+        };
+        let applied = accounts_to_update.iter().filter_map(|pk| {
+            let pk = pk.as_array();
+            let data = fetched.get_account(pk)?.data().to_vec();
+            let owner = fetched.get_owner(pk)?;
+            Some((*pk, SnapshotAccount { owner, data }))
+        });
+        if merge {
+            Arc::make_mut(snapshot_accounts).extend(applied);
+        } else {
+            *snapshot_accounts = Arc::new(applied.collect());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[repr(transparent)]
+struct SnapshotAccountMap(HashMap<[u8; 32], SnapshotAccount>);
+
+impl UpdateMap for SnapshotAccountMap {
+    type Account<'acc>
+        = UiAccountDataRef<'acc>
+    where
+        Self: 'acc;
+
+    #[inline]
+    fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+        self.0.get(pk).map(|acc| UiAccountDataRef(&acc.data))
+    }
+}
+
+impl UpdateMapWithOwner for SnapshotAccountMap {
+    #[inline]
+    fn get_owner(&self, pk: &[u8; 32]) -> Option<[u8; 32]> {
+        self.0.get(pk).map(|acc| acc.owner)
+    }
+}
@@ -0,0 +1,222 @@
+//! Loading of the SPL LST mint -> stake pool mapping passed to [`crate::InfAmm::new`]
+//! from a local file, for airgapped or deterministic deployments that cannot or
+//! should not fetch the Sanctum LST list.
+//!
+//! The file is a JSON object of base58 mint to base58 stake pool address:
+//!
+//! ```json
+//! {
+//!   "jupSoLaHXQiZZTSfEWMTRRgpnyFm8f6sZdosWBjx93v": "8VpRhuxa7sUUepdY3kQiTmX9rS5vx4WgaXiAnXq4KCtr"
+//! }
+//! ```
+//!
+//! If [`SPL_LSTS_PATH_ENV`] is set, [`jupiter_amm_interface::Amm::from_keyed_account`]
+//! loads the mapping from the file at that path instead of the Sanctum LST list.
+//!
+//! SPL LSTs added to the pool after construction are skipped until their stake pools
+//! are known. Long-running AMMs should periodically call [`load_sanctum_spl_lsts`]
+//! and pass the result to [`InfAmm::refresh_spl_lsts`].
+//!
+//! The Sanctum LST list can be cached on disk between restarts, see [`crate::lst_list_cache`].
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs,
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+};
+
+use anyhow::{anyhow, Context, Result};
+use sanctum_lst_list::{PoolInfo, SanctumLst, SanctumLstList};
+use serde_json::Value;
+use solana_pubkey::Pubkey;
+
+use crate::{
+    config::LstListSource,
+    lst_list_cache::LstListCache,
+    mint_meta::{sanctum_mint_metas, MintMeta},
+    sanctum_lst_list::{load_lst_list_from, load_sanctum_lst_list},
+    InfAmm,
+};
+
+pub use crate::sanctum_lst_list::FetchPolicy;
+
+/// Env var of the path to the SPL LSTs file, see [`crate::spl_lsts`]
+pub const SPL_LSTS_PATH_ENV: &str = "INF_SPL_LSTS_PATH";
+
+/// Parses the contents of an SPL LSTs file, see [`crate::spl_lsts`] for the schema
+pub fn parse_spl_lsts_json(bytes: &[u8]) -> Result<HashMap<[u8; 32], [u8; 32]>> {
+    let Value::Object(entries) = serde_json::from_slice(bytes)? else {
+        return Err(anyhow!(
+            "Expected JSON object of mint to stake pool address"
+        ));
+    };
+    entries
+        .into_iter()
+        .map(|(mint, pool)| {
+            let pool = pool
+                .as_str()
+                .ok_or_else(|| anyhow!("Stake pool address of {mint} is not a string"))?;
+            Ok((
+                Pubkey::from_str(&mint)
+                    .with_context(|| format!("Invalid mint {mint}"))?
+                    .to_bytes(),
+                Pubkey::from_str(pool)
+                    .with_context(|| format!("Invalid stake pool address {pool}"))?
+                    .to_bytes(),
+            ))
+        })
+        .collect()
+}
+
+/// Loads the SPL LSTs file at `path`, see [`crate::spl_lsts`] for the schema
+pub fn load_spl_lsts_json(path: impl AsRef<Path>) -> Result<HashMap<[u8; 32], [u8; 32]>> {
+    let path = path.as_ref();
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_spl_lsts_json(&bytes)
+}
+
+/// Loads the SPL LSTs file at the path in [`SPL_LSTS_PATH_ENV`].
+///
+/// Returns `None` if the env var is not set.
+pub fn load_spl_lsts_from_env() -> Option<Result<HashMap<[u8; 32], [u8; 32]>>> {
+    let path = std::env::var_os(SPL_LSTS_PATH_ENV)?;
+    Some(load_spl_lsts_json(path))
+}
+
+/// Fetches the Sanctum LST list, falling back to the copy bundled with this crate
+/// if that fails or the `remote` feature is disabled, and returns the stake pools of its SPL LSTs.
+///
+/// Goes through the cache at [`crate::lst_list_cache::LST_LIST_CACHE_PATH_ENV`] if set
+#[inline]
+pub fn load_sanctum_spl_lsts() -> HashMap<[u8; 32], [u8; 32]> {
+    load_sanctum_spl_lsts_with(&FetchPolicy::DEFAULT)
+}
+
+/// Same as [`load_sanctum_spl_lsts`], with the retries and timeouts of `policy`
+pub fn load_sanctum_spl_lsts_with(policy: &FetchPolicy) -> HashMap<[u8; 32], [u8; 32]> {
+    match LstListCache::from_env() {
+        Some(cache) => load_cached_sanctum_spl_lsts(&cache.with_fetch_policy(*policy)),
+        None => sanctum_spl_lsts(&load_sanctum_lst_list(policy)),
+    }
+}
+
+/// Same as [`load_sanctum_spl_lsts`], but through `cache`, see [`LstListCache::load`]
+pub fn load_cached_sanctum_spl_lsts(cache: &LstListCache) -> HashMap<[u8; 32], [u8; 32]> {
+    sanctum_spl_lsts(&cache.load())
+}
+
+/// Same as [`load_sanctum_spl_lsts`], but only uses the copy of the Sanctum LST list
+/// bundled with this crate, without any network requests
+pub fn load_bundled_sanctum_spl_lsts() -> HashMap<[u8; 32], [u8; 32]> {
+    sanctum_spl_lsts(&SanctumLstList::load().data)
+}
+
+/// SPL LSTs used if none are provided: the file at [`SPL_LSTS_PATH_ENV`] if set,
+/// else those on the LST list of `source`.
+///
+/// [`LstListSource::Sanctum`] goes through `cache`, falling back to
+/// [`crate::lst_list_cache::LST_LIST_CACHE_PATH_ENV`] if `None`.
+/// If `offline`, no requests are made: the cached list is used regardless of its age,
+/// else the bundled list. [`LstListSource::Url`] loads no LSTs if `offline`.
+///
+/// `fetch_policy` applies to all requests, including those of the cache, if set.
+///
+/// Also returns the mint metadata of the LST list loaded, if any, see [`crate::mint_meta`]
+pub(crate) fn load_default_spl_lsts(
+    offline: bool,
+    cache: Option<LstListCache>,
+    source: &LstListSource,
+    fetch_policy: Option<FetchPolicy>,
+) -> Result<(
+    HashMap<[u8; 32], [u8; 32]>,
+    Option<HashMap<[u8; 32], MintMeta>>,
+)> {
+    if let Some(spl_lsts) = load_spl_lsts_from_env() {
+        return Ok((spl_lsts?, None));
+    }
+    let policy = fetch_policy.unwrap_or_default();
+    let cache = cache
+        .or_else(LstListCache::from_env)
+        .map(|c| match fetch_policy {
+            Some(p) => c.with_fetch_policy(p),
+            None => c,
+        });
+    let lsts = match source {
+        LstListSource::Sanctum => match (offline, cache) {
+            (true, Some(cache)) => cache
+                .read()
+                .map_or_else(|_| SanctumLstList::load().data, |c| c.lsts),
+            (true, None) => SanctumLstList::load().data,
+            (false, Some(cache)) => cache.load(),
+            (false, None) => load_sanctum_lst_list(&policy),
+        },
+        LstListSource::Url(url) => {
+            if offline {
+                return Ok((HashMap::new(), None));
+            }
+            load_lst_list_from(url, &policy)
+        }
+        LstListSource::None => return Ok((HashMap::new(), None)),
+    };
+    Ok((sanctum_spl_lsts(&lsts), Some(sanctum_mint_metas(&lsts))))
+}
+
+fn sanctum_spl_lsts(lsts: &[SanctumLst]) -> HashMap<[u8; 32], [u8; 32]> {
+    lsts.iter()
+        .filter_map(|SanctumLst { mint, pool, .. }| {
+            let stake_pool_address = match pool {
+                PoolInfo::Lido => return None,
+                PoolInfo::Marinade => return None,
+                PoolInfo::ReservePool => return None,
+                PoolInfo::SanctumSpl(spl_pool_accounts) => spl_pool_accounts.pool.to_bytes(),
+                PoolInfo::Spl(spl_pool_accounts) => spl_pool_accounts.pool.to_bytes(),
+                PoolInfo::SPool(_) => return None,
+                PoolInfo::SanctumSplMulti(spl_pool_accounts) => spl_pool_accounts.pool.to_bytes(),
+            };
+            Some((mint.to_bytes(), stake_pool_address))
+        })
+        .collect()
+}
+
+impl InfAmm {
+    /// Adds the entries of `spl_lsts` whose mints are not already known.
+    /// Stake pools of already known mints are never changed.
+    ///
+    /// Sol val calcs of added mints that are on the LST state list are initialized, so that
+    /// their accounts are included from the next [`jupiter_amm_interface::Amm::get_accounts_to_update`]
+    /// and they are quotable after the next update cycle.
+    ///
+    /// Returns the added mints.
+    pub fn refresh_spl_lsts(&mut self, spl_lsts: HashMap<[u8; 32], [u8; 32]>) -> Vec<[u8; 32]> {
+        let inner = Arc::make_mut(&mut self.inner);
+        let added: Vec<_> = spl_lsts
+            .into_iter()
+            .filter(|(mint, pool)| match inner.spl_lsts.entry(*mint) {
+                Entry::Occupied(_) => false,
+                Entry::Vacant(v) => {
+                    v.insert(*pool);
+                    true
+                }
+            })
+            .map(|(mint, _)| mint)
+            .collect();
+        if added.is_empty() {
+            return added;
+        }
+        // LST state list is as of the last update, or construction if none
+        let lst_states: Vec<_> = inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state())
+            .filter(|lst_state| added.contains(&lst_state.mint))
+            .collect();
+        lst_states.iter().for_each(|lst_state| {
+            // LSTs that still fail to init are skipped and retried on every update
+            let _: Result<_, _> = inner.try_get_or_init_lst_svc(lst_state);
+        });
+        added
+    }
+}
@@ -0,0 +1,65 @@
+//! One-shot quoting from a snapshot of accounts, without keeping an [`InfAmm`] around,
+//! e.g. for serverless pricing jobs that fetch all accounts up front.
+//!
+//! The snapshot must contain every account that [`jupiter_amm_interface::Amm::get_accounts_to_update`]
+//! would eventually request, i.e. the LST state list, pool state, LP token mint,
+//! pricing program accounts, and the reserves and sol val calc accounts of every LST.
+//! If it contains sysvar Clock, its epoch is checked against stake pools, see [`ClockMode::Sysvar`].
+//! Otherwise, stake pools are not checked for being up to date.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use inf1_std::{
+    inf1_ctl_core::keys::LST_STATE_LIST_ID,
+    inf1_svc_ag_std::inf1_svc_lido_core::solido_legacy_core::SYSVAR_CLOCK,
+};
+use jupiter_amm_interface::{AccountMap, Amm, KeyedAccount, Quote, QuoteParams};
+use solana_pubkey::Pubkey;
+
+use crate::{clock::ClockMode, InfAmm};
+
+/// Number of update cycles until the accounts to update no longer depend on
+/// previously applied accounts: LST state list and pool state, then everything else
+const UPDATE_CYCLES: usize = 2;
+
+impl InfAmm {
+    /// Constructs an [`InfAmm`] that is fully updated with `account_map`,
+    /// see [`crate::stateless`]. Does not make any network requests.
+    pub fn from_accounts(
+        account_map: &AccountMap,
+        spl_lsts: HashMap<[u8; 32], [u8; 32]>,
+    ) -> Result<Self> {
+        let key = Pubkey::new_from_array(LST_STATE_LIST_ID);
+        let account = account_map
+            .get(&key)
+            .ok_or_else(|| anyhow!("LST state list {key} missing from account map"))?;
+        let mut inf = Self::builder().spl_lsts(spl_lsts).build(&KeyedAccount {
+            key,
+            account: account.clone(),
+            params: None,
+        })?;
+        if account_map.contains_key(&Pubkey::new_from_array(SYSVAR_CLOCK)) {
+            inf = inf.with_clock_mode(ClockMode::Sysvar);
+        }
+
+        // earlier cycles may fail because the accounts to update
+        // were based on the defaults before the first update
+        for _ in 1..UPDATE_CYCLES {
+            let _: Result<_, _> = inf.update(account_map);
+        }
+        inf.update(account_map)?;
+        Ok(inf)
+    }
+}
+
+/// Quotes `quote_params` against the pool in `account_map`, see [`crate::stateless`].
+///
+/// Same as [`Amm::quote`] of [`InfAmm::from_accounts`].
+pub fn quote_from_accounts(
+    account_map: &AccountMap,
+    quote_params: &QuoteParams,
+    spl_lsts: HashMap<[u8; 32], [u8; 32]>,
+) -> Result<Quote> {
+    InfAmm::from_accounts(account_map, spl_lsts)?.quote(quote_params)
+}
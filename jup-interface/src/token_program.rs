@@ -0,0 +1,118 @@
+//! Token programs of LST mints and the LP token mint, for Token-2022 LSTs.
+//!
+//! A mint's owner program never changes, so
+//! [`Amm::get_accounts_to_update`](jupiter_amm_interface::Amm::get_accounts_to_update)
+//! includes each LST mint only until its token program is recorded by an update.
+//! The LP token mint is fetched in every update regardless.
+//!
+//! The account metas of INF instructions are cross-checked against the recorded token
+//! programs: the token program accounts, and the reserves and protocol fee accumulators,
+//! which are associated token accounts derived with them, are replaced with those of
+//! the mint's actual token program if they differ.
+
+use inf1_std::{inf1_ctl_core::keys::POOL_STATE_ID, inf1_pp_core::pair::Pair};
+use solana_instruction::AccountMeta;
+
+use crate::{
+    lst_info::TOKEN_PROGRAMS,
+    pair_update::in_scope,
+    pda::{find_ata, protocol_fee},
+    trade_kind::TradeKind,
+    update::UpdateMapWithOwner,
+    InfAmm,
+};
+
+/// Indices of the token program accounts in the account metas of INF instructions:
+/// - `SwapExactIn`, `SwapExactOut`: input LST's, then output LST's
+/// - `AddLiquidity`, `RemoveLiquidity`: LST's, then LP token's
+///
+/// Changes to these must bump [`crate::consts::META_LAYOUT_VERSION`]
+const TOKEN_PROGRAM_META_INDICES: [usize; 2] = [6, 7];
+
+impl InfAmm {
+    /// Owner program of `mint` as of the last update that fetched it,
+    /// `None` if `mint` has not been fetched yet
+    #[inline]
+    pub fn token_program_of(&self, mint: &[u8; 32]) -> Option<&[u8; 32]> {
+        self.token_programs.get(mint)
+    }
+
+    /// LST mints whose token program is not known yet,
+    /// to append to [`jupiter_amm_interface::Amm::get_accounts_to_update`]
+    pub(crate) fn mints_to_update(&self, scope: Option<&[[u8; 32]]>) -> Vec<[u8; 32]> {
+        self.inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state().mint)
+            .filter(|mint| in_scope(scope, mint) && !self.token_programs.contains_key(mint))
+            .collect()
+    }
+
+    /// Records the owners of LST mints in `scope` and the LP token mint in `fetched`
+    /// that are token programs.
+    ///
+    /// Should only be called after a successful update.
+    pub(crate) fn record_token_programs(
+        &mut self,
+        fetched: &impl UpdateMapWithOwner,
+        scope: Option<&[[u8; 32]]>,
+    ) {
+        let mints: Vec<_> = self
+            .inner
+            .try_lst_state_list()
+            .unwrap_or_default()
+            .iter()
+            .map(|l| l.into_lst_state().mint)
+            .filter(|mint| in_scope(scope, mint))
+            .chain([self.inner.pool.lp_token_mint])
+            .collect();
+        for mint in mints {
+            if let Some(owner) = fetched
+                .get_owner(&mint)
+                .filter(|owner| TOKEN_PROGRAMS.contains(owner))
+            {
+                self.token_programs.insert(mint, owner);
+            }
+        }
+    }
+
+    /// Replaces accounts in `metas`, the mainnet account metas of the INF instruction
+    /// for `pair`, that were derived with the wrong token program, see [`crate::token_program`]
+    pub(crate) fn fix_token_program_metas(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        metas: &mut [AccountMeta],
+    ) {
+        let mints = match self.trade_type(pair.inp, pair.out) {
+            TradeKind::Swap | TradeKind::AddLiquidity => [pair.inp, pair.out],
+            TradeKind::RemoveLiquidity => [pair.out, pair.inp],
+        };
+        for (i, mint) in TOKEN_PROGRAM_META_INDICES.into_iter().zip(mints) {
+            let Some(token_program) = self.token_programs.get(mint) else {
+                continue;
+            };
+            for wrong_program in TOKEN_PROGRAMS.iter().filter(|p| *p != token_program) {
+                for ata_owner in [Some(POOL_STATE_ID), protocol_fee()].into_iter().flatten() {
+                    let (Some(wrong), Some(right)) = (
+                        find_ata(&ata_owner, wrong_program, mint),
+                        find_ata(&ata_owner, token_program, mint),
+                    ) else {
+                        continue;
+                    };
+                    metas
+                        .iter_mut()
+                        .filter(|m| *m.pubkey.as_array() == wrong)
+                        .for_each(|m| m.pubkey = right.into());
+                }
+            }
+            // only replaced if it is a token program, in case the layout changes
+            if let Some(meta) = metas
+                .get_mut(i)
+                .filter(|m| TOKEN_PROGRAMS.contains(m.pubkey.as_array()))
+            {
+                meta.pubkey = (*token_program).into();
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Classification of pairs into the INF instruction they trade with,
+//! without quoting or building the instruction, e.g. for compute budget
+//! and account count estimation during routing.
+
+use crate::{native_sol::native_sol_to_wsol, InfAmm};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TradeKind {
+    /// `SwapExactIn` or `SwapExactOut` between 2 LSTs, depending on swap mode
+    Swap,
+
+    /// `AddLiquidity`, LST to LP token. ExactIn only.
+    AddLiquidity,
+
+    /// `RemoveLiquidity`, LP token to LST. ExactIn only.
+    RemoveLiquidity,
+}
+
+impl TradeKind {
+    /// See [`InfAmm::supports_exact_out_for_pair`]
+    #[inline]
+    pub const fn supports_exact_out(&self) -> bool {
+        matches!(self, Self::Swap)
+    }
+}
+
+impl InfAmm {
+    /// The instruction that [`jupiter_amm_interface::Amm::quote`] and
+    /// [`jupiter_amm_interface::Amm::get_swap_and_account_metas`] dispatch to
+    /// for `inp_mint` -> `out_mint`.
+    ///
+    /// Only depends on the LP token mint, so does not check whether the pair can
+    /// actually be traded, e.g. whether both mints are on the LST state list.
+    /// [`crate::consts::NATIVE_SOL_MINT_ADDR`] is classified as wSOL, see [`crate::native_sol`].
+    #[inline]
+    pub fn trade_type(&self, inp_mint: &[u8; 32], out_mint: &[u8; 32]) -> TradeKind {
+        let [inp, out] = [inp_mint, out_mint].map(native_sol_to_wsol);
+        let lp_mint = &self.inner.pool.lp_token_mint;
+        if out == lp_mint {
+            TradeKind::AddLiquidity
+        } else if inp == lp_mint {
+            TradeKind::RemoveLiquidity
+        } else {
+            TradeKind::Swap
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Building complete, ready-to-sign transactions for use outside of jupiter:
+//! quote -> slippage limit -> compute budget -> ATAs -> INF instruction -> versioned transaction.
+//!
+//! The compute unit limit defaults to [`InfAmm::estimated_compute_units`] of the trade,
+//! plus [`CREATE_ATA_CU`] for each account of [`InfAmm::required_atas`], which are
+//! created idempotently. Token programs are those recorded by the last update,
+//! see [`InfAmm::token_program_of`].
+//!
+//! Wrapping and unwrapping of native SOL is not handled,
+//! the user's wSOL token account must already hold the input amount.
+
+use anyhow::{anyhow, Result};
+use jupiter_amm_interface::{Quote, QuoteParams, SwapMode, SwapParams};
+use solana_pubkey::Pubkey;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    message::{v0, AddressLookupTableAccount, VersionedMessage},
+    transaction::VersionedTransaction,
+};
+
+pub use crate::slippage::slippage_limit;
+use crate::{pda::find_ata, slippage::SlippageQuote, InfAmm};
+
+/// Compute units budgeted for each associated token program `CreateIdempotent`
+/// instruction, enough to create a Token-2022 account
+pub const CREATE_ATA_CU: u32 = 30_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxQuoteRequest {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+
+    /// Input amount for [`SwapMode::ExactIn`], output amount for [`SwapMode::ExactOut`]
+    pub amount: u64,
+    pub swap_mode: SwapMode,
+    pub slippage_bps: u16,
+
+    /// Defaults to the estimate of the transaction's instructions, see [`crate::tx`]
+    pub compute_unit_limit: Option<u32>,
+
+    /// Priority fee. No compute unit price instruction is added if 0.
+    pub compute_unit_price_micro_lamports: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BuiltTx {
+    /// The quote that the slippage limit was computed from
+    pub quote: Quote,
+
+    /// Signatures are all default, the only required signer is `payer`
+    pub tx: VersionedTransaction,
+}
+
+impl InfAmm {
+    /// Quotes `quote_request` and builds a v0 transaction that trades between
+    /// `payer`'s ATAs of the input and output mints, creating them and the protocol fee
+    /// accumulators of the trade if they do not exist yet, see [`Self::required_atas`].
+    ///
+    /// Errs if the token program of either mint has not been recorded yet.
+    ///
+    /// `luts` are used to compress the message's account keys, and may be empty.
+    pub fn build_versioned_tx(
+        &self,
+        quote_request: &TxQuoteRequest,
+        payer: &Pubkey,
+        blockhash: Hash,
+        luts: &[AddressLookupTableAccount],
+    ) -> Result<BuiltTx> {
+        let TxQuoteRequest {
+            input_mint,
+            output_mint,
+            amount,
+            swap_mode,
+            slippage_bps,
+            compute_unit_limit,
+            compute_unit_price_micro_lamports,
+        } = *quote_request;
+
+        let SlippageQuote {
+            quote,
+            min_out_amount,
+            max_in_amount,
+            ..
+        } = self.quote_with_slippage(
+            &QuoteParams {
+                amount,
+                input_mint,
+                output_mint,
+                swap_mode,
+            },
+            slippage_bps,
+        )?;
+
+        let source_token_account = self.user_ata(payer, &input_mint)?;
+        let destination_token_account = self.user_ata(payer, &output_mint)?;
+        let swap_params = SwapParams {
+            swap_mode,
+            in_amount: max_in_amount,
+            out_amount: min_out_amount,
+            source_mint: input_mint,
+            destination_mint: output_mint,
+            source_token_account,
+            destination_token_account,
+            token_transfer_authority: *payer,
+            // dont-cares, not used by InfAmm
+            quote_mint_to_referrer: None,
+            jupiter_program_id: &Pubkey::default(),
+            missing_dynamic_accounts_as_default: false,
+        };
+        let create_atas = self.required_atas(&swap_params)?;
+        let swap_ix = self.swap_instruction(&swap_params)?;
+
+        let compute_unit_limit = compute_unit_limit.unwrap_or_else(|| {
+            let [inp, out] = [input_mint, output_mint].map(|mint| mint.to_bytes());
+            self.estimated_compute_units(&inp, &out, self.trade_type(&inp, &out))
+                + CREATE_ATA_CU * create_atas.len() as u32
+        });
+        let payer_bytes = payer.to_bytes();
+        let ixs: Vec<_> = [ComputeBudgetInstruction::set_compute_unit_limit(
+            compute_unit_limit,
+        )]
+        .into_iter()
+        .chain((compute_unit_price_micro_lamports > 0).then(|| {
+            ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price_micro_lamports)
+        }))
+        .chain(
+            create_atas
+                .iter()
+                .map(|(_, create_ata)| create_ata.to_instruction(&payer_bytes)),
+        )
+        .chain([swap_ix])
+        .collect();
+
+        let message = v0::Message::try_compile(payer, &ixs, luts, blockhash)
+            .map_err(|e| anyhow!("v0::Message::try_compile failed: {e}"))?;
+        let message = VersionedMessage::V0(message);
+        let tx = VersionedTransaction {
+            signatures: vec![
+                Default::default();
+                usize::from(message.header().num_required_signatures)
+            ],
+            message,
+        };
+        Ok(BuiltTx { quote, tx })
+    }
+
+    /// `owner`'s ATA for `mint`, with the token program recorded by the last update
+    fn user_ata(&self, owner: &Pubkey, mint: &Pubkey) -> Result<Pubkey> {
+        let token_program = self
+            .token_program_of(mint.as_array())
+            .ok_or_else(|| anyhow!("Token program of {mint} not recorded yet"))?;
+        find_ata(owner.as_array(), token_program, mint.as_array())
+            .map(Pubkey::new_from_array)
+            .ok_or_else(|| anyhow!("ATA of {mint} not found"))
+    }
+}
@@ -0,0 +1,192 @@
+//! [`Pubkey`]-typed layer over the mint-keyed APIs of [`InfAmm`],
+//! which take and return `[u8; 32]` like [`inf1_std`] does.
+//!
+//! [`LstMint`] and [`LstPair`] make it harder to mix up input and output mints
+//! and other pubkeys, and spare callers from converting between `[u8; 32]` and [`Pubkey`].
+//! Access through [`InfAmm::typed`]:
+//!
+//! ```ignore
+//! let preview = inf.typed().preview_add_liquidity(&LstMint::WSOL, 1_000_000_000)?;
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    consts::{INF_MINT_ADDR, NATIVE_SOL_MINT_ADDR, WSOL_MINT_ADDR},
+    crank::StaleStakePool,
+    depth::DepthPoint,
+    err::InfAmmErr,
+    health::LstHealth,
+    liquidity::LiquidityPreview,
+    lst_info::LstInfo,
+    route::RoutedQuote,
+    InfAmm,
+};
+
+/// Mint of an LST on the LST state list, or of the LP token
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct LstMint(pub Pubkey);
+
+impl LstMint {
+    pub const INF: Self = Self(Pubkey::new_from_array(INF_MINT_ADDR));
+
+    pub const WSOL: Self = Self(Pubkey::new_from_array(WSOL_MINT_ADDR));
+
+    /// See [`crate::native_sol`]
+    pub const NATIVE_SOL: Self = Self(Pubkey::new_from_array(NATIVE_SOL_MINT_ADDR));
+
+    #[inline]
+    pub fn as_array(&self) -> &[u8; 32] {
+        self.0.as_array()
+    }
+}
+
+impl From<Pubkey> for LstMint {
+    #[inline]
+    fn from(pk: Pubkey) -> Self {
+        Self(pk)
+    }
+}
+
+impl From<[u8; 32]> for LstMint {
+    #[inline]
+    fn from(pk: [u8; 32]) -> Self {
+        Self(Pubkey::new_from_array(pk))
+    }
+}
+
+impl From<LstMint> for Pubkey {
+    #[inline]
+    fn from(LstMint(pk): LstMint) -> Self {
+        pk
+    }
+}
+
+impl Display for LstMint {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LstPair {
+    pub inp: LstMint,
+    pub out: LstMint,
+}
+
+impl LstPair {
+    #[inline]
+    pub const fn new(inp: LstMint, out: LstMint) -> Self {
+        Self { inp, out }
+    }
+
+    /// The `[u8; 32]` pair taken by [`InfAmm`]'s methods
+    #[inline]
+    pub fn as_pair(&self) -> Pair<&[u8; 32]> {
+        Pair {
+            inp: self.inp.as_array(),
+            out: self.out.as_array(),
+        }
+    }
+}
+
+/// See [`crate::typed`]
+#[derive(Debug, Clone, Copy)]
+pub struct TypedInfAmm<'a>(pub &'a InfAmm);
+
+impl InfAmm {
+    /// See [`crate::typed`]
+    #[inline]
+    pub const fn typed(&self) -> TypedInfAmm<'_> {
+        TypedInfAmm(self)
+    }
+}
+
+impl TypedInfAmm<'_> {
+    /// See [`InfAmm::quote_raw`]. Quotes at [`InfAmm::epoch`].
+    ///
+    /// Returns `(fee_mint, quote)`
+    pub fn quote(
+        &self,
+        pair: &LstPair,
+        amount: u64,
+        limit_ty: TradeLimitTy,
+    ) -> Result<(LstMint, inf1_std::quote::Quote), InfAmmErr> {
+        self.0
+            .quote_raw(&pair.as_pair(), amount, limit_ty, self.0.try_epoch()?)
+            .map(|(fee_mint, quote)| (fee_mint.into(), quote))
+    }
+
+    /// See [`InfAmm::quote_best_route`]
+    #[inline]
+    pub fn quote_best_route(&self, pair: &LstPair, amount: u64) -> Result<RoutedQuote, InfAmmErr> {
+        self.0.quote_best_route(&pair.as_pair(), amount)
+    }
+
+    /// See [`InfAmm::supports_exact_out_for_pair`]
+    #[inline]
+    pub fn supports_exact_out_for_pair(&self, pair: &LstPair) -> bool {
+        self.0.supports_exact_out_for_pair(&pair.as_pair())
+    }
+
+    /// See [`InfAmm::quotable_mints`]
+    #[inline]
+    pub fn quotable_mints(&self) -> Vec<LstMint> {
+        self.0.quotable_mints().into_iter().map(LstMint).collect()
+    }
+
+    /// See [`InfAmm::depth`]
+    #[inline]
+    pub fn depth(
+        &self,
+        pair: &LstPair,
+        max_amount: u64,
+        steps: u64,
+    ) -> Result<Vec<DepthPoint>, InfAmmErr> {
+        self.0
+            .depth(pair.inp.as_array(), pair.out.as_array(), max_amount, steps)
+    }
+
+    /// See [`InfAmm::preview_add_liquidity`]
+    #[inline]
+    pub fn preview_add_liquidity(
+        &self,
+        mint: &LstMint,
+        amount: u64,
+    ) -> Result<LiquidityPreview, InfAmmErr> {
+        self.0.preview_add_liquidity(mint.as_array(), amount)
+    }
+
+    /// See [`InfAmm::preview_remove_liquidity`]
+    #[inline]
+    pub fn preview_remove_liquidity(
+        &self,
+        mint: &LstMint,
+        amount: u64,
+    ) -> Result<LiquidityPreview, InfAmmErr> {
+        self.0.preview_remove_liquidity(mint.as_array(), amount)
+    }
+
+    /// See [`InfAmm::lst_info`]
+    #[inline]
+    pub fn lst_info(&self, mint: &LstMint, token_program: &Pubkey) -> Option<LstInfo> {
+        self.0.lst_info(mint.as_array(), token_program.as_array())
+    }
+
+    /// See [`InfAmm::lst_health`]
+    #[inline]
+    pub fn lst_health(&self, mint: &LstMint) -> Option<&LstHealth> {
+        self.0.lst_health(mint.as_array())
+    }
+
+    /// See [`InfAmm::stale_spl_stake_pools`]
+    #[inline]
+    pub fn stale_spl_stake_pools(&self, pair: &LstPair) -> Vec<StaleStakePool> {
+        self.0.stale_spl_stake_pools(&pair.as_pair())
+    }
+}
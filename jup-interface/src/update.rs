@@ -1,7 +1,25 @@
-use inf1_std::update::{Account, UpdateMap};
+use std::{collections::HashMap, sync::OnceLock};
+
+use inf1_std::{
+    err::InfErr,
+    update::{Account, UpdateErr, UpdateMap},
+};
 use jupiter_amm_interface::AccountMap;
+use solana_account_decoder_client_types::UiAccount;
 use solana_pubkey::Pubkey;
 
+use crate::{
+    config::{DeploymentPdas, InfAmmConfig},
+    err::{acc_deser_pk, AccDeserErr, FmtErr},
+    validate::known_min_len,
+};
+
+/// An [`UpdateMap`] that can also return the owners of its accounts,
+/// which [`crate::InfAmm::update_with`] records in [`crate::health`]
+pub trait UpdateMapWithOwner: UpdateMap {
+    fn get_owner(&self, pk: &[u8; 32]) -> Option<[u8; 32]>;
+}
+
 #[derive(Debug, Clone, Copy)]
 #[repr(transparent)]
 pub(crate) struct AccountRef<'a>(pub &'a solana_account::Account);
@@ -28,3 +46,172 @@ impl UpdateMap for AccountMapRef<'_> {
         self.0.get(&Pubkey::new_from_array(*pk)).map(AccountRef)
     }
 }
+
+impl UpdateMapWithOwner for AccountMapRef<'_> {
+    #[inline]
+    fn get_owner(&self, pk: &[u8; 32]) -> Option<[u8; 32]> {
+        self.0
+            .get(&Pubkey::new_from_array(*pk))
+            .map(|acc| acc.owner.to_bytes())
+    }
+}
+
+/// [`UpdateMap`] that looks up this deployment's addresses
+/// in place of mainnet's, see [`crate::config`]
+#[derive(Debug)]
+pub(crate) struct DeploymentAccountMapRef<'a, M> {
+    pub map: &'a M,
+    pub config: &'a InfAmmConfig,
+    pub pdas: &'a DeploymentPdas,
+}
+
+impl<M> Clone for DeploymentAccountMapRef<'_, M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for DeploymentAccountMapRef<'_, M> {}
+
+impl<M: UpdateMap> UpdateMap for DeploymentAccountMapRef<'_, M> {
+    type Account<'acc>
+        = M::Account<'acc>
+    where
+        Self: 'acc;
+
+    #[inline]
+    fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+        self.map
+            .get_account(self.pdas.to_deployment(self.config, pk))
+    }
+}
+
+impl<M: UpdateMapWithOwner> UpdateMapWithOwner for DeploymentAccountMapRef<'_, M> {
+    #[inline]
+    fn get_owner(&self, pk: &[u8; 32]) -> Option<[u8; 32]> {
+        self.map.get_owner(self.pdas.to_deployment(self.config, pk))
+    }
+}
+
+/// Converts an update error into an [`anyhow::Error`], replacing `AccDeser` errors
+/// with an [`AccDeserErr`] that reports the data length and owner of the account in `fetched`.
+///
+/// `fetched` must be keyed by the same addresses as the error,
+/// i.e. mainnet addresses for [`DeploymentAccountMapRef`].
+pub(crate) fn update_err_with_ctx<M: UpdateMapWithOwner>(
+    fetched: &M,
+    lp_token_mint: &[u8; 32],
+    e: UpdateErr<InfErr>,
+) -> anyhow::Error {
+    let deser_pk = match &e {
+        UpdateErr::Inner(inner) => acc_deser_pk(inner),
+        UpdateErr::AccMissing { .. } => None,
+    };
+    match deser_pk {
+        Some(pk) => acc_deser_err(fetched, lp_token_mint, pk).into(),
+        None => FmtErr(e).into(),
+    }
+}
+
+pub(crate) fn acc_deser_err<M: UpdateMapWithOwner>(
+    fetched: &M,
+    lp_token_mint: &[u8; 32],
+    pk: [u8; 32],
+) -> AccDeserErr {
+    AccDeserErr {
+        pk,
+        expected_len: known_min_len(&pk, lp_token_mint),
+        actual_len: fetched.get_account(&pk).map(|acc| acc.data().len()),
+        owner: fetched.get_owner(&pk),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct UiAccountDataRef<'a>(pub &'a [u8]);
+
+impl Account for UiAccountDataRef<'_> {
+    #[inline]
+    fn data(&self) -> &[u8] {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LazyUiAccount {
+    account: UiAccount,
+
+    /// `None` if `account.data` failed to decode, e.g. `jsonParsed`
+    data: OnceLock<Option<Vec<u8>>>,
+}
+
+/// [`UpdateMap`] over [`UiAccount`]s, e.g. as stored by indexers or
+/// returned by RPC, for use with [`crate::InfAmm::update_with`].
+///
+/// Account data is only decoded the first time it is looked up, and then cached.
+/// Data is decoded with [`solana_account_decoder_client_types::UiAccountData::decode`],
+/// so base58 and base64 are supported. Accounts whose data fails to decode,
+/// e.g. `jsonParsed`, are treated as missing.
+#[derive(Debug, Clone, Default)]
+pub struct UiAccountMap(HashMap<Pubkey, LazyUiAccount>);
+
+impl UiAccountMap {
+    #[inline]
+    pub fn get_ui_account(&self, pk: &Pubkey) -> Option<&UiAccount> {
+        self.0.get(pk).map(|l| &l.account)
+    }
+}
+
+impl FromIterator<(Pubkey, UiAccount)> for UiAccountMap {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (Pubkey, UiAccount)>>(iter: T) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(pk, account)| {
+                    (
+                        pk,
+                        LazyUiAccount {
+                            account,
+                            data: OnceLock::new(),
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<HashMap<Pubkey, UiAccount>> for UiAccountMap {
+    #[inline]
+    fn from(accounts: HashMap<Pubkey, UiAccount>) -> Self {
+        accounts.into_iter().collect()
+    }
+}
+
+impl UpdateMap for UiAccountMap {
+    type Account<'acc>
+        = UiAccountDataRef<'acc>
+    where
+        Self: 'acc;
+
+    #[inline]
+    fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+        let LazyUiAccount { account, data } = self.0.get(&Pubkey::new_from_array(*pk))?;
+        data.get_or_init(|| account.data.decode())
+            .as_deref()
+            .map(UiAccountDataRef)
+    }
+}
+
+impl UpdateMapWithOwner for UiAccountMap {
+    #[inline]
+    fn get_owner(&self, pk: &[u8; 32]) -> Option<[u8; 32]> {
+        let owner: Pubkey = self
+            .get_ui_account(&Pubkey::new_from_array(*pk))?
+            .owner
+            .parse()
+            .ok()?;
+        Some(owner.to_bytes())
+    }
+}
@@ -0,0 +1,120 @@
+//! Dry-run validation of an [`AccountMap`] before [`Amm::update`], for operators that feed
+//! accounts from heterogeneous sources and want to reject and retry bad snapshots
+//! instead of mutating AMM state with them.
+//!
+//! Only obvious problems are detected: an [`AccountMap`] that passes validation
+//! may still fail to update, e.g. if an account is from before a layout change.
+
+use inf1_std::{
+    inf1_ctl_core::keys::{LST_STATE_LIST_ID, POOL_STATE_ID},
+    inf1_svc_ag_std::inf1_svc_lido_core::solido_legacy_core::SYSVAR_CLOCK,
+};
+use jupiter_amm_interface::{AccountMap, Amm};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+
+use crate::{lst_info::TOKEN_PROGRAMS, lst_state_list_format, InfAmm};
+
+const SYSVAR_OWNER: [u8; 32] =
+    Pubkey::from_str_const("Sysvar1111111111111111111111111111111111111").to_bytes();
+
+/// Pool state data up to and including `lp_token_mint`,
+/// the last field read by [`InfAmm`]
+const POOL_STATE_MIN_LEN: usize = 176;
+
+const MINT_MIN_LEN: usize = 82;
+
+const CLOCK_MIN_LEN: usize = 40;
+
+/// Minimum data length of `pk`, a mainnet address, if it is of a kind known to this crate
+pub(crate) fn known_min_len(pk: &[u8; 32], lp_token_mint: &[u8; 32]) -> Option<usize> {
+    match *pk {
+        POOL_STATE_ID => Some(POOL_STATE_MIN_LEN),
+        SYSVAR_CLOCK => Some(CLOCK_MIN_LEN),
+        mint if mint == *lp_token_mint => Some(MINT_MIN_LEN),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum MissingOrStale {
+    /// In [`Amm::get_accounts_to_update`] but not in the [`AccountMap`]
+    Missing {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pubkey"))]
+        pk: Pubkey,
+    },
+
+    /// Owner is not a program that owns accounts of this kind.
+    ///
+    /// Only checked for the pool state, the LST state list, the LP token mint and sysvar Clock.
+    WrongOwner {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pubkey"))]
+        pk: Pubkey,
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pubkey"))]
+        owner: Pubkey,
+    },
+
+    /// Data is shorter than the minimum for accounts of this kind,
+    /// or empty for accounts of unknown kind
+    Truncated {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pubkey"))]
+        pk: Pubkey,
+        len: usize,
+        min_len: usize,
+    },
+
+    /// LST state list data is not in any of the configured layouts,
+    /// see [`crate::lst_state_list_format`]
+    MalformedLstStateList {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::ser::pubkey"))]
+        pk: Pubkey,
+        len: usize,
+    },
+}
+
+impl InfAmm {
+    /// Checks `account_map` for obvious problems that would make [`Amm::update`] fail
+    /// or apply bad data, without updating.
+    ///
+    /// Returns an empty vec if none are found.
+    pub fn validate_account_map(&self, account_map: &AccountMap) -> Vec<MissingOrStale> {
+        self.get_accounts_to_update()
+            .into_iter()
+            .filter_map(|pk| match account_map.get(&pk) {
+                None => Some(MissingOrStale::Missing { pk }),
+                Some(acc) => self.validate_account(pk, acc),
+            })
+            .collect()
+    }
+
+    fn validate_account(&self, pk: Pubkey, acc: &Account) -> Option<MissingOrStale> {
+        let mainnet_pk = *self.to_mainnet(pk.as_array());
+        let owner = acc.owner.to_bytes();
+        let (is_owner_ok, min_len) = match mainnet_pk {
+            POOL_STATE_ID => (owner == self.config.program_id, POOL_STATE_MIN_LEN),
+            LST_STATE_LIST_ID => (owner == self.config.program_id, 0),
+            SYSVAR_CLOCK => (owner == SYSVAR_OWNER, CLOCK_MIN_LEN),
+            mint if mint == self.inner.pool.lp_token_mint => {
+                (TOKEN_PROGRAMS.contains(&owner), MINT_MIN_LEN)
+            }
+            _ => (true, 1),
+        };
+        let len = acc.data.len();
+        if !is_owner_ok {
+            Some(MissingOrStale::WrongOwner {
+                pk,
+                owner: acc.owner,
+            })
+        } else if len < min_len {
+            Some(MissingOrStale::Truncated { pk, len, min_len })
+        } else if mainnet_pk == LST_STATE_LIST_ID
+            && lst_state_list_format::to_packed(self.config.lst_state_list_formats, &acc.data)
+                .is_err()
+        {
+            Some(MissingOrStale::MalformedLstStateList { pk, len })
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! The accounts that a trade uses, e.g. for transaction size estimation,
+//! pre-simulation, building address lookup tables per pair,
+//! or for compliance systems that whitelist writable accounts per venue
+
+use std::iter::once;
+
+use anyhow::Result;
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use jupiter_amm_interface::SwapParams;
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{trade_limit_ty_to_swap_mode, InfAmm};
+
+/// Placeholders for user accounts, which are not known until swap time
+pub(crate) const SIGNER_PLACEHOLDER: Pubkey = Pubkey::new_from_array([u8::MAX; 32]);
+pub(crate) const INP_TOKEN_ACC_PLACEHOLDER: Pubkey = Pubkey::new_from_array([u8::MAX - 1; 32]);
+pub(crate) const OUT_TOKEN_ACC_PLACEHOLDER: Pubkey = Pubkey::new_from_array([u8::MAX - 2; 32]);
+
+/// The user's accounts in a trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UserAccs {
+    /// Token transfer authority
+    pub signer: Pubkey,
+    pub inp_token_acc: Pubkey,
+    pub out_token_acc: Pubkey,
+}
+
+impl UserAccs {
+    /// Distinct placeholders for when the user is not known yet,
+    /// none of which can be an actual account
+    pub const PLACEHOLDERS: Self = Self {
+        signer: SIGNER_PLACEHOLDER,
+        inp_token_acc: INP_TOKEN_ACC_PLACEHOLDER,
+        out_token_acc: OUT_TOKEN_ACC_PLACEHOLDER,
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WritableAcc {
+    /// The user's token account of the input mint
+    UserInpTokenAcc,
+
+    /// The user's token account of the output mint
+    UserOutTokenAcc,
+
+    /// An account that is the same for all users, e.g. pool state, LST reserves,
+    /// protocol fee accumulators and the LP token mint
+    Pool(Pubkey),
+}
+
+impl InfAmm {
+    /// Returns the accounts that the INF instruction for `pair` marks writable, sorted and deduped.
+    ///
+    /// Only depends on `pair` and the current LST state list,
+    /// not on amounts or the order of accounts in the instruction.
+    ///
+    /// Errs under the same conditions as [`Self::swap_instruction`].
+    pub fn writable_accounts_for(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        trade_ty: TradeLimitTy,
+    ) -> Result<Vec<WritableAcc>> {
+        let metas = self.placeholder_metas(pair, trade_ty)?;
+        let mut res: Vec<_> = metas
+            .into_iter()
+            .filter(|m| m.is_writable && m.pubkey != SIGNER_PLACEHOLDER)
+            .map(|m| match m.pubkey {
+                INP_TOKEN_ACC_PLACEHOLDER => WritableAcc::UserInpTokenAcc,
+                OUT_TOKEN_ACC_PLACEHOLDER => WritableAcc::UserOutTokenAcc,
+                pk => WritableAcc::Pool(pk),
+            })
+            .collect();
+        res.sort_unstable();
+        res.dedup();
+        Ok(res)
+    }
+
+    /// Returns the number of accounts in [`jupiter_amm_interface::Amm::get_swap_and_account_metas`]
+    /// for `pair`, including the INF program and duplicates.
+    ///
+    /// Unlike [`jupiter_amm_interface::Amm::get_accounts_len`], which must hold for all pairs,
+    /// this is exact, e.g. for fitting more legs into a transaction during route packing.
+    ///
+    /// Errs under the same conditions as [`Self::swap_instruction`].
+    #[inline]
+    pub fn accounts_len_for_pair(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        trade_ty: TradeLimitTy,
+    ) -> Result<usize> {
+        // + 1 for INF program
+        Ok(self.placeholder_metas(pair, trade_ty)?.len() + 1)
+    }
+
+    /// Returns the account metas of [`jupiter_amm_interface::Amm::get_swap_and_account_metas`]
+    /// for `pair`, including the INF program, with `user`'s accounts, before amounts are known.
+    ///
+    /// With [`UserAccs::PLACEHOLDERS`], every other account is the same for all users,
+    /// e.g. for building and caching an address lookup table per pair.
+    ///
+    /// Errs under the same conditions as [`Self::swap_instruction`].
+    #[inline]
+    pub fn accounts_for_pair(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        trade_ty: TradeLimitTy,
+        user: &UserAccs,
+    ) -> Result<Vec<AccountMeta>> {
+        Ok(once(AccountMeta::new_readonly(
+            self.config.program_id.into(),
+            false,
+        ))
+        .chain(self.user_metas(pair, trade_ty, user)?)
+        .collect())
+    }
+
+    /// Account metas of the INF instruction for `pair`, without the INF program,
+    /// with placeholders for user accounts
+    #[inline]
+    pub(crate) fn placeholder_metas(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        trade_ty: TradeLimitTy,
+    ) -> Result<Vec<AccountMeta>> {
+        self.user_metas(pair, trade_ty, &UserAccs::PLACEHOLDERS)
+    }
+
+    fn user_metas(
+        &self,
+        pair: &Pair<&[u8; 32]>,
+        trade_ty: TradeLimitTy,
+        UserAccs {
+            signer,
+            inp_token_acc,
+            out_token_acc,
+        }: &UserAccs,
+    ) -> Result<Vec<AccountMeta>> {
+        let (_, metas) = self.trade_ix_args_and_metas(&SwapParams {
+            swap_mode: trade_limit_ty_to_swap_mode(trade_ty),
+            source_mint: Pubkey::new_from_array(*pair.inp),
+            destination_mint: Pubkey::new_from_array(*pair.out),
+            source_token_account: *inp_token_acc,
+            destination_token_account: *out_token_acc,
+            token_transfer_authority: *signer,
+            // dont-cares, accounts do not depend on amounts
+            in_amount: 0,
+            out_amount: 0,
+            quote_mint_to_referrer: None,
+            jupiter_program_id: &Pubkey::default(),
+            missing_dynamic_accounts_as_default: false,
+        })?;
+        Ok(metas)
+    }
+}
@@ -50,7 +50,7 @@ pub type SwapUserKeyedAccounts = SwapUserAccs<(Pubkey, Account)>;
 /// - inits Amm struct
 /// - runs 2x update cycle
 /// - quote
-/// - swap
+/// - swap, via [`InfAmm::swap_instruction`]
 /// - mollusk execute swap
 /// - assert amount in and out matches quote
 pub fn swap_test(
@@ -58,45 +58,32 @@ pub fn swap_test(
     onchain_state: &HashMap<Pubkey, Account>,
     user: SwapUserKeyedAccounts,
 ) {
-    // init
-    let (key, account) = onchain_state
-        .get_key_value(&LST_STATE_LIST_ID.into())
-        .unwrap();
-    let mut inf = InfAmm::new(
-        &KeyedAccount {
-            key: *key,
-            account: account.clone(),
-            params: None,
-        },
-        &AMM_CONTEXT,
-        SPL_LSTS.into_iter().collect(),
-    )
-    .unwrap();
-
-    // 1st update might fail bec it might be based on stale data
-    // bec DEFAULT_MAINNET_POOL might be stale
-    let _: Result<_, _> = update_cycle(&mut inf, onchain_state);
-    // panic if 2nd update cycle fails
-    update_cycle_strict(&mut inf, onchain_state).unwrap();
+    let inf = updated_inf_amm(onchain_state);
 
     let quote = inf.quote(&qp).unwrap();
-    let saam = inf
-        .get_swap_and_account_metas(&SwapParams {
-            swap_mode: qp.swap_mode,
-            in_amount: quote.in_amount,
-            out_amount: quote.out_amount,
-            source_mint: qp.input_mint,
-            destination_mint: qp.output_mint,
-            source_token_account: user.inp_token_acc().0,
-            destination_token_account: user.out_token_acc().0,
-            token_transfer_authority: user.signer().0,
-            // dont-cares
-            quote_mint_to_referrer: Default::default(),
-            jupiter_program_id: &Default::default(),
-            missing_dynamic_accounts_as_default: Default::default(),
-        })
-        .unwrap();
-    let ix = saam_to_inf_ix(qp.amount, saam, qp.swap_mode);
+    let swap_params = SwapParams {
+        swap_mode: qp.swap_mode,
+        in_amount: quote.in_amount,
+        out_amount: quote.out_amount,
+        source_mint: qp.input_mint,
+        destination_mint: qp.output_mint,
+        source_token_account: user.inp_token_acc().0,
+        destination_token_account: user.out_token_acc().0,
+        token_transfer_authority: user.signer().0,
+        // dont-cares
+        quote_mint_to_referrer: Default::default(),
+        jupiter_program_id: &Default::default(),
+        missing_dynamic_accounts_as_default: Default::default(),
+    };
+    let saam = inf.get_swap_and_account_metas(&swap_params).unwrap();
+    // executed with quoted amounts as slippage limits,
+    // so this also checks that the quote is exact
+    let ix = inf.swap_instruction(&swap_params).unwrap();
+
+    // must be consistent with what jup builds from SwapAndAccountMetas
+    let jup_ix = saam_to_inf_ix(qp.amount, saam, qp.swap_mode);
+    assert_eq!(ix.program_id, jup_ix.program_id);
+    assert_eq!(ix.accounts, jup_ix.accounts);
 
     let (
         accs_bef,
@@ -128,6 +115,32 @@ pub fn swap_test(
     );
 }
 
+/// - inits Amm struct
+/// - runs 2x update cycle
+pub fn updated_inf_amm(onchain_state: &HashMap<Pubkey, Account>) -> InfAmm {
+    let (key, account) = onchain_state
+        .get_key_value(&LST_STATE_LIST_ID.into())
+        .unwrap();
+    let mut inf = InfAmm::new(
+        &KeyedAccount {
+            key: *key,
+            account: account.clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        SPL_LSTS.into_iter().collect(),
+    )
+    .unwrap();
+
+    // 1st update might fail bec it might be based on stale data
+    // bec DEFAULT_MAINNET_POOL might be stale
+    let _: Result<_, _> = update_cycle(&mut inf, onchain_state);
+    // panic if 2nd update cycle fails
+    update_cycle_strict(&mut inf, onchain_state).unwrap();
+
+    inf
+}
+
 /// Compared to [`update_cycle_strict`], no-ops if an account to update is missing from
 /// `onchain_state`
 fn update_cycle(inf: &mut InfAmm, onchain_state: &HashMap<Pubkey, Account>) -> anyhow::Result<()> {
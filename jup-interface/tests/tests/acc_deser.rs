@@ -0,0 +1,71 @@
+use inf1_jup_interface::{consts::INF_MINT_ADDR, err::AccDeserErr};
+use inf1_std::inf1_ctl_core::keys::POOL_STATE_ID;
+use jupiter_amm_interface::{AccountMap, Amm};
+use solana_pubkey::Pubkey;
+use test_utils::{KeyedUiAccount, ALL_FIXTURES};
+
+use crate::common::updated_inf_amm;
+
+fn update_err(modify: impl FnOnce(&mut AccountMap)) -> AccDeserErr {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES);
+    let mut accounts: AccountMap = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, ALL_FIXTURES.get(&pk)?.clone())))
+        .collect();
+    modify(&mut accounts);
+    *inf.update(&accounts)
+        .unwrap_err()
+        .downcast_ref::<AccDeserErr>()
+        .unwrap()
+}
+
+#[test]
+fn acc_deser_truncated_pool_state_fixture() {
+    let pk = Pubkey::new_from_array(POOL_STATE_ID);
+    let owner = ALL_FIXTURES.get(&pk).unwrap().owner;
+    let err = update_err(|accounts| accounts.get_mut(&pk).unwrap().data.truncate(10));
+    assert_eq!(
+        err,
+        AccDeserErr {
+            pk: POOL_STATE_ID,
+            expected_len: Some(176),
+            actual_len: Some(10),
+            owner: Some(owner.to_bytes()),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        format!("AccDeser: {pk}. Expected len: >= 176. Actual len: 10. Owner: {owner}")
+    );
+}
+
+#[test]
+fn acc_deser_empty_svc_account_fixture() {
+    let (msol_pool, _) = KeyedUiAccount::from_test_fixtures_json("msol-pool").into_keyed_account();
+    let owner = ALL_FIXTURES.get(&msol_pool).unwrap().owner;
+    let err = update_err(|accounts| accounts.get_mut(&msol_pool).unwrap().data.clear());
+    assert_eq!(
+        err,
+        AccDeserErr {
+            pk: msol_pool.to_bytes(),
+            expected_len: None,
+            actual_len: Some(0),
+            owner: Some(owner.to_bytes()),
+        }
+    );
+}
+
+#[test]
+fn acc_deser_missing_lp_mint_fixture() {
+    let pk = Pubkey::new_from_array(INF_MINT_ADDR);
+    let err = update_err(|accounts| {
+        accounts.remove(&pk);
+    });
+    assert_eq!(err.actual_len, None);
+    assert_eq!(err.owner, None);
+    assert_eq!(
+        err.to_string(),
+        format!("AccDeser: {pk}. Account not found")
+    );
+}
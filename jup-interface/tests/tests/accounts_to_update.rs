@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{
+    pda::{flat_fee_fee_account, flat_fee_program_state},
+    InfAmm,
+};
+use inf1_std::{inf1_ctl_core::keys::POOL_STATE_ID, inf1_pp_ag_std::PricingAgTy};
+use jupiter_amm_interface::Amm;
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{set_pool_state_pricing_program, KeyedUiAccount, ALL_FIXTURES};
+
+use crate::common::updated_inf_amm;
+
+fn fixtures_with_pricing_program(pricing_program: &[u8; 32]) -> HashMap<Pubkey, Account> {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    let pool = onchain_state
+        .get_mut(&Pubkey::new_from_array(POOL_STATE_ID))
+        .unwrap();
+    set_pool_state_pricing_program(pool, pricing_program);
+    onchain_state
+}
+
+/// Runs an update cycle with an `AccountMap` containing exactly the accounts returned by
+/// `get_accounts_to_update()`, using zeroed placeholders for accounts not in `onchain_state`.
+///
+/// Update may fail on malformed placeholders, but must never fail due to a missing account,
+/// else update relies on an account that it never requested.
+fn update_cycle_requested_only(
+    inf: &mut InfAmm,
+    onchain_state: &HashMap<Pubkey, Account>,
+) -> anyhow::Result<()> {
+    let am: HashMap<_, _, _> = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .map(|pk| {
+            let acc = onchain_state.get(&pk).cloned().unwrap_or_else(|| Account {
+                data: vec![0; 512],
+                ..Default::default()
+            });
+            (pk, acc)
+        })
+        .collect();
+    let res = inf.update(&am);
+    if let Err(e) = &res {
+        let msg = format!("{e:#} {e:#?}");
+        assert!(
+            !msg.contains("MissingAcc") && !msg.contains("FeeAccountMissing"),
+            "{msg}"
+        );
+    }
+    res
+}
+
+#[test]
+fn accounts_to_update_complete_flatslab_fixture() {
+    let onchain_state = fixtures_with_pricing_program(PricingAgTy::FlatSlab(()).program_id());
+    let mut inf = updated_inf_amm(&onchain_state);
+
+    // all flatslab accounts have fixtures, so this should succeed
+    update_cycle_requested_only(&mut inf, &onchain_state).unwrap();
+}
+
+#[test]
+fn accounts_to_update_complete_flatfee_fixture() {
+    let onchain_state = fixtures_with_pricing_program(PricingAgTy::FlatFee(()).program_id());
+    let mut inf = updated_inf_amm(&onchain_state);
+    assert_eq!(
+        inf.inner.pool.pricing_program,
+        *PricingAgTy::FlatFee(()).program_id()
+    );
+
+    // the program state and the fee accounts of all LSTs on the list have fixtures,
+    // so this should succeed
+    update_cycle_requested_only(&mut inf, &onchain_state).unwrap();
+
+    let accounts_to_update = inf.get_accounts_to_update();
+    let fee_accounts = ["jupsol", "msol", "stsol", "wsol"].map(|name| {
+        let (mint, _) =
+            KeyedUiAccount::from_test_fixtures_json(&format!("{name}-mint")).into_keyed_account();
+        let (fee_account, _) =
+            KeyedUiAccount::from_test_fixtures_json(&format!("{name}-flatfee-fee"))
+                .into_keyed_account();
+        assert_eq!(
+            flat_fee_fee_account(mint.as_array()),
+            Some(fee_account.to_bytes())
+        );
+        fee_account
+    });
+    let (program_state, _) =
+        KeyedUiAccount::from_test_fixtures_json("flatfee-state").into_keyed_account();
+    assert_eq!(flat_fee_program_state(), Some(program_state.to_bytes()));
+    for pk in fee_accounts.into_iter().chain([program_state]) {
+        assert!(accounts_to_update.contains(&pk), "{pk}");
+    }
+}
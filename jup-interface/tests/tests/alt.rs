@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+
+use inf1_jup_interface::{
+    alt::{
+        create_alt_ix, extend_alt_ixs, find_alt_address, missing_from_alts, resolve_with_alts, Alt,
+        ALT_PROGRAM_ID, MAX_ADDRESSES_PER_ALT, MAX_ADDRESSES_PER_EXTEND,
+    },
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    writable::UserAccs,
+};
+use inf1_std::{
+    inf1_pp_core::pair::Pair,
+    inf1_svc_ag_std::inf1_svc_marinade_core::sanctum_marinade_liquid_staking_core::MSOL_MINT_ADDR,
+    trade::TradeLimitTy,
+};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+use AccountMeta;
+
+use crate::common::updated_inf_amm;
+
+const USER: UserAccs = UserAccs {
+    signer: Pubkey::new_from_array([3; 32]),
+    inp_token_acc: Pubkey::new_from_array([1; 32]),
+    out_token_acc: Pubkey::new_from_array([2; 32]),
+};
+
+fn alts(tables: &[Vec<Pubkey>]) -> Vec<Alt> {
+    tables
+        .iter()
+        .enumerate()
+        .map(|(i, addresses)| Alt {
+            key: Pubkey::new_from_array([100 + i as u8; 32]),
+            addresses: addresses.clone(),
+        })
+        .collect()
+}
+
+#[test]
+fn suggested_alts_cover_all_pairs_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let tables = inf.suggest_alts();
+    assert_eq!(tables, inf.suggest_alts());
+    assert!(tables.iter().all(|t| t.len() <= MAX_ADDRESSES_PER_ALT));
+    let all: Vec<_> = tables.iter().flatten().collect();
+    assert_eq!(all.len(), all.iter().collect::<HashSet<_>>().len());
+    let alts = alts(&tables);
+
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let msol = &MSOL_MINT_ADDR;
+    for (inp, out) in [
+        (jupsol, &WSOL_MINT_ADDR),
+        (msol, jupsol),
+        (&WSOL_MINT_ADDR, &INF_MINT_ADDR),
+        (&INF_MINT_ADDR, msol),
+    ] {
+        let metas = inf
+            .accounts_for_pair(&Pair { inp, out }, TradeLimitTy::ExactIn, &USER)
+            .unwrap();
+        let resolved = resolve_with_alts(&metas, &alts);
+        // only user accounts are static
+        let statics: HashSet<_> = resolved.static_metas.iter().map(|m| m.pubkey).collect();
+        assert!(
+            statics.is_subset(&HashSet::from([
+                USER.signer,
+                USER.inp_token_acc,
+                USER.out_token_acc
+            ])),
+            "{statics:?}"
+        );
+        assert!(resolved.lookups.len() <= 3);
+        assert!(resolved
+            .lookups
+            .iter()
+            .all(|l| l.writable_indexes.len() + l.readonly_indexes.len() > 0));
+    }
+}
+
+#[test]
+fn resolve_with_alts_signers_static_and_flags_merged() {
+    let [a, b, signer] = [[1; 32], [2; 32], [3; 32]].map(Pubkey::new_from_array);
+    let alts = alts(&[vec![a, signer], vec![a, b]]);
+    let resolved = resolve_with_alts(
+        &[
+            AccountMeta::new_readonly(a, false),
+            AccountMeta::new_readonly(b, false),
+            AccountMeta::new(a, false),
+            AccountMeta::new_readonly(signer, true),
+        ],
+        &alts,
+    );
+    assert_eq!(
+        resolved.static_metas,
+        [AccountMeta::new_readonly(signer, true)]
+    );
+    assert_eq!(resolved.lookups.len(), 2);
+    assert_eq!(resolved.lookups[0].key, alts[0].key);
+    assert_eq!(resolved.lookups[0].writable_indexes, [0]);
+    assert!(resolved.lookups[0].readonly_indexes.is_empty());
+    assert_eq!(resolved.lookups[1].readonly_indexes, [1]);
+}
+
+#[test]
+fn alt_ixs() {
+    let [authority, payer] = [[1; 32], [2; 32]].map(Pubkey::new_from_array);
+    let (create, alt) = create_alt_ix(&authority, &payer, 1234);
+    assert_eq!(alt, find_alt_address(&authority, 1234).0);
+    assert_eq!(create.program_id, ALT_PROGRAM_ID);
+    assert_eq!(create.accounts[0].pubkey, alt);
+    assert_eq!(create.data.len(), 13);
+    assert_eq!(create.data[..4], [0; 4]);
+
+    let addresses: Vec<_> = (0..60u8).map(|i| Pubkey::new_from_array([i; 32])).collect();
+    let extends = extend_alt_ixs(&alt, &authority, &payer, &addresses);
+    assert_eq!(extends.len(), 60usize.div_ceil(MAX_ADDRESSES_PER_EXTEND));
+    for (ix, chunk) in extends
+        .iter()
+        .zip(addresses.chunks(MAX_ADDRESSES_PER_EXTEND))
+    {
+        assert_eq!(ix.data[..4], 2u32.to_le_bytes());
+        assert_eq!(ix.data[4..12], (chunk.len() as u64).to_le_bytes());
+        assert_eq!(ix.data.len(), 12 + 32 * chunk.len());
+    }
+
+    let existing = alts(&[addresses[..30].to_vec()]);
+    assert_eq!(
+        missing_from_alts(&existing, &[addresses[0], addresses[59], addresses[59]]),
+        [addresses[59]]
+    );
+}
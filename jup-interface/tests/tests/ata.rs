@@ -0,0 +1,98 @@
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR,
+    lst_info::{ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    pda::find_ata,
+};
+use jupiter_amm_interface::{SwapMode, SwapParams};
+use solana_pubkey::Pubkey;
+use test_utils::{KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const SIGNER: [u8; 32] = [7; 32];
+
+fn with_swap_params<R>(
+    source_token_account: Pubkey,
+    destination_token_account: Pubkey,
+    f: impl FnOnce(&SwapParams) -> R,
+) -> R {
+    f(&SwapParams {
+        swap_mode: SwapMode::ExactIn,
+        in_amount: 1_000_000_000,
+        out_amount: 0,
+        source_mint: WSOL_MINT_ADDR.into(),
+        destination_mint: *CONST_PUBKEYS.jupsol_mint(),
+        source_token_account,
+        destination_token_account,
+        token_transfer_authority: SIGNER.into(),
+        // dont-cares
+        quote_mint_to_referrer: None,
+        jupiter_program_id: &Default::default(),
+        missing_dynamic_accounts_as_default: false,
+    })
+}
+
+fn signer_ata(mint: &[u8; 32]) -> Pubkey {
+    find_ata(&SIGNER, &TOKEN_PROGRAM_ID, mint).unwrap().into()
+}
+
+fn pf_accum(name: &str) -> Pubkey {
+    KeyedUiAccount::from_test_fixtures_json(name)
+        .into_keyed_account()
+        .0
+}
+
+#[test]
+fn required_atas_user_atas_and_pf_accums_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let [wsol_ata, jupsol_ata] = [&WSOL_MINT_ADDR, jupsol].map(signer_ata);
+
+    let (required, ix_accounts) = with_swap_params(wsol_ata, jupsol_ata, |sp| {
+        (
+            inf.required_atas(sp).unwrap(),
+            inf.swap_instruction(sp).unwrap().accounts,
+        )
+    });
+
+    // only the accumulators that the instruction uses
+    let pf_accums: Vec<_> = ["wsol-pf-accum", "jupsol-pf-accum"]
+        .map(pf_accum)
+        .into_iter()
+        .filter(|pk| ix_accounts.iter().any(|m| m.pubkey == *pk))
+        .collect();
+    assert!(!pf_accums.is_empty());
+    let mut expected: Vec<_> = [wsol_ata, jupsol_ata]
+        .into_iter()
+        .chain(pf_accums)
+        .collect();
+    expected.sort_unstable();
+    assert_eq!(
+        required.iter().map(|(pk, _)| *pk).collect::<Vec<_>>(),
+        expected
+    );
+
+    let (_, create) = required.iter().find(|(pk, _)| *pk == jupsol_ata).unwrap();
+    assert_eq!(create.owner, SIGNER);
+    assert_eq!(create.mint, *jupsol);
+    assert_eq!(create.token_program, TOKEN_PROGRAM_ID);
+    let ix = create.to_instruction(&SIGNER);
+    assert_eq!(
+        ix.program_id,
+        Pubkey::new_from_array(ASSOCIATED_TOKEN_PROGRAM_ID)
+    );
+    assert_eq!(ix.accounts[1].pubkey, jupsol_ata);
+}
+
+#[test]
+fn required_atas_excludes_non_ata_token_accs_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let wsol_ata = signer_ata(&WSOL_MINT_ADDR);
+    let (non_ata, _) =
+        KeyedUiAccount::from_test_fixtures_json("jupsol-token-acc").into_keyed_account();
+
+    let required = with_swap_params(wsol_ata, non_ata, |sp| inf.required_atas(sp).unwrap());
+
+    assert!(required.iter().any(|(pk, _)| *pk == wsol_ata));
+    assert!(required.iter().all(|(pk, _)| *pk != non_ata));
+}
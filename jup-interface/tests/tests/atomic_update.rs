@@ -0,0 +1,46 @@
+use inf1_jup_interface::consts::INF_MINT_ADDR;
+use jupiter_amm_interface::{AccountMap, Amm};
+use solana_pubkey::Pubkey;
+use test_utils::{
+    set_spl_stake_pool_last_update_epoch, KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS,
+};
+
+use crate::common::updated_inf_amm;
+
+/// Offset of `supply: u64` in mint data, after `mint_authority: COption<Pubkey>`
+const MINT_SUPPLY_OFFSET: usize = 36;
+
+#[test]
+fn failed_update_leaves_state_unchanged_fixture() {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let supply_before = inf.lp_token_supply();
+    let generation_before = inf.generation;
+
+    let mut onchain_state = ALL_FIXTURES.clone();
+    // changes that would be applied if the update succeeded
+    set_spl_stake_pool_last_update_epoch(
+        onchain_state.get_mut(CONST_PUBKEYS.jupsol_pool()).unwrap(),
+        1,
+    );
+    onchain_state
+        .get_mut(&Pubkey::new_from_array(INF_MINT_ADDR))
+        .unwrap()
+        .data[MINT_SUPPLY_OFFSET..][..8]
+        .copy_from_slice(&(supply_before + 1).to_le_bytes());
+    // account that fails to apply
+    let (msol_pool, _) = KeyedUiAccount::from_test_fixtures_json("msol-pool").into_keyed_account();
+    onchain_state.get_mut(&msol_pool).unwrap().data.clear();
+
+    let accounts: AccountMap = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect();
+    inf.update(&accounts).unwrap_err();
+
+    assert_eq!(inf.lp_token_supply(), supply_before);
+    assert_eq!(inf.lst_calc_epoch(jupsol), Some(0));
+    // still counts as an attempt for cache invalidation
+    assert_eq!(inf.generation, generation_before + 1);
+}
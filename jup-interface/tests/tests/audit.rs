@@ -0,0 +1,41 @@
+use inf1_jup_interface::consts::INF_MINT_ADDR;
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use solana_pubkey::Pubkey;
+use test_utils::{token_acc_mint, KeyedUiAccount, ALL_FIXTURES, FIXTURE_USER_LSTS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn audit_metas_all_fixture_pairs() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let mints: Vec<[u8; 32]> = FIXTURE_USER_LSTS
+        .iter()
+        .map(|name| {
+            token_acc_mint(
+                &KeyedUiAccount::from_test_fixtures_json(&format!("{name}-token-acc"))
+                    .into_keyed_account()
+                    .1,
+            )
+            .to_bytes()
+        })
+        .chain([INF_MINT_ADDR])
+        .collect();
+    for inp in mints.iter() {
+        for out in mints.iter().filter(|out| *out != inp) {
+            let pair = Pair { inp, out };
+            let mut trade_tys = vec![TradeLimitTy::ExactIn];
+            if inf.supports_exact_out_for_pair(&pair) {
+                trade_tys.push(TradeLimitTy::ExactOut);
+            }
+            for trade_ty in trade_tys {
+                let errs = inf.audit_metas(&pair, trade_ty).unwrap();
+                assert!(
+                    errs.is_empty(),
+                    "{} -> {}: {errs:?}",
+                    Pubkey::new_from_array(*inp),
+                    Pubkey::new_from_array(*out),
+                );
+            }
+        }
+    }
+}
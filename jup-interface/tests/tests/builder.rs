@@ -0,0 +1,135 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use inf1_jup_interface::{
+    config::{Cluster, ClusterProgramIds},
+    consts::WSOL_MINT_ADDR,
+    spl_lsts::FetchPolicy,
+    InfAmm,
+};
+use inf1_std::{
+    inf1_ctl_core::keys::LST_STATE_LIST_ID, inf1_pp_core::pair::Pair, trade::TradeLimitTy,
+};
+use jupiter_amm_interface::{Amm, KeyedAccount};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+fn lst_state_list_keyed_account() -> KeyedAccount {
+    let (key, account) = ALL_FIXTURES
+        .get_key_value(&LST_STATE_LIST_ID.into())
+        .unwrap();
+    KeyedAccount {
+        key: *key,
+        account: account.clone(),
+        params: None,
+    }
+}
+
+fn update_cycle(inf: &mut InfAmm, onchain_state: &HashMap<Pubkey, Account>) -> anyhow::Result<()> {
+    let am: HashMap<_, _, _> = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| {
+            let (k, v) = onchain_state.get_key_value(&pk)?;
+            Some((*k, v.clone()))
+        })
+        .collect();
+    inf.update(&am)
+}
+
+#[test]
+fn builder_same_as_new_fixture() {
+    let mut built = InfAmm::builder()
+        .amm_context(&AMM_CONTEXT)
+        .spl_lsts(SPL_LSTS.into_iter().collect())
+        .build(&lst_state_list_keyed_account())
+        .unwrap();
+    assert!(Arc::ptr_eq(
+        &built.current_epoch,
+        &AMM_CONTEXT.clock_ref.epoch
+    ));
+    assert!(!built.lenient_updates);
+
+    let _: Result<_, _> = update_cycle(&mut built, &ALL_FIXTURES);
+    update_cycle(&mut built, &ALL_FIXTURES).unwrap();
+    let new = updated_inf_amm(&ALL_FIXTURES);
+    assert_eq!(built.get_accounts_to_update(), new.get_accounts_to_update());
+}
+
+#[test]
+fn builder_offline_detached_clock_fixture() {
+    let inf = InfAmm::builder()
+        .offline(true)
+        .build(&lst_state_list_keyed_account())
+        .unwrap();
+    assert!(!Arc::ptr_eq(
+        &inf.current_epoch,
+        &AMM_CONTEXT.clock_ref.epoch
+    ));
+    assert_eq!(inf.epoch(), 0);
+    // bundled Sanctum LST list includes jupsol
+    assert_eq!(
+        inf.inner
+            .spl_lsts
+            .get(CONST_PUBKEYS.jupsol_mint().as_array()),
+        Some(CONST_PUBKEYS.jupsol_pool().as_array())
+    );
+}
+
+#[test]
+fn fetch_policy_bounds_build_latency_fixture() {
+    let start = Instant::now();
+    let inf = InfAmm::builder()
+        .cluster(Cluster::Custom {
+            // nothing listens on port 1, so every request fails
+            api_url: Some("http://127.0.0.1:1/lsts".to_owned()),
+            program_ids: ClusterProgramIds::MAINNET,
+        })
+        .fetch_policy(FetchPolicy {
+            max_elapsed: Duration::from_millis(200),
+            initial_interval: Duration::from_millis(10),
+            timeout: Duration::from_secs(1),
+        })
+        .build(&lst_state_list_keyed_account())
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(inf.inner.spl_lsts.is_empty());
+}
+
+#[test]
+fn lenient_updates_skip_failed_calc_fixture() {
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let mut onchain_state = ALL_FIXTURES.clone();
+    // truncated, fails to deserialize
+    onchain_state
+        .get_mut(CONST_PUBKEYS.jupsol_pool())
+        .unwrap()
+        .data
+        .truncate(8);
+
+    let mut strict = updated_inf_amm(&ALL_FIXTURES);
+    assert!(update_cycle(&mut strict, &onchain_state).is_err());
+
+    let mut lenient = updated_inf_amm(&ALL_FIXTURES).with_lenient_updates();
+    let calc_epoch = lenient.lst_calc_epoch(jupsol);
+    update_cycle(&mut lenient, &onchain_state).unwrap();
+    // previous sol val calc state is kept
+    assert_eq!(lenient.lst_calc_epoch(jupsol), calc_epoch);
+    lenient
+        .quote_raw(
+            &Pair {
+                inp: jupsol,
+                out: &WSOL_MINT_ADDR,
+            },
+            1_000_000,
+            TradeLimitTy::ExactIn,
+            0,
+        )
+        .unwrap();
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, err::InfAmmErr, InfAmm};
+use inf1_std::inf1_svc_ag_std::inf1_svc_lido_core::{self, solido_legacy_core::STSOL_MINT_ADDR};
+use jupiter_amm_interface::{AccountMap, Amm, QuoteParams, SwapMode};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const PROGRAMDATA_SLOT_OFFSET: usize = 4;
+const CALC_STATE_LAST_UPGRADE_SLOT_OFFSET: usize = 32;
+
+/// lido-calc-state.json
+const LIDO_CALC_STATE: &str = "7Dv8K2G3DqfkNNdPDx6qaQKmzGQu18fg6S7AjRnew6aX";
+
+fn accounts_to_update_map(inf: &impl Amm, onchain_state: &HashMap<Pubkey, Account>) -> AccountMap {
+    inf.get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect()
+}
+
+fn updated_checked_inf_amm(onchain_state: &HashMap<Pubkey, Account>) -> InfAmm {
+    let mut inf = updated_inf_amm(onchain_state).with_calc_upgrade_checks();
+    inf.update(&accounts_to_update_map(&inf, onchain_state))
+        .unwrap();
+    inf
+}
+
+fn quote(inf: &InfAmm, input_mint: Pubkey) -> anyhow::Result<u64> {
+    inf.quote(&QuoteParams {
+        amount: 1_000_000_000,
+        input_mint,
+        output_mint: Pubkey::new_from_array(WSOL_MINT_ADDR),
+        swap_mode: SwapMode::ExactIn,
+    })
+    .map(|q| q.out_amount)
+}
+
+#[test]
+fn calc_upgrade_accs_requested_fixture() {
+    let inf = updated_checked_inf_amm(&ALL_FIXTURES);
+    let accs = inf.get_accounts_to_update();
+    for pk in [
+        Pubkey::from_str_const(LIDO_CALC_STATE),
+        *CONST_PUBKEYS.lido_progdata(),
+    ] {
+        assert!(accs.contains(&pk), "{pk}");
+    }
+    assert!(inf.outdated_calcs.is_empty());
+    quote(&inf, Pubkey::new_from_array(STSOL_MINT_ADDR)).unwrap();
+}
+
+#[test]
+fn calc_program_outdated_after_upgrade_fixture() {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state
+        .get_mut(CONST_PUBKEYS.lido_progdata())
+        .unwrap()
+        .data[PROGRAMDATA_SLOT_OFFSET..][..8]
+        .copy_from_slice(&100u64.to_le_bytes());
+    let mut inf = updated_checked_inf_amm(&onchain_state);
+
+    let err = quote(&inf, Pubkey::new_from_array(STSOL_MINT_ADDR)).unwrap_err();
+    assert!(
+        matches!(
+            err.downcast_ref::<InfAmmErr>(),
+            Some(InfAmmErr::CalcProgramOutdated { program }) if *program == inf1_svc_lido_core::ID
+        ),
+        "{err}"
+    );
+    // LSTs of other calculators unaffected
+    quote(&inf, *CONST_PUBKEYS.jupsol_mint()).unwrap();
+
+    // caught up once the calculator records the new slot
+    onchain_state
+        .get_mut(&Pubkey::from_str_const(LIDO_CALC_STATE))
+        .unwrap()
+        .data[CALC_STATE_LAST_UPGRADE_SLOT_OFFSET..][..8]
+        .copy_from_slice(&100u64.to_le_bytes());
+    inf.update(&accounts_to_update_map(&inf, &onchain_state))
+        .unwrap();
+    assert!(inf.outdated_calcs.is_empty());
+    quote(&inf, Pubkey::new_from_array(STSOL_MINT_ADDR)).unwrap();
+}
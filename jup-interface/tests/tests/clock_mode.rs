@@ -0,0 +1,139 @@
+use std::{collections::HashMap, sync::Arc};
+
+use inf1_jup_interface::{clock::ClockMode, consts::WSOL_MINT_ADDR, err::InfAmmErr, InfAmm};
+use inf1_std::{
+    inf1_pp_core::pair::Pair,
+    inf1_svc_ag_std::{
+        inf1_svc_lido_core::solido_legacy_core::SYSVAR_CLOCK,
+        inf1_svc_marinade_core::sanctum_marinade_liquid_staking_core::MSOL_MINT_ADDR,
+    },
+    trade::TradeLimitTy,
+};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+const CLOCK_EPOCH_OFFSET: usize = 16;
+
+fn sysvar_mode_updated_inf_amm(onchain_state: &HashMap<Pubkey, Account>) -> InfAmm {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES).with_clock_mode(ClockMode::Sysvar);
+    let am: HashMap<_, _, _> = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| {
+            let (k, v) = onchain_state.get_key_value(&pk)?;
+            Some((*k, v.clone()))
+        })
+        .collect();
+    inf.update(&am).unwrap();
+    inf
+}
+
+fn fixtures_with_clock_epoch(epoch: u64) -> HashMap<Pubkey, Account> {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    let clock = onchain_state.get_mut(CONST_PUBKEYS.sysvar_clock()).unwrap();
+    clock.data[CLOCK_EPOCH_OFFSET..][..8].copy_from_slice(&epoch.to_le_bytes());
+    onchain_state
+}
+
+#[test]
+fn sysvar_mode_includes_clock_once_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    assert!(!inf
+        .get_accounts_to_update()
+        .contains(&Pubkey::new_from_array(SYSVAR_CLOCK)));
+
+    let inf = sysvar_mode_updated_inf_amm(&ALL_FIXTURES);
+    assert_eq!(
+        inf.get_accounts_to_update()
+            .iter()
+            .filter(|pk| *pk.as_array() == SYSVAR_CLOCK)
+            .count(),
+        1
+    );
+    assert_eq!(inf.sysvar_clock_epoch, Some(0));
+    assert_eq!(inf.epoch(), 0);
+}
+
+#[test]
+fn sysvar_mode_quote_before_clock_update_errs_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES).with_clock_mode(ClockMode::Sysvar);
+    assert!(matches!(inf.try_epoch(), Err(InfAmmErr::ClockNotUpdated)));
+    let e = inf
+        .quote(&QuoteParams {
+            amount: 1_000_000,
+            input_mint: Pubkey::new_from_array(MSOL_MINT_ADDR),
+            output_mint: Pubkey::new_from_array(WSOL_MINT_ADDR),
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap_err();
+    assert!(
+        matches!(
+            e.downcast_ref::<InfAmmErr>(),
+            Some(InfAmmErr::ClockNotUpdated)
+        ),
+        "{e}"
+    );
+}
+
+#[test]
+fn sysvar_mode_clock_epoch_blocks_stale_fixture() {
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let calc_epoch = updated_inf_amm(&ALL_FIXTURES)
+        .lst_calc_epoch(jupsol)
+        .unwrap();
+    let inf = sysvar_mode_updated_inf_amm(&fixtures_with_clock_epoch(calc_epoch + 1));
+    assert_eq!(inf.epoch(), calc_epoch + 1);
+
+    // ClockRef epoch remains 0, but sysvar clock epoch is used
+    let stale = Pair {
+        inp: jupsol,
+        out: &WSOL_MINT_ADDR,
+    };
+    assert!(inf
+        .quote_raw(&stale, 1_000_000, TradeLimitTy::ExactIn, inf.epoch())
+        .is_err());
+    assert!(!inf.stale_spl_stake_pools(&stale).is_empty());
+
+    // msol is not epoch affected
+    let unaffected = Pair {
+        inp: &MSOL_MINT_ADDR,
+        out: &WSOL_MINT_ADDR,
+    };
+    inf.quote_raw(&unaffected, 1_000_000, TradeLimitTy::ExactIn, inf.epoch())
+        .unwrap();
+}
+
+#[test]
+fn deterministic_mode_detached_clock_fixture() {
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let calc_epoch = updated_inf_amm(&ALL_FIXTURES)
+        .lst_calc_epoch(jupsol)
+        .unwrap();
+    let pair = Pair {
+        inp: jupsol,
+        out: &WSOL_MINT_ADDR,
+    };
+
+    let [a, b] = [0, 1].map(|_| updated_inf_amm(&ALL_FIXTURES).with_deterministic_mode(0, 0));
+    assert!(!Arc::ptr_eq(&a.current_epoch, &AMM_CONTEXT.clock_ref.epoch));
+    let [qa, qb] = [&a, &b].map(|inf| {
+        inf.quote_raw(&pair, 1_000_000_000, TradeLimitTy::ExactIn, inf.epoch())
+            .unwrap()
+            .1
+    });
+    assert_eq!(
+        (qa.inp, qa.out, qa.lp_fee, qa.protocol_fee),
+        (qb.inp, qb.out, qb.lp_fee, qb.protocol_fee)
+    );
+
+    a.set_clock(1, calc_epoch + 1);
+    assert_eq!(a.epoch(), calc_epoch + 1);
+    assert_eq!(b.epoch(), 0);
+    assert!(a
+        .quote_raw(&pair, 1_000_000_000, TradeLimitTy::ExactIn, a.epoch())
+        .is_err());
+}
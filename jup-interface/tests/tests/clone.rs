@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use jupiter_amm_interface::{AccountMap, Amm};
+use test_utils::{set_spl_stake_pool_last_update_epoch, ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn clone_shares_state_until_updated_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let mut cloned = inf.clone();
+    assert!(Arc::ptr_eq(&inf.inner, &cloned.inner));
+
+    let mut onchain_state = ALL_FIXTURES.clone();
+    set_spl_stake_pool_last_update_epoch(
+        onchain_state.get_mut(CONST_PUBKEYS.jupsol_pool()).unwrap(),
+        1,
+    );
+    let accounts: AccountMap = cloned
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect();
+    cloned.update(&accounts).unwrap();
+    assert!(!Arc::ptr_eq(&inf.inner, &cloned.inner));
+
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    assert_eq!(inf.lst_calc_epoch(jupsol), Some(0));
+    assert_eq!(cloned.lst_calc_epoch(jupsol), Some(1));
+}
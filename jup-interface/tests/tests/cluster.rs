@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{
+    config::{Cluster, ClusterProgramIds, InfAmmConfig, LstListSource},
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    InfAmm,
+};
+use inf1_std::inf1_ctl_core::keys::LST_STATE_LIST_ID;
+use jupiter_amm_interface::{Amm, KeyedAccount};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::AMM_CONTEXT;
+
+const CUSTOM_PROGRAM_IDS: ClusterProgramIds = ClusterProgramIds {
+    program_id: [1; 32],
+    pool_state_id: [2; 32],
+    lst_state_list_id: [3; 32],
+    lp_mint: [4; 32],
+};
+
+fn update_cycle(inf: &mut InfAmm, onchain_state: &HashMap<Pubkey, Account>) -> anyhow::Result<()> {
+    let am: HashMap<_, _, _> = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| {
+            let (k, v) = onchain_state.get_key_value(&pk)?;
+            Some((*k, v.clone()))
+        })
+        .collect();
+    inf.update(&am)
+}
+
+#[test]
+fn mainnet_addresses() {
+    let cluster = Cluster::Mainnet;
+    let config = cluster.config();
+    assert_eq!(cluster.program_ids(), ClusterProgramIds::MAINNET);
+    assert_eq!(cluster.inf_mint(), INF_MINT_ADDR);
+    assert_eq!(config.lst_state_list_id, LST_STATE_LIST_ID);
+    assert_eq!(config.pricing_defaults.lp_token_mint, INF_MINT_ADDR);
+    assert_eq!(config.lst_list, LstListSource::Sanctum);
+    assert_eq!(Cluster::default(), Cluster::Mainnet);
+}
+
+#[test]
+fn custom_cluster_config() {
+    let cluster = Cluster::Custom {
+        api_url: Some("http://localhost:8080/lsts".to_owned()),
+        program_ids: CUSTOM_PROGRAM_IDS,
+    };
+    let config = InfAmmConfig::from(&cluster);
+    assert_eq!(config.program_id, CUSTOM_PROGRAM_IDS.program_id);
+    assert_eq!(config.pool_state_id, CUSTOM_PROGRAM_IDS.pool_state_id);
+    assert_eq!(
+        config.lst_state_list_id,
+        CUSTOM_PROGRAM_IDS.lst_state_list_id
+    );
+    assert_eq!(
+        config.pricing_defaults.lp_token_mint,
+        CUSTOM_PROGRAM_IDS.lp_mint
+    );
+    assert_eq!(cluster.inf_mint(), CUSTOM_PROGRAM_IDS.lp_mint);
+    assert_eq!(
+        config.lst_list,
+        LstListSource::Url("http://localhost:8080/lsts".into())
+    );
+
+    let no_api = Cluster::Custom {
+        api_url: None,
+        program_ids: CUSTOM_PROGRAM_IDS,
+    };
+    assert_eq!(no_api.config().lst_list, LstListSource::None);
+}
+
+#[test]
+fn no_lst_list_loads_no_spl_lsts_fixture() {
+    let (key, account) = ALL_FIXTURES
+        .get_key_value(&LST_STATE_LIST_ID.into())
+        .unwrap();
+    let mut inf = InfAmm::builder()
+        .cluster(Cluster::Custom {
+            api_url: None,
+            program_ids: ClusterProgramIds::MAINNET,
+        })
+        .build(&KeyedAccount {
+            key: *key,
+            account: account.clone(),
+            params: None,
+        })
+        .unwrap();
+    let _: Result<_, _> = update_cycle(&mut inf, &ALL_FIXTURES);
+    update_cycle(&mut inf, &ALL_FIXTURES).unwrap();
+
+    let quotable = inf.quotable_mints();
+    assert!(quotable.contains(&WSOL_MINT_ADDR.into()));
+    assert!(!quotable.contains(CONST_PUBKEYS.jupsol_mint()));
+}
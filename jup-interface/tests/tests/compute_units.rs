@@ -0,0 +1,126 @@
+use inf1_jup_interface::consts::{INF_MINT_ADDR, WSOL_MINT_ADDR};
+use inf1_std::inf1_svc_ag_std::{
+    inf1_svc_lido_core::solido_legacy_core::STSOL_MINT_ADDR,
+    inf1_svc_marinade_core::sanctum_marinade_liquid_staking_core::MSOL_MINT_ADDR,
+};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode, SwapParams};
+use mollusk_svm::result::ProgramResult;
+use solana_pubkey::Pubkey;
+use test_utils::{mollusk_exec, KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::{updated_inf_amm, SVM};
+
+fn fixture_pk(name: &str) -> Pubkey {
+    KeyedUiAccount::from_test_fixtures_json(name)
+        .into_keyed_account()
+        .0
+}
+
+/// `(input mint, output mint, amount, signer, input token acc, output token acc)`
+fn fixture_trades() -> [(
+    [u8; 32],
+    [u8; 32],
+    u64,
+    &'static str,
+    &'static str,
+    &'static str,
+); 5] {
+    let jupsol = *CONST_PUBKEYS.jupsol_mint().as_array();
+    [
+        (
+            WSOL_MINT_ADDR,
+            jupsol,
+            1_000_000_000,
+            "wsol-token-acc-owner",
+            "wsol-token-acc",
+            "jupsol-token-acc",
+        ),
+        (
+            WSOL_MINT_ADDR,
+            MSOL_MINT_ADDR,
+            7698,
+            "wsol-token-acc-owner",
+            "wsol-token-acc",
+            "msol-token-acc",
+        ),
+        (
+            WSOL_MINT_ADDR,
+            STSOL_MINT_ADDR,
+            6969,
+            "wsol-token-acc-owner",
+            "wsol-token-acc",
+            "stsol-token-acc",
+        ),
+        (
+            jupsol,
+            INF_MINT_ADDR,
+            1_000_000_000,
+            "jupsol-token-acc-owner",
+            "jupsol-token-acc",
+            "inf-token-acc",
+        ),
+        (
+            INF_MINT_ADDR,
+            jupsol,
+            1_000_000,
+            "inf-token-acc-owner",
+            "inf-token-acc",
+            "jupsol-token-acc",
+        ),
+    ]
+}
+
+#[test]
+fn estimated_compute_units_upper_bound_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    for (inp, out, amount, signer, inp_token_acc, out_token_acc) in fixture_trades() {
+        let quote = inf
+            .quote(&QuoteParams {
+                amount,
+                input_mint: inp.into(),
+                output_mint: out.into(),
+                swap_mode: SwapMode::ExactIn,
+            })
+            .unwrap();
+        let ix = inf
+            .swap_instruction(&SwapParams {
+                swap_mode: SwapMode::ExactIn,
+                in_amount: quote.in_amount,
+                out_amount: quote.out_amount,
+                source_mint: inp.into(),
+                destination_mint: out.into(),
+                source_token_account: fixture_pk(inp_token_acc),
+                destination_token_account: fixture_pk(out_token_acc),
+                token_transfer_authority: fixture_pk(signer),
+                quote_mint_to_referrer: None,
+                jupiter_program_id: &Default::default(),
+                missing_dynamic_accounts_as_default: false,
+            })
+            .unwrap();
+        let (_, res) = SVM.with(|svm| mollusk_exec(svm, &ix, &ALL_FIXTURES));
+        assert!(
+            matches!(res.program_result, ProgramResult::Success),
+            "{:#?}",
+            res.program_result
+        );
+
+        let estimate = inf.estimated_compute_units(&inp, &out, inf.trade_type(&inp, &out));
+        assert!(
+            u64::from(estimate) >= res.compute_units_consumed,
+            "{} -> {}: estimated {estimate}, consumed {}",
+            Pubkey::new_from_array(inp),
+            Pubkey::new_from_array(out),
+            res.compute_units_consumed
+        );
+    }
+}
+
+#[test]
+fn estimated_compute_units_wsol_cheaper_than_spl_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let [wsol_add, jupsol_add] = [&WSOL_MINT_ADDR, jupsol].map(|mint| {
+        inf.estimated_compute_units(mint, &INF_MINT_ADDR, inf.trade_type(mint, &INF_MINT_ADDR))
+    });
+    assert!(wsol_add < jupsol_add);
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{
+    config::InfAmmConfig,
+    consts::WSOL_MINT_ADDR,
+    lst_info::TOKEN_PROGRAMS,
+    pda::{find_ata, find_pda, PROTOCOL_FEE_SEED},
+    InfAmm,
+};
+use inf1_std::inf1_ctl_core::keys::{LST_STATE_LIST_ID, POOL_STATE_ID};
+use jupiter_amm_interface::{Amm, KeyedAccount, QuoteParams, SwapMode, SwapParams};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+const FORK_POOL_STATE_ID: [u8; 32] = [1; 32];
+const FORK_LST_STATE_LIST_ID: [u8; 32] = [2; 32];
+const FORK_PROGRAM_ID: [u8; 32] = [9; 32];
+
+/// Offset of `owner: Pubkey` in token account data
+const TOKEN_ACC_OWNER_OFFSET: usize = 32;
+
+fn fork_config() -> InfAmmConfig {
+    InfAmmConfig {
+        pool_state_id: FORK_POOL_STATE_ID,
+        lst_state_list_id: FORK_LST_STATE_LIST_ID,
+        ..InfAmmConfig::MAINNET
+    }
+}
+
+/// [`ALL_FIXTURES`] with pool state and LST state list moved to `config`'s addresses and
+/// owned by its program, and the reserves and protocol fee accumulators moved to its PDAs
+fn fork_fixtures(config: &InfAmmConfig) -> HashMap<Pubkey, Account> {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    for (mainnet, fork) in [
+        (POOL_STATE_ID, config.pool_state_id),
+        (LST_STATE_LIST_ID, config.lst_state_list_id),
+    ] {
+        let mut acc = onchain_state
+            .remove(&Pubkey::new_from_array(mainnet))
+            .unwrap();
+        acc.owner = Pubkey::new_from_array(config.program_id);
+        onchain_state.insert(Pubkey::new_from_array(fork), acc);
+    }
+    let mainnet = &InfAmmConfig::MAINNET;
+    let moves: Vec<_> = onchain_state
+        .iter()
+        .filter(|(_, acc)| TOKEN_PROGRAMS.contains(acc.owner.as_array()) && acc.data.len() >= 64)
+        .filter_map(|(pk, acc)| {
+            let tp = acc.owner.as_array();
+            let mint: [u8; 32] = acc.data[..32].try_into().unwrap();
+            [
+                (
+                    mainnet.lst_reserves(tp, &mint)?,
+                    config.lst_reserves(tp, &mint)?,
+                    config.pool_state_id,
+                ),
+                (
+                    mainnet.protocol_fee_accumulator(tp, &mint)?,
+                    config.protocol_fee_accumulator(tp, &mint)?,
+                    config.protocol_fee()?,
+                ),
+            ]
+            .into_iter()
+            .find(|(m, ..)| m == pk.as_array())
+            .map(|(_, fork, owner)| (*pk, fork, owner))
+        })
+        .collect();
+    for (mainnet_pk, fork, owner) in moves {
+        let mut acc = onchain_state.remove(&mainnet_pk).unwrap();
+        acc.data[TOKEN_ACC_OWNER_OFFSET..][..32].copy_from_slice(&owner);
+        onchain_state.insert(Pubkey::new_from_array(fork), acc);
+    }
+    onchain_state
+}
+
+fn updated_fork_inf_amm(onchain_state: &HashMap<Pubkey, Account>) -> InfAmm {
+    updated_inf_amm_with_config(onchain_state, fork_config())
+}
+
+fn updated_inf_amm_with_config(
+    onchain_state: &HashMap<Pubkey, Account>,
+    config: InfAmmConfig,
+) -> InfAmm {
+    let key = Pubkey::new_from_array(FORK_LST_STATE_LIST_ID);
+    let mut inf = InfAmm::new_with_config(
+        &KeyedAccount {
+            key,
+            account: onchain_state.get(&key).unwrap().clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        SPL_LSTS.into_iter().collect(),
+        config,
+    )
+    .unwrap();
+    // 2 update cycles, 1st one might fail because DEFAULT_MAINNET_POOL might be stale
+    for i in 0..2 {
+        let am: HashMap<_, _, _> = inf
+            .get_accounts_to_update()
+            .into_iter()
+            .filter_map(|pk| {
+                let (k, v) = onchain_state.get_key_value(&pk)?;
+                Some((*k, v.clone()))
+            })
+            .collect();
+        let res = inf.update(&am);
+        if i == 1 {
+            res.unwrap();
+        }
+    }
+    inf
+}
+
+#[test]
+fn new_with_config_rejects_mainnet_lst_state_list_fixture() {
+    let key = Pubkey::new_from_array(LST_STATE_LIST_ID);
+    assert!(InfAmm::new_with_config(
+        &KeyedAccount {
+            key,
+            account: ALL_FIXTURES.get(&key).unwrap().clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        SPL_LSTS.into_iter().collect(),
+        fork_config(),
+    )
+    .is_err());
+}
+
+#[test]
+fn fork_config_uses_fork_addresses_fixture() {
+    let inf = updated_fork_inf_amm(&fork_fixtures(&fork_config()));
+    assert_eq!(inf.key(), Pubkey::new_from_array(FORK_LST_STATE_LIST_ID));
+
+    let accs = inf.get_accounts_to_update();
+    for mainnet in [POOL_STATE_ID, LST_STATE_LIST_ID] {
+        assert!(!accs.contains(&Pubkey::new_from_array(mainnet)));
+    }
+    for fork in [FORK_POOL_STATE_ID, FORK_LST_STATE_LIST_ID] {
+        assert!(accs.contains(&Pubkey::new_from_array(fork)));
+    }
+
+    let qp = QuoteParams {
+        amount: 1_000_000,
+        input_mint: *CONST_PUBKEYS.jupsol_mint(),
+        output_mint: WSOL_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    };
+    let quote = inf.quote(&qp).unwrap();
+    let saam = inf
+        .get_swap_and_account_metas(&SwapParams {
+            swap_mode: qp.swap_mode,
+            in_amount: quote.in_amount,
+            out_amount: quote.out_amount,
+            source_mint: qp.input_mint,
+            destination_mint: qp.output_mint,
+            source_token_account: Pubkey::new_from_array([3; 32]),
+            destination_token_account: Pubkey::new_from_array([4; 32]),
+            token_transfer_authority: Pubkey::new_from_array([5; 32]),
+            // dont-cares
+            quote_mint_to_referrer: Default::default(),
+            jupiter_program_id: &Default::default(),
+            missing_dynamic_accounts_as_default: Default::default(),
+        })
+        .unwrap();
+    let metas: Vec<_> = saam.account_metas.iter().map(|m| m.pubkey).collect();
+    assert!(metas.contains(&Pubkey::new_from_array(FORK_POOL_STATE_ID)));
+    assert!(!metas.contains(&Pubkey::new_from_array(POOL_STATE_ID)));
+}
+
+#[test]
+fn fork_program_id_pdas_fixture() {
+    let config = InfAmmConfig {
+        program_id: FORK_PROGRAM_ID,
+        ..fork_config()
+    };
+    let protocol_fee = find_pda(&[PROTOCOL_FEE_SEED], &FORK_PROGRAM_ID).map(|(pda, _bump)| pda);
+    assert_eq!(config.protocol_fee(), protocol_fee);
+
+    let inf = updated_inf_amm_with_config(&fork_fixtures(&config), config.clone());
+    let mainnet_inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let tp = *inf.token_program_of(jupsol.as_array()).unwrap();
+    let fork_reserves = find_ata(&FORK_POOL_STATE_ID, &tp, jupsol.as_array()).unwrap();
+    assert_eq!(
+        config.lst_reserves(&tp, jupsol.as_array()),
+        Some(fork_reserves)
+    );
+    assert_eq!(
+        config.protocol_fee_accumulator(&tp, jupsol.as_array()),
+        find_ata(&protocol_fee.unwrap(), &tp, jupsol.as_array())
+    );
+
+    // reserves were fetched from the fork's PDAs
+    assert!(inf.invalid_reserves_mints().is_empty());
+    assert_eq!(
+        inf.reserves_balance(jupsol.as_array()),
+        mainnet_inf.reserves_balance(jupsol.as_array())
+    );
+
+    let qp = QuoteParams {
+        amount: 1_000_000,
+        input_mint: *jupsol,
+        output_mint: WSOL_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    };
+    let quote = inf.quote(&qp).unwrap();
+    assert_eq!(quote.out_amount, mainnet_inf.quote(&qp).unwrap().out_amount);
+    let swap_params = SwapParams {
+        swap_mode: qp.swap_mode,
+        in_amount: quote.in_amount,
+        out_amount: quote.out_amount,
+        source_mint: qp.input_mint,
+        destination_mint: qp.output_mint,
+        source_token_account: Pubkey::new_from_array([3; 32]),
+        destination_token_account: Pubkey::new_from_array([4; 32]),
+        token_transfer_authority: Pubkey::new_from_array([5; 32]),
+        // dont-cares
+        quote_mint_to_referrer: Default::default(),
+        jupiter_program_id: &Default::default(),
+        missing_dynamic_accounts_as_default: Default::default(),
+    };
+    let [metas, mainnet_metas] = [&inf, &mainnet_inf].map(|inf| -> Vec<_> {
+        inf.get_swap_and_account_metas(&swap_params)
+            .unwrap()
+            .account_metas
+            .iter()
+            .map(|m| m.pubkey.to_bytes())
+            .collect()
+    });
+    assert_eq!(metas.len(), mainnet_metas.len());
+    assert!(metas.contains(&fork_reserves));
+    // every mainnet PDA is replaced by the fork's
+    for (fork, mainnet) in metas.iter().zip(mainnet_metas.iter()) {
+        assert_eq!(inf.to_mainnet(fork), mainnet);
+    }
+    for mainnet in [
+        InfAmmConfig::MAINNET.lst_reserves(&tp, jupsol.as_array()),
+        InfAmmConfig::MAINNET.protocol_fee(),
+    ] {
+        assert!(!metas.contains(&mainnet.unwrap()));
+    }
+}
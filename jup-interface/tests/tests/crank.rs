@@ -0,0 +1,96 @@
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR,
+    crank::{spl_stake_pool_crank_ixs, stake_pool_validator_list, MAX_VALIDATORS_TO_UPDATE},
+    pda::PdaFns,
+};
+use inf1_std::inf1_pp_core::pair::Pair;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+/// `ValidatorList` account data with `n` zeroed `ValidatorStakeInfo`s
+fn validator_list_data(n: u32) -> Vec<u8> {
+    let mut data = vec![2u8];
+    data.extend(n.to_le_bytes());
+    data.extend(n.to_le_bytes());
+    data.extend(vec![0u8; n as usize * 73]);
+    data
+}
+
+#[test]
+fn crank_ixs_for_updated_pair_empty_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let pair = Pair {
+        inp: CONST_PUBKEYS.jupsol_mint().as_array(),
+        out: &WSOL_MINT_ADDR,
+    };
+
+    assert!(inf.stale_spl_stake_pools(&pair).is_empty());
+    assert!(inf
+        .crank_ixs_for_pair(&pair, &Default::default())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn spl_stake_pool_crank_ixs_batches_fixture() {
+    let pool = CONST_PUBKEYS.jupsol_pool();
+    let pool_data = &ALL_FIXTURES.get(pool).unwrap().data;
+    let n_validators = MAX_VALIDATORS_TO_UPDATE as u32 + 1;
+
+    let ixs = spl_stake_pool_crank_ixs(
+        &PdaFns::FAST,
+        CONST_PUBKEYS.sanctum_spl_multi_prog().as_array(),
+        pool.as_array(),
+        pool_data,
+        &validator_list_data(n_validators),
+    )
+    .unwrap();
+
+    // 2 UpdateValidatorListBalance + UpdateStakePoolBalance + CleanupRemovedValidatorEntries
+    assert_eq!(ixs.len(), 4);
+    assert_eq!(ixs[0].accounts.len(), 7 + 2 * MAX_VALIDATORS_TO_UPDATE);
+    assert_eq!(ixs[1].accounts.len(), 7 + 2);
+    assert_eq!(
+        ixs[1].accounts[2].pubkey.to_bytes(),
+        stake_pool_validator_list(pool_data).unwrap()
+    );
+    assert_eq!(ixs[2].data, [7]);
+    assert_eq!(ixs[3].data, [8]);
+}
+
+const MOCK_PDA: [u8; 32] = [9; 32];
+
+fn mock_find(_seeds: &[&[u8]], _program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+    Some((MOCK_PDA, 255))
+}
+
+#[test]
+fn spl_stake_pool_crank_ixs_uses_pda_provider_fixture() {
+    let pool = CONST_PUBKEYS.jupsol_pool();
+    let pool_data = &ALL_FIXTURES.get(pool).unwrap().data;
+    let validator_list_data = validator_list_data(1);
+    let crank_ixs = |pda: &PdaFns| {
+        spl_stake_pool_crank_ixs(
+            pda,
+            CONST_PUBKEYS.sanctum_spl_multi_prog().as_array(),
+            pool.as_array(),
+            pool_data,
+            &validator_list_data,
+        )
+        .unwrap()
+    };
+
+    assert_eq!(crank_ixs(&PdaFns::FAST), crank_ixs(&PdaFns::SAFE));
+
+    let ixs = crank_ixs(&PdaFns {
+        find: mock_find,
+        ..PdaFns::FAST
+    });
+    // UpdateValidatorListBalance: withdraw authority, validator stake, transient stake
+    for i in [1, 7, 8] {
+        assert_eq!(ixs[0].accounts[i].pubkey.to_bytes(), MOCK_PDA);
+    }
+    // UpdateStakePoolBalance: withdraw authority
+    assert_eq!(ixs[1].accounts[1].pubkey.to_bytes(), MOCK_PDA);
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use inf1_std::inf1_ctl_core::keys::LST_STATE_LIST_ID;
+use jupiter_amm_interface::{AccountMap, Amm};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const LST_STATE_LEN: usize = 80;
+
+fn accounts_to_update_map(inf: &impl Amm, onchain_state: &HashMap<Pubkey, Account>) -> AccountMap {
+    inf.get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect()
+}
+
+/// Fixtures with `mint` removed from the LST state list
+fn delisted(mint: &[u8; 32]) -> HashMap<Pubkey, Account> {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    let list = onchain_state
+        .get_mut(&Pubkey::new_from_array(LST_STATE_LIST_ID))
+        .unwrap();
+    list.data = list
+        .data
+        .chunks(LST_STATE_LEN)
+        .filter(|lst_state| !lst_state.windows(32).any(|w| w == mint))
+        .flatten()
+        .copied()
+        .collect();
+    onchain_state
+}
+
+#[test]
+fn delisted_lst_pruned_fixture() {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint().to_bytes();
+    let (jupsol_reserves, _) =
+        KeyedUiAccount::from_test_fixtures_json("jupsol-reserves").into_keyed_account();
+    assert!(inf.inner.lst_calcs.contains_key(&jupsol));
+    assert!(inf.reserves_balance(&jupsol).is_some());
+
+    let onchain_state = delisted(&jupsol);
+    inf.update(&accounts_to_update_map(&inf, &onchain_state))
+        .unwrap();
+
+    assert!(!inf.inner.lst_calcs.contains_key(&jupsol));
+    assert_eq!(inf.reserves_balance(&jupsol), None);
+    assert_eq!(inf.token_program_of(&jupsol), None);
+    assert!(inf.lst_health(&jupsol).is_none());
+    let accs = inf.get_accounts_to_update();
+    assert!(!accs.contains(CONST_PUBKEYS.jupsol_pool()));
+    assert!(!accs.contains(&jupsol_reserves));
+
+    // other LSTs and the LP token mint unaffected
+    let msol = KeyedUiAccount::from_test_fixtures_json("msol-mint")
+        .into_keyed_account()
+        .0;
+    assert!(inf.reserves_balance(msol.as_array()).is_some());
+    assert!(inf
+        .token_program_of(&inf.inner.pool.lp_token_mint)
+        .is_some());
+
+    // stays pruned in subsequent updates
+    inf.update(&accounts_to_update_map(&inf, &onchain_state))
+        .unwrap();
+    assert!(!inf.inner.lst_calcs.contains_key(&jupsol));
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::consts::WSOL_MINT_ADDR;
+use jupiter_amm_interface::{AccountMap, Amm, QuoteParams, SwapMode};
+use test_utils::{set_spl_stake_pool_last_update_epoch, ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+fn accounts_to_update_map(pks: &[solana_pubkey::Pubkey]) -> AccountMap {
+    pks.iter()
+        .filter_map(|pk| {
+            let (k, v) = ALL_FIXTURES.get_key_value(pk)?;
+            Some((*k, v.clone()))
+        })
+        .collect()
+}
+
+#[test]
+fn unchanged_accounts_filtered_out_fixture() {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES).with_delta_updates();
+    let full = accounts_to_update_map(&inf.get_accounts_to_update());
+    assert_eq!(inf.changed_accounts(&full).len(), full.len());
+
+    inf.update(&full).unwrap();
+    assert!(inf.changed_accounts(&full).is_empty());
+    assert!(inf.get_accounts_to_update_since(0).is_empty());
+
+    let mut changed = full.clone();
+    let pool = changed.get_mut(CONST_PUBKEYS.jupsol_pool()).unwrap();
+    set_spl_stake_pool_last_update_epoch(pool, 1);
+    let delta = inf.changed_accounts(&changed);
+    assert_eq!(
+        delta.keys().collect::<Vec<_>>(),
+        [CONST_PUBKEYS.jupsol_pool()]
+    );
+}
+
+#[test]
+fn update_delta_same_as_full_update_fixture() {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES).with_delta_updates();
+    let full = accounts_to_update_map(&inf.get_accounts_to_update());
+    inf.update(&full).unwrap();
+
+    let mut changed = full.clone();
+    set_spl_stake_pool_last_update_epoch(changed.get_mut(CONST_PUBKEYS.jupsol_pool()).unwrap(), 1);
+    let mut full_updated = inf.clone();
+    full_updated.update(&changed).unwrap();
+    inf.update_delta(&inf.changed_accounts(&changed)).unwrap();
+
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    assert_eq!(inf.lst_calc_epoch(jupsol.as_array()), Some(1));
+    assert_eq!(
+        inf.lst_calc_epoch(jupsol.as_array()),
+        full_updated.lst_calc_epoch(jupsol.as_array())
+    );
+    let qp = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: *jupsol,
+        output_mint: WSOL_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    };
+    assert_eq!(
+        inf.quote(&qp).unwrap().out_amount,
+        full_updated.quote(&qp).unwrap().out_amount
+    );
+}
+
+#[test]
+fn update_delta_errs_if_not_enabled_fixture() {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES);
+    assert!(inf.update_delta(&HashMap::default()).is_err());
+}
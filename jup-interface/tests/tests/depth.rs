@@ -0,0 +1,44 @@
+use inf1_jup_interface::consts::WSOL_MINT_ADDR;
+use rust_decimal::Decimal;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn depth_evenly_spaced_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let points = inf
+        .depth(
+            CONST_PUBKEYS.jupsol_mint().as_array(),
+            &WSOL_MINT_ADDR,
+            10_000_000_000,
+            10,
+        )
+        .unwrap();
+
+    assert_eq!(points.len(), 10);
+    assert_eq!(points.last().unwrap().in_amount, 10_000_000_000);
+    for (i, p) in points.iter().enumerate() {
+        assert_eq!(p.in_amount, (i as u64 + 1) * 1_000_000_000);
+        assert!(p.effective_price > Decimal::ZERO);
+    }
+    for w in points.windows(2) {
+        assert!(w[0].out_amount < w[1].out_amount);
+    }
+}
+
+#[test]
+fn depth_stops_at_liquidity_limit_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let points = inf
+        .depth(
+            CONST_PUBKEYS.jupsol_mint().as_array(),
+            &WSOL_MINT_ADDR,
+            // way more than any LST's reserves
+            1_000_000_000_000_000_000,
+            4,
+        )
+        .unwrap();
+
+    assert!(points.len() < 4);
+}
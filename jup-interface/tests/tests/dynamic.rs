@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, err::InfAmmErr, InfAmm};
+use jupiter_amm_interface::{AccountMap, Amm, SwapMode, SwapParams};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+fn update(inf: &mut InfAmm, onchain_state: &HashMap<Pubkey, Account>) {
+    let accounts: AccountMap = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect();
+    inf.update(&accounts).unwrap();
+}
+
+#[test]
+fn missing_protocol_fee_accumulator_fixture() {
+    let (wsol_pf_accum, _) =
+        KeyedUiAccount::from_test_fixtures_json("wsol-pf-accum").into_keyed_account();
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state.remove(&wsol_pf_accum).unwrap();
+
+    let mut inf = updated_inf_amm(&ALL_FIXTURES).with_dynamic_account_checks();
+    // 1st cycle fetches mints, 2nd cycle fetches accumulators
+    update(&mut inf, &onchain_state);
+    assert!(inf.missing_dynamic_accs().is_empty());
+    update(&mut inf, &onchain_state);
+    assert!(inf
+        .get_accounts_to_update()
+        .contains(&Pubkey::new_from_array(WSOL_MINT_ADDR)));
+
+    let missing = inf.missing_dynamic_accs();
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].pubkey, wsol_pf_accum.to_bytes());
+    assert_eq!(missing[0].mint, WSOL_MINT_ADDR);
+    let payer = [1; 32];
+    let create_ix = missing[0].create_ix(&payer).unwrap();
+    assert_eq!(create_ix.accounts[1].pubkey, wsol_pf_accum);
+
+    let swap_params = |missing_dynamic_accounts_as_default| {
+        inf.get_swap_and_account_metas(&SwapParams {
+            swap_mode: SwapMode::ExactIn,
+            in_amount: 1_000_000_000,
+            out_amount: 0,
+            source_mint: *CONST_PUBKEYS.jupsol_mint(),
+            destination_mint: WSOL_MINT_ADDR.into(),
+            source_token_account: Pubkey::new_from_array([2; 32]),
+            destination_token_account: Pubkey::new_from_array([3; 32]),
+            token_transfer_authority: Pubkey::new_from_array(payer),
+            quote_mint_to_referrer: None,
+            jupiter_program_id: &Pubkey::default(),
+            missing_dynamic_accounts_as_default,
+        })
+    };
+    let err = swap_params(false).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<InfAmmErr>(),
+        Some(InfAmmErr::MissingDynamicAcc { pk }) if *pk == wsol_pf_accum.to_bytes()
+    ));
+
+    let metas = swap_params(true).unwrap().account_metas;
+    assert!(metas.iter().all(|m| m.pubkey != wsol_pf_accum));
+    assert!(metas.iter().any(|m| m.pubkey == Pubkey::default()));
+
+    // resolves once the accumulator is created
+    update(&mut inf, &ALL_FIXTURES);
+    assert!(inf.missing_dynamic_accs().is_empty());
+    swap_params(false).unwrap();
+}
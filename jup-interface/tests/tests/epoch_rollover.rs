@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::consts::WSOL_MINT_ADDR;
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode, SwapParams};
+use mollusk_svm::result::ProgramResult;
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{
+    fixtures_at_clock, mollusk_exec, mollusk_inf_fixture_ctl, mollusk_set_clock,
+    set_spl_stake_pool_last_update_epoch, KeyedUiAccount, CONST_PUBKEYS,
+};
+
+use crate::common::updated_inf_amm;
+
+const SLOT: u64 = 432_000;
+const EPOCH: u64 = 1;
+
+/// Quotes and executes a jupsol -> wsol swap on mollusk with clock at [`EPOCH`].
+///
+/// The AMM is in the default `ClockMode::ClockRef` with epoch 0,
+/// so the crate's epoch pre-check does not stop the swap
+fn exec_jupsol_to_wsol(onchain_state: &HashMap<Pubkey, Account>) -> ProgramResult {
+    let inf = updated_inf_amm(onchain_state);
+    let qp = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: *CONST_PUBKEYS.jupsol_mint(),
+        output_mint: WSOL_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    };
+    let quote = inf.quote(&qp).unwrap();
+    let [signer, inp_token_acc, out_token_acc] = [
+        "jupsol-token-acc-owner",
+        "jupsol-token-acc",
+        "wsol-token-acc",
+    ]
+    .map(|n| {
+        KeyedUiAccount::from_test_fixtures_json(n)
+            .into_keyed_account()
+            .0
+    });
+    let ix = inf
+        .swap_instruction(&SwapParams {
+            swap_mode: qp.swap_mode,
+            in_amount: quote.in_amount,
+            out_amount: quote.out_amount,
+            source_mint: qp.input_mint,
+            destination_mint: qp.output_mint,
+            source_token_account: inp_token_acc,
+            destination_token_account: out_token_acc,
+            token_transfer_authority: signer,
+            // dont-cares
+            quote_mint_to_referrer: Default::default(),
+            jupiter_program_id: &Default::default(),
+            missing_dynamic_accounts_as_default: Default::default(),
+        })
+        .unwrap();
+
+    let mut svm = mollusk_inf_fixture_ctl();
+    mollusk_set_clock(&mut svm, SLOT, EPOCH);
+    mollusk_exec(&svm, &ix, onchain_state).1.program_result
+}
+
+#[test]
+fn stale_spl_stake_pool_fails_onchain_fixture() {
+    let program_result = exec_jupsol_to_wsol(&fixtures_at_clock(SLOT, EPOCH));
+    assert!(
+        !matches!(program_result, ProgramResult::Success),
+        "{program_result:#?}"
+    );
+}
+
+#[test]
+fn updated_spl_stake_pool_succeeds_onchain_fixture() {
+    let mut onchain_state = fixtures_at_clock(SLOT, EPOCH);
+    set_spl_stake_pool_last_update_epoch(
+        onchain_state.get_mut(CONST_PUBKEYS.jupsol_pool()).unwrap(),
+        EPOCH,
+    );
+    let program_result = exec_jupsol_to_wsol(&onchain_state);
+    assert!(
+        matches!(program_result, ProgramResult::Success),
+        "{program_result:#?}"
+    );
+}
@@ -0,0 +1,88 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use inf1_jup_interface::{metrics::InfAmmMetrics, InfAmm};
+use inf1_std::inf1_ctl_core::keys::POOL_STATE_ID;
+use jupiter_amm_interface::Amm;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[derive(Debug, Default)]
+struct RecordingMetrics {
+    rollovers: Mutex<Vec<(u64, u64, Vec<[u8; 32]>)>>,
+}
+
+impl InfAmmMetrics for RecordingMetrics {
+    fn on_epoch_rollover(&self, prev_epoch: u64, epoch: u64, stale_mints: &[[u8; 32]]) {
+        self.rollovers
+            .lock()
+            .unwrap()
+            .push((prev_epoch, epoch, stale_mints.to_vec()));
+    }
+}
+
+fn update_from_fixtures(inf: &mut InfAmm) {
+    let am: HashMap<_, _, _> = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| {
+            let (k, v) = ALL_FIXTURES.get_key_value(&pk)?;
+            Some((*k, v.clone()))
+        })
+        .collect();
+    inf.update(&am).unwrap();
+}
+
+#[test]
+fn epoch_rollover_marks_stale_and_prioritizes_fixture() {
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let metrics = Arc::new(RecordingMetrics::default());
+    let mut inf = updated_inf_amm(&ALL_FIXTURES).with_deterministic_mode(0, 0);
+    // latest of all epoch affected LSTs, so that none are stale at this epoch
+    let calc_epoch = inf
+        .inner
+        .try_lst_state_list()
+        .unwrap()
+        .iter()
+        .filter_map(|l| inf.lst_calc_epoch(&l.into_lst_state().mint))
+        .max()
+        .unwrap();
+    inf.set_clock(0, calc_epoch);
+    update_from_fixtures(&mut inf);
+    assert!(inf.epoch_stale_mints().is_empty());
+    let mut inf = inf.with_metrics(metrics.clone());
+    let pool_state_pos = |inf: &InfAmm| {
+        inf.get_accounts_to_update()
+            .iter()
+            .position(|pk| *pk.as_array() == POOL_STATE_ID)
+            .unwrap()
+    };
+    assert_eq!(pool_state_pos(&inf), 0);
+
+    // marked stale as soon as the epoch increments, before any update
+    inf.set_clock(1, calc_epoch + 1);
+    let stale = inf.epoch_stale_mints();
+    assert!(stale.contains(jupsol.as_array()));
+    let accs = inf.get_accounts_to_update();
+    let jupsol_pool_pos = accs
+        .iter()
+        .position(|pk| pk == CONST_PUBKEYS.jupsol_pool())
+        .unwrap();
+    assert!(jupsol_pool_pos < pool_state_pos(&inf));
+
+    // fixtures' stake pools have not been cranked for the new epoch
+    update_from_fixtures(&mut inf);
+    let rollovers = metrics.rollovers.lock().unwrap();
+    assert_eq!(rollovers.len(), 1);
+    let (prev_epoch, epoch, stale_mints) = &rollovers[0];
+    assert_eq!((*prev_epoch, *epoch), (calc_epoch, calc_epoch + 1));
+    assert_eq!(*stale_mints, stale);
+    drop(rollovers);
+
+    // not reported again within the same epoch
+    update_from_fixtures(&mut inf);
+    assert_eq!(metrics.rollovers.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,106 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    err::{ErrCategory, InfAmmErr},
+};
+use inf1_std::{
+    err::InfErr,
+    inf1_pp_core::pair::Pair,
+    inf1_svc_ag_std::{inf1_svc_spl_core::calc::SplCalcErr, SvcAg},
+    quote::swap::err::SwapQuoteErr,
+    trade::TradeLimitTy,
+};
+use test_utils::ALL_FIXTURES;
+
+use crate::common::updated_inf_amm;
+
+const PK: [u8; 32] = [1; 32];
+
+/// Codes are part of the public API, these must never change
+#[test]
+fn codes_stable() {
+    [
+        (
+            InfAmmErr::Inf(InfErr::SwapQuote(SwapQuoteErr::InpCalc(SvcAg::Spl(
+                SplCalcErr::NotUpdated,
+            )))),
+            1001,
+            ErrCategory::Stale,
+        ),
+        (InfAmmErr::ClockNotUpdated, 1003, ErrCategory::Stale),
+        (
+            InfAmmErr::Inf(InfErr::AccDeser { pk: PK }),
+            2001,
+            ErrCategory::MissingData,
+        ),
+        (
+            InfAmmErr::Inf(InfErr::MissingAcc { pk: PK }),
+            2002,
+            ErrCategory::MissingData,
+        ),
+        (
+            InfAmmErr::MissingDynamicAcc { pk: PK },
+            2005,
+            ErrCategory::MissingData,
+        ),
+        (
+            InfAmmErr::ReserveAccountInvalid { mint: PK },
+            2006,
+            ErrCategory::MissingData,
+        ),
+        (
+            InfAmmErr::SamePairMint { mint: PK },
+            4001,
+            ErrCategory::Unsupported,
+        ),
+        (
+            InfAmmErr::ReferralFeesUnsupported { mint: PK },
+            4003,
+            ErrCategory::Unsupported,
+        ),
+        (
+            InfAmmErr::Inf(InfErr::UnsupportedMint { mint: PK }),
+            4004,
+            ErrCategory::Unsupported,
+        ),
+        (InfAmmErr::PoolDisabled, 5001, ErrCategory::Unavailable),
+        (InfAmmErr::PoolRebalancing, 5002, ErrCategory::Unavailable),
+        (
+            InfAmmErr::LpMintAuthorityMismatch {
+                expected: PK,
+                actual: None,
+            },
+            5003,
+            ErrCategory::Unavailable,
+        ),
+        (
+            InfAmmErr::Inf(InfErr::SwapQuote(SwapQuoteErr::Overflow)),
+            9002,
+            ErrCategory::Internal,
+        ),
+    ]
+    .into_iter()
+    .for_each(|(e, code, category)| {
+        assert_eq!(e.code(), code, "{e}");
+        assert_eq!(u32::from(e), code, "{e}");
+        assert_eq!(e.category(), category, "{e}");
+        assert!((category.base_code()..category.base_code() + 1000).contains(&code));
+    });
+}
+
+#[test]
+fn exact_out_liquidity_code_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let e = inf
+        .quote_raw(
+            &Pair {
+                inp: &WSOL_MINT_ADDR,
+                out: &INF_MINT_ADDR,
+            },
+            1_000_000_000,
+            TradeLimitTy::ExactOut,
+            inf.epoch(),
+        )
+        .unwrap_err();
+    assert_eq!(e.code(), 4002);
+    assert_eq!(e.category(), ErrCategory::Unsupported);
+}
@@ -0,0 +1,73 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    err::InfAmmErr,
+};
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const OUT_AMOUNT: u64 = 1_000_000_000;
+
+#[test]
+fn direct_exact_out_not_emulated_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let pair = Pair {
+        inp: CONST_PUBKEYS.jupsol_mint().as_array(),
+        out: &WSOL_MINT_ADDR,
+    };
+    let q = inf.quote_exact_out_emulated(&pair, OUT_AMOUNT).unwrap();
+    assert!(!q.approximate);
+    let (_, direct) = inf
+        .quote_raw(&pair, OUT_AMOUNT, TradeLimitTy::ExactOut, inf.epoch())
+        .unwrap();
+    assert_eq!(q.quote.inp, direct.inp);
+    assert_eq!(q.quote.out, OUT_AMOUNT);
+}
+
+#[test]
+fn emulated_exact_out_smallest_input_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    // INF program does not support ExactOut for AddLiquidity
+    let pair = Pair {
+        inp: &WSOL_MINT_ADDR,
+        out: &INF_MINT_ADDR,
+    };
+    assert!(matches!(
+        inf.quote_raw(&pair, OUT_AMOUNT, TradeLimitTy::ExactOut, inf.epoch()),
+        Err(InfAmmErr::ExactOutUnsupportedForLiquidity { .. })
+    ));
+
+    let q = inf.quote_exact_out_emulated(&pair, OUT_AMOUNT).unwrap();
+    assert!(q.approximate);
+    assert!(q.quote.out >= OUT_AMOUNT);
+    let (_, exact_in) = inf
+        .quote_raw(&pair, q.quote.inp, TradeLimitTy::ExactIn, inf.epoch())
+        .unwrap();
+    assert_eq!(exact_in.out, q.quote.out);
+    let (_, less) = inf
+        .quote_raw(&pair, q.quote.inp - 1, TradeLimitTy::ExactIn, inf.epoch())
+        .unwrap();
+    assert!(less.out < OUT_AMOUNT);
+}
+
+#[test]
+fn amm_quote_exact_out_never_emulated_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let err = inf
+        .quote(&QuoteParams {
+            amount: OUT_AMOUNT,
+            input_mint: WSOL_MINT_ADDR.into(),
+            output_mint: INF_MINT_ADDR.into(),
+            swap_mode: SwapMode::ExactOut,
+        })
+        .unwrap_err();
+    assert!(
+        matches!(
+            err.downcast_ref::<InfAmmErr>(),
+            Some(InfAmmErr::ExactOutUnsupportedForLiquidity { .. })
+        ),
+        "{err}"
+    );
+}
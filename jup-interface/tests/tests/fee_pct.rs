@@ -0,0 +1,141 @@
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR,
+    fee_pct::{FeePctRounding, FeePctRoundingMode},
+    to_jup_quote_with_fee_pct_rounding, JupQuoteFees,
+};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use rust_decimal::Decimal;
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const INP_MINT: [u8; 32] = [1; 32];
+const OUT_MINT: [u8; 32] = [2; 32];
+
+/// fee_pct of a quote charging `fee` LP fees in the input mint on `in_amount`
+fn inp_fee_pct(in_amount: u64, fee: u64, rounding: Option<FeePctRounding>) -> Decimal {
+    to_jup_quote_with_fee_pct_rounding(
+        &INP_MINT,
+        inf1_std::quote::Quote {
+            inp: in_amount,
+            out: 1,
+            lp_fee: fee,
+            protocol_fee: 0,
+            inp_mint: INP_MINT,
+            out_mint: OUT_MINT,
+        },
+        JupQuoteFees::All,
+        rounding,
+    )
+    .unwrap()
+    .fee_pct
+}
+
+#[test]
+fn fee_pct_tiny_fee_nonzero() {
+    let fee_pct = inp_fee_pct(u64::MAX, 1, None);
+    assert!(fee_pct > Decimal::ZERO);
+    assert!((fee_pct * Decimal::from(u64::MAX) - Decimal::ONE).abs() < Decimal::new(1, 8));
+}
+
+#[test]
+fn fee_pct_large_amounts_exact() {
+    assert_eq!(inp_fee_pct(u64::MAX, u64::MAX, None), Decimal::ONE);
+    // f64 rounds both to the same value, giving exactly 1
+    assert!(inp_fee_pct(u64::MAX, u64::MAX - 1, None) < Decimal::ONE);
+}
+
+#[test]
+fn fee_pct_deterministic_repr() {
+    assert_eq!(
+        inp_fee_pct(3, 1, None).to_string(),
+        "0.3333333333333333333333333333"
+    );
+    assert_eq!(inp_fee_pct(10_000, 1, None).to_string(), "0.0001");
+}
+
+#[test]
+fn fee_pct_out_mint_denom_includes_fees() {
+    let q = to_jup_quote_with_fee_pct_rounding(
+        &OUT_MINT,
+        inf1_std::quote::Quote {
+            inp: 1_000_000,
+            out: 999_998,
+            lp_fee: 1,
+            protocol_fee: 1,
+            inp_mint: INP_MINT,
+            out_mint: OUT_MINT,
+        },
+        JupQuoteFees::LpOnly,
+        None,
+    )
+    .unwrap();
+    assert_eq!(q.fee_pct.to_string(), "0.000001");
+}
+
+#[test]
+fn fee_pct_zero_denom_errs() {
+    assert!(to_jup_quote_with_fee_pct_rounding(
+        &INP_MINT,
+        inf1_std::quote::Quote {
+            inp: 0,
+            out: 0,
+            lp_fee: 0,
+            protocol_fee: 0,
+            inp_mint: INP_MINT,
+            out_mint: OUT_MINT,
+        },
+        JupQuoteFees::All,
+        None,
+    )
+    .is_err());
+}
+
+#[test]
+fn fee_pct_rounding_modes() {
+    let rounded = |in_amount, fee, dp, mode| {
+        inp_fee_pct(in_amount, fee, Some(FeePctRounding { dp, mode })).to_string()
+    };
+    // 1/3
+    assert_eq!(rounded(3, 1, 4, FeePctRoundingMode::HalfEven), "0.3333");
+    assert_eq!(rounded(3, 1, 4, FeePctRoundingMode::Down), "0.3333");
+    assert_eq!(rounded(3, 1, 4, FeePctRoundingMode::Up), "0.3334");
+    // 2/3
+    assert_eq!(rounded(3, 2, 4, FeePctRoundingMode::HalfEven), "0.6667");
+    assert_eq!(rounded(3, 2, 4, FeePctRoundingMode::Down), "0.6666");
+    // 1/8 = 0.125, a midpoint
+    assert_eq!(rounded(8, 1, 2, FeePctRoundingMode::HalfEven), "0.12");
+    assert_eq!(rounded(8, 1, 2, FeePctRoundingMode::Up), "0.13");
+    // tiny fees do not round up to 0 with Up
+    assert!(
+        inp_fee_pct(
+            u64::MAX,
+            1,
+            Some(FeePctRounding {
+                dp: 6,
+                mode: FeePctRoundingMode::Up
+            })
+        ) > Decimal::ZERO
+    );
+}
+
+#[test]
+fn with_fee_pct_rounding_fixture() {
+    let exact = updated_inf_amm(&ALL_FIXTURES);
+    let rounded = exact.clone().with_fee_pct_rounding(Some(FeePctRounding {
+        dp: 4,
+        mode: FeePctRoundingMode::Up,
+    }));
+    let qp = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: *CONST_PUBKEYS.jupsol_mint(),
+        output_mint: Pubkey::new_from_array(WSOL_MINT_ADDR),
+        swap_mode: SwapMode::ExactIn,
+    };
+    let [exact, rounded] = [exact, rounded].map(|inf| inf.quote(&qp).unwrap());
+    assert!(rounded.fee_pct.scale() <= 4);
+    assert!(rounded.fee_pct >= exact.fee_pct);
+    assert!(rounded.fee_pct - exact.fee_pct < Decimal::new(1, 4));
+    assert_eq!(rounded.out_amount, exact.out_amount);
+}
@@ -0,0 +1,86 @@
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR,
+    feed::{AccountStream, InfAmmFeed},
+    InfAmm,
+};
+use inf1_std::inf1_ctl_core::keys::LST_STATE_LIST_ID;
+use jupiter_amm_interface::{AccountMap, Amm, KeyedAccount, QuoteParams, SwapMode};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+/// Yields the subscribed accounts of [`ALL_FIXTURES`] for `batches_left` batches
+#[derive(Debug)]
+struct FixtureStream {
+    subscribed: Vec<Pubkey>,
+    subscribe_calls: usize,
+    batches_left: usize,
+}
+
+impl AccountStream for FixtureStream {
+    async fn subscribe(&mut self, pks: &[Pubkey]) -> anyhow::Result<()> {
+        self.subscribed = pks.to_vec();
+        self.subscribe_calls += 1;
+        Ok(())
+    }
+
+    async fn next_batch(&mut self) -> Option<AccountMap> {
+        self.batches_left = self.batches_left.checked_sub(1)?;
+        Some(
+            self.subscribed
+                .iter()
+                .filter_map(|pk| {
+                    let (k, v) = ALL_FIXTURES.get_key_value(pk)?;
+                    Some((*k, v.clone()))
+                })
+                .collect(),
+        )
+    }
+}
+
+fn new_inf_amm() -> InfAmm {
+    let (key, account) = ALL_FIXTURES
+        .get_key_value(&LST_STATE_LIST_ID.into())
+        .unwrap();
+    InfAmm::new(
+        &KeyedAccount {
+            key: *key,
+            account: account.clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        SPL_LSTS.into_iter().collect(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn feed_publishes_updated_amm_fixture() {
+    let feed = InfAmmFeed::new(
+        new_inf_amm(),
+        FixtureStream {
+            subscribed: Vec::new(),
+            subscribe_calls: 0,
+            batches_left: 3,
+        },
+    );
+    let reader = feed.reader();
+    assert_eq!(reader.load().generation, 0);
+    feed.run().await.unwrap();
+
+    let published = reader.load();
+    assert!(published.generation > 0);
+    let expected = updated_inf_amm(&ALL_FIXTURES);
+    let qp = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: *CONST_PUBKEYS.jupsol_mint(),
+        output_mint: WSOL_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    };
+    let [published_quote, expected_quote] =
+        [&*published, &expected].map(|inf| inf.quote(&qp).unwrap());
+    assert_eq!(published_quote.in_amount, expected_quote.in_amount);
+    assert_eq!(published_quote.out_amount, expected_quote.out_amount);
+    assert_eq!(published_quote.fee_amount, expected_quote.fee_amount);
+}
@@ -0,0 +1,86 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    err::InfAmmErr,
+};
+use inf1_std::{inf1_ctl_core::keys::POOL_STATE_ID, inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn lst_health_records_spl_pool_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let health = inf
+        .lst_health(CONST_PUBKEYS.jupsol_mint().as_array())
+        .unwrap();
+
+    let pool = health
+        .applied_accs
+        .iter()
+        .find(|a| a.pubkey == *CONST_PUBKEYS.jupsol_pool())
+        .unwrap();
+    assert_eq!(pool.generation, inf.generation);
+    assert!(health.calc_epoch.is_some());
+}
+
+#[test]
+fn lst_health_wsol_not_epoch_affected_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let health = inf.lst_health(&WSOL_MINT_ADDR).unwrap();
+
+    assert!(health.calc_epoch.is_none());
+}
+
+#[test]
+fn lp_mint_authority_ok_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let health = inf.lp_mint_health().unwrap();
+
+    assert_eq!(health.mint_authority, Some(POOL_STATE_ID));
+    assert_eq!(health.decimals, 9);
+    assert!(inf.is_lp_mint_authority_ok());
+}
+
+#[test]
+fn lp_mint_authority_mismatch_refuses_lp_pairs_fixture() {
+    const ATTACKER: [u8; 32] = [7; 32];
+
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state
+        .get_mut(&Pubkey::new_from_array(INF_MINT_ADDR))
+        .unwrap()
+        .data[4..36]
+        .copy_from_slice(&ATTACKER);
+    let inf = updated_inf_amm(&onchain_state);
+    assert!(!inf.is_lp_mint_authority_ok());
+
+    let err = inf
+        .quote_raw(
+            &Pair {
+                inp: &WSOL_MINT_ADDR,
+                out: &INF_MINT_ADDR,
+            },
+            1_000_000,
+            TradeLimitTy::ExactIn,
+            0,
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        InfAmmErr::LpMintAuthorityMismatch { expected, actual }
+            if expected == POOL_STATE_ID && actual == Some(ATTACKER)
+    ));
+
+    // LST-LST pairs are unaffected
+    inf.quote_raw(
+        &Pair {
+            inp: CONST_PUBKEYS.jupsol_mint().as_array(),
+            out: &WSOL_MINT_ADDR,
+        },
+        1_000_000,
+        TradeLimitTy::ExactIn,
+        0,
+    )
+    .unwrap();
+}
@@ -0,0 +1,76 @@
+use inf1_jup_interface::consts::{INF_MINT_ADDR, WSOL_MINT_ADDR};
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn swap_impact_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let pair = Pair {
+        inp: jupsol,
+        out: &WSOL_MINT_ADDR,
+    };
+    let inp_before = inf.reserves_balance(jupsol).unwrap();
+    let out_before = inf.reserves_balance(&WSOL_MINT_ADDR).unwrap();
+
+    for limit_ty in [TradeLimitTy::ExactIn, TradeLimitTy::ExactOut] {
+        let q = inf
+            .quote_with_impact(&pair, 1_000_000_000, limit_ty)
+            .unwrap();
+        let protocol_fee = |mint: &[u8; 32]| {
+            if q.fee_mint == *mint {
+                q.quote.protocol_fee
+            } else {
+                0
+            }
+        };
+        assert_eq!(
+            q.impact.inp_reserve_after,
+            Some(inp_before + q.quote.inp - protocol_fee(jupsol))
+        );
+        assert_eq!(
+            q.impact.out_reserve_after,
+            Some(out_before - q.quote.out - protocol_fee(&WSOL_MINT_ADDR))
+        );
+    }
+}
+
+#[test]
+fn liquidity_impact_lp_leg_none_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let wsol_before = inf.reserves_balance(&WSOL_MINT_ADDR).unwrap();
+
+    let add = inf
+        .quote_with_impact(
+            &Pair {
+                inp: &WSOL_MINT_ADDR,
+                out: &INF_MINT_ADDR,
+            },
+            1_000_000_000,
+            TradeLimitTy::ExactIn,
+        )
+        .unwrap();
+    assert_eq!(
+        add.impact.inp_reserve_after,
+        Some(wsol_before + add.quote.inp - add.quote.protocol_fee)
+    );
+    assert_eq!(add.impact.out_reserve_after, None);
+
+    let remove = inf
+        .quote_with_impact(
+            &Pair {
+                inp: &INF_MINT_ADDR,
+                out: &WSOL_MINT_ADDR,
+            },
+            1_000_000_000,
+            TradeLimitTy::ExactIn,
+        )
+        .unwrap();
+    assert_eq!(remove.impact.inp_reserve_after, None);
+    assert_eq!(
+        remove.impact.out_reserve_after,
+        Some(wsol_before - remove.quote.out - remove.quote.protocol_fee)
+    );
+}
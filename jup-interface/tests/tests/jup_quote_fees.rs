@@ -0,0 +1,39 @@
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, JupQuoteFees};
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const QUOTE_PARAMS: QuoteParams = QuoteParams {
+    amount: 1_000_000_000,
+    input_mint: *CONST_PUBKEYS.jupsol_mint(),
+    output_mint: Pubkey::new_from_array(WSOL_MINT_ADDR),
+    swap_mode: SwapMode::ExactIn,
+};
+
+#[test]
+fn lp_only_excludes_protocol_fee_fixture() {
+    let all = updated_inf_amm(&ALL_FIXTURES);
+    let lp_only = all.clone().with_jup_quote_fees(JupQuoteFees::LpOnly);
+
+    let (_, raw) = all
+        .quote_raw(
+            &Pair {
+                inp: QUOTE_PARAMS.input_mint.as_array(),
+                out: QUOTE_PARAMS.output_mint.as_array(),
+            },
+            QUOTE_PARAMS.amount,
+            TradeLimitTy::ExactIn,
+            0,
+        )
+        .unwrap();
+    let [all, lp_only] = [all, lp_only].map(|inf| inf.quote(&QUOTE_PARAMS).unwrap());
+
+    assert_eq!(all.fee_amount, raw.lp_fee + raw.protocol_fee);
+    assert_eq!(lp_only.fee_amount, raw.lp_fee);
+    assert!(lp_only.fee_pct <= all.fee_pct);
+    assert_eq!(lp_only.in_amount, all.in_amount);
+    assert_eq!(lp_only.out_amount, all.out_amount);
+}
@@ -0,0 +1,62 @@
+use inf1_jup_interface::trade_ix_args_to_jup_swap;
+use inf1_std::{
+    inf1_ctl_core::instructions::{liquidity::IxArgs as LiqIxArgs, swap::IxArgs as SwapIxArgs},
+    trade::Trade,
+};
+use jupiter_amm_interface::Swap;
+
+const LIQ_IX_ARGS: LiqIxArgs = LiqIxArgs {
+    lst_value_calc_accs: 3,
+    lst_index: 7,
+    amount: 1_000_000_000,
+    min_out: 0,
+};
+
+const SWAP_IX_ARGS: SwapIxArgs = SwapIxArgs {
+    inp_lst_value_calc_accs: 2,
+    out_lst_value_calc_accs: 5,
+    inp_lst_index: 1,
+    out_lst_index: 9,
+    amount: 1_000_000_000,
+    limit: 0,
+};
+
+#[test]
+fn add_liquidity_to_jup_swap() {
+    assert!(matches!(
+        trade_ix_args_to_jup_swap(&Trade::AddLiquidity(LIQ_IX_ARGS)),
+        Swap::SanctumSAddLiquidity {
+            lst_value_calc_accs: 3,
+            lst_index: 7,
+        }
+    ));
+}
+
+#[test]
+fn remove_liquidity_to_jup_swap() {
+    assert!(matches!(
+        trade_ix_args_to_jup_swap(&Trade::RemoveLiquidity(LIQ_IX_ARGS)),
+        Swap::SanctumSRemoveLiquidity {
+            lst_value_calc_accs: 3,
+            lst_index: 7,
+        }
+    ));
+}
+
+#[test]
+fn swaps_to_jup_swap() {
+    for ix_args in [
+        Trade::SwapExactIn(SWAP_IX_ARGS),
+        Trade::SwapExactOut(SWAP_IX_ARGS),
+    ] {
+        assert!(matches!(
+            trade_ix_args_to_jup_swap(&ix_args),
+            Swap::SanctumS {
+                src_lst_value_calc_accs: 2,
+                dst_lst_value_calc_accs: 5,
+                src_lst_index: 1,
+                dst_lst_index: 9,
+            }
+        ));
+    }
+}
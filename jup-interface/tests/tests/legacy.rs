@@ -0,0 +1,50 @@
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, legacy::LegacyInfAmm};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use jupiter_amm_interface_legacy as legacy;
+use solana_sdk_legacy::pubkey::Pubkey as LegacyPubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn legacy_quote_same_as_inf_amm_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let legacy_inf = LegacyInfAmm::from(inf.clone());
+
+    assert_eq!(
+        legacy::Amm::key(&legacy_inf).to_bytes(),
+        inf.key().to_bytes()
+    );
+    assert_eq!(
+        legacy::Amm::get_accounts_to_update(&legacy_inf).len(),
+        inf.get_accounts_to_update().len()
+    );
+
+    for swap_mode in [SwapMode::ExactIn, SwapMode::ExactOut] {
+        let expected = inf
+            .quote(&QuoteParams {
+                amount: 1_000_000_000,
+                input_mint: *CONST_PUBKEYS.jupsol_mint(),
+                output_mint: WSOL_MINT_ADDR.into(),
+                swap_mode,
+            })
+            .unwrap();
+        let q = legacy::Amm::quote(
+            &legacy_inf,
+            &legacy::QuoteParams {
+                amount: 1_000_000_000,
+                input_mint: LegacyPubkey::new_from_array(CONST_PUBKEYS.jupsol_mint().to_bytes()),
+                output_mint: LegacyPubkey::new_from_array(WSOL_MINT_ADDR),
+                swap_mode: match swap_mode {
+                    SwapMode::ExactIn => legacy::SwapMode::ExactIn,
+                    SwapMode::ExactOut => legacy::SwapMode::ExactOut,
+                },
+            },
+        )
+        .unwrap();
+        assert_eq!(q.in_amount, expected.in_amount);
+        assert_eq!(q.out_amount, expected.out_amount);
+        assert_eq!(q.fee_amount, expected.fee_amount);
+        assert_eq!(q.fee_pct, expected.fee_pct);
+    }
+}
@@ -0,0 +1,52 @@
+use inf1_jup_interface::consts::INF_MINT_ADDR;
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const AMOUNT: u64 = 1_000_000_000;
+
+#[test]
+fn preview_add_liquidity_matches_quote_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let preview = inf
+        .preview_add_liquidity(jupsol.as_array(), AMOUNT)
+        .unwrap();
+    let quote = inf
+        .quote(&QuoteParams {
+            amount: AMOUNT,
+            input_mint: *jupsol,
+            output_mint: INF_MINT_ADDR.into(),
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+
+    assert_eq!(preview.mint, jupsol.to_bytes());
+    assert_eq!(preview.in_amount, quote.in_amount);
+    assert_eq!(preview.out_amount, quote.out_amount);
+    assert_eq!(preview.lp_fee + preview.protocol_fee, quote.fee_amount);
+    assert!(preview.pool_sol_value_after.unwrap() > inf.inner.pool.total_sol_value);
+}
+
+#[test]
+fn preview_remove_liquidity_matches_quote_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let preview = inf
+        .preview_remove_liquidity(jupsol.as_array(), AMOUNT)
+        .unwrap();
+    let quote = inf
+        .quote(&QuoteParams {
+            amount: AMOUNT,
+            input_mint: INF_MINT_ADDR.into(),
+            output_mint: *jupsol,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+
+    assert_eq!(preview.in_amount, quote.in_amount);
+    assert_eq!(preview.out_amount, quote.out_amount);
+    assert_eq!(preview.lp_fee + preview.protocol_fee, quote.fee_amount);
+    assert!(preview.pool_sol_value_after.unwrap() < inf.inner.pool.total_sol_value);
+}
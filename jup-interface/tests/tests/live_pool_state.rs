@@ -0,0 +1,79 @@
+use inf1_jup_interface::{rebalance::RebalanceParams, InfAmm};
+use inf1_std::inf1_ctl_core::{
+    accounts::lst_state_list::LstStatePackedList, keys::LST_STATE_LIST_ID,
+};
+use jupiter_amm_interface::KeyedAccount;
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+#[test]
+fn no_live_pool_state_before_update_fixture() {
+    let (key, account) = ALL_FIXTURES
+        .get_key_value(&LST_STATE_LIST_ID.into())
+        .unwrap();
+    let inf = InfAmm::new(
+        &KeyedAccount {
+            key: *key,
+            account: account.clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        SPL_LSTS.into_iter().collect(),
+    )
+    .unwrap();
+    assert!(inf.live_pool_state().is_none());
+    assert_eq!(inf.admin(), None);
+    assert_eq!(inf.rebalance_authority(), None);
+    assert_eq!(inf.protocol_fee_beneficiary(), None);
+    inf.rebalance_ixs(&RebalanceParams {
+        inp_mint: *CONST_PUBKEYS.jupsol_mint(),
+        out_mint: *CONST_PUBKEYS.jupsol_mint(),
+        amount: 1,
+        max_inp_amount: 1,
+        withdraw_to: Pubkey::new_from_array([1; 32]),
+    })
+    .unwrap_err();
+}
+
+#[test]
+fn live_pool_state_after_update_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let pool = inf.live_pool_state().unwrap();
+    assert_ne!(pool.protocol_fee_beneficiary, [0; 32]);
+    assert_ne!(pool.rebalance_authority, [0; 32]);
+    assert_eq!(
+        inf.protocol_fee_beneficiary(),
+        Some(Pubkey::new_from_array(pool.protocol_fee_beneficiary))
+    );
+    assert_eq!(
+        inf.rebalance_authority(),
+        Some(Pubkey::new_from_array(pool.rebalance_authority))
+    );
+    assert_eq!(inf.admin(), Some(Pubkey::new_from_array(pool.admin)));
+}
+
+#[test]
+fn raw_accessors_same_as_fixtures_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    assert!(std::ptr::eq(
+        inf.pool_state(),
+        inf.live_pool_state().unwrap()
+    ));
+
+    let list = ALL_FIXTURES.get(&LST_STATE_LIST_ID.into()).unwrap();
+    let expected: Vec<_> = LstStatePackedList::of_acc_data(&list.data)
+        .unwrap()
+        .0
+        .iter()
+        .map(|l| l.into_lst_state().mint)
+        .collect();
+    assert_eq!(
+        inf.lst_states().map(|l| l.mint).collect::<Vec<_>>(),
+        expected
+    );
+    assert!(inf
+        .lst_states()
+        .any(|l| l.mint == *CONST_PUBKEYS.jupsol_mint().as_array()));
+}
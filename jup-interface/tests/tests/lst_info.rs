@@ -0,0 +1,125 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    lst_info::{MetaRole, ASSOCIATED_TOKEN_PROGRAM_ID},
+    writable::UserAccs,
+};
+use inf1_std::{inf1_ctl_core::keys::POOL_STATE_ID, inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use solana_pubkey::Pubkey;
+use test_utils::{KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+#[test]
+fn lst_info_reserves_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let mint = CONST_PUBKEYS.jupsol_mint();
+    let info = inf
+        .lst_info(mint.as_array(), TOKEN_PROGRAM_ID.as_array())
+        .unwrap();
+
+    let (expected, _bump) = Pubkey::find_program_address(
+        &[
+            POOL_STATE_ID.as_slice(),
+            TOKEN_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &Pubkey::new_from_array(ASSOCIATED_TOKEN_PROGRAM_ID),
+    );
+    assert_eq!(info.reserves, expected.to_bytes());
+    assert!(ALL_FIXTURES.contains_key(&expected));
+    assert_eq!(info.mint, *mint.as_array());
+}
+
+#[test]
+fn lst_info_not_on_list_none_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    assert!(inf
+        .lst_info(&[1; 32], TOKEN_PROGRAM_ID.as_array())
+        .is_none());
+}
+
+#[test]
+fn lst_info_protocol_fee_accumulator_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let info = inf
+        .lst_info(&WSOL_MINT_ADDR, TOKEN_PROGRAM_ID.as_array())
+        .unwrap();
+
+    let (expected, _) =
+        KeyedUiAccount::from_test_fixtures_json("wsol-pf-accum").into_keyed_account();
+    assert_eq!(info.protocol_fee_accumulator, expected.to_bytes());
+}
+
+fn annotated_roles(inp: &[u8; 32], out: &[u8; 32]) -> Vec<(MetaRole, Option<[u8; 32]>)> {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let pair = Pair { inp, out };
+    let annotated = inf
+        .annotated_accounts_for_pair(&pair, TradeLimitTy::ExactIn, &UserAccs::PLACEHOLDERS)
+        .unwrap();
+    let metas = inf
+        .accounts_for_pair(&pair, TradeLimitTy::ExactIn, &UserAccs::PLACEHOLDERS)
+        .unwrap();
+    assert_eq!(annotated.len(), metas.len());
+    for (a, m) in annotated.iter().zip(&metas) {
+        assert_eq!(a.pubkey, m.pubkey.to_bytes());
+        assert_eq!(a.is_writable, m.is_writable);
+    }
+    annotated.into_iter().map(|a| (a.role, a.mint)).collect()
+}
+
+#[test]
+fn annotated_swap_accounts_fixture() {
+    let jupsol = *CONST_PUBKEYS.jupsol_mint().as_array();
+    let wsol = WSOL_MINT_ADDR;
+    let expected: Vec<_> = [
+        (MetaRole::InfProgram, None),
+        (MetaRole::Signer, None),
+        (MetaRole::Mint, Some(jupsol)),
+        (MetaRole::Mint, Some(wsol)),
+        (MetaRole::UserTokenAcc, Some(jupsol)),
+        (MetaRole::UserTokenAcc, Some(wsol)),
+        (MetaRole::ProtocolFeeAccumulator, Some(wsol)),
+        (MetaRole::TokenProgram, Some(jupsol)),
+        (MetaRole::TokenProgram, Some(wsol)),
+        (MetaRole::PoolState, None),
+        (MetaRole::LstStateList, None),
+        (MetaRole::Reserves, Some(jupsol)),
+        (MetaRole::Reserves, Some(wsol)),
+    ]
+    .into_iter()
+    // jupSOL: program, calculator state, stake pool, stake pool program, stake pool program data
+    .chain([(MetaRole::SolValCalc, Some(jupsol)); 5])
+    // wSOL: program only
+    .chain([(MetaRole::SolValCalc, Some(wsol))])
+    // flatslab: program, slab
+    .chain([(MetaRole::Pricing, None); 2])
+    .collect();
+    assert_eq!(annotated_roles(&jupsol, &wsol), expected);
+}
+
+#[test]
+fn annotated_remove_liquidity_accounts_fixture() {
+    let wsol = WSOL_MINT_ADDR;
+    let expected: Vec<_> = [
+        (MetaRole::InfProgram, None),
+        (MetaRole::Signer, None),
+        (MetaRole::Mint, Some(wsol)),
+        (MetaRole::Mint, Some(INF_MINT_ADDR)),
+        (MetaRole::UserTokenAcc, Some(wsol)),
+        (MetaRole::UserTokenAcc, Some(INF_MINT_ADDR)),
+        (MetaRole::ProtocolFeeAccumulator, Some(wsol)),
+        (MetaRole::TokenProgram, Some(wsol)),
+        (MetaRole::TokenProgram, Some(INF_MINT_ADDR)),
+        (MetaRole::PoolState, None),
+        (MetaRole::LstStateList, None),
+        (MetaRole::Reserves, Some(wsol)),
+        (MetaRole::SolValCalc, Some(wsol)),
+        (MetaRole::Pricing, None),
+        (MetaRole::Pricing, None),
+    ]
+    .into();
+    assert_eq!(annotated_roles(&INF_MINT_ADDR, &wsol), expected);
+}
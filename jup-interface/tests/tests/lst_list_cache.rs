@@ -0,0 +1,62 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use inf1_jup_interface::lst_list_cache::LstListCache;
+use serde_json::json;
+
+fn cache(name: &str) -> LstListCache {
+    LstListCache::new(std::env::temp_dir().join(format!("inf1-jup-interface-{name}.json")))
+}
+
+#[test]
+fn fresh_cache_loaded_without_fetching() {
+    let cache = cache("lst-list-cache-fresh");
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    std::fs::write(
+        &cache.path,
+        json!({ "fetched_at": now, "etag": "abc", "body": { "data": [] } }).to_string(),
+    )
+    .unwrap();
+    // both the remote and bundled lists are nonempty
+    let lsts = cache.load();
+    std::fs::remove_file(&cache.path).unwrap();
+
+    assert!(lsts.is_empty());
+}
+
+#[test]
+fn store_roundtrip() {
+    let cache = cache("lst-list-cache-store").with_ttl(Duration::from_secs(60));
+    cache.store(Some("abc"), json!({ "data": [] })).unwrap();
+    let cached = cache.read().unwrap();
+
+    // invalid responses do not replace the cache
+    cache
+        .store(Some("def"), json!({ "nodata": [] }))
+        .unwrap_err();
+    let after_invalid = cache.read().unwrap();
+    std::fs::remove_file(&cache.path).unwrap();
+
+    assert_eq!(cached.etag.as_deref(), Some("abc"));
+    assert!(cached.lsts.is_empty());
+    assert!(cached.is_fresh(cache.ttl));
+    assert!(!cached.is_fresh(Duration::ZERO));
+    assert_eq!(after_invalid.etag.as_deref(), Some("abc"));
+}
+
+#[test]
+fn read_rejects_invalid() {
+    let cache = cache("lst-list-cache-invalid");
+    for invalid in [
+        json!([]),
+        json!({ "etag": null, "body": { "data": [] } }),
+        json!({ "fetched_at": 0, "etag": null }),
+        json!({ "fetched_at": 0, "etag": null, "body": [] }),
+    ] {
+        std::fs::write(&cache.path, invalid.to_string()).unwrap();
+        assert!(cache.read().is_err(), "{invalid}");
+    }
+    std::fs::remove_file(&cache.path).unwrap();
+}
@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+
+use inf1_jup_interface::{
+    config::InfAmmConfig,
+    consts::WSOL_MINT_ADDR,
+    lst_state_list_format::{
+        version_of, LstStateListFormat, LstStateListFormatErr, Packed,
+        DEFAULT_LST_STATE_LIST_FORMATS, PACKED_VERSION,
+    },
+    InfAmm,
+};
+use inf1_std::inf1_ctl_core::{
+    accounts::lst_state_list::LstStatePackedList, keys::LST_STATE_LIST_ID,
+};
+use jupiter_amm_interface::{AccountMap, Amm, KeyedAccount, QuoteParams, SwapMode};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+use crate::common::updated_inf_amm;
+
+const PREFIXED_VERSION: u8 = 7;
+
+/// A version byte followed by the packed layout
+#[derive(Debug)]
+struct Prefixed;
+
+impl LstStateListFormat for Prefixed {
+    fn version(&self) -> u8 {
+        PREFIXED_VERSION
+    }
+
+    fn to_packed<'a>(&self, data: &'a [u8]) -> Option<Cow<'a, [u8]>> {
+        let packed = data.get(1..)?;
+        LstStatePackedList::of_acc_data(packed).map(|_| Cow::Borrowed(packed))
+    }
+}
+
+static WITH_PREFIXED: &[&dyn LstStateListFormat] = &[&Packed, &Prefixed];
+
+/// [`ALL_FIXTURES`] with the LST state list in the [`Prefixed`] layout
+fn prefixed_fixtures() -> AccountMap {
+    let mut account_map: AccountMap = ALL_FIXTURES.iter().map(|(k, v)| (*k, v.clone())).collect();
+    account_map
+        .get_mut(&Pubkey::new_from_array(LST_STATE_LIST_ID))
+        .unwrap()
+        .data
+        .insert(0, PREFIXED_VERSION);
+    account_map
+}
+
+fn updated_inf_amm_with(
+    account_map: &AccountMap,
+    formats: &'static [&'static dyn LstStateListFormat],
+) -> anyhow::Result<InfAmm> {
+    let key = Pubkey::new_from_array(LST_STATE_LIST_ID);
+    let mut inf = InfAmm::builder()
+        .spl_lsts(SPL_LSTS.into_iter().collect())
+        .cluster(InfAmmConfig {
+            lst_state_list_formats: formats,
+            ..InfAmmConfig::MAINNET
+        })
+        .build(&KeyedAccount {
+            key,
+            account: account_map[&key].clone(),
+            params: None,
+        })?;
+    // 1st update might fail bec DEFAULT_MAINNET_POOL might be stale
+    let _: Result<_, _> = inf.update(account_map);
+    inf.update(account_map)?;
+    Ok(inf)
+}
+
+#[test]
+fn version_of_packed_fixture() {
+    assert_eq!(version_of(&[]), PACKED_VERSION);
+    assert_eq!(
+        version_of(&ALL_FIXTURES[&Pubkey::new_from_array(LST_STATE_LIST_ID)].data),
+        PACKED_VERSION
+    );
+    assert_eq!(
+        version_of(&prefixed_fixtures()[&Pubkey::new_from_array(LST_STATE_LIST_ID)].data),
+        PREFIXED_VERSION
+    );
+}
+
+#[test]
+fn new_layout_same_as_packed_fixture() {
+    let packed = updated_inf_amm(&ALL_FIXTURES);
+    let prefixed = updated_inf_amm_with(&prefixed_fixtures(), WITH_PREFIXED).unwrap();
+
+    assert_eq!(
+        prefixed.inner.try_lst_state_list().unwrap().len(),
+        packed.inner.try_lst_state_list().unwrap().len()
+    );
+    let quote_params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: *CONST_PUBKEYS.jupsol_mint(),
+        output_mint: Pubkey::new_from_array(WSOL_MINT_ADDR),
+        swap_mode: SwapMode::ExactIn,
+    };
+    assert_eq!(
+        prefixed.quote(&quote_params).unwrap().out_amount,
+        packed.quote(&quote_params).unwrap().out_amount
+    );
+    assert_eq!(prefixed.validate_account_map(&prefixed_fixtures()), vec![]);
+}
+
+#[test]
+fn unknown_version_rejected_fixture() {
+    let err =
+        updated_inf_amm_with(&prefixed_fixtures(), DEFAULT_LST_STATE_LIST_FORMATS).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<LstStateListFormatErr>(),
+        Some(&LstStateListFormatErr::UnknownVersion(PREFIXED_VERSION))
+    );
+}
@@ -0,0 +1,19 @@
+use test_utils::ALL_FIXTURES;
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn memory_usage_accounts_for_state_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let usage = inf.memory_usage();
+
+    assert_eq!(
+        usage.lst_state_list_data,
+        inf.inner.lst_state_list_data.len()
+    );
+    assert!(usage.lst_calcs > 0);
+    assert!(usage.spl_lsts > 0);
+    assert!(usage.lst_health > 0);
+    assert_eq!(usage.quote_cache, 0);
+    assert!(usage.total() > usage.amm);
+}
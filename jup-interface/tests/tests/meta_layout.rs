@@ -0,0 +1,199 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, META_LAYOUT_VERSION, WSOL_MINT_ADDR},
+    lst_info::TOKEN_PROGRAM_ID,
+};
+use inf1_std::inf1_ctl_core::{
+    self,
+    keys::{LST_STATE_LIST_ID, POOL_STATE_ID},
+};
+use jupiter_amm_interface::{Amm, SwapMode, SwapParams};
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+use test_utils::{KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const SIGNER: Pubkey = Pubkey::new_from_array([5; 32]);
+const INP_TOKEN_ACC: Pubkey = Pubkey::new_from_array([3; 32]);
+const OUT_TOKEN_ACC: Pubkey = Pubkey::new_from_array([4; 32]);
+
+fn metas(swap_mode: SwapMode, inp: &[u8; 32], out: &[u8; 32]) -> Vec<AccountMeta> {
+    updated_inf_amm(&ALL_FIXTURES)
+        .get_swap_and_account_metas(&SwapParams {
+            swap_mode,
+            in_amount: 1_000_000_000,
+            out_amount: match swap_mode {
+                SwapMode::ExactIn => 0,
+                SwapMode::ExactOut => 1_000_000,
+            },
+            source_mint: (*inp).into(),
+            destination_mint: (*out).into(),
+            source_token_account: INP_TOKEN_ACC,
+            destination_token_account: OUT_TOKEN_ACC,
+            token_transfer_authority: SIGNER,
+            quote_mint_to_referrer: None,
+            jupiter_program_id: &Pubkey::default(),
+            missing_dynamic_accounts_as_default: false,
+        })
+        .unwrap()
+        .account_metas
+}
+
+/// `(pubkey, is_writable, is_signer)` of every account, in order
+fn assert_layout(metas: &[AccountMeta], expected: &[(Pubkey, bool, bool)]) {
+    let actual: Vec<_> = metas
+        .iter()
+        .map(|m| (m.pubkey, m.is_writable, m.is_signer))
+        .collect();
+    assert_eq!(actual.len(), expected.len());
+    for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+        assert_eq!(actual, expected, "{i}");
+    }
+}
+
+fn fixture_pk(name: &str) -> Pubkey {
+    KeyedUiAccount::from_test_fixtures_json(name)
+        .into_keyed_account()
+        .0
+}
+
+/// Changing this means account positions changed, which breaks downstream signers.
+/// Update the expected layouts below together with [`META_LAYOUT_VERSION`].
+#[test]
+fn meta_layout_version() {
+    assert_eq!(META_LAYOUT_VERSION, 1);
+}
+
+/// Sol val calculator accounts of jupSOL: program, calculator state, stake pool,
+/// stake pool program, stake pool program data
+fn jupsol_calc_accs() -> [(Pubkey, bool, bool); 5] {
+    [
+        CONST_PUBKEYS.sanctum_spl_multi_calc_prog(),
+        &fixture_pk("sanctum-spl-multi-calc-state"),
+        CONST_PUBKEYS.jupsol_pool(),
+        CONST_PUBKEYS.sanctum_spl_multi_prog(),
+        CONST_PUBKEYS.sanctum_spl_multi_progdata(),
+    ]
+    .map(|pk| (*pk, false, false))
+}
+
+/// Sol val calculator accounts of wSOL: program only
+fn wsol_calc_accs() -> [(Pubkey, bool, bool); 1] {
+    [(*CONST_PUBKEYS.wsol_calc_prog(), false, false)]
+}
+
+/// Pricing program accounts of the fixture pool: program, slab
+fn pricing_accs() -> [(Pubkey, bool, bool); 2] {
+    [
+        (*CONST_PUBKEYS.flatslab_pp_prog(), false, false),
+        (fixture_pk("flatslab-slab"), false, false),
+    ]
+}
+
+#[test]
+fn swap_meta_layout_fixture() {
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    for swap_mode in [SwapMode::ExactIn, SwapMode::ExactOut] {
+        let expected: Vec<_> = [
+            (inf1_ctl_core::ID.into(), false, false),
+            (SIGNER, false, false),
+            (*jupsol, false, false),
+            (WSOL_MINT_ADDR.into(), false, false),
+            (INP_TOKEN_ACC, true, false),
+            (OUT_TOKEN_ACC, true, false),
+            (fixture_pk("wsol-pf-accum"), true, false),
+            (TOKEN_PROGRAM_ID.into(), false, false),
+            (TOKEN_PROGRAM_ID.into(), false, false),
+            (POOL_STATE_ID.into(), true, false),
+            (LST_STATE_LIST_ID.into(), true, false),
+            (fixture_pk("jupsol-reserves"), true, false),
+            (fixture_pk("wsol-reserves"), true, false),
+        ]
+        .into_iter()
+        .chain(jupsol_calc_accs())
+        .chain(wsol_calc_accs())
+        .chain(pricing_accs())
+        .collect();
+        assert_layout(
+            &metas(swap_mode, jupsol.as_array(), &WSOL_MINT_ADDR),
+            &expected,
+        );
+    }
+}
+
+#[test]
+fn add_liquidity_meta_layout_fixture() {
+    let expected: Vec<_> = [
+        (inf1_ctl_core::ID.into(), false, false),
+        (SIGNER, false, false),
+        (WSOL_MINT_ADDR.into(), false, false),
+        (INF_MINT_ADDR.into(), true, false),
+        (INP_TOKEN_ACC, true, false),
+        (OUT_TOKEN_ACC, true, false),
+        (fixture_pk("wsol-pf-accum"), true, false),
+        (TOKEN_PROGRAM_ID.into(), false, false),
+        (TOKEN_PROGRAM_ID.into(), false, false),
+        (POOL_STATE_ID.into(), true, false),
+        (LST_STATE_LIST_ID.into(), true, false),
+        (fixture_pk("wsol-reserves"), true, false),
+    ]
+    .into_iter()
+    .chain(wsol_calc_accs())
+    .chain(pricing_accs())
+    .collect();
+    assert_layout(
+        &metas(SwapMode::ExactIn, &WSOL_MINT_ADDR, &INF_MINT_ADDR),
+        &expected,
+    );
+}
+
+#[test]
+fn remove_liquidity_meta_layout_fixture() {
+    // LST before LP token
+    let expected: Vec<_> = [
+        (inf1_ctl_core::ID.into(), false, false),
+        (SIGNER, false, false),
+        (WSOL_MINT_ADDR.into(), false, false),
+        (INF_MINT_ADDR.into(), true, false),
+        (OUT_TOKEN_ACC, true, false),
+        (INP_TOKEN_ACC, true, false),
+        (fixture_pk("wsol-pf-accum"), true, false),
+        (TOKEN_PROGRAM_ID.into(), false, false),
+        (TOKEN_PROGRAM_ID.into(), false, false),
+        (POOL_STATE_ID.into(), true, false),
+        (LST_STATE_LIST_ID.into(), true, false),
+        (fixture_pk("wsol-reserves"), true, false),
+    ]
+    .into_iter()
+    .chain(wsol_calc_accs())
+    .chain(pricing_accs())
+    .collect();
+    assert_layout(
+        &metas(SwapMode::ExactIn, &INF_MINT_ADDR, &WSOL_MINT_ADDR),
+        &expected,
+    );
+}
+
+#[test]
+fn meta_layout_independent_of_amounts_fixture() {
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let [small, large] = [1, 1_000_000_000_000].map(|in_amount| {
+        inf.get_swap_and_account_metas(&SwapParams {
+            swap_mode: SwapMode::ExactIn,
+            in_amount,
+            out_amount: 0,
+            source_mint: *jupsol,
+            destination_mint: WSOL_MINT_ADDR.into(),
+            source_token_account: INP_TOKEN_ACC,
+            destination_token_account: OUT_TOKEN_ACC,
+            token_transfer_authority: SIGNER,
+            quote_mint_to_referrer: None,
+            jupiter_program_id: &Pubkey::default(),
+            missing_dynamic_accounts_as_default: false,
+        })
+        .unwrap()
+        .account_metas
+    });
+    assert_eq!(small, large);
+}
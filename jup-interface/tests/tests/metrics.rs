@@ -0,0 +1,96 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR,
+    metrics::{InfAmmMetrics, QuoteErrKind},
+};
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use jupiter_amm_interface::{Amm, SwapMode};
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[derive(Debug, Default)]
+struct RecordingMetrics {
+    updates: Mutex<Vec<(usize, usize)>>,
+    quote_errs: Mutex<Vec<QuoteErrKind>>,
+}
+
+impl InfAmmMetrics for RecordingMetrics {
+    fn on_update_complete(&self, _duration: Duration, lsts_updated: usize, lsts_skipped: usize) {
+        self.updates
+            .lock()
+            .unwrap()
+            .push((lsts_updated, lsts_skipped));
+    }
+
+    fn on_quote_err(&self, kind: QuoteErrKind) {
+        self.quote_errs.lock().unwrap().push(kind);
+    }
+}
+
+#[test]
+fn update_complete_reported_fixture() {
+    let metrics = Arc::new(RecordingMetrics::default());
+    let mut inf = updated_inf_amm(&ALL_FIXTURES).with_metrics(metrics.clone());
+    let am: HashMap<_, _, _> = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| {
+            let (k, v) = ALL_FIXTURES.get_key_value(&pk)?;
+            Some((*k, v.clone()))
+        })
+        .collect();
+    inf.update(&am).unwrap();
+
+    let updates = metrics.updates.lock().unwrap();
+    assert_eq!(updates.len(), 1);
+    let (lsts_updated, lsts_skipped) = updates[0];
+    assert!(lsts_updated > 0);
+    assert_eq!(
+        lsts_updated + lsts_skipped,
+        inf.inner.try_lst_state_list().unwrap().len()
+    );
+}
+
+#[test]
+fn quote_errs_reported_by_kind_fixture() {
+    let metrics = Arc::new(RecordingMetrics::default());
+    let inf = updated_inf_amm(&ALL_FIXTURES).with_metrics(metrics.clone());
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let pair = Pair {
+        inp: jupsol,
+        out: &WSOL_MINT_ADDR,
+    };
+
+    inf.quote_raw(&pair, 1_000_000, TradeLimitTy::ExactIn, 0)
+        .unwrap();
+    assert!(metrics.quote_errs.lock().unwrap().is_empty());
+
+    let stale_epoch = inf.lst_calc_epoch(jupsol).unwrap() + 1;
+    assert!(inf
+        .quote_raw(&pair, 1_000_000, TradeLimitTy::ExactIn, stale_epoch)
+        .is_err());
+    assert!(inf
+        // way more than any LST's reserves
+        .quote_raw(&pair, 1_000_000_000_000_000_000, TradeLimitTy::ExactIn, 0)
+        .is_err());
+    let results = inf.quote_many(
+        &Pair {
+            inp: jupsol,
+            out: &[0; 32],
+        },
+        &[1, 2],
+        SwapMode::ExactIn,
+    );
+    assert!(results.iter().all(|r| r.is_err()));
+
+    let quote_errs = metrics.quote_errs.lock().unwrap();
+    assert_eq!(quote_errs[0], QuoteErrKind::NotUpdated);
+    assert_eq!(quote_errs[1], QuoteErrKind::NotEnoughLiquidity);
+    assert_eq!(quote_errs.len(), 4);
+}
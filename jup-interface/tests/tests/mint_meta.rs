@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{
+    consts::{NATIVE_SOL_MINT_ADDR, WSOL_MINT_ADDR},
+    mint_meta::MintMeta,
+};
+use test_utils::{KeyedUiAccount, ALL_FIXTURES};
+
+use crate::common::updated_inf_amm;
+
+/// Offset of `decimals: u8` in mint account data
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+#[test]
+fn mint_metadata_decimals_same_as_mint_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    for name in ["inf", "jupsol", "msol", "stsol", "wsol"] {
+        let (mint, acc) =
+            KeyedUiAccount::from_test_fixtures_json(&format!("{name}-mint")).into_keyed_account();
+        let meta = inf.mint_metadata(mint.as_array()).unwrap();
+        assert_eq!(meta.decimals, acc.data[MINT_DECIMALS_OFFSET], "{name}");
+        assert!(!meta.symbol.is_empty(), "{name}");
+    }
+    assert_eq!(
+        inf.mint_metadata(&NATIVE_SOL_MINT_ADDR),
+        inf.mint_metadata(&WSOL_MINT_ADDR)
+    );
+    assert_eq!(inf.mint_metadata(&[1; 32]), None);
+}
+
+#[test]
+fn with_mint_metas_replaces_fixture() {
+    let meta = MintMeta {
+        symbol: "TEST".to_owned(),
+        decimals: 6,
+        logo_uri: String::new(),
+    };
+    let inf =
+        updated_inf_amm(&ALL_FIXTURES).with_mint_metas(HashMap::from([([1; 32], meta.clone())]));
+    assert_eq!(inf.mint_metadata(&[1; 32]), Some(&meta));
+    assert_eq!(inf.mint_metadata(&WSOL_MINT_ADDR), None);
+}
@@ -1,4 +1,90 @@
+mod acc_deser;
+mod accounts_to_update;
 mod add_liquidity;
+mod alt;
+mod ata;
+mod atomic_update;
+mod audit;
+mod builder;
+mod calc_upgrade;
+mod clock_mode;
+mod clone;
+mod cluster;
+mod compute_units;
+mod config;
+mod crank;
+mod delist;
+mod delta;
+mod depth;
+mod dynamic;
+mod epoch_rollover;
+mod epoch_watch;
+mod err_code;
+mod exact_out;
+mod fee_pct;
+#[cfg(feature = "feed")]
+mod feed;
+mod health;
+mod impact;
+mod jup_quote_fees;
+mod jup_swap;
+#[cfg(feature = "legacy")]
+mod legacy;
+mod liquidity_preview;
+mod live_pool_state;
+mod lst_info;
+mod lst_list_cache;
+mod lst_state_list_format;
+mod memory;
+mod meta_layout;
+mod metrics;
+mod mint_meta;
+mod native_sol;
+mod nav;
+mod pair_fees;
+mod pair_update;
+mod pairs;
+mod pda;
+mod pool_defaults;
+mod pool_status;
+mod prices;
+mod pricing_migration;
+mod program_dependencies;
+mod quotable_mints;
+mod quote_at_epoch;
+mod quote_cache;
+mod quote_core;
+mod quote_many;
+mod quote_snapshot;
+mod quote_vs_exec;
+mod rebalance;
+mod rebalance_tracking;
+mod referral;
+mod refresh_spl_lsts;
 mod remove_liquidity;
+#[cfg(feature = "replay")]
+mod replay;
+mod reserves;
+mod route;
+mod same_mint;
+#[cfg(feature = "serde")]
+mod serialize;
+mod shared;
+#[cfg(feature = "simulate")]
+mod simulate;
+mod slippage;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod spl_lsts;
+mod stateless;
 mod swap_exact_in;
 mod swap_exact_out;
+mod token_program;
+mod trade_kind;
+#[cfg(feature = "tx")]
+mod tx;
+mod typed;
+mod ui_account;
+mod unknown_svc;
+mod validate;
+mod writable;
@@ -0,0 +1,85 @@
+use inf1_jup_interface::consts::{NATIVE_SOL_MINT_ADDR, WSOL_MINT_ADDR};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode, SwapParams};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const JUPITER_PROGRAM_ID_PLACEHOLDER: Pubkey = Pubkey::new_from_array([0; 32]);
+
+#[test]
+fn native_sol_quoted_as_wsol_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = *CONST_PUBKEYS.jupsol_mint();
+    for (native, wsol) in [
+        (
+            (NATIVE_SOL_MINT_ADDR.into(), jupsol),
+            (WSOL_MINT_ADDR.into(), jupsol),
+        ),
+        (
+            (jupsol, NATIVE_SOL_MINT_ADDR.into()),
+            (jupsol, WSOL_MINT_ADDR.into()),
+        ),
+    ] {
+        let [native_quote, wsol_quote] = [native, wsol].map(|(input_mint, output_mint)| {
+            inf.quote(&QuoteParams {
+                amount: 1_000_000_000,
+                input_mint,
+                output_mint,
+                swap_mode: SwapMode::ExactIn,
+            })
+            .unwrap()
+        });
+        assert_eq!(native_quote.in_amount, wsol_quote.in_amount);
+        assert_eq!(native_quote.out_amount, wsol_quote.out_amount);
+    }
+}
+
+#[test]
+fn native_sol_swap_flags_wrap_unwrap_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = *CONST_PUBKEYS.jupsol_mint();
+    let native = Pubkey::new_from_array(NATIVE_SOL_MINT_ADDR);
+    let wsol = Pubkey::new_from_array(WSOL_MINT_ADDR);
+    let swap_params = |source_mint, destination_mint| SwapParams {
+        swap_mode: SwapMode::ExactIn,
+        in_amount: 1_000_000_000,
+        out_amount: 0,
+        source_mint,
+        destination_mint,
+        source_token_account: Pubkey::new_from_array([1; 32]),
+        destination_token_account: Pubkey::new_from_array([2; 32]),
+        token_transfer_authority: Pubkey::new_from_array([3; 32]),
+        quote_mint_to_referrer: None,
+        jupiter_program_id: &JUPITER_PROGRAM_ID_PLACEHOLDER,
+        missing_dynamic_accounts_as_default: false,
+    };
+
+    // jupiter's path does not accept the sentinel
+    assert!(inf
+        .get_swap_and_account_metas(&swap_params(native, jupsol))
+        .is_err());
+
+    for (src, dst, wrap_inp, unwrap_out) in [
+        (native, jupsol, true, false),
+        (jupsol, native, false, true),
+        (wsol, jupsol, false, false),
+    ] {
+        let res = inf
+            .get_swap_and_account_metas_native(&swap_params(src, dst))
+            .unwrap();
+        assert_eq!(res.wrap_inp, wrap_inp);
+        assert_eq!(res.unwrap_out, unwrap_out);
+
+        let expected = inf
+            .get_swap_and_account_metas(&swap_params(
+                if wrap_inp { wsol } else { src },
+                if unwrap_out { wsol } else { dst },
+            ))
+            .unwrap();
+        assert_eq!(
+            res.swap_and_account_metas.account_metas,
+            expected.account_metas
+        );
+    }
+}
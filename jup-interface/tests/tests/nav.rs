@@ -0,0 +1,19 @@
+use rust_decimal::Decimal;
+use test_utils::ALL_FIXTURES;
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn inf_sol_value_per_token_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let supply = inf.lp_token_supply();
+    assert!(supply > 0);
+
+    let price = inf.inf_sol_value_per_token().unwrap();
+    assert_eq!(
+        price,
+        Decimal::from(inf.inner.pool.total_sol_value) / Decimal::from(supply)
+    );
+    // INF has accrued yield since launch
+    assert!(price > Decimal::ONE);
+}
@@ -0,0 +1,55 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, NATIVE_SOL_MINT_ADDR, WSOL_MINT_ADDR},
+    pair_fees::{LpProtocolSplit, PairFees},
+};
+use rust_decimal::Decimal;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+/// The fixture pool uses the flat slab pricing program, with fees of
+/// - wSOL: 5,000,000 nanos input, 6,000,000 nanos output
+/// - jupSOL: -1,000,000 nanos input, -2,000,000 nanos output
+/// - INF: 9,000,000 nanos input, 10,000,000 nanos output
+///
+/// and trading and LP protocol fees of 1000 bps
+const PROTOCOL_SPLIT: LpProtocolSplit = LpProtocolSplit {
+    lp_bps: 9_000,
+    protocol_bps: 1_000,
+};
+
+#[test]
+fn pair_fees_flatslab_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+
+    assert_eq!(
+        inf.pair_fees(&WSOL_MINT_ADDR, jupsol),
+        Some(PairFees {
+            in_fee_bps: Decimal::from(50),
+            out_fee_bps: Decimal::from(-20),
+            lp_protocol_split: PROTOCOL_SPLIT,
+        })
+    );
+    assert_eq!(
+        inf.pair_fees(jupsol, &WSOL_MINT_ADDR),
+        Some(PairFees {
+            in_fee_bps: Decimal::from(-10),
+            out_fee_bps: Decimal::from(60),
+            lp_protocol_split: PROTOCOL_SPLIT,
+        })
+    );
+    assert_eq!(
+        inf.pair_fees(&NATIVE_SOL_MINT_ADDR, jupsol),
+        inf.pair_fees(&WSOL_MINT_ADDR, jupsol)
+    );
+    assert_eq!(
+        inf.pair_fees(&WSOL_MINT_ADDR, &INF_MINT_ADDR),
+        Some(PairFees {
+            in_fee_bps: Decimal::from(50),
+            out_fee_bps: Decimal::from(100),
+            lp_protocol_split: PROTOCOL_SPLIT,
+        })
+    );
+    assert_eq!(inf.pair_fees(&WSOL_MINT_ADDR, &[1; 32]), None);
+}
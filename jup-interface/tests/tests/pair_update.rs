@@ -0,0 +1,44 @@
+use inf1_jup_interface::consts::{NATIVE_SOL_MINT_ADDR, WSOL_MINT_ADDR};
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use jupiter_amm_interface::{AccountMap, Amm};
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn pair_update_limited_working_set_fixture() {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES);
+    let pair = Pair {
+        inp: CONST_PUBKEYS.jupsol_mint().as_array(),
+        out: &WSOL_MINT_ADDR,
+    };
+    let all = inf.get_accounts_to_update();
+    let for_pair = inf.get_accounts_to_update_for_pair(&pair);
+    assert!(for_pair.len() < all.len());
+    assert!(for_pair.iter().all(|pk| all.contains(pk)));
+    assert!(for_pair.contains(CONST_PUBKEYS.jupsol_pool()));
+
+    // native SOL is updated as wSOL
+    assert_eq!(
+        inf.get_accounts_to_update_for_pair(&Pair {
+            inp: pair.inp,
+            out: &NATIVE_SOL_MINT_ADDR,
+        }),
+        for_pair
+    );
+
+    // every account in the working set has a fixture,
+    // so this fails if update relies on an account outside of it
+    let am: AccountMap = for_pair
+        .iter()
+        .map(|pk| (*pk, ALL_FIXTURES.get(pk).unwrap().clone()))
+        .collect();
+    let generation = inf.generation;
+    inf.update_for_pair(&pair, &am).unwrap();
+    assert_eq!(inf.generation, generation + 1);
+    inf.quote_raw(&pair, 1_000_000_000, TradeLimitTy::ExactIn, inf.epoch())
+        .unwrap();
+
+    // LSTs out of the pair keep their state, so the full set is unaffected
+    assert_eq!(inf.get_accounts_to_update(), all);
+}
@@ -0,0 +1,66 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    trade_kind::TradeKind,
+};
+use inf1_std::{
+    inf1_ctl_core::keys::LST_STATE_LIST_ID,
+    inf1_svc_ag_std::inf1_svc_marinade_core::sanctum_marinade_liquid_staking_core::MSOL_MINT_ADDR,
+};
+use jupiter_amm_interface::Amm;
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+/// Offsets in packed `LstState`, see `unknown_svc.rs`
+const LST_STATE_LEN: usize = 80;
+const LST_STATE_IS_INPUT_DISABLED_OFFSET: usize = 0;
+const LST_STATE_MINT_OFFSET: usize = 16;
+
+#[test]
+fn pairs_all_combinations_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let n = inf.get_reserve_mints().len();
+    let pairs: Vec<_> = inf.pairs().collect();
+    assert_eq!(pairs.len(), n * (n - 1));
+    assert!(pairs.iter().all(|p| p.inp != p.out));
+
+    let jupsol = CONST_PUBKEYS.jupsol_mint().to_bytes();
+    let swap = pairs
+        .iter()
+        .find(|p| p.inp == jupsol && p.out == WSOL_MINT_ADDR)
+        .unwrap();
+    assert_eq!(swap.kind, TradeKind::Swap);
+    assert!(!swap.lp_route);
+    assert!(swap.is_viable());
+
+    for (inp, out, kind) in [
+        (WSOL_MINT_ADDR, INF_MINT_ADDR, TradeKind::AddLiquidity),
+        (INF_MINT_ADDR, WSOL_MINT_ADDR, TradeKind::RemoveLiquidity),
+    ] {
+        let p = pairs.iter().find(|p| p.inp == inp && p.out == out).unwrap();
+        assert_eq!(p.kind, kind);
+        assert!(p.lp_route);
+        assert!(!p.zero_reserve);
+    }
+}
+
+#[test]
+fn pairs_input_disabled_fixture() {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state
+        .get_mut(&Pubkey::new_from_array(LST_STATE_LIST_ID))
+        .unwrap()
+        .data
+        .chunks_exact_mut(LST_STATE_LEN)
+        .find(|s| s[LST_STATE_MINT_OFFSET..][..32] == MSOL_MINT_ADDR)
+        .unwrap()[LST_STATE_IS_INPUT_DISABLED_OFFSET] = 1;
+
+    let inf = updated_inf_amm(&onchain_state);
+    for p in inf.pairs() {
+        assert_eq!(p.input_disabled, p.inp == MSOL_MINT_ADDR, "{p:?}");
+        if p.inp == MSOL_MINT_ADDR {
+            assert!(!p.is_viable());
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR,
+    pda::{
+        find_pda, lst_reserves, protocol_fee_accumulator, FastPda, PdaFns, PdaProvider, SafePda,
+    },
+    writable::WritableAcc,
+    InfAmm,
+};
+use inf1_std::{
+    inf1_ctl_core::keys::LST_STATE_LIST_ID, inf1_pp_core::pair::Pair, trade::TradeLimitTy,
+};
+use jupiter_amm_interface::{Amm, KeyedAccount};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+const TOKEN_PROGRAM_ID: [u8; 32] =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").to_bytes();
+
+#[test]
+fn find_pda_cached_same_as_uncached() {
+    let program_id = [7; 32];
+    let seeds: [&[u8]; 2] = [b"seed", &[1, 2, 3]];
+    let (expected, expected_bump) =
+        Pubkey::find_program_address(&seeds, &Pubkey::new_from_array(program_id));
+    for _ in 0..2 {
+        assert_eq!(
+            find_pda(&seeds, &program_id),
+            Some((expected.to_bytes(), expected_bump))
+        );
+    }
+}
+
+#[test]
+fn lst_reserves_same_as_lst_info_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    for mint in [CONST_PUBKEYS.jupsol_mint().as_array(), &WSOL_MINT_ADDR] {
+        assert_eq!(
+            lst_reserves(&TOKEN_PROGRAM_ID, mint),
+            inf.lst_info(mint, &TOKEN_PROGRAM_ID).map(|i| i.reserves)
+        );
+    }
+}
+
+#[test]
+fn protocol_fee_accumulators_written_in_swap_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let writable = inf
+        .writable_accounts_for(
+            &Pair {
+                inp: jupsol,
+                out: &WSOL_MINT_ADDR,
+            },
+            TradeLimitTy::ExactIn,
+        )
+        .unwrap();
+    let accumulator = protocol_fee_accumulator(&TOKEN_PROGRAM_ID, &WSOL_MINT_ADDR).unwrap();
+    assert!(writable.contains(&WritableAcc::Pool(accumulator.into())));
+}
+
+#[test]
+fn safe_pda_same_as_fast_pda() {
+    let seeds: [&[u8]; 3] = [&[1; 32], &TOKEN_PROGRAM_ID, &WSOL_MINT_ADDR];
+    let program_id = [7; 32];
+    let found = FastPda::find(&seeds, &program_id);
+    assert!(found.is_some());
+    assert_eq!(SafePda::find(&seeds, &program_id), found);
+
+    let (pda, bump) = found.unwrap();
+    let bump_seed = [bump];
+    let seeds_with_bump = [seeds[0], seeds[1], seeds[2], &bump_seed];
+    assert_eq!(
+        FastPda::create_raw(&seeds_with_bump, &program_id),
+        Some(pda)
+    );
+    assert_eq!(
+        SafePda::create_raw(&seeds_with_bump, &program_id),
+        Some(pda)
+    );
+}
+
+#[test]
+fn safe_pda_checks_seed_lens() {
+    let seeds: [&[u8]; 1] = [&[1; 33]];
+    let program_id = [7; 32];
+    assert!(FastPda::create_raw(&seeds, &program_id).is_some());
+    assert_eq!(SafePda::create_raw(&seeds, &program_id), None);
+    assert_eq!(SafePda::find(&seeds, &program_id), None);
+}
+
+static MOCK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// [`SafePda`] that counts its calls
+struct CountingPda;
+
+impl PdaProvider for CountingPda {
+    fn find(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+        MOCK_CALLS.fetch_add(1, Ordering::Relaxed);
+        SafePda::find(seeds, program_id)
+    }
+
+    fn create_raw(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<[u8; 32]> {
+        MOCK_CALLS.fetch_add(1, Ordering::Relaxed);
+        SafePda::create_raw(seeds, program_id)
+    }
+}
+
+fn updated_inf_amm_with_pda(pda: PdaFns) -> InfAmm {
+    let (key, account) = ALL_FIXTURES
+        .get_key_value(&LST_STATE_LIST_ID.into())
+        .unwrap();
+    let mut inf = InfAmm::builder()
+        .amm_context(&AMM_CONTEXT)
+        .spl_lsts(SPL_LSTS.into_iter().collect())
+        .pda(pda)
+        .build(&KeyedAccount {
+            key: *key,
+            account: account.clone(),
+            params: None,
+        })
+        .unwrap();
+    for i in 0..2 {
+        let am: HashMap<_, _> = inf
+            .get_accounts_to_update()
+            .into_iter()
+            .filter_map(|pk| Some((pk, ALL_FIXTURES.get(&pk)?.clone())))
+            .collect();
+        // 1st update might fail bec DEFAULT_MAINNET_POOL might be stale
+        let res = inf.update(&am);
+        if i == 1 {
+            res.unwrap();
+        }
+    }
+    inf
+}
+
+#[test]
+fn pda_provider_used_by_inf_amm_fixture() {
+    let calls_before = MOCK_CALLS.load(Ordering::Relaxed);
+    let inf = updated_inf_amm_with_pda(PdaFns::of::<CountingPda>());
+    assert!(MOCK_CALLS.load(Ordering::Relaxed) > calls_before);
+
+    let fast = updated_inf_amm(&ALL_FIXTURES);
+    assert_eq!(inf.get_accounts_to_update(), fast.get_accounts_to_update());
+    assert!(inf.invalid_reserves.is_empty());
+    for mint in [CONST_PUBKEYS.jupsol_mint().as_array(), &WSOL_MINT_ADDR] {
+        assert_eq!(
+            inf.lst_info(mint, &TOKEN_PROGRAM_ID),
+            fast.lst_info(mint, &TOKEN_PROGRAM_ID)
+        );
+    }
+}
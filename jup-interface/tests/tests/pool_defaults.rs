@@ -0,0 +1,94 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use inf1_jup_interface::{
+    consts::DEFAULT_MAINNET_POOL,
+    metrics::InfAmmMetrics,
+    pool_defaults::{PoolDefaults, PoolDefaultsCheck},
+    InfAmm,
+};
+use inf1_std::{
+    inf1_ctl_core::{accounts::pool_state::PoolState, keys::LST_STATE_LIST_ID},
+    inf1_pp_ag_std::PricingAgTy,
+};
+use jupiter_amm_interface::{Amm, KeyedAccount};
+use test_utils::{ALL_FIXTURES, SPL_LSTS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+#[derive(Debug, Default)]
+struct RecordingMetrics {
+    diverged: Mutex<Vec<PoolDefaultsCheck>>,
+}
+
+impl InfAmmMetrics for RecordingMetrics {
+    fn on_pool_defaults_diverged(&self, check: &PoolDefaultsCheck) {
+        self.diverged.lock().unwrap().push(*check);
+    }
+}
+
+/// Updates `inf` with all fixtures `n` times, ignoring errs of the 1st update,
+/// which might be based on stale pool defaults
+fn update_n(inf: &mut InfAmm, n: usize) {
+    let am: HashMap<_, _, _> = ALL_FIXTURES.iter().map(|(k, v)| (*k, v.clone())).collect();
+    let _: Result<_, _> = inf.update(&am);
+    for _ in 1..n {
+        inf.update(&am).unwrap();
+    }
+}
+
+fn inf_with_metrics(metrics: Arc<RecordingMetrics>, pricing_default: Option<PoolState>) -> InfAmm {
+    let (key, account) = ALL_FIXTURES
+        .get_key_value(&LST_STATE_LIST_ID.into())
+        .unwrap();
+    let builder = InfAmm::builder()
+        .amm_context(&AMM_CONTEXT)
+        .spl_lsts(SPL_LSTS.into_iter().collect())
+        .metrics(metrics);
+    let builder = match pricing_default {
+        Some(pool) => builder.pricing_default(pool),
+        None => builder,
+    };
+    builder
+        .build(&KeyedAccount {
+            key: *key,
+            account: account.clone(),
+            params: None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn diverged_defaults_reported_once_fixture() {
+    let metrics = Arc::new(RecordingMetrics::default());
+    let mut inf = inf_with_metrics(metrics.clone(), None);
+    assert!(inf.pool_defaults_check().is_none());
+
+    update_n(&mut inf, 3);
+
+    // fixture pool uses flat slab pricing, see pair_fees.rs
+    let expected = PoolDefaultsCheck {
+        defaults: PoolDefaults::of(&DEFAULT_MAINNET_POOL),
+        live: PoolDefaults {
+            pricing_program: *PricingAgTy::FlatSlab(()).program_id(),
+            lp_token_mint: DEFAULT_MAINNET_POOL.lp_token_mint,
+        },
+    };
+    assert!(expected.is_diverged());
+    assert_eq!(inf.pool_defaults_check(), Some(expected));
+    assert_eq!(*metrics.diverged.lock().unwrap(), [expected]);
+}
+
+#[test]
+fn up_to_date_defaults_not_reported_fixture() {
+    let live = updated_inf_amm(&ALL_FIXTURES).pool_state().clone();
+    let metrics = Arc::new(RecordingMetrics::default());
+    let mut inf = inf_with_metrics(metrics.clone(), Some(live));
+
+    update_n(&mut inf, 2);
+
+    assert!(!inf.pool_defaults_check().unwrap().is_diverged());
+    assert!(metrics.diverged.lock().unwrap().is_empty());
+}
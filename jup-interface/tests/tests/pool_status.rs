@@ -0,0 +1,51 @@
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, err::InfAmmErr};
+use inf1_std::{inf1_ctl_core::keys::POOL_STATE_ID, inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const IS_DISABLED_OFFSET: usize = 13;
+const IS_REBALANCING_OFFSET: usize = 14;
+
+fn quote_err_with_pool_flag_set(offset: usize) -> InfAmmErr {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state
+        .get_mut(&Pubkey::new_from_array(POOL_STATE_ID))
+        .unwrap()
+        .data[offset] = 1;
+    let inf = updated_inf_amm(&onchain_state);
+    assert!(!inf.is_active());
+
+    inf.quote_raw(
+        &Pair {
+            inp: CONST_PUBKEYS.jupsol_mint().as_array(),
+            out: &WSOL_MINT_ADDR,
+        },
+        1_000_000,
+        TradeLimitTy::ExactIn,
+        0,
+    )
+    .unwrap_err()
+}
+
+#[test]
+fn active_fixture() {
+    assert!(updated_inf_amm(&ALL_FIXTURES).is_active());
+}
+
+#[test]
+fn disabled_pool_refuses_quotes_fixture() {
+    assert!(matches!(
+        quote_err_with_pool_flag_set(IS_DISABLED_OFFSET),
+        InfAmmErr::PoolDisabled
+    ));
+}
+
+#[test]
+fn rebalancing_pool_refuses_quotes_fixture() {
+    assert!(matches!(
+        quote_err_with_pool_flag_set(IS_REBALANCING_OFFSET),
+        InfAmmErr::PoolRebalancing
+    ));
+}
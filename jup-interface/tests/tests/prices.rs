@@ -0,0 +1,27 @@
+use rust_decimal::Decimal;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn pool_valuation_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let v = inf.pool_valuation();
+
+    assert_eq!(v.total_sol_value, inf.inner.pool.total_sol_value);
+    assert_eq!(v.inf_sol_value_per_token, inf.inf_sol_value_per_token());
+    assert_eq!(v.lsts.len(), inf.inner.try_lst_state_list().unwrap().len());
+
+    let jupsol = v
+        .lsts
+        .iter()
+        .find(|l| l.mint == *CONST_PUBKEYS.jupsol_mint().as_array())
+        .unwrap();
+    assert!(jupsol.reserves > 0);
+    // jupSOL has accrued staking yield
+    assert!(jupsol.lst_to_sol.unwrap() > Decimal::ONE);
+    assert!(jupsol.share.unwrap() > Decimal::ZERO);
+
+    let shares: Decimal = v.lsts.iter().filter_map(|l| l.share).sum();
+    assert!(shares <= Decimal::ONE);
+}
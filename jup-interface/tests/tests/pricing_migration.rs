@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, InfAmm};
+use inf1_std::{
+    inf1_ctl_core::keys::POOL_STATE_ID,
+    inf1_pp_ag_std::{inf1_pp_flatfee_core, PricingAgTy},
+    inf1_pp_core::pair::Pair,
+    trade::TradeLimitTy,
+};
+use jupiter_amm_interface::Amm;
+use rust_decimal::Decimal;
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{
+    pool_state_pricing_program, set_pool_state_pricing_program, ALL_FIXTURES, CONST_PUBKEYS,
+};
+
+use crate::common::updated_inf_amm;
+
+/// Fees in the flat fee pricing program fixtures, `*-flatfee-fee.json`
+const INPUT_FEE_BPS: i16 = 10;
+const OUTPUT_FEE_BPS: i16 = 20;
+
+fn set_pricing_program(onchain_state: &mut HashMap<Pubkey, Account>, program: &[u8; 32]) {
+    let pool = onchain_state
+        .get_mut(&Pubkey::new_from_array(POOL_STATE_ID))
+        .unwrap();
+    set_pool_state_pricing_program(pool, program);
+}
+
+/// Updates with only the requested accounts that exist in `onchain_state`
+fn update_cycle(inf: &mut InfAmm, onchain_state: &HashMap<Pubkey, Account>) -> anyhow::Result<()> {
+    let am: HashMap<_, _> = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect();
+    inf.update(&am)
+}
+
+fn quote_jupsol_to_wsol(inf: &InfAmm) -> bool {
+    inf.quote_raw(
+        &Pair {
+            inp: CONST_PUBKEYS.jupsol_mint().as_array(),
+            out: &WSOL_MINT_ADDR,
+        },
+        1_000_000_000,
+        TradeLimitTy::ExactIn,
+        inf.epoch(),
+    )
+    .is_ok()
+}
+
+/// The `swap_exact_in`, `swap_exact_out`, `add_liquidity` and `remove_liquidity` fixture
+/// matrices run against the fixture pool, so they only cover the flat slab pricing program
+/// as long as this holds. Flat fee pricing is covered offchain only by the tests below,
+/// since its program is not loaded in mollusk.
+#[test]
+fn fixture_pool_uses_flatslab() {
+    let pool = &ALL_FIXTURES[&Pubkey::new_from_array(POOL_STATE_ID)];
+    assert_eq!(
+        &pool_state_pricing_program(pool),
+        PricingAgTy::FlatSlab(()).program_id()
+    );
+}
+
+#[test]
+fn pricing_migration_no_dead_window_fixture() {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    let mut migrating = updated_inf_amm(&onchain_state).with_pricing_migration();
+    let mut plain = migrating.clone();
+    plain.pricing_migration = false;
+
+    // 1st cycle initializes the standby, 2nd applies its accounts
+    for _ in 0..2 {
+        update_cycle(&mut migrating, &onchain_state).unwrap();
+    }
+    assert_eq!(
+        migrating.standby_pricing_program(),
+        Some(&inf1_pp_flatfee_core::ID)
+    );
+
+    // pool state switches from flat slab to flat fee
+    set_pricing_program(&mut onchain_state, &inf1_pp_flatfee_core::ID);
+
+    let plain_ok = update_cycle(&mut plain, &onchain_state).is_ok() && quote_jupsol_to_wsol(&plain);
+    assert!(!plain_ok);
+
+    update_cycle(&mut migrating, &onchain_state).unwrap();
+    assert!(quote_jupsol_to_wsol(&migrating));
+    assert_eq!(
+        migrating.standby_pricing_program(),
+        Some(PricingAgTy::FlatSlab(()).program_id())
+    );
+    let fees = migrating
+        .pair_fees(CONST_PUBKEYS.jupsol_mint().as_array(), &WSOL_MINT_ADDR)
+        .unwrap();
+    assert_eq!(fees.in_fee_bps, Decimal::from(INPUT_FEE_BPS));
+    assert_eq!(fees.out_fee_bps, Decimal::from(OUTPUT_FEE_BPS));
+}
+
+#[test]
+fn no_standby_by_default_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    assert_eq!(inf.standby_pricing_program(), None);
+}
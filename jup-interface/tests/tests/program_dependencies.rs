@@ -0,0 +1,69 @@
+use inf1_jup_interface::{all_program_dependencies, InfAmm, PROGRAM_DEPENDENCIES};
+use inf1_std::{
+    inf1_ctl_core::keys::LST_STATE_LIST_ID, inf1_pp_ag_std::PricingAgTy,
+    inf1_svc_ag_std::inf1_svc_marinade_core,
+};
+use jupiter_amm_interface::{Amm, KeyedAccount};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, SPL_LSTS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+/// Offsets in packed `LstState`, see unknown_svc.rs
+const LST_STATE_LEN: usize = 80;
+const LST_STATE_SOL_VALUE_CALCULATOR_OFFSET: usize = 48;
+
+const UNKNOWN_SVC_PROG_ID: [u8; 32] = [9; 32];
+
+#[test]
+fn program_dependencies_only_active_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let deps = inf.program_dependencies();
+    let all = all_program_dependencies();
+
+    assert_eq!(all.len(), PROGRAM_DEPENDENCIES.len());
+    assert!(deps.iter().all(|d| all.contains(d)));
+    assert!(deps.len() < all.len());
+
+    let has = |program_id: &[u8; 32]| deps.iter().any(|(pk, _)| pk.as_array() == program_id);
+    assert!(has(PricingAgTy::FlatSlab(()).program_id()));
+    assert!(!has(PricingAgTy::FlatFee(()).program_id()));
+}
+
+#[test]
+fn program_dependencies_all_before_update_fixture() {
+    let key = Pubkey::new_from_array(LST_STATE_LIST_ID);
+    let inf = InfAmm::new(
+        &KeyedAccount {
+            key,
+            account: ALL_FIXTURES.get(&key).unwrap().clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        SPL_LSTS.into_iter().collect(),
+    )
+    .unwrap();
+    assert_eq!(inf.program_dependencies(), all_program_dependencies());
+}
+
+#[test]
+fn program_dependencies_exclude_skipped_lsts_fixture() {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state
+        .get_mut(&Pubkey::new_from_array(LST_STATE_LIST_ID))
+        .unwrap()
+        .data
+        .chunks_exact_mut(LST_STATE_LEN)
+        .filter(|s| s[LST_STATE_SOL_VALUE_CALCULATOR_OFFSET..][..32] == inf1_svc_marinade_core::ID)
+        .for_each(|s| {
+            s[LST_STATE_SOL_VALUE_CALCULATOR_OFFSET..][..32].copy_from_slice(&UNKNOWN_SVC_PROG_ID)
+        });
+
+    let has_marinade = |inf: &InfAmm| {
+        inf.program_dependencies()
+            .iter()
+            .any(|(pk, _)| *pk.as_array() == inf1_svc_marinade_core::ID)
+    };
+    assert!(has_marinade(&updated_inf_amm(&ALL_FIXTURES)));
+    assert!(!has_marinade(&updated_inf_amm(&onchain_state)));
+}
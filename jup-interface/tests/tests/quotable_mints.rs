@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    InfAmm,
+};
+use inf1_std::{
+    inf1_ctl_core::keys::LST_STATE_LIST_ID,
+    inf1_svc_ag_std::inf1_svc_marinade_core::sanctum_marinade_liquid_staking_core::MSOL_MINT_ADDR,
+};
+use jupiter_amm_interface::{AccountMap, Amm, KeyedAccount};
+use solana_pubkey::Pubkey;
+use test_utils::{
+    set_spl_stake_pool_last_update_epoch, KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS,
+};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+#[test]
+fn epoch_stale_mints_excluded_until_updated_fixture() {
+    let jupsol = *CONST_PUBKEYS.jupsol_mint();
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let epoch = inf.lst_calc_epoch(jupsol.as_array()).unwrap() + 1;
+    let inf = inf.with_deterministic_mode(0, epoch);
+    assert!(inf.get_reserve_mints().contains(&jupsol));
+
+    let mut inf = inf.with_epoch_stale_exclusion();
+    let quotable = inf.get_reserve_mints();
+    assert_eq!(quotable, inf.quotable_mints());
+    assert!(!quotable.contains(&jupsol));
+    for mint in [MSOL_MINT_ADDR, WSOL_MINT_ADDR, INF_MINT_ADDR] {
+        assert!(quotable.contains(&Pubkey::new_from_array(mint)));
+    }
+
+    let mut onchain_state = ALL_FIXTURES.clone();
+    set_spl_stake_pool_last_update_epoch(
+        onchain_state.get_mut(CONST_PUBKEYS.jupsol_pool()).unwrap(),
+        epoch,
+    );
+    let accounts: AccountMap = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect();
+    inf.update(&accounts).unwrap();
+    assert!(inf.get_reserve_mints().contains(&jupsol));
+}
+
+#[test]
+fn reserve_mints_exclude_unquotable_fixture() {
+    let jupsol = *CONST_PUBKEYS.jupsol_mint();
+    let msol = Pubkey::new_from_array(MSOL_MINT_ADDR);
+    let key = Pubkey::new_from_array(LST_STATE_LIST_ID);
+    // no SPL LSTs, so jupSOL is skipped in updates with MissingSplData
+    let mut inf = InfAmm::new(
+        &KeyedAccount {
+            key,
+            account: ALL_FIXTURES.get(&key).unwrap().clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        HashMap::new(),
+    )
+    .unwrap();
+    // all mints before the first update
+    assert!(inf.get_reserve_mints().contains(&jupsol));
+
+    let (msol_reserves, _) =
+        KeyedUiAccount::from_test_fixtures_json("msol-reserves").into_keyed_account();
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state.remove(&msol_reserves).unwrap();
+    for _ in 0..2 {
+        let accounts: AccountMap = inf
+            .get_accounts_to_update()
+            .into_iter()
+            .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+            .collect();
+        // 1st update might fail because DEFAULT_MAINNET_POOL might be stale
+        let _: Result<_, _> = inf.update(&accounts);
+    }
+
+    let reserve_mints = inf.get_reserve_mints();
+    assert!(!reserve_mints.contains(&jupsol));
+    assert!(!reserve_mints.contains(&msol));
+    for mint in [WSOL_MINT_ADDR, INF_MINT_ADDR] {
+        assert!(reserve_mints.contains(&Pubkey::new_from_array(mint)));
+    }
+    assert!(!inf.quotable_mints().contains(&msol));
+}
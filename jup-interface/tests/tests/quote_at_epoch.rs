@@ -0,0 +1,42 @@
+use inf1_jup_interface::consts::WSOL_MINT_ADDR;
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+/// Far after the epoch that the fixtures' stake pools were updated for
+const FUTURE_EPOCH: u64 = 1_000_000;
+
+fn jupsol_wsol_qp() -> QuoteParams {
+    QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: *CONST_PUBKEYS.jupsol_mint(),
+        output_mint: WSOL_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    }
+}
+
+#[test]
+fn quote_at_epoch_overrides_live_epoch_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let qp = jupsol_wsol_qp();
+    let expected = inf.quote(&qp).unwrap();
+
+    inf.set_clock(0, FUTURE_EPOCH);
+    assert!(inf.quote(&qp).is_err());
+
+    let at_snapshot = inf.quote_at_epoch(&qp, 0).unwrap();
+    assert_eq!(at_snapshot.in_amount, expected.in_amount);
+    assert_eq!(at_snapshot.out_amount, expected.out_amount);
+    assert_eq!(at_snapshot.fee_amount, expected.fee_amount);
+}
+
+#[test]
+fn quote_at_future_epoch_not_updated_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let qp = jupsol_wsol_qp();
+    inf.quote(&qp).unwrap();
+    assert!(inf.quote_at_epoch(&qp, FUTURE_EPOCH).is_err());
+    // live epoch unaffected
+    inf.quote(&qp).unwrap();
+}
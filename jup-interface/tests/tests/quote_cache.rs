@@ -0,0 +1,71 @@
+use inf1_jup_interface::{cache::QuoteCacheKey, consts::WSOL_MINT_ADDR};
+use inf1_std::trade::TradeLimitTy;
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const QUOTE_PARAMS: QuoteParams = QuoteParams {
+    amount: 1_000_000_000,
+    input_mint: *CONST_PUBKEYS.jupsol_mint(),
+    output_mint: Pubkey::new_from_array(WSOL_MINT_ADDR),
+    swap_mode: SwapMode::ExactIn,
+};
+
+#[test]
+fn quote_cache_hit_same_as_uncached_fixture() {
+    let uncached = updated_inf_amm(&ALL_FIXTURES);
+    let cached = uncached.clone().with_quote_cache(16);
+
+    let expected = uncached.quote(&QUOTE_PARAMS).unwrap();
+    for _ in 0..2 {
+        let q = cached.quote(&QUOTE_PARAMS).unwrap();
+        assert_eq!(q.in_amount, expected.in_amount);
+        assert_eq!(q.out_amount, expected.out_amount);
+        assert_eq!(q.fee_amount, expected.fee_amount);
+    }
+
+    let key = QuoteCacheKey::new(
+        QUOTE_PARAMS.input_mint.as_array(),
+        QUOTE_PARAMS.output_mint.as_array(),
+        QUOTE_PARAMS.amount,
+        TradeLimitTy::ExactIn,
+    );
+    assert!(cached.quote_cache.as_ref().unwrap().get(&key).is_some());
+}
+
+#[test]
+fn quote_cache_cleared_on_update_fixture() {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES).with_quote_cache(16);
+    inf.quote(&QUOTE_PARAMS).unwrap();
+    inf.update(&Default::default()).unwrap_err();
+
+    let key = QuoteCacheKey::new(
+        QUOTE_PARAMS.input_mint.as_array(),
+        QUOTE_PARAMS.output_mint.as_array(),
+        QUOTE_PARAMS.amount,
+        TradeLimitTy::ExactIn,
+    );
+    assert!(inf.quote_cache.as_ref().unwrap().get(&key).is_none());
+}
+
+#[test]
+fn quote_cache_max_entries_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES).with_quote_cache(1);
+    for amount in [1_000_000_000, 2_000_000_000] {
+        inf.quote(&QuoteParams {
+            amount,
+            ..QUOTE_PARAMS
+        })
+        .unwrap();
+    }
+
+    let key = QuoteCacheKey::new(
+        QUOTE_PARAMS.input_mint.as_array(),
+        QUOTE_PARAMS.output_mint.as_array(),
+        2_000_000_000,
+        TradeLimitTy::ExactIn,
+    );
+    assert!(inf.quote_cache.as_ref().unwrap().get(&key).is_none());
+}
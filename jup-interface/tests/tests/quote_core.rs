@@ -0,0 +1,46 @@
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR, quote_core, to_jup_quote_with_fees, JupQuoteFees,
+};
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use rust_decimal::Decimal;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn quote_core_same_as_jup_quote_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let pair = Pair {
+        inp: CONST_PUBKEYS.jupsol_mint().as_array(),
+        out: &WSOL_MINT_ADDR,
+    };
+    for limit_ty in [TradeLimitTy::ExactIn, TradeLimitTy::ExactOut] {
+        let (fee_mint, raw) = inf.quote_raw(&pair, 1_000_000_000, limit_ty, 0).unwrap();
+        for fees in [JupQuoteFees::All, JupQuoteFees::LpOnly] {
+            let jup = to_jup_quote_with_fees(&fee_mint, raw, fees).unwrap();
+            assert_eq!(
+                jup.fee_amount,
+                quote_core::fee_amount(raw.lp_fee, raw.protocol_fee, fees)
+            );
+            let (num, denom) = quote_core::fee_pct_ratio(
+                fee_mint == raw.inp_mint,
+                raw.inp,
+                raw.out,
+                raw.lp_fee,
+                raw.protocol_fee,
+                fees,
+            );
+            assert_eq!(jup.fee_pct, Decimal::from(num) / Decimal::from(denom));
+        }
+    }
+}
+
+#[test]
+fn quote_core_slippage_limits() {
+    assert_eq!(quote_core::exact_in_slippage_limit(1_000, 50), 995);
+    assert_eq!(quote_core::exact_out_slippage_limit(1_000, 50), 1_005);
+    // rounds against the user
+    assert_eq!(quote_core::exact_in_slippage_limit(7, 1), 6);
+    assert_eq!(quote_core::exact_out_slippage_limit(7, 1), 8);
+    assert_eq!(quote_core::exact_out_slippage_limit(u64::MAX, 1), u64::MAX);
+}
@@ -0,0 +1,102 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, NATIVE_SOL_MINT_ADDR, WSOL_MINT_ADDR},
+    err::InfAmmErr,
+};
+use inf1_std::{
+    inf1_pp_core::pair::Pair,
+    inf1_svc_ag_std::{
+        inf1_svc_lido_core::solido_legacy_core::STSOL_MINT_ADDR,
+        inf1_svc_marinade_core::sanctum_marinade_liquid_staking_core::MSOL_MINT_ADDR,
+    },
+};
+use jupiter_amm_interface::{Amm, Quote, QuoteParams, SwapMode};
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const AMOUNTS: [u64; 4] = [100_000_000, 1_000_000_000, 10_000_000_000, 100_000_000_000];
+
+fn assert_quote_eq(actual: anyhow::Result<Quote>, expected: anyhow::Result<Quote>) {
+    match (actual, expected) {
+        (Ok(q), Ok(expected)) => {
+            assert_eq!(q.in_amount, expected.in_amount);
+            assert_eq!(q.out_amount, expected.out_amount);
+            assert_eq!(q.fee_amount, expected.fee_amount);
+            assert_eq!(q.fee_mint, expected.fee_mint);
+            assert_eq!(q.fee_pct, expected.fee_pct);
+        }
+        (Err(e), Err(expected)) => assert_eq!(e.to_string(), expected.to_string()),
+        (q, expected) => panic!("{q:?} {expected:?}"),
+    }
+}
+
+#[test]
+fn quote_many_same_as_quote_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let pair = Pair {
+        inp: jupsol.as_array(),
+        out: &WSOL_MINT_ADDR,
+    };
+
+    let quotes = inf.quote_many(&pair, &AMOUNTS, SwapMode::ExactIn);
+    assert_eq!(quotes.len(), AMOUNTS.len());
+    for (amount, q) in AMOUNTS.into_iter().zip(quotes) {
+        let expected = inf.quote(&QuoteParams {
+            amount,
+            input_mint: *jupsol,
+            output_mint: WSOL_MINT_ADDR.into(),
+            swap_mode: SwapMode::ExactIn,
+        });
+        assert_quote_eq(q, expected);
+    }
+}
+
+#[test]
+fn quote_many_same_as_quote_all_pairs_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let mints = [
+        INF_MINT_ADDR,
+        *CONST_PUBKEYS.jupsol_mint().as_array(),
+        MSOL_MINT_ADDR,
+        STSOL_MINT_ADDR,
+        WSOL_MINT_ADDR,
+        NATIVE_SOL_MINT_ADDR,
+    ];
+
+    for inp in mints.iter() {
+        for out in mints.iter() {
+            for swap_mode in [SwapMode::ExactIn, SwapMode::ExactOut] {
+                let quotes = inf.quote_many(&Pair { inp, out }, &AMOUNTS, swap_mode);
+                assert_eq!(quotes.len(), AMOUNTS.len());
+                for (amount, q) in AMOUNTS.into_iter().zip(quotes) {
+                    let expected = inf.quote(&QuoteParams {
+                        amount,
+                        input_mint: (*inp).into(),
+                        output_mint: (*out).into(),
+                        swap_mode,
+                    });
+                    assert_quote_eq(q, expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn quote_many_pair_err_for_all_amounts_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let pair = Pair {
+        inp: &WSOL_MINT_ADDR,
+        out: &INF_MINT_ADDR,
+    };
+
+    let quotes = inf.quote_many(&pair, &AMOUNTS, SwapMode::ExactOut);
+    assert_eq!(quotes.len(), AMOUNTS.len());
+    for q in quotes {
+        assert!(matches!(
+            q.unwrap_err().downcast_ref::<InfAmmErr>(),
+            Some(InfAmmErr::ExactOutUnsupportedForLiquidity { .. })
+        ));
+    }
+}
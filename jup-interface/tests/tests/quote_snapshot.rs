@@ -0,0 +1,101 @@
+//! Regression test against recorded quotes, to catch silent quote drift
+//! e.g. when upstream `inf1_*` deps are bumped. See `test-fixtures/README.md`
+//! for how to update the recorded quotes.
+
+use std::{env, fmt::Write, fs};
+
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use test_utils::{test_fixtures_dir, KeyedUiAccount, ALL_FIXTURES};
+
+use crate::common::updated_inf_amm;
+
+/// Set to re-record the snapshot instead of comparing against it
+const UPDATE_ENV_VAR: &str = "UPDATE_QUOTE_SNAPSHOT";
+
+const SNAPSHOT_FILE: &str = "snapshots/quotes.snap";
+
+/// Named by their `{name}-mint` fixture
+const MINTS: [&str; 5] = ["inf", "jupsol", "msol", "stsol", "wsol"];
+
+const AMOUNTS: [u64; 5] = [
+    1_000,
+    1_000_000,
+    1_000_000_000,
+    100_000_000_000,
+    10_000_000_000_000,
+];
+
+const SWAP_MODES: [SwapMode; 2] = [SwapMode::ExactIn, SwapMode::ExactOut];
+
+/// One line per quote, so that changes show up as line diffs.
+///
+/// Only whether a quote failed is recorded, not the error,
+/// so that rewording errors does not require re-recording.
+fn record_quotes() -> String {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let mints = MINTS.map(|name| {
+        let (mint, _) =
+            KeyedUiAccount::from_test_fixtures_json(&format!("{name}-mint")).into_keyed_account();
+        (name, mint)
+    });
+    let mut res = String::new();
+    for (inp_name, inp) in mints {
+        for (out_name, out) in mints.into_iter().filter(|(_, out)| *out != inp) {
+            for swap_mode in SWAP_MODES {
+                for amount in AMOUNTS {
+                    write!(res, "{inp_name} -> {out_name} {swap_mode:?} {amount}: ").unwrap();
+                    match inf.quote(&QuoteParams {
+                        amount,
+                        input_mint: inp,
+                        output_mint: out,
+                        swap_mode,
+                    }) {
+                        Ok(q) => writeln!(
+                            res,
+                            "in={} out={} fee={} fee_mint={} fee_pct={}",
+                            q.in_amount, q.out_amount, q.fee_amount, q.fee_mint, q.fee_pct
+                        ),
+                        Err(_) => writeln!(res, "err"),
+                    }
+                    .unwrap();
+                }
+            }
+        }
+    }
+    res
+}
+
+#[test]
+fn quote_snapshot_fixture() {
+    let path = test_fixtures_dir().join(SNAPSHOT_FILE);
+    let actual = record_quotes();
+    if env::var_os(UPDATE_ENV_VAR).is_some() {
+        // re-recorded snapshot, to be reviewed in the diff before committing
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let recorded = fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "{}: {e}. Record it with {UPDATE_ENV_VAR}=1 and commit it.",
+            path.display()
+        )
+    });
+    if recorded == actual {
+        return;
+    }
+    let new_path = path.with_extension("snap.new");
+    fs::write(&new_path, &actual).unwrap();
+    let changed: Vec<_> = recorded
+        .lines()
+        .zip(actual.lines())
+        .filter(|(r, a)| r != a)
+        .map(|(r, a)| format!("-{r}\n+{a}"))
+        .collect();
+    panic!(
+        "{} quotes changed, written to {}. Rerun with {UPDATE_ENV_VAR}=1 if expected.\n{}",
+        changed.len().max(1),
+        new_path.display(),
+        changed.join("\n"),
+    );
+}
@@ -0,0 +1,52 @@
+//! Property-based counterpart of the hand-picked swap tests,
+//! to catch rounding drift between the rust calcs and the onchain programs
+
+use inf1_jup_interface::InfAmm;
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use proptest::prelude::*;
+use test_utils::{
+    fixture_swap_case, fixture_token_acc_amount, token_acc_mint, FixtureSwapCase, KeyedUiAccount,
+    ALL_FIXTURES,
+};
+
+use crate::common::{swap_test, updated_inf_amm, SwapUserAccs};
+
+thread_local! {
+    static INF: InfAmm = updated_inf_amm(&ALL_FIXTURES)
+}
+
+proptest! {
+    // each case runs a full mollusk execution
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn quote_same_as_executed_fixture(case in fixture_swap_case()) {
+        let FixtureSwapCase { inp, out, is_exact_out, amount } = case;
+        let user = SwapUserAccs::default()
+            .with_signer(format!("{inp}-token-acc-owner"))
+            .with_inp_token_acc(format!("{inp}-token-acc"))
+            .with_out_token_acc(format!("{out}-token-acc"))
+            .map(|n| KeyedUiAccount::from_test_fixtures_json(&n).into_keyed_account());
+        let qp = QuoteParams {
+            amount,
+            input_mint: token_acc_mint(&user.inp_token_acc().1),
+            output_mint: token_acc_mint(&user.out_token_acc().1),
+            swap_mode: if is_exact_out {
+                SwapMode::ExactOut
+            } else {
+                SwapMode::ExactIn
+            },
+        };
+
+        // skip cases that are expected to fail, e.g. not enough liquidity
+        // or user does not have enough input for the ExactOut amount
+        let quote = INF.with(|inf| inf.quote(&qp));
+        let Ok(quote) = quote else {
+            return Err(TestCaseError::reject("unquotable"));
+        };
+        let inp_balance = fixture_token_acc_amount(&format!("{inp}-token-acc"));
+        prop_assume!(quote.in_amount <= inp_balance);
+
+        swap_test(qp, &ALL_FIXTURES, user);
+    }
+}
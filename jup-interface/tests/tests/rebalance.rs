@@ -0,0 +1,114 @@
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR, pda::rebalance_record, rebalance::RebalanceParams,
+};
+use inf1_std::inf1_ctl_core::{
+    self,
+    instructions::rebalance::end::EndRebalanceIxData,
+    keys::{LST_STATE_LIST_ID, POOL_STATE_ID},
+};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use test_utils::{KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const AMOUNT: u64 = 1_000_000_000;
+
+const WITHDRAW_TO: Pubkey = Pubkey::new_from_array([7; 32]);
+
+fn fixture_pk(name: &str) -> Pubkey {
+    KeyedUiAccount::from_test_fixtures_json(name)
+        .into_keyed_account()
+        .0
+}
+
+fn meta<'a>(ix: &'a Instruction, pk: &Pubkey) -> &'a AccountMeta {
+    ix.accounts.iter().find(|m| m.pubkey == *pk).unwrap()
+}
+
+fn contains_le_bytes(data: &[u8], v: u64) -> bool {
+    data.windows(8).any(|w| w == v.to_le_bytes())
+}
+
+#[test]
+fn quote_rebalance_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let wsol = Pubkey::new_from_array(WSOL_MINT_ADDR);
+
+    let q = inf.quote_rebalance(&wsol, jupsol, AMOUNT).unwrap();
+    assert_eq!(q.out, AMOUNT);
+    // jupSOL is worth more than 1 SOL
+    assert!(q.inp > AMOUNT);
+
+    // rebalances are not charged fees, unlike swapping for the same amount out
+    let swap = inf
+        .quote(&QuoteParams {
+            amount: AMOUNT,
+            input_mint: wsol,
+            output_mint: *jupsol,
+            swap_mode: SwapMode::ExactOut,
+        })
+        .unwrap();
+    assert!(q.inp <= swap.in_amount);
+}
+
+#[test]
+fn quote_rebalance_more_than_reserves_errs_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    inf.quote_rebalance(
+        &Pubkey::new_from_array(WSOL_MINT_ADDR),
+        CONST_PUBKEYS.jupsol_mint(),
+        u64::MAX,
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn rebalance_ixs_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let wsol = Pubkey::new_from_array(WSOL_MINT_ADDR);
+    let rebalance_auth = inf.rebalance_authority().unwrap();
+    let rebalance_record = Pubkey::new_from_array(rebalance_record().unwrap());
+    let max_inp_amount = inf.quote_rebalance(&wsol, jupsol, AMOUNT).unwrap().inp;
+
+    let [start, end] = inf
+        .rebalance_ixs(&RebalanceParams {
+            inp_mint: wsol,
+            out_mint: *jupsol,
+            amount: AMOUNT,
+            max_inp_amount,
+            withdraw_to: WITHDRAW_TO,
+        })
+        .unwrap();
+
+    for ix in [&start, &end] {
+        assert_eq!(ix.program_id, inf1_ctl_core::ID.into());
+        for (pk, is_writable) in [
+            (rebalance_record, true),
+            (POOL_STATE_ID.into(), true),
+            (LST_STATE_LIST_ID.into(), true),
+        ] {
+            assert_eq!(meta(ix, &pk).is_writable, is_writable);
+        }
+        // only the rebalance authority signs
+        assert!(meta(ix, &rebalance_auth).is_signer);
+        assert!(ix
+            .accounts
+            .iter()
+            .all(|m| m.is_signer == (m.pubkey == rebalance_auth)));
+    }
+
+    // StartRebalance withdraws jupSOL from its reserves to WITHDRAW_TO
+    for pk in [fixture_pk("jupsol-reserves"), WITHDRAW_TO] {
+        assert!(meta(&start, &pk).is_writable);
+    }
+    assert!(contains_le_bytes(&start.data, AMOUNT));
+    assert!(contains_le_bytes(&start.data, max_inp_amount));
+
+    // EndRebalance checks the wSOL deposited into its reserves
+    assert!(meta(&end, &fixture_pk("wsol-reserves")).is_writable);
+    assert_eq!(end.data, EndRebalanceIxData::new().as_buf());
+}
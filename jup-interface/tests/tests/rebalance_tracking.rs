@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{
+    lst_info::TOKEN_PROGRAM_ID, pda::rebalance_record, rebalance::RebalanceInProgress,
+    INF_PROGRAM_ID,
+};
+use inf1_std::inf1_ctl_core::keys::POOL_STATE_ID;
+use jupiter_amm_interface::{AccountMap, Amm};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const IS_REBALANCING_OFFSET: usize = 14;
+
+const OLD_TOTAL_SOL_VALUE: u64 = 1_234_567_890;
+
+fn accounts_to_update_map(inf: &impl Amm, onchain_state: &HashMap<Pubkey, Account>) -> AccountMap {
+    inf.get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect()
+}
+
+/// [`ALL_FIXTURES`] mid-rebalance that is waiting on `inp_lst_index`
+fn rebalancing_fixtures(inp_lst_index: u32) -> HashMap<Pubkey, Account> {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state
+        .get_mut(&Pubkey::new_from_array(POOL_STATE_ID))
+        .unwrap()
+        .data[IS_REBALANCING_OFFSET] = 1;
+    let mut data = OLD_TOTAL_SOL_VALUE.to_le_bytes().to_vec();
+    data.extend(inp_lst_index.to_le_bytes());
+    data.extend([0; 4]);
+    onchain_state.insert(
+        Pubkey::new_from_array(rebalance_record().unwrap()),
+        Account {
+            lamports: 1_000_000,
+            data,
+            owner: INF_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    onchain_state
+}
+
+#[test]
+fn rebalance_in_progress_tracked_fixture() {
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let index = updated_inf_amm(&ALL_FIXTURES)
+        .lst_info(jupsol.as_array(), &TOKEN_PROGRAM_ID)
+        .unwrap()
+        .index;
+    let onchain_state = rebalancing_fixtures(index.try_into().unwrap());
+
+    let mut inf = updated_inf_amm(&onchain_state).with_rebalance_tracking();
+    assert_eq!(inf.rebalance_in_progress(), None);
+    assert!(inf.quotable_mints().contains(jupsol));
+
+    let record = Pubkey::new_from_array(rebalance_record().unwrap());
+    assert!(inf.get_accounts_to_update().contains(&record));
+    inf.update(&accounts_to_update_map(&inf, &onchain_state))
+        .unwrap();
+    assert_eq!(
+        inf.rebalance_in_progress(),
+        Some(RebalanceInProgress {
+            inp_mint: jupsol.to_bytes(),
+            old_total_sol_value: OLD_TOTAL_SOL_VALUE,
+        })
+    );
+    assert!(!inf.quotable_mints().contains(jupsol));
+
+    // rebalance ended
+    inf.update(&accounts_to_update_map(&inf, &ALL_FIXTURES))
+        .unwrap();
+    assert_eq!(inf.rebalance_in_progress(), None);
+    assert!(!inf.get_accounts_to_update().contains(&record));
+    assert!(inf.quotable_mints().contains(jupsol));
+}
+
+#[test]
+fn rebalance_not_tracked_by_default_fixture() {
+    let onchain_state = rebalancing_fixtures(0);
+    let mut inf = updated_inf_amm(&onchain_state);
+    let record = Pubkey::new_from_array(rebalance_record().unwrap());
+    assert!(!inf.get_accounts_to_update().contains(&record));
+    inf.update(&accounts_to_update_map(&inf, &onchain_state))
+        .unwrap();
+    assert_eq!(inf.rebalance_in_progress(), None);
+}
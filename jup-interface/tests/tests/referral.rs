@@ -0,0 +1,45 @@
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, err::InfAmmErr};
+use jupiter_amm_interface::{Amm, SwapMode, SwapParams};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const REFERRER_TOKEN_ACC: Pubkey = Pubkey::new_from_array([4; 32]);
+
+#[test]
+fn referrer_for_pair_mint_refused_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    assert!(!inf.supports_referral_fees());
+
+    let jupsol = *CONST_PUBKEYS.jupsol_mint();
+    let wsol = Pubkey::new_from_array(WSOL_MINT_ADDR);
+    for (referrer_mint, expected_err_mint) in [
+        (jupsol, Some(jupsol)),
+        (wsol, Some(wsol)),
+        // referrers for mints not in the pair are irrelevant
+        (Pubkey::new_from_array([5; 32]), None),
+    ] {
+        let referrers = [(referrer_mint, REFERRER_TOKEN_ACC)].into_iter().collect();
+        let res = inf.get_swap_and_account_metas(&SwapParams {
+            swap_mode: SwapMode::ExactIn,
+            in_amount: 1_000_000_000,
+            out_amount: 0,
+            source_mint: jupsol,
+            destination_mint: wsol,
+            source_token_account: Pubkey::new_from_array([1; 32]),
+            destination_token_account: Pubkey::new_from_array([2; 32]),
+            token_transfer_authority: Pubkey::new_from_array([3; 32]),
+            quote_mint_to_referrer: Some(&referrers),
+            jupiter_program_id: &Default::default(),
+            missing_dynamic_accounts_as_default: Default::default(),
+        });
+        match expected_err_mint {
+            None => assert!(res.is_ok()),
+            Some(expected) => assert!(matches!(
+                res.unwrap_err().downcast_ref::<InfAmmErr>(),
+                Some(InfAmmErr::ReferralFeesUnsupported { mint }) if *mint == expected.to_bytes()
+            )),
+        }
+    }
+}
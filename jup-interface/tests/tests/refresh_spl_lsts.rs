@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, InfAmm};
+use inf1_std::inf1_ctl_core::keys::LST_STATE_LIST_ID;
+use jupiter_amm_interface::{AccountMap, Amm, KeyedAccount, QuoteParams, SwapMode};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+use crate::common::AMM_CONTEXT;
+
+fn update(inf: &mut InfAmm, onchain_state: &HashMap<Pubkey, Account>) {
+    let accounts: AccountMap = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect();
+    // 1st update might fail bec DEFAULT_MAINNET_POOL might be stale
+    let _: Result<_, _> = inf.update(&accounts);
+    inf.update(&accounts).unwrap();
+}
+
+#[test]
+fn refresh_spl_lsts_makes_skipped_lst_quotable_fixture() {
+    let jupsol = *CONST_PUBKEYS.jupsol_mint();
+    let key = Pubkey::new_from_array(LST_STATE_LIST_ID);
+    let mut inf = InfAmm::new(
+        &KeyedAccount {
+            key,
+            account: ALL_FIXTURES.get(&key).unwrap().clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        SPL_LSTS
+            .into_iter()
+            .filter(|(mint, _)| mint != jupsol.as_array())
+            .collect(),
+    )
+    .unwrap();
+    update(&mut inf, &ALL_FIXTURES);
+
+    let qp = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jupsol,
+        output_mint: WSOL_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    };
+    inf.quote(&qp).unwrap_err();
+    assert!(!inf
+        .get_accounts_to_update()
+        .contains(CONST_PUBKEYS.jupsol_pool()));
+
+    let added = inf.refresh_spl_lsts(SPL_LSTS.into_iter().collect());
+    assert_eq!(added.len(), 1);
+    assert_eq!(added[0], jupsol.to_bytes());
+    assert!(inf
+        .get_accounts_to_update()
+        .contains(CONST_PUBKEYS.jupsol_pool()));
+    // already known mints are not added again
+    assert!(inf
+        .refresh_spl_lsts(SPL_LSTS.into_iter().collect())
+        .is_empty());
+
+    update(&mut inf, &ALL_FIXTURES);
+    inf.quote(&qp).unwrap();
+}
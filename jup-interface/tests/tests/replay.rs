@@ -0,0 +1,136 @@
+use std::{fs, path::PathBuf};
+
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR,
+    replay::{load_dir, KeyedUiAccount, ReplayStep},
+    InfAmm,
+};
+use inf1_std::inf1_ctl_core::keys::{LST_STATE_LIST_ID, POOL_STATE_ID};
+use jupiter_amm_interface::{Amm, KeyedAccount, QuoteParams, SwapMode};
+use solana_pubkey::Pubkey;
+use test_utils::{account_to_ui_account, ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+/// Removed on drop
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn write_step(dir: &TempDir, file_name: &str, step: &ReplayStep) {
+    fs::write(dir.0.join(file_name), serde_json::to_vec(step).unwrap()).unwrap();
+}
+
+fn keyed_ui_accounts<'a>(pks: impl IntoIterator<Item = &'a Pubkey>) -> Vec<KeyedUiAccount> {
+    pks.into_iter()
+        .map(|pk| KeyedUiAccount {
+            pubkey: pk.to_string(),
+            account: account_to_ui_account(ALL_FIXTURES.get(pk).unwrap()),
+        })
+        .collect()
+}
+
+fn jupsol_wsol_qp() -> QuoteParams {
+    QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: *CONST_PUBKEYS.jupsol_mint(),
+        output_mint: WSOL_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    }
+}
+
+#[test]
+fn replay_dir_fixture() {
+    let dir = TempDir::new("inf-replay-fixture");
+    // written out of slot order, and with a non-step file
+    write_step(
+        &dir,
+        "a.json",
+        &ReplayStep {
+            slot: 20,
+            epoch: 0,
+            accounts: keyed_ui_accounts([&Pubkey::new_from_array(POOL_STATE_ID)]),
+        },
+    );
+    write_step(
+        &dir,
+        "b.json",
+        &ReplayStep {
+            slot: 10,
+            epoch: 0,
+            accounts: keyed_ui_accounts(ALL_FIXTURES.keys()),
+        },
+    );
+    fs::write(dir.0.join("README.md"), "not a step").unwrap();
+
+    let steps = load_dir(&dir.0).unwrap();
+    assert_eq!(steps.iter().map(|s| s.slot).collect::<Vec<_>>(), [10, 20]);
+
+    let (key, account) = ALL_FIXTURES
+        .get_key_value(&LST_STATE_LIST_ID.into())
+        .unwrap();
+    let mut inf = InfAmm::new(
+        &KeyedAccount {
+            key: *key,
+            account: account.clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        SPL_LSTS.into_iter().collect(),
+    )
+    .unwrap()
+    .with_deterministic_mode(0, 0);
+
+    let expected = updated_inf_amm(&ALL_FIXTURES)
+        .quote(&jupsol_wsol_qp())
+        .unwrap();
+    let qps = [jupsol_wsol_qp()];
+    let outcomes: Vec<_> = inf.replay(steps, &qps).collect::<Result<_, _>>().unwrap();
+    assert_eq!(outcomes.len(), 2);
+    // 1st update may fail if the pricing program differs from the default pool's,
+    // same as the 1st update cycle of a new AMM.
+    // 2nd step only has pool state, the rest carry over from the 1st
+    let last = outcomes.into_iter().last().unwrap();
+    assert_eq!(last.slot, 20);
+    last.update.unwrap();
+    let [quote] = <[_; 1]>::try_from(last.quotes).unwrap();
+    assert_eq!(quote.unwrap().out_amount, expected.out_amount);
+}
+
+#[test]
+fn replay_invalid_pubkey_err() {
+    let (key, account) = ALL_FIXTURES
+        .get_key_value(&LST_STATE_LIST_ID.into())
+        .unwrap();
+    let mut inf = InfAmm::new(
+        &KeyedAccount {
+            key: *key,
+            account: account.clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        SPL_LSTS.into_iter().collect(),
+    )
+    .unwrap()
+    .with_deterministic_mode(0, 0);
+    let mut accounts = keyed_ui_accounts([&Pubkey::new_from_array(POOL_STATE_ID)]);
+    accounts[0].pubkey = "not a pubkey".to_owned();
+    let step = ReplayStep {
+        slot: 1,
+        epoch: 0,
+        accounts,
+    };
+    assert!(inf.replay([step], &[]).next().unwrap().is_err());
+}
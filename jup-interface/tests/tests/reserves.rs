@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, err::InfAmmErr};
+use jupiter_amm_interface::{AccountMap, Amm, QuoteParams, SwapMode};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+use test_utils::{KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+fn accounts_to_update_map(inf: &impl Amm, onchain_state: &HashMap<Pubkey, Account>) -> AccountMap {
+    inf.get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect()
+}
+
+fn jupsol_wsol_qp() -> QuoteParams {
+    QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: *CONST_PUBKEYS.jupsol_mint(),
+        output_mint: WSOL_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    }
+}
+
+fn assert_jupsol_reserves_invalid(onchain_state: &HashMap<Pubkey, Account>) {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint().to_bytes();
+
+    inf.update(&accounts_to_update_map(&inf, onchain_state))
+        .unwrap();
+    assert_eq!(inf.invalid_reserves_mints(), vec![jupsol]);
+    let err = inf.quote(&jupsol_wsol_qp()).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<InfAmmErr>(),
+        Some(InfAmmErr::ReserveAccountInvalid { mint }) if *mint == jupsol
+    ));
+    // other LSTs unaffected
+    inf.quote(&QuoteParams {
+        input_mint: WSOL_MINT_ADDR.into(),
+        output_mint: KeyedUiAccount::from_test_fixtures_json("msol-mint")
+            .into_keyed_account()
+            .0,
+        ..jupsol_wsol_qp()
+    })
+    .unwrap();
+
+    // recovers once reserves are valid again
+    inf.update(&accounts_to_update_map(&inf, &ALL_FIXTURES))
+        .unwrap();
+    assert!(inf.invalid_reserves_mints().is_empty());
+    inf.quote(&jupsol_wsol_qp()).unwrap();
+}
+
+#[test]
+fn all_fixture_reserves_valid_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    assert!(inf.invalid_reserves_mints().is_empty());
+}
+
+#[test]
+fn missing_reserves_fixture() {
+    let (reserves, _) =
+        KeyedUiAccount::from_test_fixtures_json("jupsol-reserves").into_keyed_account();
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state.remove(&reserves);
+    assert_jupsol_reserves_invalid(&onchain_state);
+}
+
+#[test]
+fn wrong_reserves_mint_fixture() {
+    let (reserves, _) =
+        KeyedUiAccount::from_test_fixtures_json("jupsol-reserves").into_keyed_account();
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state.get_mut(&reserves).unwrap().data[..32].copy_from_slice(&WSOL_MINT_ADDR);
+    assert_jupsol_reserves_invalid(&onchain_state);
+}
+
+#[test]
+fn truncated_reserves_fixture() {
+    let (reserves, _) =
+        KeyedUiAccount::from_test_fixtures_json("jupsol-reserves").into_keyed_account();
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state.get_mut(&reserves).unwrap().data.truncate(64);
+    assert_jupsol_reserves_invalid(&onchain_state);
+}
@@ -0,0 +1,83 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    route::Route,
+};
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode, SwapParams};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const AMOUNT: u64 = 1_000_000_000;
+
+#[test]
+fn best_route_at_least_direct_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let pair = Pair {
+        inp: jupsol.as_array(),
+        out: &WSOL_MINT_ADDR,
+    };
+    let (_, direct) = inf
+        .quote_raw(&pair, AMOUNT, TradeLimitTy::ExactIn, inf.epoch())
+        .unwrap();
+    let best = inf.quote_best_route(&pair, AMOUNT).unwrap();
+    assert!(best.quote.out >= direct.out);
+    assert_eq!(best.quote.inp, AMOUNT);
+
+    let quote = inf
+        .quote(&QuoteParams {
+            amount: AMOUNT,
+            input_mint: *jupsol,
+            output_mint: WSOL_MINT_ADDR.into(),
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+    // Amm::quote never routes, matching the direct swap of get_swap_and_account_metas
+    assert_eq!(quote.out_amount, direct.out);
+
+    let lp_token_account = Pubkey::new_from_array([4; 32]);
+    let ixs = inf
+        .routed_swap_instructions(
+            &SwapParams {
+                swap_mode: SwapMode::ExactIn,
+                in_amount: AMOUNT,
+                out_amount: best.quote.out,
+                source_mint: *jupsol,
+                destination_mint: WSOL_MINT_ADDR.into(),
+                source_token_account: Pubkey::new_from_array([1; 32]),
+                destination_token_account: Pubkey::new_from_array([2; 32]),
+                token_transfer_authority: Pubkey::new_from_array([3; 32]),
+                quote_mint_to_referrer: None,
+                jupiter_program_id: &Pubkey::default(),
+                missing_dynamic_accounts_as_default: false,
+            },
+            &lp_token_account,
+        )
+        .unwrap();
+    match best.route {
+        Route::Direct => assert_eq!(ixs.len(), 1),
+        Route::ViaLpToken { .. } => {
+            assert_eq!(ixs.len(), 2);
+            for ix in ixs.iter() {
+                assert!(ix.accounts.iter().any(|m| m.pubkey == lp_token_account));
+            }
+        }
+    }
+}
+
+#[test]
+fn via_lp_token_never_for_liquidity_pairs_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let best = inf
+        .quote_best_route(
+            &Pair {
+                inp: &WSOL_MINT_ADDR,
+                out: &INF_MINT_ADDR,
+            },
+            AMOUNT,
+        )
+        .unwrap();
+    assert_eq!(best.route, Route::Direct);
+}
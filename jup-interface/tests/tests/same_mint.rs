@@ -0,0 +1,51 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    err::InfAmmErr,
+};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode, SwapParams};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn same_pair_mint_refused_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    for mint in [
+        *CONST_PUBKEYS.jupsol_mint(),
+        WSOL_MINT_ADDR.into(),
+        INF_MINT_ADDR.into(),
+    ] {
+        for swap_mode in [SwapMode::ExactIn, SwapMode::ExactOut] {
+            let quote_err = inf
+                .quote(&QuoteParams {
+                    amount: 1_000_000_000,
+                    input_mint: mint,
+                    output_mint: mint,
+                    swap_mode,
+                })
+                .unwrap_err();
+            let metas_err = inf
+                .get_swap_and_account_metas(&SwapParams {
+                    swap_mode,
+                    in_amount: 1_000_000_000,
+                    out_amount: 1_000_000_000,
+                    source_mint: mint,
+                    destination_mint: mint,
+                    source_token_account: Pubkey::new_from_array([1; 32]),
+                    destination_token_account: Pubkey::new_from_array([2; 32]),
+                    token_transfer_authority: Pubkey::new_from_array([3; 32]),
+                    quote_mint_to_referrer: Default::default(),
+                    jupiter_program_id: &Default::default(),
+                    missing_dynamic_accounts_as_default: Default::default(),
+                })
+                .unwrap_err();
+            for err in [quote_err, metas_err] {
+                assert!(matches!(
+                    err.downcast_ref::<InfAmmErr>(),
+                    Some(InfAmmErr::SamePairMint { mint: m }) if *m == mint.to_bytes()
+                ));
+            }
+        }
+    }
+}
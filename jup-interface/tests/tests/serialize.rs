@@ -0,0 +1,64 @@
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, err::InfAmmErr, lst_info::TOKEN_PROGRAM_ID};
+use serde_json::{json, to_value};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn err_pubkeys_base58() {
+    let wsol = Pubkey::new_from_array(WSOL_MINT_ADDR).to_string();
+    assert_eq!(
+        to_value(InfAmmErr::SamePairMint {
+            mint: WSOL_MINT_ADDR
+        })
+        .unwrap(),
+        json!({ "SamePairMint": { "mint": wsol } })
+    );
+    assert_eq!(
+        to_value(InfAmmErr::LpMintAuthorityMismatch {
+            expected: WSOL_MINT_ADDR,
+            actual: None,
+        })
+        .unwrap(),
+        json!({ "LpMintAuthorityMismatch": { "expected": wsol, "actual": null } })
+    );
+}
+
+#[test]
+fn report_pubkeys_base58_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint();
+    let info = inf.lst_info(jupsol.as_array(), &TOKEN_PROGRAM_ID).unwrap();
+    let v = to_value(info).unwrap();
+    assert_eq!(v["mint"], json!(jupsol.to_string()));
+    assert_eq!(
+        v["reserves"],
+        json!(Pubkey::new_from_array(info.reserves).to_string())
+    );
+    assert_eq!(v["index"], json!(info.index));
+
+    let preview = inf
+        .preview_add_liquidity(jupsol.as_array(), 1_000_000_000)
+        .unwrap();
+    let v = to_value(preview).unwrap();
+    assert_eq!(v["mint"], json!(jupsol.to_string()));
+    assert_eq!(v["out_amount"], json!(preview.out_amount));
+}
+
+#[test]
+fn pool_valuation_serialize_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let v = inf.pool_valuation();
+    let lst = v.lsts[0];
+    let ser = to_value(&v).unwrap();
+    assert_eq!(ser["total_sol_value"], json!(v.total_sol_value));
+    assert_eq!(
+        ser["lsts"][0]["mint"],
+        json!(Pubkey::new_from_array(lst.mint).to_string())
+    );
+    assert_eq!(
+        ser["lsts"][0]["share"],
+        lst.share.map_or(json!(null), |s| json!(s.to_string()))
+    );
+}
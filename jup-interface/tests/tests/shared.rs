@@ -0,0 +1,97 @@
+use std::thread;
+
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    shared::InfAmmShared,
+};
+use jupiter_amm_interface::{AccountMap, Amm, QuoteParams, SwapMode};
+use solana_pubkey::Pubkey;
+use test_utils::{KeyedUiAccount, ALL_FIXTURES};
+
+use crate::common::updated_inf_amm;
+
+/// Offset of `supply: u64` in mint data, after `mint_authority: COption<Pubkey>`
+const MINT_SUPPLY_OFFSET: usize = 36;
+
+fn accounts_with_lp_supply(shared: &InfAmmShared, supply: u64) -> AccountMap {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state
+        .get_mut(&Pubkey::new_from_array(INF_MINT_ADDR))
+        .unwrap()
+        .data[MINT_SUPPLY_OFFSET..][..8]
+        .copy_from_slice(&supply.to_le_bytes());
+    shared
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, onchain_state.get(&pk)?.clone())))
+        .collect()
+}
+
+#[test]
+fn update_publishes_without_affecting_loaded_fixture() {
+    let shared = InfAmmShared::new(updated_inf_amm(&ALL_FIXTURES));
+    let before = shared.load();
+    let supply_before = before.lp_token_supply();
+
+    let accounts = accounts_with_lp_supply(&shared, supply_before + 1);
+    shared.update(&accounts).unwrap();
+    assert_eq!(before.lp_token_supply(), supply_before);
+    assert_eq!(shared.load().lp_token_supply(), supply_before + 1);
+
+    // same accounts again is a no-op
+    shared.update(&accounts).unwrap();
+    assert_eq!(shared.load().lp_token_supply(), supply_before + 1);
+}
+
+#[test]
+fn failed_update_publishes_nothing_fixture() {
+    let shared = InfAmmShared::new(updated_inf_amm(&ALL_FIXTURES));
+    let before = shared.load();
+
+    let mut accounts = accounts_with_lp_supply(&shared, before.lp_token_supply() + 1);
+    let (msol_pool, _) = KeyedUiAccount::from_test_fixtures_json("msol-pool").into_keyed_account();
+    accounts.get_mut(&msol_pool).unwrap().data.clear();
+    shared.update(&accounts).unwrap_err();
+
+    let after = shared.load();
+    assert_eq!(after.lp_token_supply(), before.lp_token_supply());
+    assert_eq!(after.generation, before.generation);
+}
+
+#[test]
+fn concurrent_quotes_and_updates_fixture() {
+    const UPDATES: u64 = 8;
+
+    let shared = InfAmmShared::new(updated_inf_amm(&ALL_FIXTURES));
+    let supply_before = shared.load().lp_token_supply();
+    let qp = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: WSOL_MINT_ADDR.into(),
+        output_mint: INF_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    };
+    let out_before = shared.quote(&qp).unwrap().out_amount;
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..32 {
+                    // whichever snapshot is loaded, bumping LP supply by a few atoms barely moves the quote
+                    let out = shared.quote(&qp).unwrap().out_amount;
+                    assert!(out.abs_diff(out_before) <= out_before / 1_000);
+                }
+            });
+        }
+        for i in 1..=UPDATES {
+            let shared = &shared;
+            s.spawn(move || {
+                let accounts = accounts_with_lp_supply(shared, supply_before + i);
+                shared.update(&accounts).unwrap();
+            });
+        }
+    });
+
+    // updates were serialized, so the last one applied is published whole
+    let after = shared.load();
+    assert!((supply_before + 1..=supply_before + UPDATES).contains(&after.lp_token_supply()));
+}
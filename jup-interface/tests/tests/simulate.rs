@@ -0,0 +1,80 @@
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, simulate::SwapSimulator};
+use jupiter_amm_interface::{AccountMap, Amm, QuoteParams, SwapMode, SwapParams};
+use test_utils::{
+    test_fixtures_dir, KeyedUiAccount, ALL_FIXTURES, CONST_PUBKEYS, FIXTURE_PROGRAMS,
+};
+
+use crate::common::updated_inf_amm;
+
+fn fixture_simulator() -> SwapSimulator {
+    let elfs: Vec<_> = FIXTURE_PROGRAMS
+        .into_iter()
+        .map(|(fname, key)| {
+            let path = test_fixtures_dir()
+                .join("programs")
+                .join(fname)
+                .with_extension("so");
+            (key, std::fs::read(path).unwrap())
+        })
+        .collect();
+    SwapSimulator::new(elfs.iter().map(|(key, elf)| (*key, elf.as_slice())))
+}
+
+#[test]
+fn simulate_matches_quote_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let accounts: AccountMap = ALL_FIXTURES.iter().map(|(k, v)| (*k, v.clone())).collect();
+    let [signer, source_token_account, destination_token_account] = [
+        "jupsol-token-acc-owner",
+        "jupsol-token-acc",
+        "wsol-token-acc",
+    ]
+    .map(|n| {
+        KeyedUiAccount::from_test_fixtures_json(n)
+            .into_keyed_account()
+            .0
+    });
+    let quote = inf
+        .quote(&QuoteParams {
+            amount: 1_000_000_000,
+            input_mint: *CONST_PUBKEYS.jupsol_mint(),
+            output_mint: WSOL_MINT_ADDR.into(),
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+    let swap_params = SwapParams {
+        swap_mode: SwapMode::ExactIn,
+        in_amount: quote.in_amount,
+        out_amount: quote.out_amount,
+        source_mint: *CONST_PUBKEYS.jupsol_mint(),
+        destination_mint: WSOL_MINT_ADDR.into(),
+        source_token_account,
+        destination_token_account,
+        token_transfer_authority: signer,
+        quote_mint_to_referrer: None,
+        jupiter_program_id: &Default::default(),
+        missing_dynamic_accounts_as_default: false,
+    };
+
+    let mut sim = fixture_simulator();
+    let res = inf
+        .simulate_swap(&mut sim, &swap_params, &accounts)
+        .unwrap();
+    assert!(res.is_success(), "{:#?}", res.program_result);
+    assert_eq!(res.in_amount, quote.in_amount);
+    assert_eq!(res.out_amount, quote.out_amount);
+
+    // slippage limit exceeded
+    let res = inf
+        .simulate_swap(
+            &mut sim,
+            &SwapParams {
+                out_amount: quote.out_amount + 1,
+                ..swap_params
+            },
+            &accounts,
+        )
+        .unwrap();
+    assert!(!res.is_success());
+    assert_eq!(res.out_amount, 0);
+}
@@ -0,0 +1,50 @@
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, slippage::slippage_limit};
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use rust_decimal::Decimal;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const SLIPPAGE_BPS: u16 = 50;
+
+#[test]
+fn quote_with_slippage_limits_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    for swap_mode in [SwapMode::ExactIn, SwapMode::ExactOut] {
+        let sq = inf
+            .quote_with_slippage(
+                &QuoteParams {
+                    amount: 1_000_000_000,
+                    input_mint: *CONST_PUBKEYS.jupsol_mint(),
+                    output_mint: WSOL_MINT_ADDR.into(),
+                    swap_mode,
+                },
+                SLIPPAGE_BPS,
+            )
+            .unwrap();
+        match swap_mode {
+            SwapMode::ExactIn => {
+                assert_eq!(sq.max_in_amount, sq.quote.in_amount);
+                assert_eq!(
+                    sq.min_out_amount,
+                    slippage_limit(sq.quote.out_amount, SLIPPAGE_BPS, swap_mode)
+                );
+                assert!(sq.min_out_amount < sq.quote.out_amount);
+                assert_eq!(sq.limit(), sq.min_out_amount);
+            }
+            SwapMode::ExactOut => {
+                assert_eq!(sq.min_out_amount, sq.quote.out_amount);
+                assert_eq!(
+                    sq.max_in_amount,
+                    slippage_limit(sq.quote.in_amount, SLIPPAGE_BPS, swap_mode)
+                );
+                assert!(sq.max_in_amount > sq.quote.in_amount);
+                assert_eq!(sq.limit(), sq.max_in_amount);
+            }
+        }
+        assert_eq!(
+            sq.effective_price(),
+            Some(Decimal::from(sq.quote.out_amount) / Decimal::from(sq.quote.in_amount))
+        );
+    }
+}
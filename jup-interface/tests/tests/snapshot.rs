@@ -0,0 +1,77 @@
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, InfAmm};
+use jupiter_amm_interface::{AccountMap, Amm, QuoteParams, SwapMode};
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+fn snapshotted_inf_amm() -> InfAmm {
+    let mut inf = updated_inf_amm(&ALL_FIXTURES).with_snapshots();
+    let accounts: AccountMap = inf
+        .get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| {
+            let (k, v) = ALL_FIXTURES.get_key_value(&pk)?;
+            Some((*k, v.clone()))
+        })
+        .collect();
+    inf.update(&accounts).unwrap();
+    inf
+}
+
+#[test]
+fn restore_same_as_snapshotted_fixture() {
+    let inf = snapshotted_inf_amm();
+    let bytes = inf.snapshot().unwrap();
+    let restored = InfAmm::restore(&bytes, &AMM_CONTEXT).unwrap();
+
+    assert_eq!(restored.generation, inf.generation);
+    assert_eq!(
+        restored.inner.lst_state_list_data,
+        inf.inner.lst_state_list_data
+    );
+    assert_eq!(
+        restored.get_accounts_to_update(),
+        inf.get_accounts_to_update()
+    );
+    for (input_mint, output_mint) in [
+        (*CONST_PUBKEYS.jupsol_mint(), WSOL_MINT_ADDR.into()),
+        (WSOL_MINT_ADDR.into(), *CONST_PUBKEYS.jupsol_mint()),
+    ] {
+        let qp = QuoteParams {
+            amount: 1_000_000_000,
+            input_mint,
+            output_mint,
+            swap_mode: SwapMode::ExactIn,
+        };
+        let (q, expected) = (restored.quote(&qp).unwrap(), inf.quote(&qp).unwrap());
+        assert_eq!(q.in_amount, expected.in_amount);
+        assert_eq!(q.out_amount, expected.out_amount);
+        assert_eq!(q.fee_amount, expected.fee_amount);
+    }
+}
+
+#[test]
+fn snapshot_of_restored_same_as_original_fixture() {
+    let inf = snapshotted_inf_amm();
+    let restored = InfAmm::restore(&inf.snapshot().unwrap(), &AMM_CONTEXT).unwrap();
+
+    let mut expected = inf.to_snapshot().unwrap();
+    let mut actual = restored.to_snapshot().unwrap();
+    for s in [&mut expected, &mut actual] {
+        s.spl_lsts.sort();
+        s.accounts.sort_by_key(|(pk, _)| *pk);
+    }
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn snapshot_not_enabled_errs_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    assert!(inf.to_snapshot().is_none());
+    assert!(inf.snapshot().is_err());
+}
+
+#[test]
+fn restore_invalid_bytes_errs() {
+    assert!(InfAmm::restore(b"not a snapshot", &AMM_CONTEXT).is_err());
+}
@@ -0,0 +1,39 @@
+use inf1_jup_interface::spl_lsts::{load_spl_lsts_json, parse_spl_lsts_json};
+use test_utils::CONST_PUBKEYS;
+
+#[test]
+fn load_spl_lsts_json_roundtrip() {
+    let path = std::env::temp_dir().join("inf1-jup-interface-spl-lsts.json");
+    std::fs::write(
+        &path,
+        format!(
+            r#"{{"{}": "{}"}}"#,
+            CONST_PUBKEYS.jupsol_mint(),
+            CONST_PUBKEYS.jupsol_pool()
+        ),
+    )
+    .unwrap();
+    let spl_lsts = load_spl_lsts_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(spl_lsts.len(), 1);
+    assert_eq!(
+        spl_lsts.get(CONST_PUBKEYS.jupsol_mint().as_array()),
+        Some(CONST_PUBKEYS.jupsol_pool().as_array())
+    );
+}
+
+#[test]
+fn parse_spl_lsts_json_rejects_invalid() {
+    for invalid in [
+        r#"[]"#,
+        r#"{"notapubkey": "8VpRhuxa7sUUepdY3kQiTmX9rS5vx4WgaXiAnXq4KCtr"}"#,
+        r#"{"jupSoLaHXQiZZTSfEWMTRRgpnyFm8f6sZdosWBjx93v": 1}"#,
+    ] {
+        assert!(
+            parse_spl_lsts_json(invalid.as_bytes()).is_err(),
+            "{invalid}"
+        );
+    }
+    assert!(parse_spl_lsts_json(b"{}").unwrap().is_empty());
+}
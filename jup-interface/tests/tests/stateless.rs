@@ -0,0 +1,51 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    stateless::quote_from_accounts,
+    InfAmm,
+};
+use inf1_std::inf1_ctl_core::keys::LST_STATE_LIST_ID;
+use jupiter_amm_interface::{AccountMap, Amm, QuoteParams, SwapMode};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+use crate::common::updated_inf_amm;
+
+fn account_map() -> AccountMap {
+    ALL_FIXTURES.iter().map(|(k, v)| (*k, v.clone())).collect()
+}
+
+#[test]
+fn quote_from_accounts_same_as_updated_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let account_map = account_map();
+    for (input_mint, output_mint) in [
+        (*CONST_PUBKEYS.jupsol_mint(), WSOL_MINT_ADDR.into()),
+        (WSOL_MINT_ADDR.into(), INF_MINT_ADDR.into()),
+    ] {
+        for swap_mode in [SwapMode::ExactIn, SwapMode::ExactOut] {
+            let qp = QuoteParams {
+                amount: 1_000_000_000,
+                input_mint,
+                output_mint,
+                swap_mode,
+            };
+            let stateless = quote_from_accounts(&account_map, &qp, SPL_LSTS.into_iter().collect());
+            match inf.quote(&qp) {
+                Ok(expected) => {
+                    let q = stateless.unwrap();
+                    assert_eq!(q.in_amount, expected.in_amount);
+                    assert_eq!(q.out_amount, expected.out_amount);
+                    assert_eq!(q.fee_amount, expected.fee_amount);
+                }
+                Err(_) => assert!(stateless.is_err()),
+            }
+        }
+    }
+}
+
+#[test]
+fn from_accounts_requires_lst_state_list_fixture() {
+    let mut account_map = account_map();
+    account_map.remove(&Pubkey::new_from_array(LST_STATE_LIST_ID));
+    assert!(InfAmm::from_accounts(&account_map, SPL_LSTS.into_iter().collect()).is_err());
+}
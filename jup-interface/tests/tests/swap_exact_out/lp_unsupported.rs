@@ -0,0 +1,57 @@
+use inf1_jup_interface::{consts::INF_MINT_ADDR, err::InfAmmErr};
+use inf1_std::{
+    inf1_pp_core::pair::Pair,
+    inf1_svc_ag_std::inf1_svc_marinade_core::sanctum_marinade_liquid_staking_core::MSOL_MINT_ADDR,
+};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode, SwapParams};
+use solana_pubkey::Pubkey;
+use test_utils::ALL_FIXTURES;
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn exact_out_lp_pairs_unsupported_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    assert!(inf.supports_exact_out());
+
+    for (inp, out) in [
+        (MSOL_MINT_ADDR, INF_MINT_ADDR),
+        (INF_MINT_ADDR, MSOL_MINT_ADDR),
+    ] {
+        assert!(!inf.supports_exact_out_for_pair(&Pair {
+            inp: &inp,
+            out: &out
+        }));
+
+        let quote_err = inf
+            .quote(&QuoteParams {
+                amount: 1_000_000_000,
+                input_mint: inp.into(),
+                output_mint: out.into(),
+                swap_mode: SwapMode::ExactOut,
+            })
+            .unwrap_err();
+        let metas_err = inf
+            .get_swap_and_account_metas(&SwapParams {
+                swap_mode: SwapMode::ExactOut,
+                in_amount: 1_000_000_000,
+                out_amount: 1_000_000_000,
+                source_mint: inp.into(),
+                destination_mint: out.into(),
+                source_token_account: Pubkey::new_from_array([1; 32]),
+                destination_token_account: Pubkey::new_from_array([2; 32]),
+                token_transfer_authority: Pubkey::new_from_array([3; 32]),
+                quote_mint_to_referrer: Default::default(),
+                jupiter_program_id: &Default::default(),
+                missing_dynamic_accounts_as_default: Default::default(),
+            })
+            .unwrap_err();
+        for err in [quote_err, metas_err] {
+            assert!(matches!(
+                err.downcast_ref::<InfAmmErr>(),
+                Some(InfAmmErr::ExactOutUnsupportedForLiquidity { pair })
+                    if pair.inp == inp && pair.out == out
+            ));
+        }
+    }
+}
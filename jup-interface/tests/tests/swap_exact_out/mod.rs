@@ -2,3 +2,4 @@ mod from_lido;
 mod from_marinade;
 mod from_spl;
 mod from_wsol;
+mod lp_unsupported;
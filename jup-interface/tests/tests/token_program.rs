@@ -0,0 +1,93 @@
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR,
+    lst_info::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    pda::find_ata,
+};
+use inf1_std::inf1_ctl_core::keys::POOL_STATE_ID;
+use jupiter_amm_interface::{Amm, SwapMode, SwapParams};
+use solana_pubkey::Pubkey;
+use test_utils::{KeyedUiAccount, ALL_FIXTURES};
+
+use crate::common::updated_inf_amm;
+
+fn msol_mint() -> Pubkey {
+    KeyedUiAccount::from_test_fixtures_json("msol-mint")
+        .into_keyed_account()
+        .0
+}
+
+#[test]
+fn token_2022_lst_metas_fixture() {
+    let msol = msol_mint();
+    let mut onchain_state = ALL_FIXTURES.clone();
+    onchain_state.get_mut(&msol).unwrap().owner = TOKEN_2022_PROGRAM_ID.into();
+    let inf = updated_inf_amm(&onchain_state);
+
+    assert_eq!(
+        inf.token_program_of(msol.as_array()),
+        Some(&TOKEN_2022_PROGRAM_ID)
+    );
+    assert_eq!(
+        inf.token_program_of(&WSOL_MINT_ADDR),
+        Some(&TOKEN_PROGRAM_ID)
+    );
+    // mints are no longer fetched once their token program is known
+    let accs = inf.get_accounts_to_update();
+    assert!(!accs.contains(&msol));
+    assert!(!accs.contains(&WSOL_MINT_ADDR.into()));
+
+    let token_2022_reserves: Pubkey =
+        find_ata(&POOL_STATE_ID, &TOKEN_2022_PROGRAM_ID, msol.as_array())
+            .unwrap()
+            .into();
+    let token_reserves: Pubkey = find_ata(&POOL_STATE_ID, &TOKEN_PROGRAM_ID, msol.as_array())
+        .unwrap()
+        .into();
+    let [wsol_acc, msol_acc] = ["wsol-token-acc", "msol-token-acc"].map(|name| {
+        KeyedUiAccount::from_test_fixtures_json(name)
+            .into_keyed_account()
+            .0
+    });
+    let metas = inf
+        .get_swap_and_account_metas(&SwapParams {
+            swap_mode: SwapMode::ExactIn,
+            in_amount: 1_000_000_000,
+            out_amount: 0,
+            source_mint: WSOL_MINT_ADDR.into(),
+            destination_mint: msol,
+            source_token_account: wsol_acc,
+            destination_token_account: msol_acc,
+            token_transfer_authority: Default::default(),
+            quote_mint_to_referrer: None,
+            jupiter_program_id: &Default::default(),
+            missing_dynamic_accounts_as_default: false,
+        })
+        .unwrap()
+        .account_metas;
+    let pks: Vec<_> = metas.iter().map(|m| m.pubkey).collect();
+    assert!(pks.contains(&TOKEN_2022_PROGRAM_ID.into()));
+    assert!(pks.contains(&TOKEN_PROGRAM_ID.into()));
+    assert!(pks.contains(&token_2022_reserves));
+    assert!(!pks.contains(&token_reserves));
+}
+
+#[test]
+fn token_programs_recorded_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let accs = inf.get_accounts_to_update();
+    for name in ["jupsol-mint", "msol-mint", "stsol-mint", "wsol-mint"] {
+        let mint = KeyedUiAccount::from_test_fixtures_json(name)
+            .into_keyed_account()
+            .0;
+        assert_eq!(
+            inf.token_program_of(mint.as_array()),
+            Some(&TOKEN_PROGRAM_ID),
+            "{name}"
+        );
+        assert!(!accs.contains(&mint), "{name}");
+    }
+    // LP token mint is always fetched for its supply
+    let lp_mint = inf.inner.pool.lp_token_mint;
+    assert_eq!(inf.token_program_of(&lp_mint), Some(&TOKEN_PROGRAM_ID));
+    assert!(accs.contains(&lp_mint.into()));
+}
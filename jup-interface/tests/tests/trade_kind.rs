@@ -0,0 +1,37 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, NATIVE_SOL_MINT_ADDR, WSOL_MINT_ADDR},
+    trade_kind::TradeKind,
+};
+use inf1_std::inf1_pp_core::pair::Pair;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+#[test]
+fn trade_type_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    for (inp, out, expected) in [
+        (jupsol, &WSOL_MINT_ADDR, TradeKind::Swap),
+        (&NATIVE_SOL_MINT_ADDR, jupsol, TradeKind::Swap),
+        (jupsol, &INF_MINT_ADDR, TradeKind::AddLiquidity),
+        (
+            &NATIVE_SOL_MINT_ADDR,
+            &INF_MINT_ADDR,
+            TradeKind::AddLiquidity,
+        ),
+        (&INF_MINT_ADDR, jupsol, TradeKind::RemoveLiquidity),
+        (
+            &INF_MINT_ADDR,
+            &NATIVE_SOL_MINT_ADDR,
+            TradeKind::RemoveLiquidity,
+        ),
+    ] {
+        let kind = inf.trade_type(inp, out);
+        assert_eq!(kind, expected);
+        assert_eq!(
+            kind.supports_exact_out(),
+            inf.supports_exact_out_for_pair(&Pair { inp, out })
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use inf1_jup_interface::{
+    consts::WSOL_MINT_ADDR,
+    pda::find_ata,
+    tx::{slippage_limit, TxQuoteRequest, CREATE_ATA_CU},
+    InfAmm,
+};
+use jupiter_amm_interface::{SwapMode, SwapParams};
+use solana_pubkey::Pubkey;
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, hash::Hash};
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+fn ata(inf: &InfAmm, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    find_ata(
+        owner.as_array(),
+        inf.token_program_of(mint.as_array()).unwrap(),
+        mint.as_array(),
+    )
+    .unwrap()
+    .into()
+}
+
+#[test]
+fn slippage_limit_rounds_against_user() {
+    assert_eq!(slippage_limit(10_001, 50, SwapMode::ExactIn), 9_950);
+    assert_eq!(slippage_limit(10_001, 50, SwapMode::ExactOut), 10_052);
+    assert_eq!(slippage_limit(u64::MAX, 50, SwapMode::ExactOut), u64::MAX);
+    assert_eq!(slippage_limit(1_000, 10_001, SwapMode::ExactIn), 0);
+}
+
+#[test]
+fn build_versioned_tx_contains_swap_ix_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let payer = Pubkey::new_from_array([5; 32]);
+    let req = TxQuoteRequest {
+        input_mint: *CONST_PUBKEYS.jupsol_mint(),
+        output_mint: WSOL_MINT_ADDR.into(),
+        amount: 1_000_000_000,
+        swap_mode: SwapMode::ExactIn,
+        slippage_bps: 50,
+        compute_unit_limit: None,
+        compute_unit_price_micro_lamports: 1,
+    };
+    let built = inf
+        .build_versioned_tx(&req, &payer, Hash::default(), &[])
+        .unwrap();
+
+    let swap_params = SwapParams {
+        swap_mode: req.swap_mode,
+        in_amount: built.quote.in_amount,
+        out_amount: slippage_limit(built.quote.out_amount, req.slippage_bps, req.swap_mode),
+        source_mint: req.input_mint,
+        destination_mint: req.output_mint,
+        source_token_account: ata(&inf, &payer, &req.input_mint),
+        destination_token_account: ata(&inf, &payer, &req.output_mint),
+        token_transfer_authority: payer,
+        // dont-cares
+        quote_mint_to_referrer: Default::default(),
+        jupiter_program_id: &Default::default(),
+        missing_dynamic_accounts_as_default: Default::default(),
+    };
+    let create_atas = inf.required_atas(&swap_params).unwrap();
+    let expected = inf.swap_instruction(&swap_params).unwrap();
+
+    let message = &built.tx.message;
+    let ixs = message.instructions();
+    let keys = message.static_account_keys();
+    assert_eq!(built.tx.signatures.len(), 1);
+    assert_eq!(keys[0], payer);
+    // compute unit limit, compute unit price, create ATAs, swap
+    assert_eq!(ixs.len(), 3 + create_atas.len());
+
+    let [inp, out] = [req.input_mint, req.output_mint].map(|m| m.to_bytes());
+    let cu_limit = inf.estimated_compute_units(&inp, &out, inf.trade_type(&inp, &out))
+        + CREATE_ATA_CU * create_atas.len() as u32;
+    assert_eq!(
+        ixs[0].data,
+        ComputeBudgetInstruction::set_compute_unit_limit(cu_limit).data
+    );
+
+    for (ix, (ata, _)) in ixs[2..ixs.len() - 1].iter().zip(&create_atas) {
+        let ix_keys: Vec<_> = ix.accounts.iter().map(|i| keys[usize::from(*i)]).collect();
+        assert_eq!(ix_keys[1], *ata);
+    }
+
+    let swap_ix = ixs.last().unwrap();
+    assert_eq!(
+        keys[usize::from(swap_ix.program_id_index)],
+        expected.program_id
+    );
+    assert_eq!(swap_ix.data, expected.data);
+    let accounts: Vec<_> = swap_ix
+        .accounts
+        .iter()
+        .map(|i| keys[usize::from(*i)])
+        .collect();
+    let expected_accounts: Vec<_> = expected.accounts.iter().map(|m| m.pubkey).collect();
+    assert_eq!(accounts, expected_accounts);
+}
@@ -0,0 +1,52 @@
+use inf1_jup_interface::{
+    lst_info::TOKEN_PROGRAM_ID,
+    typed::{LstMint, LstPair},
+};
+use inf1_std::{inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const AMOUNT: u64 = 1_000_000_000;
+
+#[test]
+fn typed_same_as_raw_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = LstMint(*CONST_PUBKEYS.jupsol_mint());
+    let pair = LstPair::new(jupsol, LstMint::WSOL);
+    let typed = inf.typed();
+
+    let (fee_mint, quote) = typed.quote(&pair, AMOUNT, TradeLimitTy::ExactIn).unwrap();
+    let (raw_fee_mint, raw_quote) = inf
+        .quote_raw(
+            &Pair {
+                inp: jupsol.as_array(),
+                out: LstMint::WSOL.as_array(),
+            },
+            AMOUNT,
+            TradeLimitTy::ExactIn,
+            inf.epoch(),
+        )
+        .unwrap();
+    assert_eq!(*fee_mint.as_array(), raw_fee_mint);
+    assert_eq!(quote.out, raw_quote.out);
+
+    assert_eq!(
+        typed.quotable_mints(),
+        inf.quotable_mints()
+            .into_iter()
+            .map(LstMint)
+            .collect::<Vec<_>>()
+    );
+    assert!(!typed.supports_exact_out_for_pair(&LstPair::new(jupsol, LstMint::INF)));
+    assert_eq!(
+        typed.lst_info(&jupsol, &Pubkey::new_from_array(TOKEN_PROGRAM_ID)),
+        inf.lst_info(jupsol.as_array(), &TOKEN_PROGRAM_ID)
+    );
+    assert_eq!(
+        typed.preview_add_liquidity(&jupsol, AMOUNT).unwrap(),
+        inf.preview_add_liquidity(jupsol.as_array(), AMOUNT)
+            .unwrap()
+    );
+}
@@ -0,0 +1,78 @@
+use inf1_jup_interface::{consts::WSOL_MINT_ADDR, update::UiAccountMap, InfAmm};
+use inf1_std::{
+    inf1_ctl_core::keys::{LST_STATE_LIST_ID, POOL_STATE_ID},
+    update::{Account, UpdateMap},
+};
+use jupiter_amm_interface::{Amm, KeyedAccount, QuoteParams, SwapMode};
+use solana_account_decoder_client_types::{UiAccountData, UiAccountEncoding};
+use solana_pubkey::Pubkey;
+use test_utils::{account_to_ui_account, ALL_FIXTURES, CONST_PUBKEYS, SPL_LSTS};
+
+use crate::common::{updated_inf_amm, AMM_CONTEXT};
+
+fn all_fixtures_ui() -> UiAccountMap {
+    ALL_FIXTURES
+        .iter()
+        .map(|(pk, acc)| (*pk, account_to_ui_account(acc)))
+        .collect()
+}
+
+#[test]
+fn ui_account_map_decodes_fixture() {
+    let ui = all_fixtures_ui();
+    for pk in [POOL_STATE_ID, LST_STATE_LIST_ID] {
+        let expected = ALL_FIXTURES.get(&Pubkey::new_from_array(pk)).unwrap();
+        // 2nd lookup hits cache
+        for _ in 0..2 {
+            assert_eq!(ui.get_account(&pk).unwrap().data(), expected.data);
+        }
+    }
+}
+
+#[test]
+fn ui_account_map_undecodable_missing() {
+    let pk = Pubkey::new_from_array(POOL_STATE_ID);
+    let mut acc = account_to_ui_account(ALL_FIXTURES.get(&pk).unwrap());
+    acc.data = UiAccountData::Binary("not base64!".to_owned(), UiAccountEncoding::Base64);
+    let ui: UiAccountMap = [(pk, acc)].into_iter().collect();
+
+    assert!(ui.get_account(&POOL_STATE_ID).is_none());
+    assert!(ui.get_ui_account(&pk).is_some());
+}
+
+#[test]
+fn update_with_ui_account_map_same_as_account_map_fixture() {
+    let ui = all_fixtures_ui();
+    let key = Pubkey::new_from_array(LST_STATE_LIST_ID);
+    let mut inf = InfAmm::new(
+        &KeyedAccount {
+            key,
+            account: ALL_FIXTURES.get(&key).unwrap().clone(),
+            params: None,
+        },
+        &AMM_CONTEXT,
+        SPL_LSTS.into_iter().collect(),
+    )
+    .unwrap();
+    // 1st update might fail because DEFAULT_MAINNET_POOL might be stale
+    let _: Result<_, _> = inf.update_with(&ui);
+    inf.update_with(&ui).unwrap();
+
+    let expected = updated_inf_amm(&ALL_FIXTURES);
+    let qp = QuoteParams {
+        amount: 1_000_000,
+        input_mint: *CONST_PUBKEYS.jupsol_mint(),
+        output_mint: WSOL_MINT_ADDR.into(),
+        swap_mode: SwapMode::ExactIn,
+    };
+    let quote = inf.quote(&qp).unwrap();
+    let expected_quote = expected.quote(&qp).unwrap();
+    assert_eq!(quote.out_amount, expected_quote.out_amount);
+    assert_eq!(quote.fee_amount, expected_quote.fee_amount);
+
+    let mint = CONST_PUBKEYS.jupsol_mint().as_array();
+    assert_eq!(
+        inf.lst_health(mint).unwrap().applied_accs.len(),
+        expected.lst_health(mint).unwrap().applied_accs.len(),
+    );
+}
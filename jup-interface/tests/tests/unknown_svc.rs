@@ -0,0 +1,45 @@
+use inf1_jup_interface::consts::WSOL_MINT_ADDR;
+use inf1_std::{
+    inf1_ctl_core::keys::LST_STATE_LIST_ID,
+    inf1_svc_ag_std::inf1_svc_marinade_core::sanctum_marinade_liquid_staking_core::MSOL_MINT_ADDR,
+};
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+/// Offsets in packed `LstState`:
+/// `is_input_disabled: u8, pool_reserves_bump: u8, protocol_fee_accumulator_bump: u8,
+/// padding: [u8; 5], sol_value: u64, mint: [u8; 32], sol_value_calculator: [u8; 32]`
+const LST_STATE_LEN: usize = 80;
+const LST_STATE_MINT_OFFSET: usize = 16;
+const LST_STATE_SOL_VALUE_CALCULATOR_OFFSET: usize = 48;
+
+/// e.g. INF's own sol val calc, once it is added to the LST state list
+const UNKNOWN_SVC_PROG_ID: [u8; 32] = [9; 32];
+
+#[test]
+fn unknown_svc_lst_skipped_fixture() {
+    let mut onchain_state = ALL_FIXTURES.clone();
+    let lst_state = onchain_state
+        .get_mut(&Pubkey::new_from_array(LST_STATE_LIST_ID))
+        .unwrap()
+        .data
+        .chunks_exact_mut(LST_STATE_LEN)
+        .find(|s| s[LST_STATE_MINT_OFFSET..][..32] == MSOL_MINT_ADDR)
+        .unwrap();
+    lst_state[LST_STATE_SOL_VALUE_CALCULATOR_OFFSET..][..32].copy_from_slice(&UNKNOWN_SVC_PROG_ID);
+
+    let inf = updated_inf_amm(&onchain_state);
+    let quote = |input_mint: Pubkey| {
+        inf.quote(&QuoteParams {
+            amount: 1_000_000_000,
+            input_mint,
+            output_mint: WSOL_MINT_ADDR.into(),
+            swap_mode: SwapMode::ExactIn,
+        })
+    };
+    assert!(quote(*CONST_PUBKEYS.jupsol_mint()).is_ok());
+    assert!(quote(MSOL_MINT_ADDR.into()).is_err());
+}
@@ -0,0 +1,64 @@
+use inf1_jup_interface::{consts::INF_MINT_ADDR, validate::MissingOrStale};
+use inf1_std::inf1_ctl_core::keys::POOL_STATE_ID;
+use jupiter_amm_interface::{AccountMap, Amm};
+use solana_pubkey::Pubkey;
+use test_utils::ALL_FIXTURES;
+
+use crate::common::updated_inf_amm;
+
+fn accounts_to_update_map(inf: &impl Amm) -> AccountMap {
+    inf.get_accounts_to_update()
+        .into_iter()
+        .filter_map(|pk| Some((pk, ALL_FIXTURES.get(&pk)?.clone())))
+        .collect()
+}
+
+#[test]
+fn validate_all_fixtures_ok_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    assert_eq!(
+        inf.validate_account_map(&accounts_to_update_map(&inf)),
+        vec![]
+    );
+}
+
+#[test]
+fn validate_missing_pool_state_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let mut accounts = accounts_to_update_map(&inf);
+    let pk = Pubkey::new_from_array(POOL_STATE_ID);
+    accounts.remove(&pk);
+    assert_eq!(
+        inf.validate_account_map(&accounts),
+        vec![MissingOrStale::Missing { pk }]
+    );
+}
+
+#[test]
+fn validate_wrong_pool_state_owner_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let mut accounts = accounts_to_update_map(&inf);
+    let pk = Pubkey::new_from_array(POOL_STATE_ID);
+    let owner = Pubkey::new_from_array([1; 32]);
+    accounts.get_mut(&pk).unwrap().owner = owner;
+    assert_eq!(
+        inf.validate_account_map(&accounts),
+        vec![MissingOrStale::WrongOwner { pk, owner }]
+    );
+}
+
+#[test]
+fn validate_truncated_lp_mint_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let mut accounts = accounts_to_update_map(&inf);
+    let pk = Pubkey::new_from_array(INF_MINT_ADDR);
+    accounts.get_mut(&pk).unwrap().data.truncate(36);
+    assert_eq!(
+        inf.validate_account_map(&accounts),
+        vec![MissingOrStale::Truncated {
+            pk,
+            len: 36,
+            min_len: 82
+        }]
+    );
+}
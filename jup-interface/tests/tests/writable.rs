@@ -0,0 +1,158 @@
+use inf1_jup_interface::{
+    consts::{INF_MINT_ADDR, WSOL_MINT_ADDR},
+    writable::{UserAccs, WritableAcc},
+};
+use inf1_std::{inf1_ctl_core::keys::POOL_STATE_ID, inf1_pp_core::pair::Pair, trade::TradeLimitTy};
+use jupiter_amm_interface::{Amm, SwapMode, SwapParams};
+use solana_pubkey::Pubkey;
+use test_utils::{ALL_FIXTURES, CONST_PUBKEYS};
+
+use crate::common::updated_inf_amm;
+
+const TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+#[test]
+fn writable_accounts_for_swap_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let jupsol = CONST_PUBKEYS.jupsol_mint().as_array();
+    let pair = Pair {
+        inp: jupsol,
+        out: &WSOL_MINT_ADDR,
+    };
+    let exact_in = inf
+        .writable_accounts_for(&pair, TradeLimitTy::ExactIn)
+        .unwrap();
+
+    for acc in [
+        WritableAcc::UserInpTokenAcc,
+        WritableAcc::UserOutTokenAcc,
+        WritableAcc::Pool(POOL_STATE_ID.into()),
+    ] {
+        assert!(exact_in.contains(&acc), "{acc:?}");
+    }
+    for mint in [jupsol, &WSOL_MINT_ADDR] {
+        let reserves = inf
+            .lst_info(mint, TOKEN_PROGRAM_ID.as_array())
+            .unwrap()
+            .reserves;
+        assert!(exact_in.contains(&WritableAcc::Pool(reserves.into())));
+        // mints are not written to in LST-LST swaps
+        assert!(!exact_in.contains(&WritableAcc::Pool((*mint).into())));
+    }
+    assert!(exact_in.windows(2).all(|w| w[0] < w[1]));
+
+    // same accounts regardless of direction of limit
+    assert_eq!(
+        inf.writable_accounts_for(&pair, TradeLimitTy::ExactOut)
+            .unwrap(),
+        exact_in
+    );
+}
+
+#[test]
+fn writable_accounts_for_add_liquidity_includes_lp_mint_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let accs = inf
+        .writable_accounts_for(
+            &Pair {
+                inp: &WSOL_MINT_ADDR,
+                out: &INF_MINT_ADDR,
+            },
+            TradeLimitTy::ExactIn,
+        )
+        .unwrap();
+    assert!(accs.contains(&WritableAcc::Pool(INF_MINT_ADDR.into())));
+}
+
+#[test]
+fn accounts_len_for_pair_matches_account_metas_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    for (inp, out) in [
+        (CONST_PUBKEYS.jupsol_mint().as_array(), &WSOL_MINT_ADDR),
+        (&WSOL_MINT_ADDR, &INF_MINT_ADDR),
+        (&INF_MINT_ADDR, CONST_PUBKEYS.jupsol_mint().as_array()),
+    ] {
+        let len = inf
+            .accounts_len_for_pair(&Pair { inp, out }, TradeLimitTy::ExactIn)
+            .unwrap();
+        let metas = inf
+            .get_swap_and_account_metas(&SwapParams {
+                swap_mode: SwapMode::ExactIn,
+                in_amount: 1_000_000_000,
+                out_amount: 0,
+                source_mint: (*inp).into(),
+                destination_mint: (*out).into(),
+                source_token_account: Pubkey::new_from_array([1; 32]),
+                destination_token_account: Pubkey::new_from_array([2; 32]),
+                token_transfer_authority: Pubkey::new_from_array([3; 32]),
+                quote_mint_to_referrer: None,
+                jupiter_program_id: &Pubkey::default(),
+                missing_dynamic_accounts_as_default: false,
+            })
+            .unwrap()
+            .account_metas;
+        assert_eq!(len, metas.len());
+    }
+}
+
+#[test]
+fn accounts_for_pair_same_as_swap_metas_fixture() {
+    let inf = updated_inf_amm(&ALL_FIXTURES);
+    let user = UserAccs {
+        signer: Pubkey::new_from_array([3; 32]),
+        inp_token_acc: Pubkey::new_from_array([1; 32]),
+        out_token_acc: Pubkey::new_from_array([2; 32]),
+    };
+    for (inp, out) in [
+        (CONST_PUBKEYS.jupsol_mint().as_array(), &WSOL_MINT_ADDR),
+        (&WSOL_MINT_ADDR, &INF_MINT_ADDR),
+        (&INF_MINT_ADDR, &WSOL_MINT_ADDR),
+    ] {
+        let pair = Pair { inp, out };
+        let accs = inf
+            .accounts_for_pair(&pair, TradeLimitTy::ExactIn, &user)
+            .unwrap();
+        let swap_metas = inf
+            .get_swap_and_account_metas(&SwapParams {
+                swap_mode: SwapMode::ExactIn,
+                in_amount: 1_000_000_000,
+                out_amount: 0,
+                source_mint: Pubkey::new_from_array(*inp),
+                destination_mint: Pubkey::new_from_array(*out),
+                source_token_account: user.inp_token_acc,
+                destination_token_account: user.out_token_acc,
+                token_transfer_authority: user.signer,
+                quote_mint_to_referrer: None,
+                jupiter_program_id: &Pubkey::default(),
+                missing_dynamic_accounts_as_default: false,
+            })
+            .unwrap()
+            .account_metas;
+        assert_eq!(accs, swap_metas);
+        assert_eq!(
+            accs.len(),
+            inf.accounts_len_for_pair(&pair, TradeLimitTy::ExactIn)
+                .unwrap()
+        );
+
+        // only user accounts differ with placeholders
+        let placeholders = inf
+            .accounts_for_pair(&pair, TradeLimitTy::ExactIn, &UserAccs::PLACEHOLDERS)
+            .unwrap();
+        let user_pks = [user.signer, user.inp_token_acc, user.out_token_acc];
+        let placeholder_pks = [
+            UserAccs::PLACEHOLDERS.signer,
+            UserAccs::PLACEHOLDERS.inp_token_acc,
+            UserAccs::PLACEHOLDERS.out_token_acc,
+        ];
+        assert_eq!(placeholders.len(), accs.len());
+        for (p, a) in placeholders.iter().zip(&accs) {
+            if user_pks.contains(&a.pubkey) {
+                assert!(placeholder_pks.contains(&p.pubkey));
+            } else {
+                assert_eq!(p, a);
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! `no_std` quote math of `inf1-jup-interface` with no dependencies, e.g. for on-chain
+//! programs that pre-check INF quotes: fee reporting and slippage limits.
+//!
+//! Amounts are those of an [`inf1_std`](https://github.com/igneous-labs/inf-1.5) quote.
+//! `inf1-jup-interface` re-exports this crate as `quote_core`.
+
+#![no_std]
+
+pub const BPS_DENOM: u64 = 10_000;
+
+/// Which fee components are reported as a quote's fee amount and fee percentage.
+///
+/// Some venues only display LP-side fees and report protocol fees separately.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JupQuoteFees {
+    /// LP fees + protocol fees
+    #[default]
+    All,
+
+    /// LP fees only, protocol fees are excluded
+    LpOnly,
+}
+
+/// The reported fee amount of a quote, in its fee mint
+#[inline]
+pub const fn fee_amount(lp_fee: u64, protocol_fee: u64, fees: JupQuoteFees) -> u64 {
+    match fees {
+        JupQuoteFees::All => lp_fee.saturating_add(protocol_fee),
+        JupQuoteFees::LpOnly => lp_fee,
+    }
+}
+
+/// `(numerator, denominator)` of a quote's fee percentage, to be divided exactly,
+/// e.g. with a decimal type, instead of with floating point:
+/// - numerator: [`fee_amount`]
+/// - denominator: the amount it is charged on, i.e. the input amount if fees are charged
+///   in the input mint, the output amount before all fees otherwise
+#[inline]
+pub const fn fee_pct_ratio(
+    fee_mint_is_inp: bool,
+    in_amount: u64,
+    out_amount: u64,
+    lp_fee: u64,
+    protocol_fee: u64,
+    fees: JupQuoteFees,
+) -> (u64, u64) {
+    let denom = if fee_mint_is_inp {
+        in_amount
+    } else {
+        out_amount.saturating_add(lp_fee.saturating_add(protocol_fee))
+    };
+    (fee_amount(lp_fee, protocol_fee, fees), denom)
+}
+
+/// The min output amount of an ExactIn trade quoted to output `quoted_out`,
+/// rounding against the user
+#[inline]
+pub const fn exact_in_slippage_limit(quoted_out: u64, slippage_bps: u16) -> u64 {
+    let bps = BPS_DENOM.saturating_sub(slippage_bps as u64);
+    ((quoted_out as u128 * bps as u128) / BPS_DENOM as u128) as u64
+}
+
+/// The max input amount of an ExactOut trade quoted to take `quoted_in`,
+/// rounding against the user
+#[inline]
+pub const fn exact_out_slippage_limit(quoted_in: u64, slippage_bps: u16) -> u64 {
+    let bps = BPS_DENOM + slippage_bps as u64;
+    let ceil = (quoted_in as u128 * bps as u128).div_ceil(BPS_DENOM as u128);
+    if ceil > u64::MAX as u128 {
+        u64::MAX
+    } else {
+        ceil as u64
+    }
+}
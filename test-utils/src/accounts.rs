@@ -1,13 +1,42 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
 use solana_account::Account;
+use solana_account_decoder_client_types::{UiAccount, UiAccountData, UiAccountEncoding};
 use solana_pubkey::Pubkey;
 
 use crate::CONST_PUBKEYS;
 
+/// Offsets in sysvar Clock account data:
+/// `slot: u64, epoch_start_timestamp: i64, epoch: u64, leader_schedule_epoch: u64, unix_timestamp: i64`
+const CLOCK_SLOT_OFFSET: usize = 0;
+const CLOCK_EPOCH_OFFSET: usize = 16;
+const CLOCK_LEADER_SCHEDULE_EPOCH_OFFSET: usize = 24;
+
+/// Offset of `last_update_epoch` in SPL (and sanctum SPL, sanctum SPL multi) stake pool account data
+const SPL_STAKE_POOL_LAST_UPDATE_EPOCH_OFFSET: usize = 274;
+
+/// Offset of `pricing_program` in INF pool state account data,
+/// `total_sol_value: u64, .., pricing_program: Pubkey, lp_token_mint: Pubkey`
+const POOL_STATE_PRICING_PROGRAM_OFFSET: usize = 112;
+
 /// Clock with everything = 0
 /// Currently only used as return value in get_accounts_to_update
 pub fn mock_clock() -> Account {
+    mock_clock_at(0, 0)
+}
+
+/// Clock at `slot` and `epoch`, with `leader_schedule_epoch = epoch`
+/// and everything else = 0
+pub fn mock_clock_at(slot: u64, epoch: u64) -> Account {
+    let mut data = vec![0; 40];
+    for (offset, val) in [
+        (CLOCK_SLOT_OFFSET, slot),
+        (CLOCK_EPOCH_OFFSET, epoch),
+        (CLOCK_LEADER_SCHEDULE_EPOCH_OFFSET, epoch),
+    ] {
+        data[offset..][..8].copy_from_slice(&val.to_le_bytes());
+    }
     Account {
-        data: vec![0; 40],
+        data,
         owner: *CONST_PUBKEYS.sysvar_owner(),
         executable: false,
         // dont-cares
@@ -16,6 +45,25 @@ pub fn mock_clock() -> Account {
     }
 }
 
+/// Sets `last_update_epoch` of a SPL stake pool account,
+/// e.g. to make it stale or up to date relative to a [`mock_clock_at`]
+pub fn set_spl_stake_pool_last_update_epoch(stake_pool: &mut Account, epoch: u64) {
+    stake_pool.data[SPL_STAKE_POOL_LAST_UPDATE_EPOCH_OFFSET..][..8]
+        .copy_from_slice(&epoch.to_le_bytes());
+}
+
+/// Returns `pricing_program` of an INF pool state account
+pub fn pool_state_pricing_program(pool_state: &Account) -> [u8; 32] {
+    pool_state.data[POOL_STATE_PRICING_PROGRAM_OFFSET..][..32]
+        .try_into()
+        .unwrap()
+}
+
+/// Sets `pricing_program` of an INF pool state account
+pub fn set_pool_state_pricing_program(pool_state: &mut Account, pricing_program: &[u8; 32]) {
+    pool_state.data[POOL_STATE_PRICING_PROGRAM_OFFSET..][..32].copy_from_slice(pricing_program);
+}
+
 /// Creates a mock program data account with last upgrade slot 0
 pub fn mock_progdata_acc() -> Account {
     let mut data = vec![0u8; 45];
@@ -46,3 +94,23 @@ pub fn mock_prog_acc(programdata_address: Pubkey) -> Account {
         rent_epoch: u64::MAX,
     }
 }
+
+/// Inverse of [`UiAccount::decode`], with base64 encoded data
+pub fn account_to_ui_account(
+    Account {
+        lamports,
+        data,
+        owner,
+        executable,
+        rent_epoch,
+    }: &Account,
+) -> UiAccount {
+    UiAccount {
+        lamports: *lamports,
+        data: UiAccountData::Binary(BASE64_STANDARD.encode(data), UiAccountEncoding::Base64),
+        owner: owner.to_string(),
+        executable: *executable,
+        rent_epoch: *rent_epoch,
+        space: Some(data.len() as u64),
+    }
+}
@@ -11,7 +11,7 @@ use solana_account::Account;
 use solana_account_decoder_client_types::UiAccount;
 use solana_pubkey::Pubkey;
 
-use crate::{mock_clock, mock_prog_acc, mock_progdata_acc, CONST_PUBKEYS};
+use crate::{mock_clock, mock_clock_at, mock_prog_acc, mock_progdata_acc, CONST_PUBKEYS};
 
 pub const FIXTURE_PROGRAMS: [(&str, Pubkey); 6] = [
     ("inf", *CONST_PUBKEYS.inf_ctl_prog()),
@@ -71,6 +71,16 @@ lazy_static! {
     };
 }
 
+/// [`ALL_FIXTURES`] with sysvar clock replaced by [`mock_clock_at`]`(slot, epoch)`.
+///
+/// Use with [`crate::mollusk_set_clock`] so that programs reading clock
+/// via syscall see the same clock.
+pub fn fixtures_at_clock(slot: u64, epoch: u64) -> HashMap<Pubkey, Account> {
+    let mut res = ALL_FIXTURES.clone();
+    res.insert(*CONST_PUBKEYS.sysvar_clock(), mock_clock_at(slot, epoch));
+    res
+}
+
 /// Continues if fixture account not found for given pubkey
 pub fn fixtures_accounts_opt_cloned(
     itr: impl IntoIterator<Item = impl Into<Pubkey>>,
@@ -81,17 +91,13 @@ pub fn fixtures_accounts_opt_cloned(
     })
 }
 
-/// Copied from https://stackoverflow.com/a/74942075/5057425
+/// Resolved at compile time from this crate's location in the workspace,
+/// without spawning `cargo locate-project`
 pub fn workspace_root_dir() -> PathBuf {
-    let output = std::process::Command::new(env!("CARGO"))
-        .arg("locate-project")
-        .arg("--workspace")
-        .arg("--message-format=plain")
-        .output()
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
         .unwrap()
-        .stdout;
-    let cargo_path = Path::new(std::str::from_utf8(&output).unwrap().trim());
-    cargo_path.parent().unwrap().to_path_buf()
+        .to_path_buf()
 }
 
 /// Returns `/path/to/workspace/root/test-fixtures`
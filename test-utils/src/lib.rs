@@ -2,10 +2,16 @@ mod accounts;
 mod fixtures;
 mod keys;
 mod mollusk;
+mod prop;
+#[cfg(feature = "rpc")]
+mod rpc;
 mod spl_lsts;
 
 pub use accounts::*;
 pub use fixtures::*;
 pub use keys::*;
 pub use mollusk::*;
+pub use prop::*;
+#[cfg(feature = "rpc")]
+pub use rpc::*;
 pub use spl_lsts::*;
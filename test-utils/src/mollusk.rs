@@ -47,6 +47,15 @@ fn mollusk_add_so_files(
     });
 }
 
+/// Sets the clock that programs executed by `svm` see to `slot` and `epoch`,
+/// with `leader_schedule_epoch = epoch`, same as [`crate::mock_clock_at`]
+pub fn mollusk_set_clock(svm: &mut Mollusk, slot: u64, epoch: u64) {
+    let clock = &mut svm.sysvars.clock;
+    clock.slot = slot;
+    clock.epoch = epoch;
+    clock.leader_schedule_epoch = epoch;
+}
+
 /// Returns `(accounts before, exec result)`
 pub fn mollusk_exec(
     svm: &Mollusk,
@@ -0,0 +1,92 @@
+use proptest::{prelude::*, strategy::Union};
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+
+use crate::KeyedUiAccount;
+
+/// LSTs that have the user fixtures `{name}-token-acc` and `{name}-token-acc-owner`,
+/// and the pool fixture `{name}-reserves`
+pub const FIXTURE_USER_LSTS: [&str; 4] = ["jupsol", "msol", "stsol", "wsol"];
+
+/// Offsets in token account data: `mint: Pubkey, owner: Pubkey, amount: u64, ..`
+const TOKEN_ACC_MINT_OFFSET: usize = 0;
+const TOKEN_ACC_AMOUNT_OFFSET: usize = 64;
+
+pub fn token_acc_mint(token_acc: &Account) -> Pubkey {
+    Pubkey::new_from_array(
+        token_acc.data[TOKEN_ACC_MINT_OFFSET..][..32]
+            .try_into()
+            .unwrap(),
+    )
+}
+
+pub fn token_acc_amount(token_acc: &Account) -> u64 {
+    u64::from_le_bytes(
+        token_acc.data[TOKEN_ACC_AMOUNT_OFFSET..][..8]
+            .try_into()
+            .unwrap(),
+    )
+}
+
+/// Balance of the fixture token account named `name`
+pub fn fixture_token_acc_amount(name: &str) -> u64 {
+    token_acc_amount(
+        &KeyedUiAccount::from_test_fixtures_json(name)
+            .into_keyed_account()
+            .1,
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixtureSwapCase {
+    /// One of [`FIXTURE_USER_LSTS`]
+    pub inp: &'static str,
+
+    /// One of [`FIXTURE_USER_LSTS`], never the same as [`Self::inp`]
+    pub out: &'static str,
+
+    pub is_exact_out: bool,
+
+    /// Input amount if ExactIn, output amount if ExactOut
+    pub amount: u64,
+}
+
+/// Uniform in `1..=max`, with extra weight on the edges `1`, `2`, `max - 1` and `max`
+/// where rounding drift is most likely
+pub fn edge_biased_amount(max: u64) -> impl Strategy<Value = u64> {
+    let max = max.max(1);
+    Union::new_weighted(vec![
+        (1, Just(1).boxed()),
+        (1, Just(2.min(max)).boxed()),
+        (1, Just(max.saturating_sub(1).max(1)).boxed()),
+        (1, Just(max).boxed()),
+        (4, (1..=max).boxed()),
+    ])
+}
+
+/// Distinct pairs of [`FIXTURE_USER_LSTS`]
+pub fn fixture_lst_pair() -> impl Strategy<Value = (&'static str, &'static str)> {
+    let n = FIXTURE_USER_LSTS.len();
+    (0..n, 1..n).prop_map(move |(i, d)| (FIXTURE_USER_LSTS[i], FIXTURE_USER_LSTS[(i + d) % n]))
+}
+
+/// Amounts are bounded by the user's input balance for ExactIn
+/// and by the pool's output reserves for ExactOut.
+///
+/// Cases may still be unquotable, e.g. ExactIn near the user's balance
+/// for an output LST with low reserves, so harnesses should skip cases that fail to quote.
+pub fn fixture_swap_case() -> impl Strategy<Value = FixtureSwapCase> {
+    (fixture_lst_pair(), any::<bool>()).prop_flat_map(|((inp, out), is_exact_out)| {
+        let max = if is_exact_out {
+            fixture_token_acc_amount(&format!("{out}-reserves"))
+        } else {
+            fixture_token_acc_amount(&format!("{inp}-token-acc"))
+        };
+        edge_biased_amount(max).prop_map(move |amount| FixtureSwapCase {
+            inp,
+            out,
+            is_exact_out,
+            amount,
+        })
+    })
+}
@@ -0,0 +1,86 @@
+//! Refreshing fixtures from a live RPC, see `jup-interface/examples/refresh_fixtures.rs`
+
+use std::{collections::HashMap, fs::File};
+
+use glob::glob;
+use serde::Deserialize;
+use serde_json::json;
+use solana_account_decoder_client_types::UiAccount;
+use solana_pubkey::Pubkey;
+
+use crate::{test_fixtures_dir, KeyedUiAccount};
+
+/// Max number of accounts per `getMultipleAccounts` request
+const MAX_ACCOUNTS_PER_REQ: usize = 100;
+
+#[derive(Deserialize)]
+struct RpcResp {
+    result: RpcResult,
+}
+
+#[derive(Deserialize)]
+struct RpcResult {
+    value: Vec<Option<UiAccount>>,
+}
+
+/// Fetches `pks` with base64 encoding. Accounts that do not exist are skipped.
+///
+/// Panics on RPC errors
+pub fn fetch_keyed_ui_accounts(rpc_url: &str, pks: &[Pubkey]) -> Vec<KeyedUiAccount> {
+    pks.chunks(MAX_ACCOUNTS_PER_REQ)
+        .flat_map(|chunk| {
+            let pk_strs: Vec<_> = chunk.iter().map(|pk| pk.to_string()).collect();
+            let RpcResp {
+                result: RpcResult { value },
+            } = ureq::post(rpc_url)
+                .send_json(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "getMultipleAccounts",
+                    "params": [pk_strs, { "encoding": "base64" }],
+                }))
+                .unwrap()
+                .body_mut()
+                .read_json()
+                .unwrap();
+            pk_strs
+                .into_iter()
+                .zip(value)
+                .filter_map(|(pubkey, account)| {
+                    Some(KeyedUiAccount {
+                        pubkey,
+                        account: account?,
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Writes `accounts` to `test-fixtures/`, overwriting the existing fixture of the same pubkey
+/// if any, else creating `<pubkey>.json`.
+///
+/// Returns the names of the fixtures written
+pub fn write_fixtures<'a>(accounts: impl IntoIterator<Item = &'a KeyedUiAccount>) -> Vec<String> {
+    let existing: HashMap<String, String> =
+        glob(test_fixtures_dir().join("*.json").to_str().unwrap())
+            .unwrap()
+            .map(|p| {
+                let p = p.unwrap();
+                let name = p.file_stem().unwrap().to_str().unwrap().to_owned();
+                (KeyedUiAccount::from_file(p).pubkey, name)
+            })
+            .collect();
+    accounts
+        .into_iter()
+        .map(|acc| {
+            let name = existing
+                .get(&acc.pubkey)
+                .cloned()
+                .unwrap_or_else(|| acc.pubkey.clone());
+            let file =
+                File::create(test_fixtures_dir().join(&name).with_extension("json")).unwrap();
+            serde_json::to_writer_pretty(file, acc).unwrap();
+            name
+        })
+        .collect()
+}